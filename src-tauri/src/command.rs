@@ -13,7 +13,7 @@
 //! # 使用示例
 //! 
 //! ```rust
-//! use crate::command::{CommandResponse, CommandResult};
+//! use crate::command::{AppError, CommandResponse, CommandResult};
 //! 
 //! // 成功响应
 //! fn get_data() -> CommandResult<String> {
@@ -22,7 +22,7 @@
 //! 
 //! // 错误响应
 //! fn process_data() -> CommandResult<i32> {
-//!     CommandResponse::err("VALIDATION_ERROR", "Invalid input format")
+//!     Err(AppError::Validation("Invalid input format".into()))
 //! }
 //! ```
 
@@ -37,6 +37,7 @@
 /// - `code`: 响应代码，成功时通常为 "OK"，失败时为错误代码
 /// - `message`: 响应消息，成功时通常为空字符串，失败时为错误描述
 /// - `data`: 实际的数据载荷，使用 `Option<T>` 类型，失败时为 `None`
+/// - `meta`: 可选的执行信息（耗时、处理节点），只有部分命令会填充，为 `None` 时不出现在 JSON 中
 /// 
 /// # 泛型参数
 /// 
@@ -75,12 +76,15 @@ pub struct CommandResponse<T> {
     
     /// 响应代码
     /// 
-    /// 成功时通常为 "OK"，失败时为具体的错误代码，如：
-    /// - "VALIDATION_ERROR": 输入验证失败
+    /// 成功时为 "OK"，失败时为 `AppError::code` 给出的错误代码，如：
+    /// - "INVALID_ARGUMENT": 参数校验失败
     /// - "NOT_FOUND": 资源未找到
-    /// - "PERMISSION_DENIED": 权限不足
-    /// - "INTERNAL_ERROR": 内部服务器错误
-    /// - "NETWORK_ERROR": 网络连接错误
+    /// - "AUTH_FAILED": Redis 认证失败
+    /// - "TIMEOUT": 操作超时
+    /// - "CONNECT_TIMEOUT": 建立连接超时
+    /// - "JSON_MODULE_MISSING": 服务端未加载 RedisJSON 模块
+    /// - "SERVER_ERROR": 服务端拒绝执行命令（通用的 `ERR` 回复）
+    /// - "REDIS_ERROR": 其他 Redis 错误（网络、协议等）
     pub code: String,
     
     /// 响应消息
@@ -95,8 +99,18 @@ pub struct CommandResponse<T> {
     /// - 成功时：`Some(data)` 包含实际数据
     /// - 失败时：`None` 表示无数据可返回
     pub data: Option<T>,
+
+    /// 执行信息
+    /// 
+    /// 目前只由执行任意命令的处理器填充；其他响应为 `None`，序列化时省略该字段，
+    /// 响应格式与之前保持一致。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<ResponseMeta>,
 }
 
+/// 命令的执行信息，定义见 `redis_service::ResponseMeta`
+pub use crate::redis_service::ResponseMeta;
+
 impl<T> CommandResponse<T> {
     /// 创建成功响应
     /// 
@@ -131,10 +145,17 @@ impl<T> CommandResponse<T> {
             success: true, 
             code: "OK".into(), 
             message: String::new(), 
-            data: Some(data) 
+            data: Some(data),
+            meta: None,
         }
     }
 
+    /// 为响应附加执行信息
+    pub fn with_meta(mut self, meta: ResponseMeta) -> Self {
+        self.meta = Some(meta);
+        self
+    }
+
     /// 创建错误响应
     /// 
     /// 创建一个表示操作失败的响应对象，包含错误代码和描述信息。
@@ -163,17 +184,359 @@ impl<T> CommandResponse<T> {
     /// 
     /// // 带验证错误的响应
     /// let response: CommandResponse<User> = 
-    ///     CommandResponse::err("VALIDATION_ERROR", "Invalid email format");
+    ///     CommandResponse::err("INVALID_ARGUMENT", "Invalid email format");
     /// ```
     pub fn err(code: impl Into<String>, message: impl Into<String>) -> Self {
         Self { 
             success: false, 
             code: code.into(), 
             message: message.into(), 
-            data: None 
+            data: None,
+            meta: None,
+        }
+    }
+}
+
+
+/// 命令处理错误
+/// 
+/// 替代散落在各处理器中的字符串错误码，每个变体对应一个固定的 `code`：
+/// 
+/// | 变体 | code |
+/// |------|------|
+/// | `NotFound` | `NOT_FOUND` |
+/// | `NameExists` | `NAME_EXISTS` |
+/// | `NotCluster` | `NOT_CLUSTER` |
+/// | `Validation` | `INVALID_ARGUMENT` |
+/// | `Timeout` | `TIMEOUT` |
+/// | `ConnectTimeout` | `CONNECT_TIMEOUT` |
+/// | `AuthFailed` | `AUTH_FAILED` |
+/// | `ConnectionRefused` | `CONNECTION_REFUSED` |
+/// | `JsonModuleMissing` | `JSON_MODULE_MISSING` |
+/// | `PolicyBlocked` | `POLICY_BLOCKED` |
+/// | `ReadOnlyConnection` | `READONLY_CONNECTION` |
+/// | `Server` | 服务端错误前缀，如 `WRONGTYPE`、`READONLY`、`MOVED`；通用的 `ERR` 前缀为 `SERVER_ERROR` |
+/// | `Redis` | `REDIS_ERROR` |
+/// | `Db` | `DB_ERROR` |
+/// 
+/// 处理器内部通过 `?` 传播的 `anyhow::Error` 会按底层错误类型自动归类，见 `From<anyhow::Error>`。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AppError {
+    /// 资源（连接、配置等）不存在
+    NotFound(String),
+    /// 同名的连接已存在
+    NameExists(String),
+    /// 仅集群模式支持的操作
+    NotCluster,
+    /// 参数校验失败，包括处理器中的检查与 `RedisService` 返回的 `InvalidArgument`
+    /// 
+    /// 此前的代码为 `VALIDATION_ERROR`，现与其他参数错误统一为 `INVALID_ARGUMENT`。
+    Validation(String),
+    /// 连接或命令超时
+    Timeout(String),
+    /// 在 `connect_timeout_ms` 内未能建立连接
+    ConnectTimeout(String),
+    /// Redis 认证失败（密码错误、未认证）
+    AuthFailed(String),
+    /// 无法连接到 Redis 服务器（连接被拒绝）
+    ConnectionRefused(String),
+    /// 服务端未加载 RedisJSON 模块，`JSON.*` 命令不可用
+    JsonModuleMissing(String),
+    /// 命令被连接的命令策略（`command_policy`）拒绝
+    PolicyBlocked(String),
+    /// 在只读连接（`readonly`）上尝试写入
+    ReadOnlyConnection(String),
+    /// 带有错误前缀的服务端错误
+    /// 
+    /// `code` 为服务端回复中的错误前缀（如 `WRONGTYPE`、`READONLY`），直接作为响应的 `code`；
+    /// 通用的 `ERR` 前缀不携带额外信息，`code` 为 `SERVER_ERROR`（例如服务端禁用了 DEBUG 命令）。
+    Server { code: String, message: String },
+    /// 其他 Redis 错误（网络、协议、无法归类的错误等）
+    Redis(String),
+    /// SQLite 配置库错误
+    Db(String),
+}
+
+impl AppError {
+    /// 返回稳定的错误代码，前端据此做程序化处理
+    pub fn code(&self) -> &str {
+        match self {
+            AppError::NotFound(_) => "NOT_FOUND",
+            AppError::NameExists(_) => "NAME_EXISTS",
+            AppError::NotCluster => "NOT_CLUSTER",
+            AppError::Validation(_) => "INVALID_ARGUMENT",
+            AppError::Timeout(_) => "TIMEOUT",
+            AppError::ConnectTimeout(_) => "CONNECT_TIMEOUT",
+            AppError::AuthFailed(_) => "AUTH_FAILED",
+            AppError::ConnectionRefused(_) => "CONNECTION_REFUSED",
+            AppError::JsonModuleMissing(_) => "JSON_MODULE_MISSING",
+            AppError::PolicyBlocked(_) => "POLICY_BLOCKED",
+            AppError::ReadOnlyConnection(_) => "READONLY_CONNECTION",
+            AppError::Server { code, .. } => code,
+            AppError::Redis(_) => "REDIS_ERROR",
+            AppError::Db(_) => "DB_ERROR",
+        }
+    }
+
+    /// 转换为失败的 `CommandResponse`
+    pub fn into_response<T>(self) -> CommandResponse<T> {
+        CommandResponse::err(self.code(), self.to_string())
+    }
+
+    /// 将处理器结果转换为 Tauri 命令的返回值
+    /// 
+    /// 错误以 `success = false` 的 `CommandResponse` 返回，而不是拒绝前端的 Promise，
+    /// 因此前端总能拿到 `code` 和 `message`。
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust
+    /// #[tauri::command]
+    /// async fn get_value(...) -> Result<CommandResponse<Option<String>>, InvokeError> {
+    ///     async fn inner(...) -> CommandResult<Option<String>> { ... }
+    ///     AppError::respond(inner(...).await)
+    /// }
+    /// ```
+    pub fn respond<T>(res: CommandResult<T>) -> Result<CommandResponse<T>, tauri::ipc::InvokeError> {
+        Ok(res.unwrap_or_else(AppError::into_response))
+    }
+
+    /// 根据 `redis::RedisError` 的类型归类
+    /// 
+    /// 服务端错误保留其错误前缀作为 `code`；通用的 `ERR` 前缀不携带额外信息，`code` 为 `SERVER_ERROR`。
+    /// 没有错误前缀的错误（网络、协议等）归为 `Redis`。
+    fn from_redis(e: &redis::RedisError, message: String) -> Self {
+        if e.kind() == redis::ErrorKind::AuthenticationFailed || matches!(e.code(), Some("NOAUTH" | "WRONGPASS")) {
+            AppError::AuthFailed(message)
+        } else if e.is_timeout() {
+            AppError::Timeout(message)
+        } else if e.is_connection_refusal() {
+            AppError::ConnectionRefused(message)
+        } else if is_json_module_missing(&e.to_string()) {
+            AppError::JsonModuleMissing(format!("RedisJSON module is not loaded on the server: {}", message))
+        } else {
+            match e.code() {
+                Some("ERR") => AppError::Server { code: "SERVER_ERROR".into(), message },
+                Some(code) => AppError::Server { code: code.to_string(), message },
+                None => AppError::Redis(message),
+            }
+        }
+    }
+}
+
+/// 判断错误是否由服务端未加载 RedisJSON 模块引起（`unknown command 'JSON.xxx'`）
+fn is_json_module_missing(message: &str) -> bool {
+    let text = message.to_ascii_lowercase();
+    text.contains("unknown command") && text.contains("json.")
+}
+
+impl std::fmt::Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AppError::NotCluster => write!(f, "operation requires cluster mode"),
+            AppError::NotFound(m)
+            | AppError::NameExists(m)
+            | AppError::Validation(m)
+            | AppError::Timeout(m)
+            | AppError::ConnectTimeout(m)
+            | AppError::AuthFailed(m)
+            | AppError::ConnectionRefused(m)
+            | AppError::JsonModuleMissing(m)
+            | AppError::PolicyBlocked(m)
+            | AppError::ReadOnlyConnection(m)
+            | AppError::Server { message: m, .. }
+            | AppError::Redis(m)
+            | AppError::Db(m) => write!(f, "{}", m),
         }
     }
 }
 
+impl std::error::Error for AppError {}
 
-pub type CommandResult<T> = anyhow::Result<CommandResponse<T>>;
\ No newline at end of file
+/// 将 `anyhow::Error` 归类为 `AppError`
+/// 
+/// 沿错误链查找底层错误：
+/// - `redis::RedisError`：认证失败映射为 `AuthFailed`，超时映射为 `Timeout`，
+///   连接被拒绝映射为 `ConnectionRefused`，服务端错误映射为 `Server`，其余为 `Redis`
+/// - `sqlx::Error`：映射为 `Db`
+/// - `redis_service::ConnectTimeout`：映射为 `ConnectTimeout`
+/// - `redis_service::InvalidArgument`：映射为 `Validation`
+/// - `redis_service::PolicyBlocked`：映射为 `PolicyBlocked`
+/// - `redis_service::ReadOnlyConnection`：映射为 `ReadOnlyConnection`
+/// - `redis_service::WrongType`：映射为 `code` 为 `WRONGTYPE` 的 `Server`，消息中包含键的实际类型（写入时还包含命令）
+/// - `tokio::time::error::Elapsed`：映射为 `Timeout`
+/// 
+/// 无法识别的错误归为 `Redis`。
+/// `message` 保留完整的上下文链。
+impl From<anyhow::Error> for AppError {
+    fn from(e: anyhow::Error) -> Self {
+        let message = format!("{:#}", e);
+        for cause in e.chain() {
+            if let Some(app) = cause.downcast_ref::<AppError>() {
+                return app.clone();
+            }
+            if let Some(re) = cause.downcast_ref::<redis::RedisError>() {
+                return AppError::from_redis(re, message);
+            }
+            if cause.downcast_ref::<sqlx::Error>().is_some() {
+                return AppError::Db(message);
+            }
+            if cause.downcast_ref::<crate::redis_service::ConnectTimeout>().is_some() {
+                return AppError::ConnectTimeout(message);
+            }
+            if cause.downcast_ref::<crate::redis_service::InvalidArgument>().is_some() {
+                return AppError::Validation(message);
+            }
+            if cause.downcast_ref::<crate::redis_service::PolicyBlocked>().is_some() {
+                return AppError::PolicyBlocked(message);
+            }
+            if cause.downcast_ref::<crate::redis_service::ReadOnlyConnection>().is_some() {
+                return AppError::ReadOnlyConnection(message);
+            }
+            if cause.downcast_ref::<crate::redis_service::WrongType>().is_some() {
+                return AppError::Server { code: "WRONGTYPE".into(), message };
+            }
+            if cause.downcast_ref::<tokio::time::error::Elapsed>().is_some() {
+                return AppError::Timeout(message);
+            }
+        }
+        AppError::Redis(message)
+    }
+}
+
+impl<T> From<AppError> for CommandResponse<T> {
+    fn from(e: AppError) -> Self {
+        e.into_response()
+    }
+}
+
+/// 命令处理器的内部结果类型
+pub type CommandResult<T> = Result<CommandResponse<T>, AppError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 错误代码是前端约定的一部分，不应随意修改
+    #[test]
+    fn test_error_codes_stable() {
+        assert_eq!(AppError::NotFound("x".into()).code(), "NOT_FOUND");
+        assert_eq!(AppError::NameExists("x".into()).code(), "NAME_EXISTS");
+        assert_eq!(AppError::NotCluster.code(), "NOT_CLUSTER");
+        assert_eq!(AppError::Validation("x".into()).code(), "INVALID_ARGUMENT");
+        assert_eq!(AppError::Timeout("x".into()).code(), "TIMEOUT");
+        assert_eq!(AppError::ConnectTimeout("x".into()).code(), "CONNECT_TIMEOUT");
+        assert_eq!(AppError::JsonModuleMissing("x".into()).code(), "JSON_MODULE_MISSING");
+        assert_eq!(AppError::PolicyBlocked("x".into()).code(), "POLICY_BLOCKED");
+        assert_eq!(AppError::ReadOnlyConnection("x".into()).code(), "READONLY_CONNECTION");
+        assert_eq!(AppError::AuthFailed("x".into()).code(), "AUTH_FAILED");
+        assert_eq!(AppError::Redis("x".into()).code(), "REDIS_ERROR");
+        assert_eq!(AppError::Db("x".into()).code(), "DB_ERROR");
+        assert_eq!(AppError::ConnectionRefused("x".into()).code(), "CONNECTION_REFUSED");
+        assert_eq!(AppError::Server { code: "WRONGTYPE".into(), message: "x".into() }.code(), "WRONGTYPE");
+    }
+
+    /// 测试转换为失败响应
+    #[test]
+    fn test_into_response() {
+        let resp: CommandResponse<String> = AppError::NotFound("service not found".into()).into_response();
+        assert!(!resp.success);
+        assert_eq!(resp.code, "NOT_FOUND");
+        assert_eq!(resp.message, "service not found");
+        assert!(resp.data.is_none());
+
+        let ok = AppError::respond::<i32>(Err(AppError::NotCluster));
+        assert_eq!(ok.unwrap().code, "NOT_CLUSTER");
+    }
+
+    /// 未附加执行信息时响应中不出现 `meta` 字段
+    #[test]
+    fn test_response_meta_serialization() {
+        let plain = serde_json::to_value(CommandResponse::ok(1)).unwrap();
+        assert!(plain.get("meta").is_none());
+
+        let resp = CommandResponse::ok(1).with_meta(ResponseMeta { duration_ms: 1.5, node: Some("10.0.0.1:7000".into()) });
+        let json = serde_json::to_value(resp).unwrap();
+        assert_eq!(json["meta"], serde_json::json!({ "duration_ms": 1.5, "node": "10.0.0.1:7000" }));
+    }
+
+    /// 测试 Redis 认证错误映射为 AuthFailed
+    #[test]
+    fn test_redis_auth_error_maps_to_auth_failed() {
+        let re = redis::RedisError::from((redis::ErrorKind::AuthenticationFailed, "Password authentication failed"));
+        let err = anyhow::Error::new(re).context("connect");
+        let app = AppError::from(err);
+        assert_eq!(app.code(), "AUTH_FAILED");
+        assert!(app.to_string().starts_with("connect"));
+    }
+
+    /// 测试其他错误的归类
+    #[test]
+    fn test_anyhow_mapping() {
+        assert_eq!(AppError::from(anyhow::anyhow!("boom")).code(), "REDIS_ERROR");
+        let db = anyhow::Error::new(sqlx::Error::RowNotFound).context("load config");
+        assert_eq!(AppError::from(db).code(), "DB_ERROR");
+        let wrapped = anyhow::Error::new(AppError::Validation("bad".into()));
+        assert_eq!(AppError::from(wrapped), AppError::Validation("bad".into()));
+        let connect = anyhow::Error::new(crate::redis_service::ConnectTimeout { timeout_ms: 3000 }).context("connect");
+        assert_eq!(AppError::from(connect).code(), "CONNECT_TIMEOUT");
+        let blocked = anyhow::Error::new(crate::redis_service::PolicyBlocked { command: "FLUSHALL".into() });
+        assert_eq!(AppError::from(blocked), AppError::PolicyBlocked("FLUSHALL is blocked by this connection's command policy".into()));
+        let readonly = anyhow::Error::new(crate::redis_service::ReadOnlyConnection { command: "SET".into() });
+        assert_eq!(AppError::from(readonly).code(), "READONLY_CONNECTION");
+        let invalid = anyhow::Error::new(crate::redis_service::InvalidArgument { message: "Pattern must not be empty".into() });
+        assert_eq!(AppError::from(invalid), AppError::Validation("Pattern must not be empty".into()));
+        let wrong = anyhow::Error::new(crate::redis_service::WrongType { key: "k".into(), actual: "list".into(), operation: None });
+        assert_eq!(AppError::from(wrong), AppError::Server { code: "WRONGTYPE".into(), message: "key 'k' is a list, use the list viewer".into() });
+    }
+
+    /// 测试连接被拒绝的映射
+    #[test]
+    fn test_connection_refused_mapping() {
+        let io = std::io::Error::from(std::io::ErrorKind::ConnectionRefused);
+        let err = anyhow::Error::new(redis::RedisError::from(io)).context("get connection");
+        assert_eq!(AppError::from(err).code(), "CONNECTION_REFUSED");
+    }
+
+    /// 测试未加载 RedisJSON 模块时的映射
+    #[test]
+    fn test_json_module_missing_detection() {
+        assert!(is_json_module_missing("ERR unknown command 'JSON.DEL', with args beginning with: 'k' '$'"));
+        assert!(is_json_module_missing("unknown command `json.get`"));
+        assert!(!is_json_module_missing("ERR unknown command 'FOO'"));
+        assert!(!is_json_module_missing("WRONGTYPE Operation against a key holding the wrong kind of value"));
+    }
+
+    /// 测试类型不匹配时返回 WRONGTYPE（需要本地 Redis）
+    #[tokio::test]
+    #[ignore]
+    async fn test_wrongtype_code() {
+        use crate::redis_service::{RedisConfig, RedisService};
+
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let key = "test:command:wrongtype";
+        svc.set(0, key, "string", None, false).await.unwrap();
+
+        let err = svc.lpush(0, key, "v").await.unwrap_err();
+        let resp: CommandResponse<i64> = AppError::from(err).into_response();
+        assert_eq!(resp.code, "WRONGTYPE");
+
+        svc.del(0, key).await.unwrap();
+    }
+
+    /// 测试服务端的通用 `ERR` 错误返回 SERVER_ERROR（需要本地 Redis，且未使用 LFU 淘汰策略）
+    #[tokio::test]
+    #[ignore]
+    async fn test_server_error_code() {
+        use crate::redis_service::{RedisConfig, RedisService};
+
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let key = "test:command:server_error";
+        svc.set(0, key, "v", None, false).await.unwrap();
+
+        let err = svc.object_freq(0, key).await.unwrap_err();
+        assert_eq!(AppError::from(err).code(), "SERVER_ERROR");
+
+        svc.del(0, key).await.unwrap();
+    }
+}
@@ -18,7 +18,8 @@
 //!     id INTEGER PRIMARY KEY,           -- 自增主键
 //!     name TEXT NOT NULL UNIQUE,        -- 连接名称（唯一）
 //!     config_json TEXT NOT NULL,        -- 配置信息的 JSON 字符串
-//!     created_at DATETIME DEFAULT CURRENT_TIMESTAMP  -- 创建时间
+//!     created_at DATETIME DEFAULT CURRENT_TIMESTAMP,  -- 创建时间
+//!     last_connected_at INTEGER         -- 最近一次使用的 Unix 毫秒时间戳，从未使用时为 NULL
 //! );
 //! ```
 //! 
@@ -39,10 +40,24 @@
 //! ```
 
 use anyhow::Result;
-use sqlx::{sqlite::SqlitePoolOptions, Pool, Sqlite};
-use std::path::Path;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::{ConnectOptions, Connection, Pool, Sqlite};
+use std::path::{Path, PathBuf};
 use crate::redis_service::RedisConfig;
 
+/// `import_from_db` 的结果
+/// 
+/// 记录从备份文件导入时各个配置的去向。
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ConfigImportReport {
+    /// 新增或已覆盖的配置
+    pub imported: Vec<String>,
+    /// 同名配置已存在且未开启覆盖，保持不变的配置
+    pub skipped: Vec<String>,
+    /// 无法解析的配置，格式为 `名称: 原因`
+    pub errors: Vec<String>,
+}
+
 /// SQLite 数据库管理器
 /// 
 /// 负责管理与 Redis 连接配置相关的所有数据库操作。
@@ -134,7 +149,8 @@ impl DbManager {
     ///     id INTEGER PRIMARY KEY,
     ///     name TEXT NOT NULL UNIQUE,
     ///     config_json TEXT NOT NULL,
-    ///     created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+    ///     created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    ///     last_connected_at INTEGER
     /// )
     /// ```
     /// 
@@ -144,6 +160,11 @@ impl DbManager {
     /// - `name`: 连接名称，用户友好的标识符，必须唯一
     /// - `config_json`: Redis 配置的 JSON 序列化字符串
     /// - `created_at`: 记录创建时间，默认为当前时间戳
+    /// - `last_connected_at`: 最近一次使用的 Unix 毫秒时间戳
+    /// 
+    /// # 迁移
+    /// 
+    /// 旧版本创建的表没有 `last_connected_at` 列，初始化时检测并补充。
     /// 
     /// # 错误处理
     /// 
@@ -155,12 +176,25 @@ impl DbManager {
                 id INTEGER PRIMARY KEY,
                 name TEXT NOT NULL UNIQUE,
                 config_json TEXT NOT NULL,
-                created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                last_connected_at INTEGER
             )
             "#
         )
         .execute(&self.pool)
         .await?;
+
+        // 旧表补充 last_connected_at 列（SQLite 不支持 ADD COLUMN IF NOT EXISTS）
+        let has_column: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM pragma_table_info('redis_configs') WHERE name = 'last_connected_at'"
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        if has_column == 0 {
+            sqlx::query("ALTER TABLE redis_configs ADD COLUMN last_connected_at INTEGER")
+                .execute(&self.pool)
+                .await?;
+        }
         Ok(())
     }
 
@@ -286,6 +320,45 @@ impl DbManager {
         Ok(result)
     }
 
+    /// 按最近使用时间获取配置列表
+    /// 
+    /// 最近使用的配置排在最前；从未使用过的配置排在最后，按名称排序。
+    pub async fn list_configs_by_recent(&self) -> Result<Vec<(String, RedisConfig)>> {
+        let rows = sqlx::query!(
+            "SELECT name, config_json FROM redis_configs ORDER BY last_connected_at DESC, name"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut result = Vec::new();
+        for row in rows {
+            let config: RedisConfig = serde_json::from_str(&row.config_json)?;
+            result.push((row.name, config));
+        }
+        Ok(result)
+    }
+
+    /// 将配置的最近使用时间更新为当前时间
+    /// 
+    /// # 返回值
+    /// 
+    /// - `true`: 更新成功
+    /// - `false`: 没有找到指定名称的配置
+    pub async fn touch_config(&self, name: &str) -> Result<bool> {
+        let now_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0);
+        let result = sqlx::query!(
+            "UPDATE redis_configs SET last_connected_at = ? WHERE name = ?",
+            now_ms,
+            name
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
     /// 删除指定的 Redis 配置
     /// 
     /// 从数据库中删除指定名称的 Redis 连接配置。
@@ -320,6 +393,135 @@ impl DbManager {
         // 检查是否影响了行数
         Ok(result.rows_affected() > 0)
     }
+
+    /// 将数据库备份到指定文件
+    /// 
+    /// 使用 SQLite 的 `VACUUM INTO` 生成备份，读取在一个事务中完成，
+    /// 应用运行期间有其他连接写入时得到的仍是一致的快照，不会复制到写了一半的页面。
+    /// 
+    /// # 参数
+    /// 
+    /// - `dest`: 备份文件路径，已存在时覆盖
+    /// 
+    /// # 注意事项
+    /// 
+    /// `VACUUM INTO` 要求目标文件不存在，因此先写入同目录下的临时文件，成功后再重命名为 `dest`，
+    /// 备份失败时不会破坏已有的同名文件。
+    /// 
+    /// `dest` 经规范化后与当前打开的数据库文件相同时返回错误，避免重命名时覆盖正在使用的数据库。
+    pub async fn backup_to(&self, dest: &Path) -> Result<()> {
+        let dest = canonical_dest(dest).await?;
+        if let Some(current) = self.file_path().await? {
+            if dest == current {
+                return Err(anyhow::anyhow!("backup destination {} is the open database", dest.display()));
+            }
+        }
+
+        let mut tmp = dest.as_os_str().to_owned();
+        tmp.push(".partial");
+        let tmp = PathBuf::from(tmp);
+        let _ = tokio::fs::remove_file(&tmp).await;
+
+        let tmp_str = tmp.to_str().ok_or_else(|| anyhow::anyhow!("backup path is not valid UTF-8"))?;
+        if let Err(e) = sqlx::query("VACUUM INTO ?").bind(tmp_str).execute(&self.pool).await {
+            let _ = tokio::fs::remove_file(&tmp).await;
+            return Err(e.into());
+        }
+        tokio::fs::rename(&tmp, &dest).await?;
+        Ok(())
+    }
+
+    /// 返回当前打开的数据库文件的规范化路径，内存数据库返回 `None`
+    async fn file_path(&self) -> Result<Option<PathBuf>> {
+        let file: Option<String> = sqlx::query_scalar("SELECT file FROM pragma_database_list WHERE name = 'main'")
+            .fetch_optional(&self.pool)
+            .await?;
+        match file.filter(|f| !f.is_empty()) {
+            Some(f) => Ok(Some(tokio::fs::canonicalize(&f).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// 从备份文件导入连接配置
+    /// 
+    /// 以只读方式打开 `backup_to` 生成的（或其他实例使用的）数据库文件，
+    /// 在一个事务中把其中的配置写入当前数据库，源文件不会被修改。
+    /// 
+    /// # 参数
+    /// 
+    /// - `src`: 源数据库文件路径
+    /// - `overwrite`: 同名配置已存在时是否覆盖；为 `false` 时保留现有配置并计入 `skipped`
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回 `ConfigImportReport`。源文件不存在或不包含配置表时返回错误；
+    /// 单个配置无法解析时只计入 `errors`，不影响其他配置的导入。
+    /// 
+    /// # 注意事项
+    /// 
+    /// 只导入配置内容，不导入最近使用时间。导入后需要调用 `AppState::reload_from_db` 才会建立新连接。
+    pub async fn import_from_db(&self, src: &Path, overwrite: bool) -> Result<ConfigImportReport> {
+        let mut source = SqliteConnectOptions::new()
+            .filename(src)
+            .read_only(true)
+            .connect()
+            .await?;
+        let rows: Vec<(String, String)> = sqlx::query_as("SELECT name, config_json FROM redis_configs ORDER BY name")
+            .fetch_all(&mut source)
+            .await;
+        source.close().await?;
+        let rows = rows?;
+
+        let upsert = if overwrite {
+            "INSERT INTO redis_configs (name, config_json) VALUES (?, ?) \
+             ON CONFLICT(name) DO UPDATE SET config_json = excluded.config_json"
+        } else {
+            "INSERT INTO redis_configs (name, config_json) VALUES (?, ?) ON CONFLICT(name) DO NOTHING"
+        };
+        let mut report = ConfigImportReport::default();
+        let mut tx = self.pool.begin().await?;
+        for (name, json) in rows {
+            // 重新序列化，旧版本写入的配置会补齐新增字段的默认值
+            let config = match serde_json::from_str::<RedisConfig>(&json) {
+                Ok(config) => serde_json::to_string(&config)?,
+                Err(e) => {
+                    report.errors.push(format!("{}: {}", name, e));
+                    continue;
+                }
+            };
+            let result = sqlx::query(upsert).bind(&name).bind(&config).execute(&mut *tx).await?;
+            if result.rows_affected() > 0 {
+                report.imported.push(name);
+            } else {
+                report.skipped.push(name);
+            }
+        }
+        tx.commit().await?;
+        Ok(report)
+    }
+
+    /// 关闭数据库连接池
+    /// 
+    /// 等待正在执行的查询完成后关闭所有连接，之后的数据库操作都会失败。
+    /// 通常只在应用退出时调用。
+    pub async fn close(&self) {
+        self.pool.close().await;
+    }
+}
+
+/// 规范化备份目标路径
+/// 
+/// 目标文件已存在时解析其完整路径（包括符号链接）；不存在时规范化所在目录后拼接文件名。
+async fn canonical_dest(dest: &Path) -> Result<PathBuf> {
+    if let Ok(path) = tokio::fs::canonicalize(dest).await {
+        return Ok(path);
+    }
+    let name = dest.file_name().ok_or_else(|| anyhow::anyhow!("backup path {} has no file name", dest.display()))?;
+    let parent = match dest.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+    Ok(tokio::fs::canonicalize(parent).await?.join(name))
 }
 
 #[cfg(test)]
@@ -388,4 +590,109 @@ mod tests {
         // 清理测试文件
         let _ = fs::remove_file(db_path);
     }
+
+    /// 测试备份文件可以作为新的数据库打开且包含原有配置
+    #[tokio::test]
+    async fn test_backup_to() {
+        let db_path = "test_backup_source.db";
+        let backup_path = "test_backup_copy.db";
+        let _ = fs::remove_file(db_path);
+        let _ = fs::remove_file(backup_path);
+        let db = DbManager::new(db_path).await.unwrap();
+
+        let cfg = RedisConfig { pool_size: 7, ..Default::default() };
+        db.save_config("alpha", &cfg).await.unwrap();
+        db.save_config("beta", &RedisConfig::default()).await.unwrap();
+
+        db.backup_to(Path::new(backup_path)).await.unwrap();
+        // 目标文件已存在时覆盖
+        db.delete_config("beta").await.unwrap();
+        db.backup_to(Path::new(backup_path)).await.unwrap();
+        assert!(fs::metadata(backup_path).unwrap().len() > 0);
+        // 不允许覆盖当前打开的数据库，即使以不同写法指定同一路径
+        assert!(db.backup_to(Path::new(db_path)).await.is_err());
+        assert!(db.backup_to(&Path::new(".").join(db_path)).await.is_err());
+        assert_eq!(db.list_configs().await.unwrap().len(), 1);
+
+        let copy = DbManager::new(backup_path).await.unwrap();
+        let list = copy.list_configs().await.unwrap();
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].0, "alpha");
+        assert_eq!(list[0].1.pool_size, 7);
+
+        db.close().await;
+        copy.close().await;
+        let _ = fs::remove_file(db_path);
+        let _ = fs::remove_file(backup_path);
+    }
+
+    /// 测试从备份文件导入配置
+    #[tokio::test]
+    async fn test_import_from_db() {
+        let src_path = "test_import_source.db";
+        let db_path = "test_import_target.db";
+        let _ = fs::remove_file(src_path);
+        let _ = fs::remove_file(db_path);
+
+        let src = DbManager::new(src_path).await.unwrap();
+        src.save_config("alpha", &RedisConfig { pool_size: 3, ..Default::default() }).await.unwrap();
+        src.save_config("beta", &RedisConfig { pool_size: 4, ..Default::default() }).await.unwrap();
+        src.close().await;
+
+        let db = DbManager::new(db_path).await.unwrap();
+        let report = db.import_from_db(Path::new(src_path), false).await.unwrap();
+        assert_eq!(report.imported, vec!["alpha", "beta"]);
+        assert!(report.skipped.is_empty() && report.errors.is_empty());
+        let list = db.list_configs().await.unwrap();
+        assert_eq!(list.iter().map(|(n, c)| (n.as_str(), c.pool_size)).collect::<Vec<_>>(), vec![("alpha", 3), ("beta", 4)]);
+
+        // 未开启覆盖时保留现有配置
+        db.save_config("alpha", &RedisConfig { pool_size: 9, ..Default::default() }).await.unwrap();
+        let report = db.import_from_db(Path::new(src_path), false).await.unwrap();
+        assert!(report.imported.is_empty());
+        assert_eq!(report.skipped, vec!["alpha", "beta"]);
+        assert_eq!(db.get_config("alpha").await.unwrap().unwrap().pool_size, 9);
+
+        let report = db.import_from_db(Path::new(src_path), true).await.unwrap();
+        assert_eq!(report.imported, vec!["alpha", "beta"]);
+        assert_eq!(db.get_config("alpha").await.unwrap().unwrap().pool_size, 3);
+
+        // 源文件不存在时返回错误，且不会创建该文件
+        assert!(db.import_from_db(Path::new("test_import_missing.db"), false).await.is_err());
+        assert!(!Path::new("test_import_missing.db").exists());
+
+        db.close().await;
+        let _ = fs::remove_file(src_path);
+        let _ = fs::remove_file(db_path);
+    }
+
+    /// 测试按最近使用时间排序
+    #[tokio::test]
+    async fn test_list_configs_by_recent() {
+        let db_path = "test_recent_configs.db";
+        let _ = fs::remove_file(db_path);
+        let db = DbManager::new(db_path).await.unwrap();
+
+        let cfg = RedisConfig::default();
+        for name in ["alpha", "beta", "gamma"] {
+            db.save_config(name, &cfg).await.unwrap();
+        }
+
+        // 从未使用过时按名称排序
+        let names = |list: Vec<(String, RedisConfig)>| list.into_iter().map(|(n, _)| n).collect::<Vec<_>>();
+        assert_eq!(names(db.list_configs_by_recent().await.unwrap()), vec!["alpha", "beta", "gamma"]);
+
+        assert!(db.touch_config("beta").await.unwrap());
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        assert!(db.touch_config("gamma").await.unwrap());
+        assert!(!db.touch_config("missing").await.unwrap());
+        assert_eq!(names(db.list_configs_by_recent().await.unwrap()), vec!["gamma", "beta", "alpha"]);
+
+        // 再次使用后排到最前
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        db.touch_config("beta").await.unwrap();
+        assert_eq!(names(db.list_configs_by_recent().await.unwrap()), vec!["beta", "gamma", "alpha"]);
+
+        let _ = fs::remove_file(db_path);
+    }
 }
\ No newline at end of file
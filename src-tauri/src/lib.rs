@@ -6,6 +6,7 @@
 //! - Redis 服务：Redis 连接和操作封装
 //! - 数据库管理：SQLite 数据库操作
 //! - 应用状态：全局状态管理
+//! - 数据传输：键值数据的文件导入导出
 
 // 模块声明
 pub mod command;      // 命令处理和响应格式
@@ -13,13 +14,21 @@ pub mod logging;      // 日志记录插件和工具
 pub mod redis_service; // Redis 服务封装
 pub mod db;          // 数据库管理
 pub mod app_state;   // 应用程序状态管理
+pub mod transfer;    // 数据导入导出
+pub mod pubsub;      // 共享 Pub/Sub 连接管理
+pub mod read_cache;  // 读结果缓存
+pub mod message_buffer; // 订阅消息缓冲
 
 // 导入必要的类型和函数
-use command::{CommandResponse, CommandResult};
-use app_state::AppState;
+use command::{AppError, CommandResponse, CommandResult};
+use app_state::{AppState, AuditEntry, ConnectionDetail, PurgeSummary};
+use message_buffer::{BufferedMessage, MessageBuffer};
+use std::sync::Arc;
 use tauri::Manager;
 use tauri::Emitter;
-use crate::redis_service::{RedisConfig, ClusterNodeInfo};
+use crate::redis_service::{RedisConfig, ClusterNodeInfo, ClusterStatus, KeyTtlInfo, ConnMetrics, ValuePage, PersistenceStatus, TestResult, ExpireFlag, KeyCount, SetExpiry, KeyPreview, KeyInspection, ParsedHashField, StreamEntry, BenchmarkResult, ServerTime, VolatileDeleteStatus, Capabilities, KeyPayload};
+use crate::db::ConfigImportReport;
+use crate::transfer::{ExportReport, ImportFormat, ImportReport, MigrateReport};
 use tauri::ipc::InvokeError;
 use serde::Serialize;
 
@@ -33,7 +42,7 @@ use serde::Serialize;
 /// 
 /// # 错误处理
 /// 
-/// 将任何内部错误转换为 Tauri 的 `InvokeError` 类型，以便前端能够正确处理。
+/// 内部错误通过 `AppError::respond` 转换为失败的 `CommandResponse`，以便前端能够正确处理。
 #[tauri::command]
 fn health_check() -> Result<CommandResponse<String>, tauri::ipc::InvokeError> {
     // 内部健康检查逻辑
@@ -44,8 +53,29 @@ fn health_check() -> Result<CommandResponse<String>, tauri::ipc::InvokeError> {
         Ok(CommandResponse::ok("ok".to_string()))
     }
     
-    // 将 anyhow 错误转换为 Tauri 的 InvokeError
-    inner().map_err(tauri::ipc::InvokeError::from_anyhow)
+    // 错误统一转换为失败的 CommandResponse
+    AppError::respond(inner())
+}
+
+/// 在运行时调整日志级别
+/// 
+/// 参数：
+/// - `level`: `trace`、`debug`、`info`、`warn` 或 `error`（不区分大小写）
+/// 
+/// 返回：调整后的级别名称（如 `DEBUG`）。级别名称无效时返回 `INVALID_ARGUMENT`。
+/// 
+/// # 前端示例
+/// 
+/// ```ts
+/// await setLogLevel('debug');
+/// ```
+#[tauri::command]
+fn set_log_level(level: String) -> Result<CommandResponse<String>, InvokeError> {
+    fn inner(level: String) -> CommandResult<String> {
+        let filter = logging::set_level(&level).map_err(AppError::Validation)?;
+        Ok(CommandResponse::ok(filter.to_string()))
+    }
+    AppError::respond(inner(level))
 }
 
 #[derive(Serialize)]
@@ -77,7 +107,23 @@ async fn list_configs(state: tauri::State<'_, AppState>) -> Result<CommandRespon
         let items = rows.into_iter().map(|(name, config)| ConfigItem { name, config }).collect();
         Ok(CommandResponse::ok(items))
     }
-    inner(state).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state).await)
+}
+
+/// 按最近使用时间列出已保存的配置
+/// 
+/// 最近使用的连接排在最前，从未使用过的连接排在最后并按名称排序。
+/// 使用时间在命令访问连接时记录，同一连接每分钟最多更新一次。
+/// 
+/// 返回 `CommandResponse<Vec<ConfigItem>>`。
+#[tauri::command]
+async fn list_configs_recent(state: tauri::State<'_, AppState>) -> Result<CommandResponse<Vec<ConfigItem>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>) -> CommandResult<Vec<ConfigItem>> {
+        let rows = state.db.list_configs_by_recent().await?;
+        let items = rows.into_iter().map(|(name, config)| ConfigItem { name, config }).collect();
+        Ok(CommandResponse::ok(items))
+    }
+    AppError::respond(inner(state).await)
 }
 
 /// 获取指定名称的 Redis 配置
@@ -106,7 +152,7 @@ async fn get_config(state: tauri::State<'_, AppState>, name: String) -> Result<C
         let cfg = state.db.get_config(&name).await?;
         Ok(CommandResponse::ok(cfg))
     }
-    inner(state, name).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name).await)
 }
 
 /// 保存（新增或更新）Redis 配置到数据库
@@ -136,13 +182,13 @@ async fn save_config(state: tauri::State<'_, AppState>, name: String, config: Re
         state.db.save_config(&name, &config).await?;
         Ok(CommandResponse::ok(true))
     }
-    inner(state, name, config).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name, config).await)
 }
 
 /// 删除指定名称的 Redis 配置
 /// 
 /// 仅从数据库中删除配置记录，**不会**影响当前内存中已运行的服务实例。
-/// 如需停止服务，请使用 `remove_connection`。
+/// 如需停止服务，请使用 `remove_connection`；如需同时终止订阅，请使用 `purge_connection`。
 /// 
 /// # 参数
 /// 
@@ -163,13 +209,75 @@ async fn delete_config(state: tauri::State<'_, AppState>, name: String) -> Resul
         let ok = state.db.delete_config(&name).await?;
         Ok(CommandResponse::ok(ok))
     }
-    inner(state, name).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name).await)
+}
+
+/// 备份保存连接配置的 SQLite 数据库
+/// 
+/// 使用 `VACUUM INTO` 生成一致的快照，应用运行期间也可以安全执行。
+/// 
+/// # 参数
+/// 
+/// - `dest_path`: 备份文件路径，已存在时覆盖
+/// 
+/// # 返回值
+/// 
+/// 返回 `CommandResponse<u64>`，即备份文件的字节数。
+/// 
+/// # 前端示例
+/// 
+/// ```ts
+/// const bytes = await backupDatabase('/Users/me/redis-mate-backup.db');
+/// ```
+#[tauri::command]
+async fn backup_database(state: tauri::State<'_, AppState>, dest_path: String) -> Result<CommandResponse<u64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, dest_path: String) -> CommandResult<u64> {
+        let dest = std::path::Path::new(&dest_path);
+        state.db.backup_to(dest).await?;
+        let size = tokio::fs::metadata(dest).await.map_err(anyhow::Error::from)?.len();
+        logging::info("DB", &format!("backup path={} bytes={}", dest_path, size));
+        Ok(CommandResponse::ok(size))
+    }
+    AppError::respond(inner(state, dest_path).await)
+}
+
+/// 从备份文件恢复或合并连接配置
+/// 
+/// 读取 `backup_database` 生成的文件，把其中的配置写入当前数据库，
+/// 随后执行增量重载，新导入的连接立即可用，未变化的连接保持不动。
+/// 
+/// # 参数
+/// 
+/// - `src_path`: 备份文件路径，只读打开，不会被修改
+/// - `overwrite`: 同名配置已存在时是否覆盖，默认 `false`（保留现有配置）
+/// 
+/// # 返回值
+/// 
+/// 返回 `CommandResponse<ConfigImportReport>`，列出导入、跳过和无法解析的配置。
+/// 
+/// # 前端示例
+/// 
+/// ```ts
+/// const report = await restoreDatabase('/Users/me/redis-mate-backup.db', true);
+/// ```
+#[tauri::command]
+async fn restore_database(state: tauri::State<'_, AppState>, src_path: String, overwrite: Option<bool>) -> Result<CommandResponse<ConfigImportReport>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, src_path: String, overwrite: Option<bool>) -> CommandResult<ConfigImportReport> {
+        let report = state.db.import_from_db(std::path::Path::new(&src_path), overwrite.unwrap_or(false)).await?;
+        logging::info("DB", &format!(
+            "restore path={} imported={} skipped={} errors={}",
+            src_path, report.imported.len(), report.skipped.len(), report.errors.len()
+        ));
+        state.reload_from_db().await?;
+        Ok(CommandResponse::ok(report))
+    }
+    AppError::respond(inner(state, src_path, overwrite).await)
 }
 
 /// 列出当前内存中的所有服务连接名称
 /// 
-/// 返回当前 `AppState` 中已初始化并运行的 Redis 服务实例名称列表。
-/// 这些服务可以直接用于执行 Redis 命令。
+/// 返回当前 `AppState` 中的 Redis 服务名称列表，按名称排序。
+/// 已保存但尚未建立连接的配置也包含在内，首次使用时才会连接。
 /// 
 /// # 返回值
 /// 
@@ -184,19 +292,30 @@ async fn delete_config(state: tauri::State<'_, AppState>, name: String) -> Resul
 #[tauri::command]
 async fn list_services(state: tauri::State<'_, AppState>) -> Result<CommandResponse<Vec<String>>, InvokeError> {
     async fn inner(state: tauri::State<'_, AppState>) -> CommandResult<Vec<String>> {
-        let map = state.services.read().await;
-        let names = map.keys().cloned().collect::<Vec<_>>();
-        Ok(CommandResponse::ok(names))
+        Ok(CommandResponse::ok(state.service_names().await))
+    }
+    AppError::respond(inner(state).await)
+}
+
+/// 列出所有连接的概况
+/// 
+/// 返回 `CommandResponse<Vec<ConnectionDetail>>`，包含每个连接的模式、主要地址（已隐藏密码）、
+/// 是否已连接、健康状况和数据库数量。健康检查并发执行且有超时，不健康的连接不会导致调用失败；
+/// 尚未建立连接的配置以 `connected: false` 列出。
+#[tauri::command]
+async fn list_connections_detailed(state: tauri::State<'_, AppState>) -> Result<CommandResponse<Vec<ConnectionDetail>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>) -> CommandResult<Vec<ConnectionDetail>> {
+        Ok(CommandResponse::ok(state.connections_detailed().await))
     }
-    inner(state).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state).await)
 }
 
 /// 从数据库重载所有连接到内存
 /// 
-/// 执行全量重载操作：
-/// 1. 清空当前内存中的所有服务实例（断开现有连接）
-/// 2. 从数据库读取所有配置
-/// 3. 重新建立连接并初始化服务
+/// 执行增量重载操作：
+/// 1. 从数据库读取所有配置
+/// 2. 与内存中的服务实例比对，为新增和变化的配置重新建立连接
+/// 3. 移除已删除配置对应的服务，未变化的连接保持不动
 /// 
 /// # 返回值
 /// 
@@ -213,7 +332,7 @@ async fn reload_services(state: tauri::State<'_, AppState>) -> Result<CommandRes
         state.reload_from_db().await?;
         Ok(CommandResponse::ok("ok".to_string()))
     }
-    inner(state).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state).await)
 }
 
 /// 检查指定服务是否存在于内存映射
@@ -233,7 +352,37 @@ async fn service_exists(state: tauri::State<'_, AppState>, name: String) -> Resu
         let map = state.services.read().await;
         Ok(CommandResponse::ok(map.contains_key(&name)))
     }
-    inner(state, name).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name).await)
+}
+
+/// 获取命令历史（审计日志）
+/// 
+/// # 参数
+/// 
+/// - `limit`: 最多返回最近的条数，不传则返回全部
+/// 
+/// # 返回值
+/// 
+/// 返回 `CommandResponse<Vec<AuditEntry>>`，按执行顺序从早到晚排列。
+#[tauri::command]
+async fn get_command_history(state: tauri::State<'_, AppState>, limit: Option<usize>) -> Result<CommandResponse<Vec<AuditEntry>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, limit: Option<usize>) -> CommandResult<Vec<AuditEntry>> {
+        let entries = state.command_history(limit).await;
+        Ok(CommandResponse::ok(entries))
+    }
+    AppError::respond(inner(state, limit).await)
+}
+
+/// 清空命令历史
+/// 
+/// 返回 `CommandResponse<bool>`，成功时为 `true`。
+#[tauri::command]
+async fn clear_command_history(state: tauri::State<'_, AppState>) -> Result<CommandResponse<bool>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>) -> CommandResult<bool> {
+        state.clear_command_history().await;
+        Ok(CommandResponse::ok(true))
+    }
+    AppError::respond(inner(state).await)
 }
 
 /// 添加新的 Redis 连接配置并建立服务实例
@@ -246,20 +395,45 @@ async fn service_exists(state: tauri::State<'_, AppState>, name: String) -> Resu
 /// 参数：
 /// - `name`: 连接名称（唯一标识）
 /// - `config`: 后端 `RedisConfig`，包含地址、模式、重试参数等
+/// - `validate`: 是否先验证连接再保存，默认 `true`；为 `false` 时直接保存，首次使用时才建立连接，
+///   适合预先配置暂时无法访问的服务器
 /// 
-/// 返回：`CommandResponse<String>`，成功返回 `"added"`
+/// 返回：`CommandResponse<String>`，成功返回 `"added"`；名称已存在时返回 `NAME_EXISTS`
 /// 
 /// 前端示例：
 /// ```ts
 /// await addConnection('local', { urls: ['redis://127.0.0.1:6379'] })
 /// ```
 #[tauri::command]
-async fn add_connection(state: tauri::State<'_, AppState>, name: String, config: RedisConfig) -> Result<CommandResponse<String>, InvokeError> {
-    async fn inner(state: tauri::State<'_, AppState>, name: String, config: RedisConfig) -> CommandResult<String> {
-        state.add_connection(&name, config).await?;
+async fn add_connection(state: tauri::State<'_, AppState>, name: String, config: RedisConfig, validate: Option<bool>) -> Result<CommandResponse<String>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, config: RedisConfig, validate: Option<bool>) -> CommandResult<String> {
+        if validate.unwrap_or(true) {
+            state.add_connection(&name, config).await?;
+        } else {
+            state.add_connection_unchecked(&name, config).await?;
+        }
         Ok(CommandResponse::ok("added".to_string()))
     }
-    inner(state, name, config).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name, config, validate).await)
+}
+
+/// 修改已有的 Redis 连接
+/// 
+/// 使用新配置重新建立连接，成功后写入数据库并替换内存中的服务实例。
+/// `add_connection` 不会覆盖同名连接，编辑连接时应调用此命令。
+/// 
+/// 参数：
+/// - `name`: 连接名称
+/// - `config`: 新的 `RedisConfig`
+/// 
+/// 返回：`CommandResponse<String>`，成功返回 `"updated"`；连接不存在时返回 `NOT_FOUND`
+#[tauri::command]
+async fn update_connection(state: tauri::State<'_, AppState>, name: String, config: RedisConfig) -> Result<CommandResponse<String>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, config: RedisConfig) -> CommandResult<String> {
+        state.update_connection(&name, config).await?;
+        Ok(CommandResponse::ok("updated".to_string()))
+    }
+    AppError::respond(inner(state, name, config).await)
 }
 
 /// 删除已保存的 Redis 连接配置并移除服务实例
@@ -274,7 +448,53 @@ async fn remove_connection(state: tauri::State<'_, AppState>, name: String) -> R
         state.remove_connection(&name).await?;
         Ok(CommandResponse::ok("removed".to_string()))
     }
-    inner(state, name).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name).await)
+}
+
+/// 彻底删除一个连接
+/// 
+/// 同时删除数据库中的配置、移除内存中的服务实例，并终止该连接上的所有订阅任务。
+/// 如只需删除其中一部分，请使用 `delete_config` 或 `remove_connection`。
+/// 
+/// 参数：
+/// - `name`: 连接名称
+/// 
+/// 返回：`CommandResponse<PurgeSummary>`，说明实际清理了哪些内容；连接不存在时各字段均为空
+/// 
+/// # 前端示例
+/// 
+/// ```ts
+/// const summary = await purgeConnection('old-redis');
+/// ```
+#[tauri::command]
+async fn purge_connection(state: tauri::State<'_, AppState>, name: String) -> Result<CommandResponse<PurgeSummary>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String) -> CommandResult<PurgeSummary> {
+        let summary = state.purge_connection(&name).await?;
+        Ok(CommandResponse::ok(summary))
+    }
+    AppError::respond(inner(state, name).await)
+}
+
+/// 重建单个连接
+/// 
+/// 从数据库读取配置并重新建立该连接，其他连接不受影响。
+/// 
+/// 返回：`CommandResponse<String>`，成功返回 `"reconnected"`；配置不存在时返回 `NOT_FOUND`
+/// 
+/// 前端示例：
+/// ```ts
+/// await reconnectService('local')
+/// ```
+#[tauri::command]
+async fn reconnect_service(state: tauri::State<'_, AppState>, name: String) -> Result<CommandResponse<String>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String) -> CommandResult<String> {
+        if state.db.get_config(&name).await?.is_none() {
+            return Err(AppError::NotFound("config not found".into()));
+        }
+        state.reconnect_service(&name).await?;
+        Ok(CommandResponse::ok("reconnected".to_string()))
+    }
+    AppError::respond(inner(state, name).await)
 }
 
 /// 对指定连接执行健康检查（`PING`）
@@ -286,14 +506,79 @@ async fn remove_connection(state: tauri::State<'_, AppState>, name: String) -> R
 #[tauri::command]
 async fn check_connection(state: tauri::State<'_, AppState>, name: String) -> Result<CommandResponse<String>, InvokeError> {
     async fn inner(state: tauri::State<'_, AppState>, name: String) -> CommandResult<String> {
-        if let Some(svc) = state.get_service(&name).await {
-            svc.check_health().await?;
+        if let Some(svc) = state.get_service(&name).await? {
+            state.audited(&name, "PING", None, svc.check_health()).await?;
             Ok(CommandResponse::ok("ok".to_string()))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name).await)
+}
+
+/// 测量指定连接的 `PING` 往返耗时
+/// 
+/// 参数：
+/// - `name`: 连接名称
+/// 
+/// 返回：`CommandResponse<f64>`，单位为毫秒
+#[tauri::command]
+async fn ping_latency(state: tauri::State<'_, AppState>, name: String) -> Result<CommandResponse<f64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String) -> CommandResult<f64> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let ms = state.audited(&name, "PING", None, svc.ping_latency()).await?;
+            Ok(CommandResponse::ok(ms))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name).await)
+}
+
+/// 获取连接的延迟指标
+/// 
+/// 返回 `CommandResponse<ConnMetrics>`，包含命令次数、平均耗时、P95 耗时、失败次数以及读缓存的命中统计。
+#[tauri::command]
+async fn get_connection_metrics(state: tauri::State<'_, AppState>, name: String) -> Result<CommandResponse<ConnMetrics>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String) -> CommandResult<ConnMetrics> {
+        if let Some(svc) = state.get_service(&name).await? {
+            Ok(CommandResponse::ok(svc.metrics_snapshot()))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name).await)
+}
+
+/// 获取连接的运行时长
+/// 
+/// 返回 `CommandResponse<u64>`，为连接建立以来经过的毫秒数。
+#[tauri::command]
+async fn get_connection_uptime(state: tauri::State<'_, AppState>, name: String) -> Result<CommandResponse<u64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String) -> CommandResult<u64> {
+        if let Some(svc) = state.get_service(&name).await? {
+            Ok(CommandResponse::ok(svc.uptime().as_millis() as u64))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name).await)
+}
+
+/// 重置连接的延迟指标
+/// 
+/// 返回 `CommandResponse<bool>`，成功为 `true`。
+#[tauri::command]
+async fn reset_connection_metrics(state: tauri::State<'_, AppState>, name: String) -> Result<CommandResponse<bool>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String) -> CommandResult<bool> {
+        if let Some(svc) = state.get_service(&name).await? {
+            svc.reset_metrics();
+            Ok(CommandResponse::ok(true))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name).await)
 }
 
 /// 读取键值（`GET`），返回 `Option<String>`
@@ -302,18 +587,19 @@ async fn check_connection(state: tauri::State<'_, AppState>, name: String) -> Re
 /// - `name`: 连接名称
 /// - `key`: 键名
 /// 
-/// 返回：`CommandResponse<Option<String>>`
+/// 返回：`CommandResponse<Option<String>>`；键不是字符串类型时返回 `WRONGTYPE` 错误，
+/// 消息中包含键的实际类型，如 `key 'k' is a list, use the list viewer`
 #[tauri::command]
 async fn get_value(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> Result<CommandResponse<Option<String>>, InvokeError> {
     async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> CommandResult<Option<String>> {
-        if let Some(svc) = state.get_service(&name).await {
-            let v: Option<String> = svc.get(db.unwrap_or(0), &key).await?;
+        if let Some(svc) = state.get_service(&name).await? {
+            let v: Option<String> = state.audited(&name, "GET", db, svc.get(svc.resolve_db(db), &key)).await?;
             Ok(CommandResponse::ok(v))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name, key, db).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name, key, db).await)
 }
 
 /// 设置键值（`SET`），可选过期时间（秒）
@@ -323,19 +609,93 @@ async fn get_value(state: tauri::State<'_, AppState>, name: String, key: String,
 /// - `key`: 键名
 /// - `value`: 字符串值
 /// - `expire_seconds`: 过期时间（秒，可选）
+/// - `keep_ttl`: 为 `true` 时保留键原有的过期时间（`KEEPTTL`），默认 `false` 会清除过期时间；
+///   同时传入 `expire_seconds` 时以后者为准
+/// 
+/// 返回：`CommandResponse<bool>`，成功 `true`
+#[tauri::command]
+async fn set_value(state: tauri::State<'_, AppState>, name: String, key: String, value: String, expire_seconds: Option<u64>, keep_ttl: Option<bool>, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, value: String, expire_seconds: Option<u64>, keep_ttl: Option<bool>, db: Option<u32>) -> CommandResult<bool> {
+        if let Some(svc) = state.get_service(&name).await? {
+            state.audited(&name, "SET", db, svc.set(svc.resolve_db(db), &key, value, expire_seconds, keep_ttl.unwrap_or(false))).await?;
+            Ok(CommandResponse::ok(true))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, value, expire_seconds, keep_ttl, db).await)
+}
+
+/// 按类型整体替换键的值
+/// 
+/// 参数：
+/// - `name`: 连接名称
+/// - `key`: 键名
+/// - `payload`: 带 `type` 标签的新值，如 `{ "type": "hash", "fields": { "f": "v" } }`、
+///   `{ "type": "list", "items": ["a"] }`，格式见 `KeyPayload`
+/// 
+/// 覆盖语义：旧值被完全替换（在同一事务中 `DEL` 后重新写入），不与原有字段或成员合并；
+/// 键原有的过期时间会被保留；集合类型的值为空时键会被删除。
 /// 
 /// 返回：`CommandResponse<bool>`，成功 `true`
+/// 
+/// # 前端示例
+/// 
+/// ```ts
+/// await writeKey('local', 'user:1', { type: 'hash', fields: { name: 'alice' } });
+/// ```
 #[tauri::command]
-async fn set_value(state: tauri::State<'_, AppState>, name: String, key: String, value: String, expire_seconds: Option<u64>, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
-    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, value: String, expire_seconds: Option<u64>, db: Option<u32>) -> CommandResult<bool> {
-        if let Some(svc) = state.get_service(&name).await {
-            svc.set(db.unwrap_or(0), &key, value, expire_seconds).await?;
+async fn write_key(state: tauri::State<'_, AppState>, name: String, key: String, payload: KeyPayload, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, payload: KeyPayload, db: Option<u32>) -> CommandResult<bool> {
+        if let Some(svc) = state.get_service(&name).await? {
+            state.audited(&name, "WRITE KEY", db, svc.write_key(svc.resolve_db(db), &key, &payload)).await?;
             Ok(CommandResponse::ok(true))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, payload, db).await)
+}
+
+/// 原子地获取并删除键（`GETDEL`）
+/// 
+/// 参数：
+/// - `name`: 连接名称
+/// - `key`: 键名
+/// 
+/// 返回：`CommandResponse<Option<String>>`，键不存在时为 `None`
+#[tauri::command]
+async fn getdel_value(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> Result<CommandResponse<Option<String>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> CommandResult<Option<String>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let v = state.audited(&name, "GETDEL", db, svc.getdel(svc.resolve_db(db), &key)).await?;
+            Ok(CommandResponse::ok(v))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, db).await)
+}
+
+/// 获取值并更新过期时间（`GETEX`）
+/// 
+/// 参数：
+/// - `name`: 连接名称
+/// - `key`: 键名
+/// - `expiry`: 过期选项（`{"EX": 秒}` / `{"PX": 毫秒}` / `{"EXAT": 时间戳}` / `"PERSIST"`，可选）
+/// 
+/// 返回：`CommandResponse<Option<String>>`，键不存在时为 `None`
+#[tauri::command]
+async fn getex_value(state: tauri::State<'_, AppState>, name: String, key: String, expiry: Option<SetExpiry>, db: Option<u32>) -> Result<CommandResponse<Option<String>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, expiry: Option<SetExpiry>, db: Option<u32>) -> CommandResult<Option<String>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let v = state.audited(&name, "GETEX", db, svc.getex(svc.resolve_db(db), &key, expiry)).await?;
+            Ok(CommandResponse::ok(v))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name, key, value, expire_seconds, db).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name, key, expiry, db).await)
 }
 
 /// 删除键（`DEL`）
@@ -348,14 +708,107 @@ async fn set_value(state: tauri::State<'_, AppState>, name: String, key: String,
 #[tauri::command]
 async fn del_key(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
     async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> CommandResult<bool> {
-        if let Some(svc) = state.get_service(&name).await {
-            let ok = svc.del(db.unwrap_or(0), &key).await?;
+        if let Some(svc) = state.get_service(&name).await? {
+            let ok = state.audited(&name, "DEL", db, svc.del(svc.resolve_db(db), &key)).await?;
             Ok(CommandResponse::ok(ok))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name, key, db).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name, key, db).await)
+}
+
+/// 仅当键设置了过期时间时删除（软删除）
+/// 
+/// 永不过期的键不会被删除，防止误删重要的持久键。
+/// 
+/// 返回：`CommandResponse<VolatileDeleteStatus>`，为 `deleted` / `skipped_persistent` / `not_found`
+#[tauri::command]
+async fn del_if_volatile(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> Result<CommandResponse<VolatileDeleteStatus>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> CommandResult<VolatileDeleteStatus> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let status = state.audited(&name, "DEL IF VOLATILE", db, svc.del_if_volatile(svc.resolve_db(db), &key)).await?;
+            Ok(CommandResponse::ok(status))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, db).await)
+}
+
+/// 随机获取一个键（RANDOMKEY）
+/// 
+/// 返回 `CommandResponse<Option<String>>`，数据库为空时为 `null`。
+#[tauri::command]
+async fn random_key(state: tauri::State<'_, AppState>, name: String, db: Option<u32>) -> Result<CommandResponse<Option<String>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, db: Option<u32>) -> CommandResult<Option<String>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let k = state.audited(&name, "RANDOMKEY", db, svc.randomkey(svc.resolve_db(db))).await?;
+            Ok(CommandResponse::ok(k))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, db).await)
+}
+
+/// 刷新键的访问时间（TOUCH）
+/// 
+/// 返回 `CommandResponse<i64>`，即存在并被更新的键数量。
+#[tauri::command]
+async fn touch_keys(state: tauri::State<'_, AppState>, name: String, keys: Vec<String>, db: Option<u32>) -> Result<CommandResponse<i64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, keys: Vec<String>, db: Option<u32>) -> CommandResult<i64> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let n = state.audited(&name, "TOUCH", db, svc.touch(svc.resolve_db(db), &keys)).await?;
+            Ok(CommandResponse::ok(n))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, keys, db).await)
+}
+
+/// 将键移动到另一个数据库（MOVE）
+/// 
+/// 返回 `CommandResponse<bool>`：源库不存在该键或目标库已有同名键时为 `false`。
+/// 集群模式不支持，返回 `INVALID_ARGUMENT`。
+#[tauri::command]
+async fn move_key(state: tauri::State<'_, AppState>, name: String, key: String, dest_db: u32, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, dest_db: u32, db: Option<u32>) -> CommandResult<bool> {
+        if let Some(svc) = state.get_service(&name).await? {
+            if svc.config().cluster {
+                return Err(AppError::Validation("MOVE is not supported in cluster mode".into()));
+            }
+            let moved = state.audited(&name, "MOVE", db, svc.move_key(svc.resolve_db(db), &key, dest_db)).await?;
+            Ok(CommandResponse::ok(moved))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, dest_db, db).await)
+}
+
+/// 原子交换两个数据库（SWAPDB）
+/// 
+/// 返回 `CommandResponse<bool>`（成功时为 `true`）。两个库相同时返回 `INVALID_ARGUMENT`；
+/// 集群模式不支持，同样返回 `INVALID_ARGUMENT`。
+#[tauri::command]
+async fn swap_databases(state: tauri::State<'_, AppState>, name: String, db1: u32, db2: u32) -> Result<CommandResponse<bool>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, db1: u32, db2: u32) -> CommandResult<bool> {
+        if let Some(svc) = state.get_service(&name).await? {
+            if svc.config().cluster {
+                return Err(AppError::Validation("SWAPDB is not supported in cluster mode".into()));
+            }
+            if db1 == db2 {
+                return Err(AppError::Validation("databases to swap must differ".into()));
+            }
+            state.audited(&name, "SWAPDB", None, svc.swapdb(db1, db2)).await?;
+            Ok(CommandResponse::ok(true))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, db1, db2).await)
 }
 
 /// 批量读取（`MGET`），返回 `Vec<Option<String>>`
@@ -363,19 +816,20 @@ async fn del_key(state: tauri::State<'_, AppState>, name: String, key: String, d
 /// 参数：
 /// - `name`: 连接名称
 /// - `keys`: 键名数组
+/// - `db`: 数据库索引（可选，默认使用连接的默认数据库）
 /// 
-/// 返回：`CommandResponse<Vec<Option<String>>>`
+/// 返回：`CommandResponse<Vec<Option<String>>>`，顺序与 `keys` 一致
 #[tauri::command]
-async fn mget_values(state: tauri::State<'_, AppState>, name: String, keys: Vec<String>) -> Result<CommandResponse<Vec<Option<String>>>, InvokeError> {
-    async fn inner(state: tauri::State<'_, AppState>, name: String, keys: Vec<String>) -> CommandResult<Vec<Option<String>>> {
-        if let Some(svc) = state.get_service(&name).await {
-            let v: Vec<Option<String>> = svc.mget(&keys).await?;
+async fn mget_values(state: tauri::State<'_, AppState>, name: String, keys: Vec<String>, db: Option<u32>) -> Result<CommandResponse<Vec<Option<String>>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, keys: Vec<String>, db: Option<u32>) -> CommandResult<Vec<Option<String>>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let v: Vec<Option<String>> = state.audited(&name, "MGET", db, svc.mget(svc.resolve_db(db), &keys)).await?;
             Ok(CommandResponse::ok(v))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name, keys).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name, keys, db).await)
 }
 
 /// 批量写入（`MSET`）
@@ -383,19 +837,20 @@ async fn mget_values(state: tauri::State<'_, AppState>, name: String, keys: Vec<
 /// 参数：
 /// - `name`: 连接名称
 /// - `items`: 二维数组 `[key, value]`
+/// - `db`: 数据库索引（可选，默认使用连接的默认数据库）
 /// 
 /// 返回：`CommandResponse<bool>`，成功 `true`
 #[tauri::command]
-async fn mset_values(state: tauri::State<'_, AppState>, name: String, items: Vec<(String, String)>) -> Result<CommandResponse<bool>, InvokeError> {
-    async fn inner(state: tauri::State<'_, AppState>, name: String, items: Vec<(String, String)>) -> CommandResult<bool> {
-        if let Some(svc) = state.get_service(&name).await {
-            svc.mset(&items).await?;
+async fn mset_values(state: tauri::State<'_, AppState>, name: String, items: Vec<(String, String)>, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, items: Vec<(String, String)>, db: Option<u32>) -> CommandResult<bool> {
+        if let Some(svc) = state.get_service(&name).await? {
+            state.audited(&name, "MSET", db, svc.mset(svc.resolve_db(db), &items)).await?;
             Ok(CommandResponse::ok(true))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name, items).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name, items, db).await)
 }
 
 /// 发布消息（`PUBLISH`）到频道
@@ -409,30 +864,34 @@ async fn mset_values(state: tauri::State<'_, AppState>, name: String, items: Vec
 #[tauri::command]
 async fn publish_message(state: tauri::State<'_, AppState>, name: String, channel: String, message: String) -> Result<CommandResponse<i64>, InvokeError> {
     async fn inner(state: tauri::State<'_, AppState>, name: String, channel: String, message: String) -> CommandResult<i64> {
-        if let Some(svc) = state.get_service(&name).await {
-            let n = svc.publish(&channel, &message).await?;
+        if let Some(svc) = state.get_service(&name).await? {
+            let n = state.audited(&name, "PUBLISH", None, svc.publish(&channel, &message)).await?;
             Ok(CommandResponse::ok(n))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name, channel, message).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name, channel, message).await)
 }
 
 /// 订阅频道（`SUBSCRIBE`），并通过事件桥接到前端
 /// 
-/// 建立一个持续的 Redis 订阅连接。当收到消息时，后端会通过 Tauri 的事件系统
-/// 将消息转发给前端。
+/// 同一连接的所有订阅复用一条共享的 Pub/Sub 连接，同一频道被多次订阅时只向服务端订阅一次。当收到消息时，
+/// 后端会通过 Tauri 的事件系统将消息转发给前端。连接断开后会自动重新订阅，
+/// 成功时发送 `redis:resubscribed` 事件，负载为频道名。
 /// 
 /// # 参数
 /// 
 /// - `name`: 连接名称
 /// - `channel`: 频道名
 /// - `event`: 前端事件名，后端将通过 `emit(event, payload)` 推送消息
+/// - `buffer_size`: 为该订阅保留最近多少条消息，供 `get_buffered_messages` 补齐未监听期间的消息；
+///   不传则不缓冲，最大 `MAX_BUFFER_SIZE`
 /// 
 /// # 返回值
 /// 
-/// 返回 `CommandResponse<String>`，成功订阅返回 `"subscribed"`。
+/// 返回 `CommandResponse<u64>`，即订阅 id，可传给 `cancel_subscription` 退订。
+/// 该频道的最后一个订阅被取消时，后端才向服务端发送 `UNSUBSCRIBE`。
 /// 
 /// # 前端示例
 /// 
@@ -440,69 +899,228 @@ async fn publish_message(state: tauri::State<'_, AppState>, name: String, channe
 /// const unlisten = await subscribeChannel('local', 'news', 'redis:news', (msg) => {
 ///   console.log('Received:', msg);
 /// });
-/// // 页面卸载时调用
+/// // 页面卸载时调用，同时取消后端订阅
 /// unlisten();
 /// ```
 #[tauri::command]
-async fn subscribe_channel(app: tauri::AppHandle, state: tauri::State<'_, AppState>, name: String, channel: String, event: String) -> Result<CommandResponse<String>, InvokeError> {
-    async fn inner(app: tauri::AppHandle, state: tauri::State<'_, AppState>, name: String, channel: String, event: String) -> CommandResult<String> {
-        if let Some(svc) = state.get_service(&name).await {
+async fn subscribe_channel(app: tauri::AppHandle, state: tauri::State<'_, AppState>, name: String, channel: String, event: String, buffer_size: Option<usize>) -> Result<CommandResponse<u64>, InvokeError> {
+    async fn inner(app: tauri::AppHandle, state: tauri::State<'_, AppState>, name: String, channel: String, event: String, buffer_size: Option<usize>) -> CommandResult<u64> {
+        if let Some(svc) = state.get_service(&name).await? {
             let ev = event.clone();
-            svc.subscribe(channel, move |payload| {
-                let _ = app.emit(&ev, payload);
+            let emitter = app.clone();
+            let resubscribed = channel.clone();
+            let buffer = buffer_size.map(|n| Arc::new(MessageBuffer::new(n)));
+            let sink = buffer.clone();
+            let source = channel.clone();
+            let handle = svc.subscribe(channel, move |payload| {
+                if let Some(sink) = &sink {
+                    sink.push(&source, &payload);
+                }
+                let _ = emitter.emit(&ev, payload);
                 true
+            }, move || {
+                // 断线重连后通知前端，界面可提示期间可能丢失了消息
+                let _ = app.emit("redis:resubscribed", resubscribed.clone());
             }).await?;
-            Ok(CommandResponse::ok("subscribed".to_string()))
+            // 登记订阅任务，可按 id 取消，应用退出时统一终止
+            let id = state.register_task(&name, handle).await;
+            if let Some(buffer) = buffer {
+                state.register_buffer(id, buffer);
+            }
+            Ok(CommandResponse::ok(id))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(app, state, name, channel, event).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(app, state, name, channel, event, buffer_size).await)
 }
 
-/// 分布式锁：尝试加锁
+/// 订阅多个频道，并通过单个事件桥接到前端
 /// 
-/// 使用 Redis 的 `SET key value NX PX ttl` 命令实现原子加锁。
+/// 所有频道与 `subscribe_channel` 共享同一个 Pub/Sub 连接。收到消息时以 `{ channel, payload }` 的形式
+/// 通过 `emit(event, message)` 推送给前端。断线重连后按频道发送 `redis:resubscribed` 事件，负载为频道名。
 /// 
 /// # 参数
 /// 
 /// - `name`: 连接名称
-/// - `resource`: 资源名（即 Redis 键名）
-/// - `token`: 锁标识（客户端随机生成，用于解锁校验）
-/// - `ttl_ms`: 锁的自动过期时间（毫秒）
+/// - `channels`: 频道列表
+/// - `event`: 前端事件名
+/// - `buffer_size`: 为该订阅保留最近多少条消息（所有频道合计），不传则不缓冲
 /// 
 /// # 返回值
 /// 
-/// 返回 `CommandResponse<bool>`：
-/// - `true`: 加锁成功
-/// - `false`: 锁已被占用
+/// 返回 `CommandResponse<u64>`，即订阅 id，可传给 `cancel_subscription` 一次性取消全部频道。
 /// 
 /// # 前端示例
 /// 
 /// ```ts
-/// const locked = await tryLock('local', 'lock:1', 'uuid', 5000);
+/// const sub = await subscribeChannels('local', ['news', 'alerts'], 'redis:multi', (msg) => {
+///   console.log(msg.channel, msg.payload);
+/// });
+/// await sub.cancel();
 /// ```
 #[tauri::command]
-async fn try_lock(state: tauri::State<'_, AppState>, name: String, resource: String, token: String, ttl_ms: u64) -> Result<CommandResponse<bool>, InvokeError> {
-    async fn inner(state: tauri::State<'_, AppState>, name: String, resource: String, token: String, ttl_ms: u64) -> CommandResult<bool> {
-        if let Some(svc) = state.get_service(&name).await {
-            let ok = svc.try_lock(&resource, &token, ttl_ms).await?;
-            Ok(CommandResponse::ok(ok))
+async fn subscribe_channels(app: tauri::AppHandle, state: tauri::State<'_, AppState>, name: String, channels: Vec<String>, event: String, buffer_size: Option<usize>) -> Result<CommandResponse<u64>, InvokeError> {
+    async fn inner(app: tauri::AppHandle, state: tauri::State<'_, AppState>, name: String, channels: Vec<String>, event: String, buffer_size: Option<usize>) -> CommandResult<u64> {
+        if channels.is_empty() {
+            return Err(AppError::Validation("channels must not be empty".into()));
+        }
+        if let Some(svc) = state.get_service(&name).await? {
+            let buffer = buffer_size.map(|n| Arc::new(MessageBuffer::new(n)));
+            let sink = buffer.clone();
+            let emitter = app.clone();
+            let handle = svc.subscribe_many(channels, move |message| {
+                if let Some(sink) = &sink {
+                    sink.push(&message.channel, &message.payload);
+                }
+                let _ = emitter.emit(&event, message);
+                true
+            }, move |channel| {
+                let _ = app.emit("redis:resubscribed", channel);
+            }).await?;
+            let id = state.register_task(&name, handle).await;
+            if let Some(buffer) = buffer {
+                state.register_buffer(id, buffer);
+            }
+            Ok(CommandResponse::ok(id))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name, resource, token, ttl_ms).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(app, state, name, channels, event, buffer_size).await)
 }
 
-/// 分布式锁：原子解锁
+/// 订阅频道并批量推送消息到前端
 /// 
-/// 使用 Lua 脚本保证解锁操作的原子性：仅当键存在且值等于 `token` 时才删除键。
+/// 高频频道逐条推送会造成界面卡顿。后端缓冲收到的消息，每隔 `flush_ms` 毫秒
+/// 或累计 `max_batch` 条时，通过 `emit(event, payloads)` 一次性推送一个字符串数组。
+/// 断线重连后发送 `redis:resubscribed` 事件，负载为频道名。
 /// 
 /// # 参数
 /// 
 /// - `name`: 连接名称
-/// - `resource`: 资源名（键）
+/// - `channel`: 频道名
+/// - `event`: 前端事件名
+/// - `flush_ms`: 刷新间隔（毫秒）
+/// - `max_batch`: 单批最多消息数
+/// - `buffer_size`: 为该订阅保留最近多少条消息，不传则不缓冲
+/// 
+/// # 返回值
+/// 
+/// 返回 `CommandResponse<u64>`，即订阅 id，可传给 `cancel_subscription` 取消订阅。
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn subscribe_channel_batched(app: tauri::AppHandle, state: tauri::State<'_, AppState>, name: String, channel: String, event: String, flush_ms: u64, max_batch: usize, buffer_size: Option<usize>) -> Result<CommandResponse<u64>, InvokeError> {
+    #[allow(clippy::too_many_arguments)]
+    async fn inner(app: tauri::AppHandle, state: tauri::State<'_, AppState>, name: String, channel: String, event: String, flush_ms: u64, max_batch: usize, buffer_size: Option<usize>) -> CommandResult<u64> {
+        if flush_ms == 0 || max_batch == 0 {
+            return Err(AppError::Validation("flush_ms and max_batch must be greater than 0".into()));
+        }
+        if let Some(svc) = state.get_service(&name).await? {
+            let buffer = buffer_size.map(|n| Arc::new(MessageBuffer::new(n)));
+            let sink = buffer.clone();
+            let source = channel.clone();
+            let resubscribed = channel.clone();
+            let emitter = app.clone();
+            let handle = svc.subscribe_batched(channel, flush_ms, max_batch, move |payloads| {
+                if let Some(sink) = &sink {
+                    for payload in &payloads {
+                        sink.push(&source, payload);
+                    }
+                }
+                let _ = emitter.emit(&event, payloads);
+                true
+            }, move || {
+                let _ = app.emit("redis:resubscribed", resubscribed.clone());
+            }).await?;
+            let id = state.register_task(&name, handle).await;
+            if let Some(buffer) = buffer {
+                state.register_buffer(id, buffer);
+            }
+            Ok(CommandResponse::ok(id))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(app, state, name, channel, event, flush_ms, max_batch, buffer_size).await)
+}
+
+/// 取消订阅
+/// 
+/// 终止 `subscribe_channels` 返回的订阅任务并关闭其连接。
+/// 
+/// 返回 `CommandResponse<bool>`，订阅不存在（已结束或已取消）时为 `false`。
+#[tauri::command]
+async fn cancel_subscription(state: tauri::State<'_, AppState>, id: u64) -> Result<CommandResponse<bool>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, id: u64) -> CommandResult<bool> {
+        Ok(CommandResponse::ok(state.cancel_task(id).await))
+    }
+    AppError::respond(inner(state, id).await)
+}
+
+/// 读取订阅缓冲的消息
+/// 
+/// 订阅时传入 `buffer_size` 才会缓冲消息。前端切换回订阅页面时调用，补齐未监听期间收到的消息。
+/// 
+/// 参数：
+/// - `subscription_id`: 订阅 id
+/// - `limit`: 最多返回的条数（取最新的），不传则返回全部
+/// 
+/// 返回：按收到的先后顺序排列的消息；订阅不存在或未启用缓冲时返回 `NOT_FOUND`
+#[tauri::command]
+async fn get_buffered_messages(state: tauri::State<'_, AppState>, subscription_id: u64, limit: Option<usize>) -> Result<CommandResponse<Vec<BufferedMessage>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, subscription_id: u64, limit: Option<usize>) -> CommandResult<Vec<BufferedMessage>> {
+        match state.buffered_messages(subscription_id, limit) {
+            Some(messages) => Ok(CommandResponse::ok(messages)),
+            None => Err(AppError::NotFound("subscription buffer not found".into())),
+        }
+    }
+    AppError::respond(inner(state, subscription_id, limit).await)
+}
+
+/// 分布式锁：尝试加锁
+/// 
+/// 使用 Redis 的 `SET key value NX PX ttl` 命令实现原子加锁。
+/// 
+/// # 参数
+/// 
+/// - `name`: 连接名称
+/// - `resource`: 资源名（即 Redis 键名）
+/// - `token`: 锁标识（客户端随机生成，用于解锁校验）
+/// - `ttl_ms`: 锁的自动过期时间（毫秒）
+/// 
+/// # 返回值
+/// 
+/// 返回 `CommandResponse<bool>`：
+/// - `true`: 加锁成功
+/// - `false`: 锁已被占用
+/// 
+/// # 前端示例
+/// 
+/// ```ts
+/// const locked = await tryLock('local', 'lock:1', 'uuid', 5000);
+/// ```
+#[tauri::command]
+async fn try_lock(state: tauri::State<'_, AppState>, name: String, resource: String, token: String, ttl_ms: u64) -> Result<CommandResponse<bool>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, resource: String, token: String, ttl_ms: u64) -> CommandResult<bool> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let ok = state.audited(&name, "SET NX PX", None, svc.try_lock(&resource, &token, ttl_ms)).await?;
+            Ok(CommandResponse::ok(ok))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, resource, token, ttl_ms).await)
+}
+
+/// 分布式锁：原子解锁
+/// 
+/// 使用 Lua 脚本保证解锁操作的原子性：仅当键存在且值等于 `token` 时才删除键。
+/// 
+/// # 参数
+/// 
+/// - `name`: 连接名称
+/// - `resource`: 资源名（键）
 /// - `token`: 锁标识（需与加锁时一致）
 /// 
 /// # 返回值
@@ -517,369 +1135,1929 @@ async fn try_lock(state: tauri::State<'_, AppState>, name: String, resource: Str
 /// await unlock('local', 'lock:1', 'uuid');
 /// ```
 #[tauri::command]
-async fn unlock(state: tauri::State<'_, AppState>, name: String, resource: String, token: String) -> Result<CommandResponse<bool>, InvokeError> {
-    async fn inner(state: tauri::State<'_, AppState>, name: String, resource: String, token: String) -> CommandResult<bool> {
-        if let Some(svc) = state.get_service(&name).await {
-            let ok = svc.unlock(&resource, &token).await?;
-            Ok(CommandResponse::ok(ok))
+async fn unlock(state: tauri::State<'_, AppState>, name: String, resource: String, token: String) -> Result<CommandResponse<bool>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, resource: String, token: String) -> CommandResult<bool> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let ok = state.audited(&name, "UNLOCK", None, svc.unlock(&resource, &token)).await?;
+            Ok(CommandResponse::ok(ok))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, resource, token).await)
+}
+
+/// 带上限的原子自增（限流计数器）
+/// 
+/// 自增后不超过 `max` 时返回新值，否则返回 `null` 且计数器保持不变；
+/// 计数器首次创建时按 `ttl_secs` 设置过期时间。
+/// 
+/// # 参数
+/// 
+/// - `max`: 计数器允许的最大值，必须为正数
+/// - `ttl_secs`: 计数器首次创建时设置的过期时间（秒）
+/// 
+/// 返回 `CommandResponse<Option<i64>>`。
+#[tauri::command]
+async fn incr_capped(state: tauri::State<'_, AppState>, name: String, key: String, max: i64, ttl_secs: Option<u64>, db: Option<u32>) -> Result<CommandResponse<Option<i64>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, max: i64, ttl_secs: Option<u64>, db: Option<u32>) -> CommandResult<Option<i64>> {
+        if max <= 0 {
+            return Err(AppError::Validation("max must be positive".into()));
+        }
+        if let Some(svc) = state.get_service(&name).await? {
+            let v = state.audited(&name, "INCR CAPPED", db, svc.incr_capped(svc.resolve_db(db), &key, max, ttl_secs)).await?;
+            Ok(CommandResponse::ok(v))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, max, ttl_secs, db).await)
+}
+
+/// 移除键的过期时间（PERSIST）
+/// 
+/// 使键变为永久有效。
+/// 
+/// # 参数
+/// 
+/// - `name`: 连接名称
+/// - `key`: 键名
+/// 
+/// # 返回值
+/// 
+/// 返回 `CommandResponse<bool>`，成功移除过期时间返回 `true`。
+/// 
+/// # 前端示例
+/// 
+/// ```ts
+/// await persistKey('local', 'mykey');
+/// ```
+#[tauri::command]
+async fn persist_key(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> CommandResult<bool> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let ok = state.audited(&name, "PERSIST", db, svc.persist(svc.resolve_db(db), &key)).await?;
+            Ok(CommandResponse::ok(ok))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, db).await)
+}
+
+/// 设置键过期时间（EXPIRE）
+/// 
+/// # 参数
+/// 
+/// - `name`: 连接名称
+/// - `key`: 键名
+/// - `seconds`: 过期时间（秒）
+/// 
+/// # 返回值
+/// 
+/// 返回 `CommandResponse<bool>`，设置成功返回 `true`。
+/// 
+/// # 前端示例
+/// 
+/// ```ts
+/// await expireKey('local', 'mykey', 60);
+/// ```
+#[tauri::command]
+async fn expire_key(state: tauri::State<'_, AppState>, name: String, key: String, seconds: u64, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, seconds: u64, db: Option<u32>) -> CommandResult<bool> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let ok = state.audited(&name, "EXPIRE", db, svc.expire(svc.resolve_db(db), &key, seconds)).await?;
+            Ok(CommandResponse::ok(ok))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, seconds, db).await)
+}
+
+/// 按条件设置过期时间（EXPIRE NX/XX/GT/LT）
+/// 
+/// # 参数
+/// 
+/// - `flag`: `"NX"` / `"XX"` / `"GT"` / `"LT"`，为空时等同于 `expire_key`
+/// 
+/// 返回 `CommandResponse<bool>`：键不存在或条件不满足时为 `false`。条件标志需要 Redis 7.0+。
+#[tauri::command]
+async fn expire_key_flag(state: tauri::State<'_, AppState>, name: String, key: String, seconds: u64, flag: Option<ExpireFlag>, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, seconds: u64, flag: Option<ExpireFlag>, db: Option<u32>) -> CommandResult<bool> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let ok = state.audited(&name, "EXPIRE", db, svc.expire_with_flag(svc.resolve_db(db), &key, seconds, flag)).await?;
+            Ok(CommandResponse::ok(ok))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, seconds, flag, db).await)
+}
+
+/// 设置键在指定时刻过期（PEXPIREAT）
+/// 
+/// # 参数
+/// 
+/// - `unix_ms`: 过期时刻（Unix 毫秒时间戳），不能为负数
+/// - `allow_past`: 是否允许过去的时刻（默认 `false`）。允许时服务端会立即删除该键
+/// 
+/// 返回 `CommandResponse<bool>`：键不存在时为 `false`。时刻已过且未允许时返回 `INVALID_ARGUMENT`。
+#[tauri::command]
+async fn pexpireat_key(state: tauri::State<'_, AppState>, name: String, key: String, unix_ms: i64, allow_past: Option<bool>, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, unix_ms: i64, allow_past: Option<bool>, db: Option<u32>) -> CommandResult<bool> {
+        if unix_ms < 0 {
+            return Err(AppError::Validation("unix_ms must not be negative".into()));
+        }
+        let now_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0);
+        if unix_ms <= now_ms && !allow_past.unwrap_or(false) {
+            return Err(AppError::Validation("expiry time is in the past; pass allow_past to delete the key immediately".into()));
+        }
+        if let Some(svc) = state.get_service(&name).await? {
+            let ok = state.audited(&name, "PEXPIREAT", db, svc.pexpire_at(svc.resolve_db(db), &key, unix_ms)).await?;
+            Ok(CommandResponse::ok(ok))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, unix_ms, allow_past, db).await)
+}
+
+/// 扫描键（SCAN）
+/// 
+/// # 参数
+/// 
+/// - `name`: 连接名称
+/// - `cursor`: 游标
+/// - `pattern`: 匹配模式（可选）
+/// - `count`: 数量（可选）
+/// - `type_filter`: 只返回该类型的键（可选），如 `string`、`hash`
+/// 
+/// # 返回值
+/// 
+/// 返回 `CommandResponse<(u64, Vec<String>)>`
+#[tauri::command]
+async fn scan_keys(state: tauri::State<'_, AppState>, name: String, db: u32, cursor: u64, pattern: Option<String>, count: Option<usize>, type_filter: Option<String>) -> Result<CommandResponse<(u64, Vec<String>)>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, db: u32, cursor: u64, pattern: Option<String>, count: Option<usize>, type_filter: Option<String>) -> CommandResult<(u64, Vec<String>)> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let res = state.audited(&name, "SCAN", Some(db), svc.scan(db, cursor, pattern, count, type_filter)).await?;
+            Ok(CommandResponse::ok(res))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, db, cursor, pattern, count, type_filter).await)
+}
+
+/// 扫描键并附带类型（SCAN + 管道化 TYPE）
+/// 
+/// 参数与 `scan_keys` 相同。
+/// 
+/// 返回 `CommandResponse<(u64, Vec<(String, String)>)>`，即 `(新游标, [(键, 类型)])`
+#[tauri::command]
+async fn scan_keys_with_types(state: tauri::State<'_, AppState>, name: String, db: u32, cursor: u64, pattern: Option<String>, count: Option<usize>) -> Result<CommandResponse<(u64, Vec<(String, String)>)>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, db: u32, cursor: u64, pattern: Option<String>, count: Option<usize>) -> CommandResult<(u64, Vec<(String, String)>)> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let res = state.audited(&name, "SCAN TYPE", Some(db), svc.scan_with_types(db, cursor, pattern, count)).await?;
+            Ok(CommandResponse::ok(res))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, db, cursor, pattern, count).await)
+}
+
+/// 查找空闲时间超过阈值的键（SCAN + 管道化 OBJECT IDLETIME）
+/// 
+/// # 参数
+/// 
+/// - `min_idle_secs`: 空闲时间阈值（秒）
+/// - `pattern`: 匹配模式（可选）
+/// - `limit`: 最多返回的键数量
+/// 
+/// 返回 `CommandResponse<Vec<(String, i64)>>`，即 `(键, 空闲秒数)` 列表。
+#[tauri::command]
+async fn find_idle_keys(state: tauri::State<'_, AppState>, name: String, min_idle_secs: i64, pattern: Option<String>, limit: usize, db: Option<u32>) -> Result<CommandResponse<Vec<(String, i64)>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, min_idle_secs: i64, pattern: Option<String>, limit: usize, db: Option<u32>) -> CommandResult<Vec<(String, i64)>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let keys = state.audited(&name, "OBJECT IDLETIME", db, svc.find_idle_keys(svc.resolve_db(db), min_idle_secs, pattern, limit)).await?;
+            Ok(CommandResponse::ok(keys))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, min_idle_secs, pattern, limit, db).await)
+}
+
+/// 统计匹配模式的键数量（SCAN MATCH 计数）
+/// 
+/// # 参数
+/// 
+/// - `pattern`: 匹配模式，如 `session:*`
+/// 
+/// 返回 `CommandResponse<KeyCount>`，达到扫描次数上限时 `complete` 为 `false`。
+#[tauri::command]
+async fn count_keys_by_pattern(state: tauri::State<'_, AppState>, name: String, pattern: String, db: Option<u32>) -> Result<CommandResponse<KeyCount>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, pattern: String, db: Option<u32>) -> CommandResult<KeyCount> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let count = state.audited(&name, "SCAN COUNT", db, svc.count_keys(svc.resolve_db(db), pattern)).await?;
+            Ok(CommandResponse::ok(count))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, pattern, db).await)
+}
+
+/// 按模式批量删除键（SCAN + UNLINK）
+/// 
+/// # 参数
+/// 
+/// - `pattern`: 匹配模式，如 `cache:*`，不能为空
+/// - `allow_wildcard_all`: 是否允许 `*` 这类匹配全部键的模式（默认 `false`，防止误清空）
+/// - `batch_size`: 每页扫描的键数量（默认 500）
+/// 
+/// 返回 `CommandResponse<u64>`，为实际删除的键数量。
+#[tauri::command]
+async fn delete_keys_by_pattern(state: tauri::State<'_, AppState>, name: String, pattern: String, allow_wildcard_all: Option<bool>, batch_size: Option<usize>, db: Option<u32>) -> Result<CommandResponse<u64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, pattern: String, allow_wildcard_all: Option<bool>, batch_size: Option<usize>, db: Option<u32>) -> CommandResult<u64> {
+        if pattern.is_empty() {
+            return Err(AppError::Validation("pattern must not be empty".into()));
+        }
+        if crate::redis_service::matches_all_keys(&pattern) && !allow_wildcard_all.unwrap_or(false) {
+            return Err(AppError::Validation(format!("pattern '{}' matches every key; set allow_wildcard_all to confirm", pattern)));
+        }
+        if let Some(svc) = state.get_service(&name).await? {
+            let batch = batch_size.unwrap_or(500);
+            let deleted = state.audited(&name, "UNLINK PATTERN", db, svc.delete_by_pattern(svc.resolve_db(db), pattern, batch)).await?;
+            Ok(CommandResponse::ok(deleted))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, pattern, allow_wildcard_all, batch_size, db).await)
+}
+
+/// 按模式批量设置过期时间（SCAN + EXPIRE）
+/// 
+/// # 参数
+/// 
+/// - `pattern`: 匹配模式，如 `temp:*`，不能为空
+/// - `seconds`: 过期时间（秒）
+/// - `batch_size`: 每页扫描的键数量（默认 500）
+/// 
+/// 返回 `CommandResponse<u64>`，为成功设置过期时间的键数量。
+#[tauri::command]
+async fn expire_keys_by_pattern(state: tauri::State<'_, AppState>, name: String, pattern: String, seconds: u64, batch_size: Option<usize>, db: Option<u32>) -> Result<CommandResponse<u64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, pattern: String, seconds: u64, batch_size: Option<usize>, db: Option<u32>) -> CommandResult<u64> {
+        if pattern.is_empty() {
+            return Err(AppError::Validation("pattern must not be empty".into()));
+        }
+        if let Some(svc) = state.get_service(&name).await? {
+            let batch = batch_size.unwrap_or(500);
+            let applied = state.audited(&name, "EXPIRE PATTERN", db, svc.expire_by_pattern(svc.resolve_db(db), pattern, seconds, batch)).await?;
+            Ok(CommandResponse::ok(applied))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, pattern, seconds, batch_size, db).await)
+}
+
+/// 获取数据库键数量（DBSIZE）
+#[tauri::command]
+async fn get_db_size(state: tauri::State<'_, AppState>, name: String, db: u32) -> Result<CommandResponse<u64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, db: u32) -> CommandResult<u64> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let size = state.audited(&name, "DBSIZE", Some(db), svc.dbsize(db)).await?;
+            Ok(CommandResponse::ok(size))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, db).await)
+}
+
+/// 获取服务端配置的数据库数量（`CONFIG GET databases`）
+/// 
+/// 结果在连接上缓存；集群模式或无法读取配置时返回 16。
+#[tauri::command]
+async fn get_database_count(state: tauri::State<'_, AppState>, name: String) -> Result<CommandResponse<u32>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String) -> CommandResult<u32> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let count = state.audited(&name, "CONFIG GET databases", None, svc.database_count()).await?;
+            Ok(CommandResponse::ok(count))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name).await)
+}
+
+/// 检测服务端支持的功能
+/// 
+/// 根据服务端版本与 `MODULE LIST` 返回 `CommandResponse<Capabilities>`，
+/// 前端据此隐藏不支持的操作（如 RedisJSON、`GETDEL`、`EXPIRE NX`）。结果在连接上缓存。
+#[tauri::command]
+async fn get_capabilities(state: tauri::State<'_, AppState>, name: String) -> Result<CommandResponse<Capabilities>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String) -> CommandResult<Capabilities> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let caps = state.audited(&name, "CAPABILITIES", None, svc.detect_capabilities()).await?;
+            Ok(CommandResponse::ok(caps))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name).await)
+}
+
+/// 获取所有数据库的键数量
+/// 
+/// 返回 `CommandResponse<Vec<(u32, u64)>>`，即 `(数据库索引, 键数量)` 列表。
+/// 集群模式只返回 0 号库。
+#[tauri::command]
+async fn get_all_db_sizes(state: tauri::State<'_, AppState>, name: String) -> Result<CommandResponse<Vec<(u32, u64)>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String) -> CommandResult<Vec<(u32, u64)>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let sizes = state.audited(&name, "DBSIZE *", None, svc.all_dbsizes()).await?;
+            Ok(CommandResponse::ok(sizes))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name).await)
+}
+
+
+/// 查询键剩余过期时间（TTL）
+/// 
+/// # 参数
+/// 
+/// - `name`: 连接名称
+/// - `key`: 键名
+/// 
+/// # 返回值
+/// 
+/// 返回 `CommandResponse<i64>`，遵循 Redis TTL 语义：
+/// - `> 0`: 剩余秒数
+/// - `-1`: 键存在但无过期时间（永久）
+/// - `-2`: 键不存在
+/// 
+/// # 前端示例
+/// 
+/// ```ts
+/// const ttl = await ttlKey('local', 'mykey');
+/// ```
+#[tauri::command]
+async fn ttl_key(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> Result<CommandResponse<i64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> CommandResult<i64> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let v = state.audited(&name, "TTL", db, svc.ttl(svc.resolve_db(db), &key)).await?;
+            Ok(CommandResponse::ok(v))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, db).await)
+}
+
+/// 查询键的过期信息
+/// 
+/// 与 `ttl_key` 不同，返回结构化的 `KeyTtlInfo`，不再使用 `-1`/`-2` 哨兵值：
+/// - `exists`: 键是否存在
+/// - `has_expiry`: 是否设置了过期时间
+/// - `ttl_ms`: 剩余毫秒数（仅在设置了过期时间时有值）
+/// 
+/// # 前端示例
+/// 
+/// ```ts
+/// const info = await keyTtlInfo('local', 'mykey');
+/// ```
+#[tauri::command]
+async fn key_ttl_info(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> Result<CommandResponse<KeyTtlInfo>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> CommandResult<KeyTtlInfo> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let info = state.audited(&name, "PTTL", db, svc.ttl_info(svc.resolve_db(db), &key)).await?;
+            Ok(CommandResponse::ok(info))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, db).await)
+}
+
+/// 删除前预览键
+/// 
+/// 返回 `KeyPreview`，不读取值的内容：
+/// - `type`: 键类型，键不存在时为 `none`
+/// - `ttl`: 剩余秒数（`-1` 永不过期，`-2` 不存在）
+/// - `element_count`: 字符串为字节长度，集合类为元素数量，其他类型为 `null`
+/// 
+/// # 前端示例
+/// 
+/// ```ts
+/// const preview = await previewKey('local', 'mykey');
+/// ```
+#[tauri::command]
+async fn preview_key(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> Result<CommandResponse<KeyPreview>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> CommandResult<KeyPreview> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let preview = state.audited(&name, "PREVIEW", db, svc.preview_key(svc.resolve_db(db), &key)).await?;
+            Ok(CommandResponse::ok(preview))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, db).await)
+}
+
+/// 批量预览多个类型未知的键
+/// 
+/// 返回与 `keys` 顺序一致的 `KeyPreview` 列表，并附带少量样本（`sample`）。
+/// 单个键读取失败时只在该键的 `error` 中记录，不会使整个请求失败。
+/// 
+/// # 前端示例
+/// 
+/// ```ts
+/// const previews = await previewKeys('local', ['user:1', 'queue', 'tags']);
+/// ```
+#[tauri::command]
+async fn preview_keys(state: tauri::State<'_, AppState>, name: String, keys: Vec<String>, db: Option<u32>) -> Result<CommandResponse<Vec<KeyPreview>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, keys: Vec<String>, db: Option<u32>) -> CommandResult<Vec<KeyPreview>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let previews = state.audited(&name, "PREVIEW", db, svc.preview_keys(svc.resolve_db(db), keys)).await?;
+            Ok(CommandResponse::ok(previews))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, keys, db).await)
+}
+
+/// 获取键检查面板所需的元数据
+/// 
+/// 一次往返返回 `KeyInspection`：
+/// - `type`: 键类型，键不存在时为 `none`
+/// - `ttl_ms`: 剩余毫秒数（`-1` 永不过期，`-2` 不存在）
+/// - `encoding`: 内部编码，如 `listpack`
+/// - `memory_usage`: 占用内存字节数，服务端不支持时为 `null`
+/// - `element_count`: 字符串为字节长度，集合类为元素数量，其他类型为 `null`
+#[tauri::command]
+async fn inspect_key(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> Result<CommandResponse<KeyInspection>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> CommandResult<KeyInspection> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let info = state.audited(&name, "INSPECT", db, svc.inspect_key(svc.resolve_db(db), &key)).await?;
+            Ok(CommandResponse::ok(info))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, db).await)
+}
+
+/// 分页读取字符串值
+/// 
+/// 基于 `STRLEN` + `GETRANGE`，返回 `ValuePage`：
+/// - `total_len`: 值的总字节长度，便于前端计算页数
+/// - `chunk`: 本页内容（`[offset, offset + length)` 范围内的字节）
+/// - `base64`: 本页为二进制数据时为 `true`，`chunk` 为 Base64 编码
+/// - `next_offset`: 下一页的起始偏移量；文本页在字符边界结束，可能小于 `offset + length`
+/// 
+/// # 前端示例
+/// 
+/// ```ts
+/// const page = await getValuePage('local', 'bigkey', 0, 4096);
+/// ```
+#[tauri::command]
+async fn get_value_page(state: tauri::State<'_, AppState>, name: String, key: String, offset: u64, length: u64, db: Option<u32>) -> Result<CommandResponse<ValuePage>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, offset: u64, length: u64, db: Option<u32>) -> CommandResult<ValuePage> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let page = state.audited(&name, "GETRANGE", db, svc.get_value_page(svc.resolve_db(db), &key, offset, length)).await?;
+            Ok(CommandResponse::ok(page))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, offset, length, db).await)
+}
+
+/// 查看键的调试信息（DEBUG OBJECT）
+///
+/// 返回 `CommandResponse<HashMap<String, String>>`，包含 `encoding`、`serializedlength` 等字段。
+/// 服务端禁用 DEBUG 命令时返回 `SERVER_ERROR`，`message` 中包含服务端的错误说明。
+#[tauri::command]
+async fn debug_object_key(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> Result<CommandResponse<std::collections::HashMap<String, String>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> CommandResult<std::collections::HashMap<String, String>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let info = state.audited(&name, "DEBUG OBJECT", db, svc.debug_object(svc.resolve_db(db), &key)).await?;
+            Ok(CommandResponse::ok(info))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, db).await)
+}
+
+/// 查询键的 LFU 访问频率（OBJECT FREQ）
+///
+/// 返回 `CommandResponse<Option<i64>>`，键不存在时为 `null`。
+/// 服务端未使用 LFU 淘汰策略时返回 `SERVER_ERROR`，`message` 中包含服务端的错误说明。
+#[tauri::command]
+async fn object_freq_key(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> Result<CommandResponse<Option<i64>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> CommandResult<Option<i64>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let freq = state.audited(&name, "OBJECT FREQ", db, svc.object_freq(svc.resolve_db(db), &key)).await?;
+            Ok(CommandResponse::ok(freq))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, db).await)
+}
+
+/// 获取集群信息（仅集群模式有效）
+/// 
+/// 返回 `CommandResponse<Vec<ClusterNodeInfo>>`
+#[tauri::command]
+async fn get_cluster_info(state: tauri::State<'_, AppState>, name: String) -> Result<CommandResponse<Vec<ClusterNodeInfo>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String) -> CommandResult<Vec<ClusterNodeInfo>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let info = state.audited(&name, "CLUSTER NODES", None, svc.get_cluster_nodes()).await?;
+            Ok(CommandResponse::ok(info))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name).await)
+}
+
+/// 获取集群整体状态（CLUSTER INFO）
+/// 
+/// 返回 `CommandResponse<ClusterStatus>`。服务端未开启集群时 `cluster_enabled` 为 `false`。
+#[tauri::command]
+async fn get_cluster_status(state: tauri::State<'_, AppState>, name: String) -> Result<CommandResponse<ClusterStatus>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String) -> CommandResult<ClusterStatus> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let status = state.audited(&name, "CLUSTER INFO", None, svc.cluster_info()).await?;
+            Ok(CommandResponse::ok(status))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name).await)
+}
+
+/// 获取键类型 (TYPE)
+#[tauri::command]
+async fn get_type(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> Result<CommandResponse<String>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> CommandResult<String> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let t = state.audited(&name, "TYPE", db, svc.get_type(svc.resolve_db(db), &key)).await?;
+            Ok(CommandResponse::ok(t))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, db).await)
+}
+
+/// 获取哈希表所有字段 (HGETALL)
+#[tauri::command]
+async fn hgetall_hash(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> Result<CommandResponse<std::collections::HashMap<String, String>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> CommandResult<std::collections::HashMap<String, String>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let res: std::collections::HashMap<String, String> = state.audited(&name, "HGETALL", db, svc.hgetall(svc.resolve_db(db), &key)).await?;
+            Ok(CommandResponse::ok(res))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, db).await)
+}
+
+/// 获取哈希表所有字段并识别 JSON 值（HGETALL）
+/// 
+/// 返回按字段名排序的 `CommandResponse<Vec<ParsedHashField>>`，
+/// 值为 JSON 对象或数组时 `is_json` 为 `true` 并附带解析后的 `json`，其余值只保留 `raw`。
+#[tauri::command]
+async fn hgetall_parsed(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> Result<CommandResponse<Vec<ParsedHashField>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> CommandResult<Vec<ParsedHashField>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let fields = state.audited(&name, "HGETALL", db, svc.hgetall_parsed(svc.resolve_db(db), &key)).await?;
+            Ok(CommandResponse::ok(fields))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, db).await)
+}
+
+#[tauri::command]
+async fn hset_field(state: tauri::State<'_, AppState>, name: String, key: String, field: String, value: String, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, field: String, value: String, db: Option<u32>) -> CommandResult<bool> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let ok = state.audited(&name, "HSET", db, svc.hset(svc.resolve_db(db), &key, &field, value)).await?;
+            Ok(CommandResponse::ok(ok))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, field, value, db).await)
+}
+
+#[tauri::command]
+async fn hdel_field(state: tauri::State<'_, AppState>, name: String, key: String, field: String, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, field: String, db: Option<u32>) -> CommandResult<bool> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let ok = state.audited(&name, "HDEL", db, svc.hdel(svc.resolve_db(db), &key, &field)).await?;
+            Ok(CommandResponse::ok(ok))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, field, db).await)
+}
+
+/// 列表左侧推入 (LPUSH)
+#[tauri::command]
+async fn lpush_list(state: tauri::State<'_, AppState>, name: String, key: String, value: String, db: Option<u32>) -> Result<CommandResponse<i64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, value: String, db: Option<u32>) -> CommandResult<i64> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let len = state.audited(&name, "LPUSH", db, svc.lpush(svc.resolve_db(db), &key, value)).await?;
+            Ok(CommandResponse::ok(len))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, value, db).await)
+}
+
+/// 列表右侧弹出 (RPOP)
+#[tauri::command]
+async fn rpop_list(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> Result<CommandResponse<Option<String>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> CommandResult<Option<String>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let val: Option<String> = state.audited(&name, "RPOP", db, svc.rpop(svc.resolve_db(db), &key)).await?;
+            Ok(CommandResponse::ok(val))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, db).await)
+}
+
+/// 从多个列表中第一个非空的列表弹出元素 (LMPOP)
+/// 
+/// # 参数
+/// 
+/// - `keys`: 按顺序检查的列表键名
+/// - `from_left`: `true` 从头部弹出，`false` 从尾部弹出
+/// - `count`: 最多弹出的元素数量
+/// 
+/// 返回 `CommandResponse<Option<(String, Vec<String>)>>`，所有列表为空时为 `None`。
+#[tauri::command]
+async fn lmpop_lists(state: tauri::State<'_, AppState>, name: String, keys: Vec<String>, from_left: bool, count: usize, db: Option<u32>) -> Result<CommandResponse<Option<(String, Vec<String>)>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, keys: Vec<String>, from_left: bool, count: usize, db: Option<u32>) -> CommandResult<Option<(String, Vec<String>)>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let v = state.audited(&name, "LMPOP", db, svc.lmpop(svc.resolve_db(db), &keys, from_left, count)).await?;
+            Ok(CommandResponse::ok(v))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, keys, from_left, count, db).await)
+}
+
+/// 原子地取出整个列表并删除该键
+/// 
+/// 在服务端脚本中执行 `LRANGE 0 -1` 与 `DEL`，返回 `CommandResponse<Vec<String>>`，
+/// 元素按列表从头到尾的顺序排列，键不存在时为空。
+#[tauri::command]
+async fn drain_list(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> Result<CommandResponse<Vec<String>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> CommandResult<Vec<String>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let v = state.audited(&name, "DRAIN LIST", db, svc.drain_list(svc.resolve_db(db), &key)).await?;
+            Ok(CommandResponse::ok(v))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, db).await)
+}
+
+/// 阻塞式从多个列表弹出元素 (BLPOP / BRPOP)
+/// 
+/// # 参数
+/// 
+/// - `keys`: 按顺序检查的列表键名
+/// - `timeout_secs`: 服务端阻塞超时（秒），必须大于 0
+/// - `from_left`: 为 `false` 时使用 BRPOP 从尾部弹出，默认从头部弹出
+/// 
+/// 返回 `CommandResponse<Option<(String, String)>>`，超时仍无元素时为 `None`。
+#[tauri::command]
+async fn blpop_lists(state: tauri::State<'_, AppState>, name: String, keys: Vec<String>, timeout_secs: f64, from_left: Option<bool>, db: Option<u32>) -> Result<CommandResponse<Option<(String, String)>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, keys: Vec<String>, timeout_secs: f64, from_left: Option<bool>, db: Option<u32>) -> CommandResult<Option<(String, String)>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let v = if from_left.unwrap_or(true) {
+                state.audited(&name, "BLPOP", db, svc.blpop(svc.resolve_db(db), &keys, timeout_secs)).await?
+            } else {
+                state.audited(&name, "BRPOP", db, svc.brpop(svc.resolve_db(db), &keys, timeout_secs)).await?
+            };
+            Ok(CommandResponse::ok(v))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, keys, timeout_secs, from_left, db).await)
+}
+
+#[tauri::command]
+async fn lrange_list(state: tauri::State<'_, AppState>, name: String, key: String, start: isize, stop: isize, db: Option<u32>) -> Result<CommandResponse<Vec<String>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, start: isize, stop: isize, db: Option<u32>) -> CommandResult<Vec<String>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let v: Vec<String> = state.audited(&name, "LRANGE", db, svc.lrange(svc.resolve_db(db), &key, start, stop)).await?;
+            Ok(CommandResponse::ok(v))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, start, stop, db).await)
+}
+
+#[tauri::command]
+async fn lpos_list(state: tauri::State<'_, AppState>, name: String, key: String, value: String, rank: Option<i64>, count: Option<usize>, db: Option<u32>) -> Result<CommandResponse<Vec<i64>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, value: String, rank: Option<i64>, count: Option<usize>, db: Option<u32>) -> CommandResult<Vec<i64>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let v = state.audited(&name, "LPOS", db, svc.lpos(svc.resolve_db(db), &key, &value, rank, count)).await?;
+            Ok(CommandResponse::ok(v))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, value, rank, count, db).await)
+}
+
+#[tauri::command]
+async fn lrem_list(state: tauri::State<'_, AppState>, name: String, key: String, count: i64, value: String, db: Option<u32>) -> Result<CommandResponse<i64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, count: i64, value: String, db: Option<u32>) -> CommandResult<i64> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let n = state.audited(&name, "LREM", db, svc.lrem(svc.resolve_db(db), &key, count, &value)).await?;
+            Ok(CommandResponse::ok(n))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, count, value, db).await)
+}
+
+/// 集合添加元素 (SADD)
+#[tauri::command]
+async fn sadd_set(state: tauri::State<'_, AppState>, name: String, key: String, value: String, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, value: String, db: Option<u32>) -> CommandResult<bool> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let added = state.audited(&name, "SADD", db, svc.sadd(svc.resolve_db(db), &key, value)).await?;
+            Ok(CommandResponse::ok(added))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, value, db).await)
+}
+
+/// 获取集合所有成员 (SMEMBERS)
+#[tauri::command]
+async fn smembers_set(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> Result<CommandResponse<Vec<String>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> CommandResult<Vec<String>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let members: Vec<String> = state.audited(&name, "SMEMBERS", db, svc.smembers(svc.resolve_db(db), &key)).await?;
+            Ok(CommandResponse::ok(members))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, db).await)
+}
+
+#[tauri::command]
+async fn srem_set(state: tauri::State<'_, AppState>, name: String, key: String, member: String, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, member: String, db: Option<u32>) -> CommandResult<bool> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let ok = state.audited(&name, "SREM", db, svc.srem(svc.resolve_db(db), &key, member)).await?;
+            Ok(CommandResponse::ok(ok))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, member, db).await)
+}
+
+#[tauri::command]
+async fn sinterstore_set(state: tauri::State<'_, AppState>, name: String, dest: String, keys: Vec<String>, db: Option<u32>) -> Result<CommandResponse<i64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, dest: String, keys: Vec<String>, db: Option<u32>) -> CommandResult<i64> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let n = state.audited(&name, "SINTERSTORE", db, svc.sinterstore(svc.resolve_db(db), &dest, &keys)).await?;
+            Ok(CommandResponse::ok(n))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, dest, keys, db).await)
+}
+
+#[tauri::command]
+async fn sunionstore_set(state: tauri::State<'_, AppState>, name: String, dest: String, keys: Vec<String>, db: Option<u32>) -> Result<CommandResponse<i64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, dest: String, keys: Vec<String>, db: Option<u32>) -> CommandResult<i64> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let n = state.audited(&name, "SUNIONSTORE", db, svc.sunionstore(svc.resolve_db(db), &dest, &keys)).await?;
+            Ok(CommandResponse::ok(n))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, dest, keys, db).await)
+}
+
+#[tauri::command]
+async fn sdiffstore_set(state: tauri::State<'_, AppState>, name: String, dest: String, keys: Vec<String>, db: Option<u32>) -> Result<CommandResponse<i64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, dest: String, keys: Vec<String>, db: Option<u32>) -> CommandResult<i64> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let n = state.audited(&name, "SDIFFSTORE", db, svc.sdiffstore(svc.resolve_db(db), &dest, &keys)).await?;
+            Ok(CommandResponse::ok(n))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, dest, keys, db).await)
+}
+
+/// 计算多个集合交集的元素数量 (SINTERCARD)
+/// 
+/// - `limit`: 数量达到该值后提前停止计算，为空时不限制
+#[tauri::command]
+async fn sintercard_sets(state: tauri::State<'_, AppState>, name: String, keys: Vec<String>, limit: Option<usize>, db: Option<u32>) -> Result<CommandResponse<i64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, keys: Vec<String>, limit: Option<usize>, db: Option<u32>) -> CommandResult<i64> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let n = state.audited(&name, "SINTERCARD", db, svc.sintercard(svc.resolve_db(db), &keys, limit)).await?;
+            Ok(CommandResponse::ok(n))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, keys, limit, db).await)
+}
+
+#[tauri::command]
+async fn zadd_zset(state: tauri::State<'_, AppState>, name: String, key: String, member: String, score: f64, db: Option<u32>) -> Result<CommandResponse<i64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, member: String, score: f64, db: Option<u32>) -> CommandResult<i64> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let n = state.audited(&name, "ZADD", db, svc.zadd(svc.resolve_db(db), &key, member, score)).await?;
+            Ok(CommandResponse::ok(n))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, member, score, db).await)
+}
+
+#[tauri::command]
+async fn zrem_zset(state: tauri::State<'_, AppState>, name: String, key: String, member: String, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, member: String, db: Option<u32>) -> CommandResult<bool> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let ok = state.audited(&name, "ZREM", db, svc.zrem(svc.resolve_db(db), &key, member)).await?;
+            Ok(CommandResponse::ok(ok))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, member, db).await)
+}
+
+#[tauri::command]
+async fn zrange_zset(state: tauri::State<'_, AppState>, name: String, key: String, start: isize, stop: isize, db: Option<u32>) -> Result<CommandResponse<Vec<(String, f64)>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, start: isize, stop: isize, db: Option<u32>) -> CommandResult<Vec<(String, f64)>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let v = state.audited(&name, "ZRANGE", db, svc.zrange_withscores(svc.resolve_db(db), &key, start, stop)).await?;
+            Ok(CommandResponse::ok(v))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, start, stop, db).await)
+}
+
+#[tauri::command]
+async fn zrangestore_zset(state: tauri::State<'_, AppState>, name: String, dest: String, src: String, start: isize, stop: isize, db: Option<u32>) -> Result<CommandResponse<i64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, dest: String, src: String, start: isize, stop: isize, db: Option<u32>) -> CommandResult<i64> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let n = state.audited(&name, "ZRANGESTORE", db, svc.zrangestore(svc.resolve_db(db), &dest, &src, start, stop)).await?;
+            Ok(CommandResponse::ok(n))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, dest, src, start, stop, db).await)
+}
+
+/// 从多个有序集合中第一个非空的集合弹出成员 (ZMPOP)
+/// 
+/// - `min`: `true` 弹出分数最小的成员，`false` 弹出分数最大的成员
+/// 
+/// 返回 `CommandResponse<Option<(String, Vec<(String, f64)>)>>`，所有集合为空时为 `None`。
+#[tauri::command]
+async fn zmpop_zsets(state: tauri::State<'_, AppState>, name: String, keys: Vec<String>, min: bool, count: usize, db: Option<u32>) -> Result<CommandResponse<Option<(String, Vec<(String, f64)>)>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, keys: Vec<String>, min: bool, count: usize, db: Option<u32>) -> CommandResult<Option<(String, Vec<(String, f64)>)>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let v = state.audited(&name, "ZMPOP", db, svc.zmpop(svc.resolve_db(db), &keys, min, count)).await?;
+            Ok(CommandResponse::ok(v))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, keys, min, count, db).await)
+}
+
+/// 计算多个有序集合交集的元素数量 (ZINTERCARD)
+/// 
+/// - `limit`: 数量达到该值后提前停止计算，为空时不限制
+#[tauri::command]
+async fn zintercard_zsets(state: tauri::State<'_, AppState>, name: String, keys: Vec<String>, limit: Option<usize>, db: Option<u32>) -> Result<CommandResponse<i64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, keys: Vec<String>, limit: Option<usize>, db: Option<u32>) -> CommandResult<i64> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let n = state.audited(&name, "ZINTERCARD", db, svc.zintercard(svc.resolve_db(db), &keys, limit)).await?;
+            Ok(CommandResponse::ok(n))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, keys, limit, db).await)
+}
+
+#[tauri::command]
+async fn json_get_value(state: tauri::State<'_, AppState>, name: String, key: String, path: Option<String>, db: Option<u32>) -> Result<CommandResponse<Option<serde_json::Value>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, path: Option<String>, db: Option<u32>) -> CommandResult<Option<serde_json::Value>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let p = path.unwrap_or("$".to_string());
+            let v = state.audited(&name, "JSON.GET", db, svc.json_get(svc.resolve_db(db), &key, &p)).await?;
+            Ok(CommandResponse::ok(v))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, path, db).await)
+}
+
+#[tauri::command]
+async fn json_set_value(state: tauri::State<'_, AppState>, name: String, key: String, path: Option<String>, value_json: String, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, path: Option<String>, value_json: String, db: Option<u32>) -> CommandResult<bool> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let p = path.unwrap_or("$".to_string());
+            let v: serde_json::Value = serde_json::from_str(&value_json)
+                .map_err(|e| AppError::Validation(format!("invalid JSON value: {}", e)))?;
+            state.audited(&name, "JSON.SET", db, svc.json_set(svc.resolve_db(db), &key, &p, &v)).await?;
+            Ok(CommandResponse::ok(true))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, path, value_json, db).await)
+}
+
+/// 批量写入 JSON 文档（JSON.MSET）
+/// 
+/// 参数：
+/// - `items`: `(键, 路径, 值)` 三元组列表，值为任意 JSON
+/// 
+/// 不支持 `JSON.MSET` 的服务端回退为管道中逐个 `JSON.SET`。返回 `CommandResponse<bool>`。
+#[tauri::command]
+async fn json_mset_values(state: tauri::State<'_, AppState>, name: String, items: Vec<(String, String, serde_json::Value)>, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, items: Vec<(String, String, serde_json::Value)>, db: Option<u32>) -> CommandResult<bool> {
+        if items.is_empty() {
+            return Err(AppError::Validation("items must not be empty".into()));
+        }
+        if let Some(svc) = state.get_service(&name).await? {
+            state.audited(&name, "JSON.MSET", db, svc.json_mset(svc.resolve_db(db), items)).await?;
+            Ok(CommandResponse::ok(true))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, items, db).await)
+}
+
+/// 获取 JSON 路径上值的类型（JSON.TYPE）
+/// 
+/// `path` 默认为 `$`。返回 `CommandResponse<Option<String>>`，路径不存在时为 `None`。
+#[tauri::command]
+async fn json_type(state: tauri::State<'_, AppState>, name: String, key: String, path: Option<String>, db: Option<u32>) -> Result<CommandResponse<Option<String>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, path: Option<String>, db: Option<u32>) -> CommandResult<Option<String>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let p = path.unwrap_or("$".to_string());
+            let t = state.audited(&name, "JSON.TYPE", db, svc.json_type(svc.resolve_db(db), &key, &p)).await?;
+            Ok(CommandResponse::ok(t))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, path, db).await)
+}
+
+/// 获取 JSON 对象的键列表（JSON.OBJKEYS）
+/// 
+/// `path` 默认为 `$`。返回 `CommandResponse<Vec<String>>`，路径不是对象时为空列表。
+#[tauri::command]
+async fn json_objkeys(state: tauri::State<'_, AppState>, name: String, key: String, path: Option<String>, db: Option<u32>) -> Result<CommandResponse<Vec<String>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, path: Option<String>, db: Option<u32>) -> CommandResult<Vec<String>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let p = path.unwrap_or("$".to_string());
+            let keys = state.audited(&name, "JSON.OBJKEYS", db, svc.json_objkeys(svc.resolve_db(db), &key, &p)).await?;
+            Ok(CommandResponse::ok(keys))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, path, db).await)
+}
+
+/// 删除 JSON 路径上的值（JSON.DEL）
+/// 
+/// `path` 默认为 `$`（删除整个文档）。返回 `CommandResponse<i64>`，即删除的值数量。
+#[tauri::command]
+async fn json_del_path(state: tauri::State<'_, AppState>, name: String, key: String, path: Option<String>, db: Option<u32>) -> Result<CommandResponse<i64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, path: Option<String>, db: Option<u32>) -> CommandResult<i64> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let p = path.unwrap_or("$".to_string());
+            let n = state.audited(&name, "JSON.DEL", db, svc.json_del(svc.resolve_db(db), &key, &p)).await?;
+            Ok(CommandResponse::ok(n))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, path, db).await)
+}
+
+/// 向 JSON 数组追加元素（JSON.ARRAPPEND）
+/// 
+/// - `values_json`: JSON 数组文本，每个元素追加为数组的一个元素
+/// 
+/// 返回 `CommandResponse<Option<i64>>`，即追加后的数组长度；路径不是数组时为 `None`。
+#[tauri::command]
+async fn json_arr_append(state: tauri::State<'_, AppState>, name: String, key: String, path: String, values_json: String, db: Option<u32>) -> Result<CommandResponse<Option<i64>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, path: String, values_json: String, db: Option<u32>) -> CommandResult<Option<i64>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let values: Vec<serde_json::Value> = serde_json::from_str(&values_json)
+                .map_err(|e| AppError::Validation(format!("values must be a JSON array: {}", e)))?;
+            let len = state.audited(&name, "JSON.ARRAPPEND", db, svc.json_arrappend(svc.resolve_db(db), &key, &path, &values)).await?;
+            Ok(CommandResponse::ok(len))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, path, values_json, db).await)
+}
+
+/// 对 JSON 数值执行增量（JSON.NUMINCRBY）
+/// 
+/// 返回 `CommandResponse<Option<f64>>`，即增加后的值；路径不是数字时为 `None`。
+#[tauri::command]
+async fn json_num_incr_by(state: tauri::State<'_, AppState>, name: String, key: String, path: String, delta: f64, db: Option<u32>) -> Result<CommandResponse<Option<f64>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, path: String, delta: f64, db: Option<u32>) -> CommandResult<Option<f64>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let v = state.audited(&name, "JSON.NUMINCRBY", db, svc.json_numincrby(svc.resolve_db(db), &key, &path, delta)).await?;
+            Ok(CommandResponse::ok(v))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, path, delta, db).await)
+}
+
+#[tauri::command]
+async fn geoadd_geo(state: tauri::State<'_, AppState>, name: String, key: String, members: Vec<(f64, f64, String)>, db: Option<u32>) -> Result<CommandResponse<i64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, members: Vec<(f64, f64, String)>, db: Option<u32>) -> CommandResult<i64> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let n = state.audited(&name, "GEOADD", db, svc.geoadd(svc.resolve_db(db), &key, members)).await?;
+            Ok(CommandResponse::ok(n))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, members, db).await)
+}
+
+#[tauri::command]
+async fn geopos_geo(state: tauri::State<'_, AppState>, name: String, key: String, members: Vec<String>, db: Option<u32>) -> Result<CommandResponse<Vec<Option<(f64, f64)>>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, members: Vec<String>, db: Option<u32>) -> CommandResult<Vec<Option<(f64, f64)>>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let v = state.audited(&name, "GEOPOS", db, svc.geopos(svc.resolve_db(db), &key, &members)).await?;
+            Ok(CommandResponse::ok(v))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, members, db).await)
+}
+
+#[tauri::command]
+async fn geodist_geo(state: tauri::State<'_, AppState>, name: String, key: String, member1: String, member2: String, unit: Option<String>, db: Option<u32>) -> Result<CommandResponse<Option<f64>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, member1: String, member2: String, unit: Option<String>, db: Option<u32>) -> CommandResult<Option<f64>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let u = unit.unwrap_or("m".to_string());
+            let d = state.audited(&name, "GEODIST", db, svc.geodist(svc.resolve_db(db), &key, &member1, &member2, &u)).await?;
+            Ok(CommandResponse::ok(d))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, member1, member2, unit, db).await)
+}
+
+#[tauri::command]
+async fn geosearch_geo(state: tauri::State<'_, AppState>, name: String, key: String, center: (f64, f64), radius: f64, unit: Option<String>, db: Option<u32>) -> Result<CommandResponse<Vec<String>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, center: (f64, f64), radius: f64, unit: Option<String>, db: Option<u32>) -> CommandResult<Vec<String>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let u = unit.unwrap_or("m".to_string());
+            let v = state.audited(&name, "GEOSEARCH", db, svc.geosearch(svc.resolve_db(db), &key, center, radius, &u)).await?;
+            Ok(CommandResponse::ok(v))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, center, radius, unit, db).await)
+}
+
+/// 在服务端排序列表、集合或有序集合（SORT）
+/// 
+/// 参数：
+/// - `by`: 按外部键排序的模式，如 `weight_*`
+/// - `limit`: `(偏移量, 数量)`
+/// - `get`: 返回外部键值的模式列表，`#` 表示元素本身
+/// - `alpha`: 按字典序排序，默认 `false`（按数值排序，遇到非数字元素时返回错误）
+/// - `desc`: 降序排列，默认 `false`
+/// 
+/// # 前端示例
+/// 
+/// ```ts
+/// const top = await sortKey('local', 'scores', { desc: true, limit: [0, 10] });
+/// ```
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn sort_key(state: tauri::State<'_, AppState>, name: String, key: String, by: Option<String>, limit: Option<(i64, i64)>, get: Option<Vec<String>>, alpha: Option<bool>, desc: Option<bool>, db: Option<u32>) -> Result<CommandResponse<Vec<String>>, InvokeError> {
+    #[allow(clippy::too_many_arguments)]
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, by: Option<String>, limit: Option<(i64, i64)>, get: Option<Vec<String>>, alpha: Option<bool>, desc: Option<bool>, db: Option<u32>) -> CommandResult<Vec<String>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let fut = svc.sort(svc.resolve_db(db), &key, by, limit, get.unwrap_or_default(), alpha.unwrap_or(false), desc.unwrap_or(false));
+            let v = state.audited(&name, "SORT", db, fut).await?;
+            Ok(CommandResponse::ok(v))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, by, limit, get, alpha, desc, db).await)
+}
+
+/// 创建流的消费者组（XGROUP CREATE）
+/// 
+/// 参数：
+/// - `id`: 起始消息 ID，默认 `$`（只消费之后写入的消息）
+/// - `mkstream`: 流不存在时自动创建，默认 `true`
+/// 
+/// 返回：新建时为 `true`，同名消费者组已存在时为 `false`
+#[tauri::command]
+async fn xgroup_create_stream(state: tauri::State<'_, AppState>, name: String, key: String, group: String, id: Option<String>, mkstream: Option<bool>, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, group: String, id: Option<String>, mkstream: Option<bool>, db: Option<u32>) -> CommandResult<bool> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let id = id.unwrap_or("$".to_string());
+            let created = state.audited(&name, "XGROUP", db, svc.xgroup_create(svc.resolve_db(db), &key, &group, &id, mkstream.unwrap_or(true))).await?;
+            Ok(CommandResponse::ok(created))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, group, id, mkstream, db).await)
+}
+
+/// 以消费者身份读取流消息（XREADGROUP）
+/// 
+/// 参数：
+/// - `streams` / `ids`: 流的键名与一一对应的起始 ID（`>` 表示新消息）
+/// - `count`: 每个流最多返回的消息数量
+/// - `block_ms`: 没有消息时阻塞等待的毫秒数，不传则立即返回
+/// 
+/// 返回：`(流的键名, 消息列表)` 列表，阻塞超时时为空
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn xreadgroup_stream(state: tauri::State<'_, AppState>, name: String, group: String, consumer: String, streams: Vec<String>, ids: Vec<String>, count: Option<usize>, block_ms: Option<u64>, db: Option<u32>) -> Result<CommandResponse<Vec<(String, Vec<StreamEntry>)>>, InvokeError> {
+    #[allow(clippy::too_many_arguments)]
+    async fn inner(state: tauri::State<'_, AppState>, name: String, group: String, consumer: String, streams: Vec<String>, ids: Vec<String>, count: Option<usize>, block_ms: Option<u64>, db: Option<u32>) -> CommandResult<Vec<(String, Vec<StreamEntry>)>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let v = state.audited(&name, "XREADGROUP", db, svc.xreadgroup(svc.resolve_db(db), &group, &consumer, &streams, &ids, count, block_ms)).await?;
+            Ok(CommandResponse::ok(v))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, group, consumer, streams, ids, count, block_ms, db).await)
+}
+
+/// 确认流消息已处理（XACK）
+/// 
+/// 返回：实际确认的消息数量
+#[tauri::command]
+async fn xack_stream(state: tauri::State<'_, AppState>, name: String, key: String, group: String, ids: Vec<String>, db: Option<u32>) -> Result<CommandResponse<i64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, group: String, ids: Vec<String>, db: Option<u32>) -> CommandResult<i64> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let n = state.audited(&name, "XACK", db, svc.xack(svc.resolve_db(db), &key, &group, &ids)).await?;
+            Ok(CommandResponse::ok(n))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, group, ids, db).await)
+}
+
+/// 删除流中的消息（XDEL）
+/// 
+/// 返回：实际删除的消息数量
+#[tauri::command]
+async fn xdel_stream(state: tauri::State<'_, AppState>, name: String, key: String, ids: Vec<String>, db: Option<u32>) -> Result<CommandResponse<i64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, ids: Vec<String>, db: Option<u32>) -> CommandResult<i64> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let n = state.audited(&name, "XDEL", db, svc.xdel(svc.resolve_db(db), &key, ids)).await?;
+            Ok(CommandResponse::ok(n))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, ids, db).await)
+}
+
+/// 裁剪流（XTRIM）
+/// 
+/// 参数：
+/// - `maxlen` / `minid`: 裁剪策略，必须且只能指定一个
+/// - `approximate`: 使用 `~` 近似裁剪，默认 `false`
+/// 
+/// 返回：被删除的消息数量
+#[tauri::command]
+async fn xtrim_stream(state: tauri::State<'_, AppState>, name: String, key: String, maxlen: Option<u64>, minid: Option<String>, approximate: Option<bool>, db: Option<u32>) -> Result<CommandResponse<i64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, maxlen: Option<u64>, minid: Option<String>, approximate: Option<bool>, db: Option<u32>) -> CommandResult<i64> {
+        if maxlen.is_some() == minid.is_some() {
+            return Err(AppError::Validation("specify exactly one of maxlen or minid".into()));
+        }
+        if let Some(svc) = state.get_service(&name).await? {
+            let n = state.audited(&name, "XTRIM", db, svc.xtrim(svc.resolve_db(db), &key, maxlen, minid, approximate.unwrap_or(false))).await?;
+            Ok(CommandResponse::ok(n))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, maxlen, minid, approximate, db).await)
+}
+
+#[tauri::command]
+async fn pfadd_hll(state: tauri::State<'_, AppState>, name: String, key: String, elements: Vec<String>, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, elements: Vec<String>, db: Option<u32>) -> CommandResult<bool> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let changed = state.audited(&name, "PFADD", db, svc.pfadd(svc.resolve_db(db), &key, elements)).await?;
+            Ok(CommandResponse::ok(changed))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, elements, db).await)
+}
+
+#[tauri::command]
+async fn pfcount_hll(state: tauri::State<'_, AppState>, name: String, keys: Vec<String>, db: Option<u32>) -> Result<CommandResponse<i64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, keys: Vec<String>, db: Option<u32>) -> CommandResult<i64> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let n = state.audited(&name, "PFCOUNT", db, svc.pfcount(svc.resolve_db(db), &keys)).await?;
+            Ok(CommandResponse::ok(n))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, keys, db).await)
+}
+
+#[tauri::command]
+async fn pfmerge_hll(state: tauri::State<'_, AppState>, name: String, dest: String, sources: Vec<String>, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, dest: String, sources: Vec<String>, db: Option<u32>) -> CommandResult<bool> {
+        if let Some(svc) = state.get_service(&name).await? {
+            state.audited(&name, "PFMERGE", db, svc.pfmerge(svc.resolve_db(db), &dest, &sources)).await?;
+            Ok(CommandResponse::ok(true))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name, resource, token).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name, dest, sources, db).await)
 }
 
-/// 移除键的过期时间（PERSIST）
-/// 
-/// 使键变为永久有效。
-/// 
-/// # 参数
-/// 
-/// - `name`: 连接名称
-/// - `key`: 键名
-/// 
-/// # 返回值
-/// 
-/// 返回 `CommandResponse<bool>`，成功移除过期时间返回 `true`。
-/// 
-/// # 前端示例
-/// 
-/// ```ts
-/// await persistKey('local', 'mykey');
-/// ```
 #[tauri::command]
-async fn persist_key(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
-    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> CommandResult<bool> {
-        if let Some(svc) = state.get_service(&name).await {
-            let ok = svc.persist(db.unwrap_or(0), &key).await?;
-            Ok(CommandResponse::ok(ok))
+async fn setbit_bitmap(state: tauri::State<'_, AppState>, name: String, key: String, offset: u64, value: bool, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, offset: u64, value: bool, db: Option<u32>) -> CommandResult<bool> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let prev = state.audited(&name, "SETBIT", db, svc.setbit(svc.resolve_db(db), &key, offset, value)).await?;
+            Ok(CommandResponse::ok(prev))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name, key, db).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name, key, offset, value, db).await)
 }
 
-/// 设置键过期时间（EXPIRE）
-/// 
-/// # 参数
-/// 
-/// - `name`: 连接名称
-/// - `key`: 键名
-/// - `seconds`: 过期时间（秒）
+#[tauri::command]
+async fn getbit_bitmap(state: tauri::State<'_, AppState>, name: String, key: String, offset: u64, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, offset: u64, db: Option<u32>) -> CommandResult<bool> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let bit = state.audited(&name, "GETBIT", db, svc.getbit(svc.resolve_db(db), &key, offset)).await?;
+            Ok(CommandResponse::ok(bit))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, offset, db).await)
+}
+
+#[tauri::command]
+async fn bitcount_bitmap(state: tauri::State<'_, AppState>, name: String, key: String, range: Option<(i64, i64)>, db: Option<u32>) -> Result<CommandResponse<i64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, range: Option<(i64, i64)>, db: Option<u32>) -> CommandResult<i64> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let n = state.audited(&name, "BITCOUNT", db, svc.bitcount(svc.resolve_db(db), &key, range)).await?;
+            Ok(CommandResponse::ok(n))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key, range, db).await)
+}
+
+#[tauri::command]
+async fn bitop_bitmap(state: tauri::State<'_, AppState>, name: String, op: String, dest: String, keys: Vec<String>, db: Option<u32>) -> Result<CommandResponse<i64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, op: String, dest: String, keys: Vec<String>, db: Option<u32>) -> CommandResult<i64> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let n = state.audited(&name, "BITOP", db, svc.bitop(svc.resolve_db(db), &op, &dest, &keys)).await?;
+            Ok(CommandResponse::ok(n))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, op, dest, keys, db).await)
+}
+
+/// 等待副本确认写入（WAIT）
 /// 
-/// # 返回值
+/// 达到 `num_replicas` 个副本确认后立即返回，否则在 `timeout_ms` 后返回已确认的数量。
 /// 
-/// 返回 `CommandResponse<bool>`，设置成功返回 `true`。
+/// 返回 `CommandResponse<i64>`，即确认的副本数量。
+#[tauri::command]
+async fn wait_replicas(state: tauri::State<'_, AppState>, name: String, num_replicas: usize, timeout_ms: u64) -> Result<CommandResponse<i64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, num_replicas: usize, timeout_ms: u64) -> CommandResult<i64> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let n = state.audited(&name, "WAIT", None, svc.wait(num_replicas, timeout_ms)).await?;
+            Ok(CommandResponse::ok(n))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, num_replicas, timeout_ms).await)
+}
+
+/// 获取最近一次成功保存快照的时间（LASTSAVE）
 /// 
-/// # 前端示例
+/// 返回 `CommandResponse<i64>`，即 Unix 时间戳（秒）。
+#[tauri::command]
+async fn last_save(state: tauri::State<'_, AppState>, name: String) -> Result<CommandResponse<i64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String) -> CommandResult<i64> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let ts = state.audited(&name, "LASTSAVE", None, svc.lastsave()).await?;
+            Ok(CommandResponse::ok(ts))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name).await)
+}
+
+/// 获取服务端时间及与本地时钟的偏差（TIME）
 /// 
-/// ```ts
-/// await expireKey('local', 'mykey', 60);
-/// ```
+/// 返回 `CommandResponse<ServerTime>`，`skew_ms` 为服务端时间减去本地时间（毫秒）。
+/// 分布式锁等依赖时钟的功能可据此提示时钟不同步。
 #[tauri::command]
-async fn expire_key(state: tauri::State<'_, AppState>, name: String, key: String, seconds: u64, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
-    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, seconds: u64, db: Option<u32>) -> CommandResult<bool> {
-        if let Some(svc) = state.get_service(&name).await {
-            let ok = svc.expire(db.unwrap_or(0), &key, seconds).await?;
-            Ok(CommandResponse::ok(ok))
+async fn get_server_time(state: tauri::State<'_, AppState>, name: String) -> Result<CommandResponse<ServerTime>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String) -> CommandResult<ServerTime> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let time = state.audited(&name, "TIME", None, svc.server_time_skew()).await?;
+            Ok(CommandResponse::ok(time))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name, key, seconds, db).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name).await)
 }
 
-/// 扫描键（SCAN）
+/// 让服务端休眠指定秒数（DEBUG SLEEP）
 /// 
-/// # 参数
+/// 用于测试界面的加载状态与超时处理。会阻塞整个服务端，仅应在测试实例上使用。
+/// `seconds` 超出 `0..=60` 时返回 `INVALID_ARGUMENT`；服务端禁用 DEBUG 命令时返回服务端错误。
+#[tauri::command]
+async fn debug_sleep(state: tauri::State<'_, AppState>, name: String, seconds: f64) -> Result<CommandResponse<bool>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, seconds: f64) -> CommandResult<bool> {
+        if !seconds.is_finite() || !(0.0..=redis_service::DEBUG_SLEEP_MAX_SECS).contains(&seconds) {
+            return Err(AppError::Validation(format!("seconds must be between 0 and {}", redis_service::DEBUG_SLEEP_MAX_SECS)));
+        }
+        if let Some(svc) = state.get_service(&name).await? {
+            state.audited(&name, "DEBUG SLEEP", None, svc.debug_sleep(seconds)).await?;
+            Ok(CommandResponse::ok(true))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, seconds).await)
+}
+
+/// 同步保存快照（SAVE）
 /// 
-/// - `name`: 连接名称
-/// - `cursor`: 游标
-/// - `pattern`: 匹配模式（可选）
-/// - `count`: 数量（可选）
+/// 注意：SAVE 会阻塞 Redis 服务器直到保存完成，期间其他客户端的请求都会等待。
 /// 
-/// # 返回值
+/// 返回 `CommandResponse<String>`，成功返回 `"saved"`。
+#[tauri::command]
+async fn save_snapshot(state: tauri::State<'_, AppState>, name: String) -> Result<CommandResponse<String>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String) -> CommandResult<String> {
+        if let Some(svc) = state.get_service(&name).await? {
+            state.audited(&name, "SAVE", None, svc.save()).await?;
+            Ok(CommandResponse::ok("saved".to_string()))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name).await)
+}
+
+/// 触发后台 AOF 重写（BGREWRITEAOF）
 /// 
-/// 返回 `CommandResponse<(u64, Vec<String>)>`
+/// 返回 `CommandResponse<String>`，成功返回 `"started"`。
 #[tauri::command]
-async fn scan_keys(state: tauri::State<'_, AppState>, name: String, db: u32, cursor: u64, pattern: Option<String>, count: Option<usize>) -> Result<CommandResponse<(u64, Vec<String>)>, InvokeError> {
-    async fn inner(state: tauri::State<'_, AppState>, name: String, db: u32, cursor: u64, pattern: Option<String>, count: Option<usize>) -> CommandResult<(u64, Vec<String>)> {
-        if let Some(svc) = state.get_service(&name).await {
-            let res = svc.scan(db, cursor, pattern, count).await?;
-            Ok(CommandResponse::ok(res))
+async fn bgrewrite_aof(state: tauri::State<'_, AppState>, name: String) -> Result<CommandResponse<String>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String) -> CommandResult<String> {
+        if let Some(svc) = state.get_service(&name).await? {
+            state.audited(&name, "BGREWRITEAOF", None, svc.bgrewriteaof()).await?;
+            Ok(CommandResponse::ok("started".to_string()))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name, db, cursor, pattern, count).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name).await)
 }
 
-/// 获取数据库键数量（DBSIZE）
+/// 获取 RDB/AOF 持久化状态（INFO persistence）
+/// 
+/// 返回 `CommandResponse<PersistenceStatus>`。
 #[tauri::command]
-async fn get_db_size(state: tauri::State<'_, AppState>, name: String, db: u32) -> Result<CommandResponse<u64>, InvokeError> {
-    async fn inner(state: tauri::State<'_, AppState>, name: String, db: u32) -> CommandResult<u64> {
-        if let Some(svc) = state.get_service(&name).await {
-            let size = svc.dbsize(db).await?;
-            Ok(CommandResponse::ok(size))
+async fn get_persistence_status(state: tauri::State<'_, AppState>, name: String) -> Result<CommandResponse<PersistenceStatus>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String) -> CommandResult<PersistenceStatus> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let status = state.audited(&name, "INFO", None, svc.persistence_status()).await?;
+            Ok(CommandResponse::ok(status))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name, db).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name).await)
 }
 
+/// 哨兵主节点信息
+/// 
+/// - `master_name`: 查询的主节点名称
+/// - `addr`: 当前主节点地址 `(ip, port)`，哨兵未监控该名称时为 `None`
+/// - `masters`: 哨兵监控的所有主节点（`SENTINEL MASTERS` 的字段映射）
+#[derive(Serialize)]
+struct SentinelMasterInfo {
+    master_name: String,
+    addr: Option<(String, u16)>,
+    masters: Vec<std::collections::HashMap<String, String>>,
+}
 
-/// 查询键剩余过期时间（TTL）
+/// 获取哨兵主节点信息
 /// 
-/// # 参数
+/// 直接连接 `sentinel_urls` 中的哨兵查询当前主节点地址。
+/// `master` 省略时使用连接配置中的 `sentinel_master_name`。
 /// 
-/// - `name`: 连接名称
-/// - `key`: 键名
+/// 返回 `CommandResponse<SentinelMasterInfo>`。
+#[tauri::command]
+async fn sentinel_master_info(state: tauri::State<'_, AppState>, name: String, master: Option<String>) -> Result<CommandResponse<SentinelMasterInfo>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, master: Option<String>) -> CommandResult<SentinelMasterInfo> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let Some(master_name) = master.or_else(|| svc.config().sentinel_master_name.clone()) else {
+                return Err(AppError::Validation("sentinel master name required".into()));
+            };
+            let addr = state.audited(&name, "SENTINEL GET-MASTER-ADDR-BY-NAME", None, svc.sentinel_get_master_addr(&master_name)).await?;
+            let masters = state.audited(&name, "SENTINEL MASTERS", None, svc.sentinel_masters()).await?;
+            Ok(CommandResponse::ok(SentinelMasterInfo { master_name, addr, masters }))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, master).await)
+}
+
+/// 手动触发哨兵故障转移（SENTINEL FAILOVER）
 /// 
-/// # 返回值
+/// `master` 省略时使用连接配置中的 `sentinel_master_name`。
 /// 
-/// 返回 `CommandResponse<i64>`，遵循 Redis TTL 语义：
-/// - `> 0`: 剩余秒数
-/// - `-1`: 键存在但无过期时间（永久）
-/// - `-2`: 键不存在
+/// 返回 `CommandResponse<String>`，成功返回 `"failover started"`。
+#[tauri::command]
+async fn sentinel_trigger_failover(state: tauri::State<'_, AppState>, name: String, master: Option<String>) -> Result<CommandResponse<String>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, master: Option<String>) -> CommandResult<String> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let Some(master_name) = master.or_else(|| svc.config().sentinel_master_name.clone()) else {
+                return Err(AppError::Validation("sentinel master name required".into()));
+            };
+            state.audited(&name, "SENTINEL FAILOVER", None, svc.sentinel_failover(&master_name)).await?;
+            Ok(CommandResponse::ok("failover started".to_string()))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, master).await)
+}
+
+/// 计算键所属的集群槽位（CLUSTER KEYSLOT）
 /// 
-/// # 前端示例
+/// 在本地计算，单机模式下同样可用。返回 `CommandResponse<u16>`。
+#[tauri::command]
+async fn cluster_keyslot(state: tauri::State<'_, AppState>, name: String, key: String) -> Result<CommandResponse<u16>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String) -> CommandResult<u16> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let slot = svc.cluster_keyslot(&key).await?;
+            Ok(CommandResponse::ok(slot))
+        } else {
+            Err(AppError::NotFound("service not found".into()))
+        }
+    }
+    AppError::respond(inner(state, name, key).await)
+}
+
+/// 统计槽位中的键数量（CLUSTER COUNTKEYSINSLOT）
 /// 
-/// ```ts
-/// const ttl = await ttlKey('local', 'mykey');
-/// ```
+/// 返回 `CommandResponse<u64>`。槽位超出 `0..16384` 时返回 `INVALID_ARGUMENT`。
 #[tauri::command]
-async fn ttl_key(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> Result<CommandResponse<i64>, InvokeError> {
-    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> CommandResult<i64> {
-        if let Some(svc) = state.get_service(&name).await {
-            let v = svc.ttl(db.unwrap_or(0), &key).await?;
-            Ok(CommandResponse::ok(v))
+async fn cluster_count_keys_in_slot(state: tauri::State<'_, AppState>, name: String, slot: u16) -> Result<CommandResponse<u64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, slot: u16) -> CommandResult<u64> {
+        if slot >= 16384 {
+            return Err(AppError::Validation("slot must be in 0..16384".into()));
+        }
+        if let Some(svc) = state.get_service(&name).await? {
+            let n = state.audited(&name, "CLUSTER COUNTKEYSINSLOT", None, svc.cluster_countkeysinslot(slot)).await?;
+            Ok(CommandResponse::ok(n))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name, key, db).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name, slot).await)
 }
 
-/// 获取集群信息（仅集群模式有效）
+/// 将槽位分配给节点（CLUSTER ADDSLOTS）
 /// 
-/// 返回 `CommandResponse<Vec<ClusterNodeInfo>>`
+/// 返回 `CommandResponse<String>`，成功返回 `"OK"`。槽位超出 `0..16384` 时返回错误。
 #[tauri::command]
-async fn get_cluster_info(state: tauri::State<'_, AppState>, name: String) -> Result<CommandResponse<Vec<ClusterNodeInfo>>, InvokeError> {
-    async fn inner(state: tauri::State<'_, AppState>, name: String) -> CommandResult<Vec<ClusterNodeInfo>> {
-        if let Some(svc) = state.get_service(&name).await {
-            let info = svc.get_cluster_nodes().await?;
-            Ok(CommandResponse::ok(info))
+async fn cluster_add_slots(state: tauri::State<'_, AppState>, name: String, slots: Vec<u16>) -> Result<CommandResponse<String>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, slots: Vec<u16>) -> CommandResult<String> {
+        if let Some(svc) = state.get_service(&name).await? {
+            state.audited(&name, "CLUSTER ADDSLOTS", None, svc.cluster_addslots(slots)).await?;
+            Ok(CommandResponse::ok("OK".to_string()))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name, slots).await)
 }
 
-/// 获取键类型 (TYPE)
+/// 移除节点的槽位分配（CLUSTER DELSLOTS）
+/// 
+/// 返回 `CommandResponse<String>`，成功返回 `"OK"`。
 #[tauri::command]
-async fn get_type(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> Result<CommandResponse<String>, InvokeError> {
-    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> CommandResult<String> {
-        if let Some(svc) = state.get_service(&name).await {
-            let t = svc.get_type(db.unwrap_or(0), &key).await?;
-            Ok(CommandResponse::ok(t))
+async fn cluster_del_slots(state: tauri::State<'_, AppState>, name: String, slots: Vec<u16>) -> Result<CommandResponse<String>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, slots: Vec<u16>) -> CommandResult<String> {
+        if let Some(svc) = state.get_service(&name).await? {
+            state.audited(&name, "CLUSTER DELSLOTS", None, svc.cluster_delslots(slots)).await?;
+            Ok(CommandResponse::ok("OK".to_string()))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name, key, db).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name, slots).await)
 }
 
-/// 获取哈希表所有字段 (HGETALL)
+/// 设置槽位状态（CLUSTER SETSLOT）
+/// 
+/// 参数：
+/// - `slot`: 槽位编号
+/// - `slot_state`: `IMPORTING` / `MIGRATING` / `STABLE` / `NODE`
+/// - `node_id`: `STABLE` 以外的状态必填
+/// 
+/// 返回 `CommandResponse<String>`，成功返回 `"OK"`。
 #[tauri::command]
-async fn hgetall_hash(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> Result<CommandResponse<std::collections::HashMap<String, String>>, InvokeError> {
-    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> CommandResult<std::collections::HashMap<String, String>> {
-        if let Some(svc) = state.get_service(&name).await {
-            let res: std::collections::HashMap<String, String> = svc.hgetall(db.unwrap_or(0), &key).await?;
-            Ok(CommandResponse::ok(res))
+async fn cluster_set_slot(state: tauri::State<'_, AppState>, name: String, slot: u16, slot_state: String, node_id: Option<String>) -> Result<CommandResponse<String>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, slot: u16, slot_state: String, node_id: Option<String>) -> CommandResult<String> {
+        if let Some(svc) = state.get_service(&name).await? {
+            state.audited(&name, "CLUSTER SETSLOT", None, svc.cluster_setslot(slot, &slot_state, node_id.as_deref())).await?;
+            Ok(CommandResponse::ok("OK".to_string()))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name, key, db).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name, slot, slot_state, node_id).await)
 }
 
+/// 获取服务端支持的命令数量（COMMAND COUNT）
+/// 
+/// 返回 `CommandResponse<i64>`。
 #[tauri::command]
-async fn hset_field(state: tauri::State<'_, AppState>, name: String, key: String, field: String, value: String, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
-    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, field: String, value: String, db: Option<u32>) -> CommandResult<bool> {
-        if let Some(svc) = state.get_service(&name).await {
-            let ok = svc.hset(db.unwrap_or(0), &key, &field, value).await?;
-            Ok(CommandResponse::ok(ok))
+async fn command_count(state: tauri::State<'_, AppState>, name: String) -> Result<CommandResponse<i64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String) -> CommandResult<i64> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let count = state.audited(&name, "COMMAND COUNT", None, svc.command_count()).await?;
+            Ok(CommandResponse::ok(count))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name, key, field, value, db).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name).await)
 }
 
+/// 获取服务端支持的命令名称列表（COMMAND LIST，旧版本回退到 COMMAND）
+/// 
+/// 返回 `CommandResponse<Vec<String>>`，名称为小写并按字母排序，供命令面板使用。
 #[tauri::command]
-async fn hdel_field(state: tauri::State<'_, AppState>, name: String, key: String, field: String, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
-    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, field: String, db: Option<u32>) -> CommandResult<bool> {
-        if let Some(svc) = state.get_service(&name).await {
-            let ok = svc.hdel(db.unwrap_or(0), &key, &field).await?;
-            Ok(CommandResponse::ok(ok))
+async fn list_commands(state: tauri::State<'_, AppState>, name: String) -> Result<CommandResponse<Vec<String>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String) -> CommandResult<Vec<String>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let names = state.audited(&name, "COMMAND LIST", None, svc.command_list()).await?;
+            Ok(CommandResponse::ok(names))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name, key, field, db).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name).await)
 }
 
-/// 列表左侧推入 (LPUSH)
+/// 执行任意命令并返回回复结构的调试文本
+/// 
+/// # 参数
+/// 
+/// - `args`: 命令及参数，如 `["HGETALL", "user:1"]`
+/// 
+/// 返回 `CommandResponse<String>`，保留 RESP 类型与嵌套，如 `Array[BulkString("a"), Int(5), Nil]`。
+/// 响应的 `meta` 包含命令耗时，集群模式下还包含处理该命令的节点。
+/// `args` 为空时返回 `INVALID_ARGUMENT`。
 #[tauri::command]
-async fn lpush_list(state: tauri::State<'_, AppState>, name: String, key: String, value: String, db: Option<u32>) -> Result<CommandResponse<i64>, InvokeError> {
-    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, value: String, db: Option<u32>) -> CommandResult<i64> {
-        if let Some(svc) = state.get_service(&name).await {
-            let len = svc.lpush(db.unwrap_or(0), &key, value).await?;
-            Ok(CommandResponse::ok(len))
+async fn run_command_debug(state: tauri::State<'_, AppState>, name: String, args: Vec<String>, db: Option<u32>) -> Result<CommandResponse<String>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, args: Vec<String>, db: Option<u32>) -> CommandResult<String> {
+        let Some(command) = args.first().map(|c| c.to_uppercase()) else {
+            return Err(AppError::Validation("args must not be empty".into()));
+        };
+        if let Some(svc) = state.get_service(&name).await? {
+            let (out, meta) = state.audited(&name, &command, db, svc.run_raw_command_timed(svc.resolve_db(db), &args)).await?;
+            Ok(CommandResponse::ok(out).with_meta(meta))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name, key, value, db).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name, args, db).await)
 }
 
-/// 列表右侧弹出 (RPOP)
+/// 获取指定事件的延迟历史（LATENCY HISTORY）
+/// 
+/// 返回 `CommandResponse<Vec<(i64, i64)>>`，每项为 `(Unix 时间戳秒, 延迟毫秒)`。
+/// `event` 为空时返回 `INVALID_ARGUMENT`。
 #[tauri::command]
-async fn rpop_list(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> Result<CommandResponse<Option<String>>, InvokeError> {
-    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> CommandResult<Option<String>> {
-        if let Some(svc) = state.get_service(&name).await {
-            let val: Option<String> = svc.rpop(db.unwrap_or(0), &key).await?;
-            Ok(CommandResponse::ok(val))
+async fn latency_history(state: tauri::State<'_, AppState>, name: String, event: String) -> Result<CommandResponse<Vec<(i64, i64)>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, event: String) -> CommandResult<Vec<(i64, i64)>> {
+        if event.is_empty() {
+            return Err(AppError::Validation("event must not be empty".into()));
+        }
+        if let Some(svc) = state.get_service(&name).await? {
+            let history = state.audited(&name, "LATENCY HISTORY", None, svc.latency_history(&event)).await?;
+            Ok(CommandResponse::ok(history))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name, key, db).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name, event).await)
 }
 
+/// 清空延迟监控记录（LATENCY RESET）
+/// 
+/// 返回 `CommandResponse<i64>`：被清空的事件数量。
 #[tauri::command]
-async fn lrange_list(state: tauri::State<'_, AppState>, name: String, key: String, start: isize, stop: isize, db: Option<u32>) -> Result<CommandResponse<Vec<String>>, InvokeError> {
-    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, start: isize, stop: isize, db: Option<u32>) -> CommandResult<Vec<String>> {
-        if let Some(svc) = state.get_service(&name).await {
-            let v: Vec<String> = svc.lrange(db.unwrap_or(0), &key, start, stop).await?;
-            Ok(CommandResponse::ok(v))
+async fn latency_reset(state: tauri::State<'_, AppState>, name: String) -> Result<CommandResponse<i64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String) -> CommandResult<i64> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let n = state.audited(&name, "LATENCY RESET", None, svc.latency_reset()).await?;
+            Ok(CommandResponse::ok(n))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name, key, start, stop, db).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name).await)
 }
 
-/// 集合添加元素 (SADD)
+/// 列出 ACL 用户规则（ACL LIST）
+/// 
+/// 返回 `CommandResponse<Vec<String>>`，每个用户一行规则描述。
 #[tauri::command]
-async fn sadd_set(state: tauri::State<'_, AppState>, name: String, key: String, value: String, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
-    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, value: String, db: Option<u32>) -> CommandResult<bool> {
-        if let Some(svc) = state.get_service(&name).await {
-            let added = svc.sadd(db.unwrap_or(0), &key, value).await?;
-            Ok(CommandResponse::ok(added))
+async fn acl_list_users(state: tauri::State<'_, AppState>, name: String) -> Result<CommandResponse<Vec<String>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String) -> CommandResult<Vec<String>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let users = state.audited(&name, "ACL LIST", None, svc.acl_list()).await?;
+            Ok(CommandResponse::ok(users))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name, key, value, db).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name).await)
 }
 
-/// 获取集合所有成员 (SMEMBERS)
+/// 获取 ACL 用户详情（ACL GETUSER）
+/// 
+/// 返回 `CommandResponse<HashMap<String, serde_json::Value>>`，用户不存在时为空对象。
 #[tauri::command]
-async fn smembers_set(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> Result<CommandResponse<Vec<String>>, InvokeError> {
-    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, db: Option<u32>) -> CommandResult<Vec<String>> {
-        if let Some(svc) = state.get_service(&name).await {
-            let members: Vec<String> = svc.smembers(db.unwrap_or(0), &key).await?;
-            Ok(CommandResponse::ok(members))
+async fn acl_get_user(state: tauri::State<'_, AppState>, name: String, user: String) -> Result<CommandResponse<std::collections::HashMap<String, serde_json::Value>>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, user: String) -> CommandResult<std::collections::HashMap<String, serde_json::Value>> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let detail = state.audited(&name, "ACL GETUSER", None, svc.acl_getuser(&user)).await?;
+            Ok(CommandResponse::ok(detail))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name, key, db).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name, user).await)
 }
 
+/// 创建或修改 ACL 用户（ACL SETUSER）
+/// 
+/// # 参数
+/// 
+/// - `user`: 用户名
+/// - `rules`: 规则列表，如 `["on", ">secret", "~cache:*", "+get"]`
+/// 
+/// 返回 `CommandResponse<String>`，成功返回 `"OK"`。
 #[tauri::command]
-async fn srem_set(state: tauri::State<'_, AppState>, name: String, key: String, member: String, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
-    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, member: String, db: Option<u32>) -> CommandResult<bool> {
-        if let Some(svc) = state.get_service(&name).await {
-            let ok = svc.srem(db.unwrap_or(0), &key, member).await?;
-            Ok(CommandResponse::ok(ok))
+async fn acl_set_user(state: tauri::State<'_, AppState>, name: String, user: String, rules: Vec<String>) -> Result<CommandResponse<String>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, user: String, rules: Vec<String>) -> CommandResult<String> {
+        if let Some(svc) = state.get_service(&name).await? {
+            state.audited(&name, "ACL SETUSER", None, svc.acl_setuser(&user, &rules)).await?;
+            Ok(CommandResponse::ok("OK".to_string()))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name, key, member, db).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name, user, rules).await)
 }
 
+/// 删除 ACL 用户（ACL DELUSER）
+/// 
+/// 返回 `CommandResponse<i64>`，即实际删除的用户数量。
 #[tauri::command]
-async fn zadd_zset(state: tauri::State<'_, AppState>, name: String, key: String, member: String, score: f64, db: Option<u32>) -> Result<CommandResponse<i64>, InvokeError> {
-    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, member: String, score: f64, db: Option<u32>) -> CommandResult<i64> {
-        if let Some(svc) = state.get_service(&name).await {
-            let n = svc.zadd(db.unwrap_or(0), &key, member, score).await?;
+async fn acl_del_user(state: tauri::State<'_, AppState>, name: String, user: String) -> Result<CommandResponse<i64>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, user: String) -> CommandResult<i64> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let n = state.audited(&name, "ACL DELUSER", None, svc.acl_deluser(&user)).await?;
             Ok(CommandResponse::ok(n))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name, key, member, score, db).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name, user).await)
 }
 
+/// 从文件批量导入键值对
+/// 
+/// # 参数
+/// 
+/// - `path`: 本地文件路径
+/// - `format`: `"json"`（键到值的对象）或 `"csv"`（每行 `key,value`）
+/// 
+/// 返回 `CommandResponse<ImportReport>`，包含成功数量、失败数量及失败原因。
 #[tauri::command]
-async fn zrem_zset(state: tauri::State<'_, AppState>, name: String, key: String, member: String, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
-    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, member: String, db: Option<u32>) -> CommandResult<bool> {
-        if let Some(svc) = state.get_service(&name).await {
-            let ok = svc.zrem(db.unwrap_or(0), &key, member).await?;
-            Ok(CommandResponse::ok(ok))
+async fn import_keys_from_file(state: tauri::State<'_, AppState>, name: String, path: String, format: ImportFormat, db: Option<u32>) -> Result<CommandResponse<ImportReport>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, path: String, format: ImportFormat, db: Option<u32>) -> CommandResult<ImportReport> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let report = state.audited(&name, "MSET (import)", db, transfer::import_keys(&svc, svc.resolve_db(db), path, format)).await?;
+            Ok(CommandResponse::ok(report))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name, key, member, db).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name, path, format, db).await)
 }
 
+/// 将匹配模式的键从一个已保存连接迁移到另一个连接（DUMP/RESTORE）
+/// 
+/// # 参数
+/// 
+/// - `source_name` / `source_db`: 源连接与数据库（数据库可选，默认使用连接的默认数据库）
+/// - `dest_name` / `dest_db`: 目标连接与数据库
+/// - `pattern`: 匹配模式，如 `user:*`，不能为空
+/// - `overwrite`: 目标端已存在同名键时是否覆盖（默认 `false`，计入 `skipped`）
+/// 
+/// 返回 `CommandResponse<MigrateReport>`。源与目标为同一连接的同一数据库时返回 `INVALID_ARGUMENT`。
 #[tauri::command]
-async fn zrange_zset(state: tauri::State<'_, AppState>, name: String, key: String, start: isize, stop: isize, db: Option<u32>) -> Result<CommandResponse<Vec<(String, f64)>>, InvokeError> {
-    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, start: isize, stop: isize, db: Option<u32>) -> CommandResult<Vec<(String, f64)>> {
-        if let Some(svc) = state.get_service(&name).await {
-            let v = svc.zrange_withscores(db.unwrap_or(0), &key, start, stop).await?;
-            Ok(CommandResponse::ok(v))
+async fn migrate_keys(state: tauri::State<'_, AppState>, source_name: String, dest_name: String, source_db: Option<u32>, dest_db: Option<u32>, pattern: String, overwrite: Option<bool>) -> Result<CommandResponse<MigrateReport>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, source_name: String, dest_name: String, source_db: Option<u32>, dest_db: Option<u32>, pattern: String, overwrite: Option<bool>) -> CommandResult<MigrateReport> {
+        if pattern.is_empty() {
+            return Err(AppError::Validation("pattern must not be empty".into()));
+        }
+        let Some(source) = state.get_service(&source_name).await? else {
+            return Err(AppError::NotFound("source service not found".into()));
+        };
+        let Some(dest) = state.get_service(&dest_name).await? else {
+            return Err(AppError::NotFound("destination service not found".into()));
+        };
+        let (from_db, to_db) = (source.resolve_db(source_db), dest.resolve_db(dest_db));
+        if source_name == dest_name && from_db == to_db {
+            return Err(AppError::Validation("source and destination must differ".into()));
+        }
+        let migrate = transfer::migrate_keys(&source, from_db, &dest, to_db, pattern, overwrite.unwrap_or(false));
+        // 记录实际写入的数据库，而不是未解析的参数（未指定时为连接的默认库）
+        let report = state.audited(&dest_name, "RESTORE (migrate)", Some(to_db), migrate).await?;
+        Ok(CommandResponse::ok(report))
+    }
+    AppError::respond(inner(state, source_name, dest_name, source_db, dest_db, pattern, overwrite).await)
+}
+
+/// 将匹配模式的键导出为 JSON 文件
+/// 
+/// # 参数
+/// 
+/// - `pattern`: 匹配模式，如 `user:*`
+/// - `path`: 输出文件路径，已存在时覆盖
+/// 
+/// 返回 `CommandResponse<ExportReport>`：导出与跳过（读取前被删除、类型已改变或类型不支持）的键数量。
+/// 文件结构见 `transfer` 模块文档。
+#[tauri::command]
+async fn export_keys_to_file(state: tauri::State<'_, AppState>, name: String, pattern: String, path: String, db: Option<u32>) -> Result<CommandResponse<ExportReport>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, pattern: String, path: String, db: Option<u32>) -> CommandResult<ExportReport> {
+        if let Some(svc) = state.get_service(&name).await? {
+            let report = state.audited(&name, "SCAN (export)", db, transfer::export_keys(&svc, svc.resolve_db(db), pattern, path)).await?;
+            Ok(CommandResponse::ok(report))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name, key, start, stop, db).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name, pattern, path, db).await)
 }
 
+/// 对连接执行 SET/GET 性能测试
+/// 
+/// # 参数
+/// 
+/// - `ops`: 操作总数（每次为一个 SET + GET），不超过 1,000,000
+/// - `concurrency`: 并发任务数，不超过 256
+/// - `value_size`: 写入值的字节数，不超过 1 MiB
+/// 
+/// 返回 `CommandResponse<BenchmarkResult>`。测试使用的临时键在结束后删除；
+/// 可通过 `cancel_benchmark` 提前终止，此时 `cancelled` 为 `true`。
 #[tauri::command]
-async fn json_get_value(state: tauri::State<'_, AppState>, name: String, key: String, path: Option<String>, db: Option<u32>) -> Result<CommandResponse<Option<serde_json::Value>>, InvokeError> {
-    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, path: Option<String>, db: Option<u32>) -> CommandResult<Option<serde_json::Value>> {
-        if let Some(svc) = state.get_service(&name).await {
-            let p = path.unwrap_or("$".to_string());
-            let v = svc.json_get(db.unwrap_or(0), &key, &p).await?;
-            Ok(CommandResponse::ok(v))
+async fn run_benchmark(state: tauri::State<'_, AppState>, name: String, ops: u64, concurrency: usize, value_size: usize, db: Option<u32>) -> Result<CommandResponse<BenchmarkResult>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String, ops: u64, concurrency: usize, value_size: usize, db: Option<u32>) -> CommandResult<BenchmarkResult> {
+        if ops == 0 || ops > redis_service::BENCHMARK_MAX_OPS {
+            return Err(AppError::Validation(format!("ops must be between 1 and {}", redis_service::BENCHMARK_MAX_OPS)));
+        }
+        if concurrency == 0 || concurrency > redis_service::BENCHMARK_MAX_CONCURRENCY {
+            return Err(AppError::Validation(format!("concurrency must be between 1 and {}", redis_service::BENCHMARK_MAX_CONCURRENCY)));
+        }
+        if value_size > redis_service::BENCHMARK_MAX_VALUE_SIZE {
+            return Err(AppError::Validation(format!("value_size must not exceed {} bytes", redis_service::BENCHMARK_MAX_VALUE_SIZE)));
+        }
+        if let Some(svc) = state.get_service(&name).await? {
+            let result = state.audited(&name, "BENCHMARK", db, svc.benchmark(svc.resolve_db(db), ops, concurrency, value_size)).await?;
+            Ok(CommandResponse::ok(result))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name, key, path, db).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name, ops, concurrency, value_size, db).await)
 }
 
+/// 终止连接上正在运行的性能测试
+/// 
+/// 返回 `CommandResponse<bool>`：有测试正在运行并已发出取消请求时为 `true`，
+/// 没有测试运行时为 `false`，且不会影响之后开始的测试。
 #[tauri::command]
-async fn json_set_value(state: tauri::State<'_, AppState>, name: String, key: String, path: Option<String>, value_json: String, db: Option<u32>) -> Result<CommandResponse<bool>, InvokeError> {
-    async fn inner(state: tauri::State<'_, AppState>, name: String, key: String, path: Option<String>, value_json: String, db: Option<u32>) -> CommandResult<bool> {
-        if let Some(svc) = state.get_service(&name).await {
-            let p = path.unwrap_or("$".to_string());
-            let v: serde_json::Value = serde_json::from_str(&value_json)?;
-            svc.json_set(db.unwrap_or(0), &key, &p, &v).await?;
-            Ok(CommandResponse::ok(true))
+async fn cancel_benchmark(state: tauri::State<'_, AppState>, name: String) -> Result<CommandResponse<bool>, InvokeError> {
+    async fn inner(state: tauri::State<'_, AppState>, name: String) -> CommandResult<bool> {
+        if let Some(svc) = state.get_service(&name).await? {
+            Ok(CommandResponse::ok(svc.cancel_benchmark()))
         } else {
-            Ok(CommandResponse::err("NOT_FOUND", "service not found"))
+            Err(AppError::NotFound("service not found".into()))
         }
     }
-    inner(state, name, key, path, value_json, db).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(state, name).await)
 }
 
 /// 测试 Redis 连接配置（不保存）
@@ -889,19 +3067,15 @@ async fn json_set_value(state: tauri::State<'_, AppState>, name: String, key: St
 /// 参数：
 /// - `config`: RedisConfig 对象
 ///
-/// 返回：`CommandResponse<String>`，成功返回 "ok"
-#[tauri::command]
-async fn test_connection_config(config: RedisConfig) -> Result<CommandResponse<String>, InvokeError> {
-    async fn inner(config: RedisConfig) -> CommandResult<String> {
-        // 尝试建立连接
-        let svc = crate::redis_service::RedisService::new(config).await?;
-        // 执行健康检查
-        svc.check_health().await?;
-        // 断开连接（虽然 Drop 会自动处理，但显式调用更清晰）
-        svc.disconnect().await;
-        Ok(CommandResponse::ok("ok".to_string()))
+/// 返回：`CommandResponse<TestResult>`，包含服务端版本、运行模式、延迟，
+/// 以及配置与服务端模式不一致（如 `cluster: true` 指向单机服务器）时的警告
+#[tauri::command]
+async fn test_connection_config(config: RedisConfig) -> Result<CommandResponse<TestResult>, InvokeError> {
+    async fn inner(config: RedisConfig) -> CommandResult<TestResult> {
+        let result = crate::redis_service::RedisService::probe(config).await?;
+        Ok(CommandResponse::ok(result))
     }
-    inner(config).await.map_err(InvokeError::from_anyhow)
+    AppError::respond(inner(config).await)
 }
 
 /// 应用程序主运行函数
@@ -923,6 +3097,10 @@ async fn test_connection_config(config: RedisConfig) -> Result<CommandResponse<S
 ///    - 注册健康检查命令
 ///    - 其他命令处理器可以在此添加
 /// 
+/// 4. **退出清理**：
+///    - 收到 `RunEvent::Exit` 时调用 `AppState::shutdown`
+///    - 终止订阅任务、释放 Redis 连接并关闭 SQLite 连接池
+/// 
 /// # 异步初始化
 /// 
 /// `AppState` 的初始化是异步的，因为它需要：
@@ -983,35 +3161,66 @@ pub fn run() {
         })
         .invoke_handler(tauri::generate_handler![
             health_check,
+            set_log_level,
             add_connection,
+            update_connection,
             remove_connection,
+            purge_connection,
             check_connection,
+            ping_latency,
             get_value,
             set_value,
+            write_key,
+            getdel_value,
+            getex_value,
             del_key,
+            del_if_volatile,
             mget_values,
             mset_values,
             publish_message,
             subscribe_channel,
+            subscribe_channels,
+            subscribe_channel_batched,
+            cancel_subscription,
+            get_buffered_messages,
             try_lock,
             unlock,
+            incr_capped,
             persist_key,
             expire_key,
+            expire_key_flag,
+            pexpireat_key,
             ttl_key,
             get_cluster_info,
+            get_cluster_status,
             scan_keys,
+            scan_keys_with_types,
+            count_keys_by_pattern,
+            delete_keys_by_pattern,
+            expire_keys_by_pattern,
+            find_idle_keys,
             get_db_size,
+            get_database_count,
+            get_capabilities,
             list_configs,
+            list_configs_recent,
             get_config,
             save_config,
             delete_config,
+            backup_database,
+            restore_database,
             list_services,
+            list_connections_detailed,
             reload_services,
             service_exists,
             get_type,
             hgetall_hash,
+            hgetall_parsed,
             lpush_list,
             rpop_list,
+            lmpop_lists,
+            drain_list,
+            blpop_lists,
             sadd_set,
             smembers_set,
             hset_field,
@@ -1023,9 +3232,95 @@ pub fn run() {
             zrange_zset,
             json_get_value,
             json_set_value,
-            test_connection_config
+            json_mset_values,
+            json_type,
+            json_objkeys,
+            json_del_path,
+            json_arr_append,
+            json_num_incr_by,
+            test_connection_config,
+            geoadd_geo,
+            geopos_geo,
+            geodist_geo,
+            geosearch_geo,
+            sort_key,
+            xgroup_create_stream,
+            xreadgroup_stream,
+            xack_stream,
+            xdel_stream,
+            xtrim_stream,
+            pfadd_hll,
+            pfcount_hll,
+            pfmerge_hll,
+            setbit_bitmap,
+            getbit_bitmap,
+            bitcount_bitmap,
+            bitop_bitmap,
+            key_ttl_info,
+            preview_key,
+            preview_keys,
+            inspect_key,
+            wait_replicas,
+            last_save,
+            get_server_time,
+            debug_sleep,
+            save_snapshot,
+            bgrewrite_aof,
+            get_persistence_status,
+            sentinel_master_info,
+            sentinel_trigger_failover,
+            cluster_keyslot,
+            cluster_count_keys_in_slot,
+            cluster_add_slots,
+            cluster_del_slots,
+            cluster_set_slot,
+            command_count,
+            list_commands,
+            run_command_debug,
+            latency_history,
+            latency_reset,
+            acl_list_users,
+            acl_get_user,
+            acl_set_user,
+            acl_del_user,
+            import_keys_from_file,
+            export_keys_to_file,
+            migrate_keys,
+            get_all_db_sizes,
+            lpos_list,
+            lrem_list,
+            get_command_history,
+            clear_command_history,
+            get_connection_metrics,
+            get_connection_uptime,
+            reset_connection_metrics,
+            run_benchmark,
+            cancel_benchmark,
+            reconnect_service,
+            sinterstore_set,
+            sunionstore_set,
+            sdiffstore_set,
+            sintercard_sets,
+            zrangestore_zset,
+            zmpop_zsets,
+            zintercard_zsets,
+            random_key,
+            touch_keys,
+            move_key,
+            swap_databases,
+            get_value_page,
+            debug_object_key,
+            object_freq_key
         ])
-        // 运行应用程序
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        // 构建应用程序
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        // 运行应用程序，退出时关闭连接并终止后台任务
+        .run(|app_handle, event| {
+            if let tauri::RunEvent::Exit = event {
+                if let Some(state) = app_handle.try_state::<AppState>() {
+                    tauri::async_runtime::block_on(state.shutdown());
+                }
+            }
+        });
 }
@@ -7,8 +7,11 @@
 //! 
 //! - **统一接口**：提供简单易用的日志记录函数
 //! - **结构化日志**：使用代码标识符进行日志分类
-//! - **多级别支持**：支持 Info、Warn、Error 等不同级别的日志
+//! - **多级别支持**：支持 Trace、Debug、Info、Warn、Error 五个级别的日志
 //! - **Tauri 集成**：与 Tauri 应用程序框架无缝集成
+//! - **文件轮转**：日志文件按大小轮转，保留最近的若干个文件
+//! - **连接上下文**：通过 `with_context` 为日志标识符加上连接名称前缀
+//! - **运行时调整级别**：通过 `set_level` 在不重启的情况下调整输出级别
 //! - **性能优化**：异步日志记录，不阻塞主线程
 //! 
 //! # 使用示例
@@ -24,10 +27,15 @@
 //! 
 //! // 记录错误级别日志
 //! logging::error("DB_ERROR", "Failed to save configuration");
+//! 
+//! // 带连接上下文的日志，标识符为 "local:REDIS_RETRY"
+//! logging::with_context("local").warn("REDIS_RETRY", "Connection failed, retrying...");
 //! ```
 //! 
 //! # 日志级别说明
 //! 
+//! - **Trace**: 最详细的跟踪信息，如每条命令的参数
+//! - **Debug**: 调试信息，仅在开发构建中输出
 //! - **Info**: 一般信息，记录正常操作流程
 //! - **Warn**: 警告信息，表示可能出现问题但不影响主要功能
 //! - **Error**: 错误信息，表示操作失败或异常情况
@@ -42,6 +50,20 @@
 //! - `COMMAND_EXEC`: 命令执行相关
 
 use log::LevelFilter;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tauri_plugin_log::{RotationStrategy, Target, TargetKind};
+
+/// 单个日志文件的最大字节数，超过后轮转
+const MAX_LOG_FILE_SIZE: u128 = 10 * 1024 * 1024;
+
+/// 轮转时保留的历史日志文件数量
+const KEEP_LOG_FILES: usize = 5;
+
+/// 默认日志级别：开发构建为 Debug，发布构建为 Info
+const DEFAULT_LEVEL: LevelFilter = if cfg!(debug_assertions) { LevelFilter::Debug } else { LevelFilter::Info };
+
+/// 当前生效的日志级别，以 `LevelFilter as usize` 存储，由 `set_level` 修改
+static LEVEL: AtomicUsize = AtomicUsize::new(DEFAULT_LEVEL as usize);
 
 /// 创建并配置 Tauri 日志插件
 /// 
@@ -49,8 +71,9 @@ use log::LevelFilter;
 /// 
 /// # 插件配置
 /// 
-/// - **日志级别**: Info 级别，记录 Info 及以上级别的日志
-/// - **输出目标**: 默认输出到控制台和文件（Tauri 自动处理）
+/// - **日志级别**: 开发构建为 Debug，发布构建为 Info，可通过 `set_level` 在运行时调整
+/// - **输出目标**: 控制台，以及应用日志目录下的 `redis-mate.log`
+/// - **文件轮转**: 单个文件超过 10 MB 时轮转，保留最近 5 个文件
 /// - **格式化**: 使用 Tauri 日志插件的默认格式
 /// 
 /// # 使用方法
@@ -67,12 +90,13 @@ use log::LevelFilter;
 /// 
 /// # 自定义配置
 /// 
-/// 如果需要自定义日志级别或格式，可以修改此函数：
+/// 插件本身以 Trace 级别构建，实际输出由 `level()` 过滤。
+/// 如果需要自定义格式，可以修改此函数：
 /// 
 /// ```rust
 /// pub fn plugin() -> tauri::plugin::TauriPlugin<tauri::Wry> {
 ///     tauri_plugin_log::Builder::new()
-///         .level(LevelFilter::Debug)  // 更详细的日志级别
+///         .format(|out, message, record| out.finish(format_args!("[{}] {}", record.level(), message)))
 ///         .build()
 /// }
 /// ```
@@ -82,10 +106,134 @@ use log::LevelFilter;
 /// 返回配置好的 Tauri 插件实例。
 pub fn plugin() -> tauri::plugin::TauriPlugin<tauri::Wry> {
     tauri_plugin_log::Builder::new()
-        .level(LevelFilter::Info)
+        .level(LevelFilter::Trace)
+        .filter(|metadata| metadata.level() <= level())
+        .clear_targets()
+        .target(Target::new(TargetKind::Stdout))
+        .target(Target::new(TargetKind::LogDir { file_name: Some("redis-mate".into()) }))
+        .max_file_size(MAX_LOG_FILE_SIZE)
+        .rotation_strategy(RotationStrategy::KeepSome(KEEP_LOG_FILES))
         .build()
 }
 
+/// 返回当前生效的日志级别
+pub fn level() -> LevelFilter {
+    LevelFilter::iter().nth(LEVEL.load(Ordering::Relaxed)).unwrap_or(DEFAULT_LEVEL)
+}
+
+/// 在运行时调整日志级别
+/// 
+/// 新级别立即对所有后续日志生效，不需要重启应用，便于临时打开 Debug/Trace 日志排查某个连接的问题。
+/// 重启后恢复为默认级别。
+/// 
+/// # 参数
+/// 
+/// - `level`: `trace`、`debug`、`info`、`warn` 或 `error`（不区分大小写）
+/// 
+/// # 返回值
+/// 
+/// 成功时返回新的级别；级别名称无效时返回错误信息，当前级别保持不变。
+pub fn set_level(level: &str) -> Result<LevelFilter, String> {
+    let filter = match level.trim().to_ascii_lowercase().as_str() {
+        "trace" => LevelFilter::Trace,
+        "debug" => LevelFilter::Debug,
+        "info" => LevelFilter::Info,
+        "warn" => LevelFilter::Warn,
+        "error" => LevelFilter::Error,
+        _ => return Err(format!("invalid log level '{}', expected one of trace, debug, info, warn, error", level)),
+    };
+    LEVEL.store(filter as usize, Ordering::Relaxed);
+    info("LOG_LEVEL", &format!("log level set to {}", filter));
+    Ok(filter)
+}
+
+/// 创建带连接上下文的日志记录器
+/// 
+/// 返回的 `LogContext` 提供与模块级函数相同的方法，
+/// 记录时会在日志标识符前加上连接名称，便于在长时间会话中按连接过滤日志。
+/// 
+/// # 示例
+/// 
+/// ```rust
+/// let log = logging::with_context("local");
+/// log.info("REDIS_CONNECT", "Connected"); // 标识符为 "local:REDIS_CONNECT"
+/// ```
+pub fn with_context(connection: &str) -> LogContext {
+    LogContext { connection: connection.to_string() }
+}
+
+/// 带连接上下文的日志记录器
+/// 
+/// 由 `with_context` 创建，`AppState::audited` 使用它按连接记录每条命令的执行结果。
+#[derive(Clone, Debug)]
+pub struct LogContext {
+    connection: String,
+}
+
+impl LogContext {
+    /// 返回加上连接名称前缀后的日志标识符
+    pub fn code(&self, code: &str) -> String {
+        format!("{}:{}", self.connection, code)
+    }
+
+    /// 以连接上下文记录跟踪级别日志，见 `trace`
+    pub fn trace(&self, code: &str, message: &str) {
+        trace(&self.code(code), message);
+    }
+
+    /// 以连接上下文记录调试级别日志，见 `debug`
+    pub fn debug(&self, code: &str, message: &str) {
+        debug(&self.code(code), message);
+    }
+
+    /// 以连接上下文记录信息级别日志，见 `info`
+    pub fn info(&self, code: &str, message: &str) {
+        info(&self.code(code), message);
+    }
+
+    /// 以连接上下文记录警告级别日志，见 `warn`
+    pub fn warn(&self, code: &str, message: &str) {
+        warn(&self.code(code), message);
+    }
+
+    /// 以连接上下文记录错误级别日志，见 `error`
+    pub fn error(&self, code: &str, message: &str) {
+        error(&self.code(code), message);
+    }
+}
+
+/// 记录跟踪级别日志
+/// 
+/// 用于记录最细粒度的执行细节，如命令参数、重试的每一步等。
+/// 默认配置下不会输出，需要将日志级别调整为 Trace。
+/// 
+/// # 参数
+/// 
+/// - `code`: 日志标识符，用于分类和过滤日志
+/// - `message`: 日志消息内容
+pub fn trace(code: &str, message: &str) {
+    log::trace!(target: code, "{}", message);
+}
+
+/// 记录调试级别日志
+/// 
+/// 用于记录排查问题时有用、但正常运行时不需要关注的信息。
+/// 仅在开发构建中输出。
+/// 
+/// # 参数
+/// 
+/// - `code`: 日志标识符，用于分类和过滤日志
+/// - `message`: 日志消息内容
+/// 
+/// # 示例
+/// 
+/// ```rust
+/// logging::debug("REDIS_POOL", &format!("picked connection {}", index));
+/// ```
+pub fn debug(code: &str, message: &str) {
+    log::debug!(target: code, "{}", message);
+}
+
 /// 记录信息级别日志
 /// 
 /// 用于记录一般性的信息，如操作成功、状态变更、重要事件等。
@@ -283,4 +431,75 @@ pub fn error(code: &str, message: &str) {
 //         }
 //     }
 // }
-// ```
\ No newline at end of file
+// ```
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use log::{Level, Log, Metadata, Record};
+    use std::sync::{Mutex, Once};
+
+    /// 测试进程共用的日志记录器
+    /// 
+    /// 保存所有日志供断言使用，同时输出到标准错误（由测试框架捕获，仅在测试失败时显示）。
+    struct CaptureLogger {
+        records: Mutex<Vec<(Level, String, String)>>,
+    }
+
+    impl Log for CaptureLogger {
+        fn enabled(&self, _: &Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &Record) {
+            eprintln!("[{}] {}: {}", record.level(), record.target(), record.args());
+            self.records.lock().unwrap().push((record.level(), record.target().to_string(), record.args().to_string()));
+        }
+
+        fn flush(&self) {}
+    }
+
+    static CAPTURE: CaptureLogger = CaptureLogger { records: Mutex::new(Vec::new()) };
+    static INSTALL: Once = Once::new();
+
+    /// 安装测试日志记录器
+    /// 
+    /// 整个测试进程只安装一次，其他模块的测试也应通过此函数初始化日志，而不是安装别的记录器。
+    pub(crate) fn init_test_logger() {
+        INSTALL.call_once(|| {
+            log::set_logger(&CAPTURE).expect("test logger must be the only logger installed");
+            log::set_max_level(LevelFilter::Trace);
+        });
+    }
+
+    /// 测试各级别函数通过 log 门面以正确的级别输出
+    #[test]
+    fn test_levels_route_through_log_facade() {
+        init_test_logger();
+
+        trace("TEST_TRACE", "t");
+        debug("TEST_DEBUG", "d");
+        with_context("local").debug("TEST_CTX", "c");
+
+        let records = CAPTURE.records.lock().unwrap();
+        assert!(records.contains(&(Level::Trace, "TEST_TRACE".into(), "t".into())));
+        assert!(records.contains(&(Level::Debug, "TEST_DEBUG".into(), "d".into())));
+        assert!(records.contains(&(Level::Debug, "local:TEST_CTX".into(), "c".into())));
+    }
+
+    /// 测试运行时调整日志级别
+    #[test]
+    fn test_set_level() {
+        let original = level();
+        assert!(set_level("verbose").is_err());
+        assert_eq!(level(), original);
+
+        assert_eq!(set_level("TRACE"), Ok(LevelFilter::Trace));
+        assert_eq!(level(), LevelFilter::Trace);
+        assert_eq!(set_level(" warn "), Ok(LevelFilter::Warn));
+        assert_eq!(level(), LevelFilter::Warn);
+
+        set_level(&original.to_string()).unwrap();
+        assert_eq!(level(), original);
+    }
+}
@@ -0,0 +1,103 @@
+//! 订阅消息缓冲模块
+//!
+//! 订阅收到的消息通过 Tauri 事件推送给前端，界面未监听该事件时（例如切换到了其他页面）消息会直接丢失。
+//! `MessageBuffer` 为单个订阅保留最近的若干条消息，前端返回时可通过 `get_buffered_messages` 补齐。
+//!
+//! # 功能特性
+//!
+//! - **环形缓冲**：达到容量后丢弃最早的消息，内存占用有上限
+//! - **顺序编号**：每条消息带有递增的 `seq`，前端可据此判断期间是否有消息被丢弃
+//! - **按订阅隔离**：每个订阅各自持有一个缓冲区，随订阅取消一起释放
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 单个缓冲区允许的最大容量
+pub const MAX_BUFFER_SIZE: usize = 10_000;
+
+/// 缓冲的一条订阅消息
+///
+/// - `seq`：该订阅内从 0 开始递增的序号
+/// - `received_at`：收到消息时的 Unix 时间戳（毫秒）
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct BufferedMessage {
+    pub seq: u64,
+    pub channel: String,
+    pub payload: String,
+    pub received_at: u64,
+}
+
+#[derive(Default)]
+struct State {
+    messages: VecDeque<BufferedMessage>,
+    next_seq: u64,
+}
+
+/// 保留最近若干条消息的环形缓冲区
+pub struct MessageBuffer {
+    capacity: usize,
+    state: Mutex<State>,
+}
+
+impl MessageBuffer {
+    /// 创建缓冲区
+    ///
+    /// # 参数
+    ///
+    /// - `capacity`: 最多保留的消息数量，限制在 1 到 `MAX_BUFFER_SIZE` 之间
+    pub fn new(capacity: usize) -> Self {
+        MessageBuffer {
+            capacity: capacity.clamp(1, MAX_BUFFER_SIZE),
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// 追加一条消息，超出容量时丢弃最早的消息
+    pub fn push(&self, channel: &str, payload: &str) {
+        let received_at = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0);
+        let mut state = self.state.lock().unwrap();
+        let seq = state.next_seq;
+        state.next_seq += 1;
+        if state.messages.len() >= self.capacity {
+            state.messages.pop_front();
+        }
+        state.messages.push_back(BufferedMessage { seq, channel: channel.to_string(), payload: payload.to_string(), received_at });
+    }
+
+    /// 返回最近的消息，按收到的先后顺序排列
+    ///
+    /// `limit` 为 `None` 时返回缓冲区中的全部消息，否则只返回最新的 `limit` 条。
+    pub fn latest(&self, limit: Option<usize>) -> Vec<BufferedMessage> {
+        let state = self.state.lock().unwrap();
+        let skip = limit.map_or(0, |n| state.messages.len().saturating_sub(n));
+        state.messages.iter().skip(skip).cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试按顺序保留最近的消息并丢弃最早的消息
+    #[test]
+    fn test_message_buffer() {
+        let buffer = MessageBuffer::new(3);
+        for i in 0..5 {
+            buffer.push("news", &format!("m{}", i));
+        }
+
+        let all = buffer.latest(None);
+        let payloads: Vec<&str> = all.iter().map(|m| m.payload.as_str()).collect();
+        assert_eq!(payloads, vec!["m2", "m3", "m4"]);
+        assert_eq!(all.iter().map(|m| m.seq).collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert!(all.iter().all(|m| m.channel == "news"));
+
+        let last = buffer.latest(Some(2));
+        assert_eq!(last.iter().map(|m| m.seq).collect::<Vec<_>>(), vec![3, 4]);
+        assert_eq!(buffer.latest(Some(10)).len(), 3);
+
+        assert_eq!(MessageBuffer::new(0).capacity, 1);
+        assert_eq!(MessageBuffer::new(usize::MAX).capacity, MAX_BUFFER_SIZE);
+    }
+}
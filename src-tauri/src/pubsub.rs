@@ -0,0 +1,318 @@
+//! 共享 Pub/Sub 连接管理模块
+//!
+//! 每个 `RedisService` 持有一个 `PubSubManager`，所有 `subscribe` 调用复用同一条 Pub/Sub 连接，
+//! 避免订阅大量频道时（尤其是集群模式下全部连到种子节点）为每个频道建立一条连接。
+//!
+//! # 功能特性
+//!
+//! - **连接复用**：首次订阅时建立连接，之后的订阅和退订都在这条连接上进行
+//! - **引用计数**：同一频道的多个订阅者共享一次服务端订阅，最后一个订阅者离开时才发送 UNSUBSCRIBE
+//! - **消息分发**：后台任务读取消息，按频道分发给该频道的全部订阅者
+//! - **断线重连**：连接断开后按重试间隔重建连接，重新订阅所有仍有订阅者的频道，并通知订阅者
+//!
+//! # 使用示例
+//!
+//! ```rust
+//! let mut sub = manager.subscribe("news").await?;
+//! while let Some(event) = sub.recv().await {
+//!     if let PubSubEvent::Message(payload) = event {
+//!         println!("{}", payload);
+//!     }
+//! }
+//! // `sub` 被丢弃时自动退订
+//! ```
+
+use anyhow::{Context, Result};
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use redis::aio::PubSubSink;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::logging;
+use crate::redis_service::run_subscription;
+
+/// 推送给订阅者的事件
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PubSubEvent {
+    /// 收到的消息负载
+    Message(String),
+    /// 连接断开后已重新订阅，期间发布的消息已丢失
+    Resubscribed,
+}
+
+/// 共享连接上的消息流，元素为 `(频道, 负载)`
+type MessageStream = BoxStream<'static, (String, String)>;
+
+/// 频道到订阅者的映射，订阅者以 id 区分
+type Listeners = HashMap<String, HashMap<u64, mpsc::UnboundedSender<PubSubEvent>>>;
+
+/// 共享 Pub/Sub 连接管理器
+///
+/// 被丢弃时终止消息分发任务，并关闭全部订阅者的事件通道。
+pub struct PubSubManager {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    client: redis::Client,
+    retry_delay: Duration,
+    /// 共享连接的发送端，`None` 表示尚未建立连接
+    ///
+    /// 订阅、退订和重连都持有此锁，保证订阅者计数与服务端的订阅状态一致。
+    sink: tokio::sync::Mutex<Option<PubSubSink>>,
+    listeners: Mutex<Listeners>,
+    next_id: AtomicU64,
+    dispatcher: Mutex<Option<JoinHandle<()>>>,
+}
+
+/// 一个频道订阅
+///
+/// 通过 `recv` 读取事件；被丢弃时自动退订，最后一个订阅者离开时才向服务端发送 UNSUBSCRIBE。
+pub struct Subscription {
+    id: u64,
+    channel: String,
+    events: mpsc::UnboundedReceiver<PubSubEvent>,
+    inner: Arc<Inner>,
+}
+
+impl PubSubManager {
+    /// 创建管理器，此时不会建立连接
+    ///
+    /// # 参数
+    ///
+    /// - `client`: 用于建立 Pub/Sub 连接的客户端
+    /// - `retry_delay`: 连接断开后的重连间隔
+    pub fn new(client: redis::Client, retry_delay: Duration) -> Self {
+        PubSubManager {
+            inner: Arc::new(Inner {
+                client,
+                retry_delay,
+                sink: tokio::sync::Mutex::new(None),
+                listeners: Mutex::new(HashMap::new()),
+                next_id: AtomicU64::new(1),
+                dispatcher: Mutex::new(None),
+            }),
+        }
+    }
+
+    /// 订阅频道
+    ///
+    /// 尚未建立连接时先建立共享连接；该频道已有订阅者时只登记新的订阅者，不再向服务端发送 SUBSCRIBE。
+    pub async fn subscribe(&self, channel: &str) -> Result<Subscription> {
+        let inner = &self.inner;
+        let mut sink = inner.sink.lock().await;
+        if sink.is_none() {
+            let (new_sink, stream) = inner.connect(Vec::new()).await?;
+            *sink = Some(new_sink);
+            let handle = tokio::spawn(Inner::dispatch(inner.clone(), stream));
+            if let Some(old) = inner.dispatcher.lock().unwrap().replace(handle) {
+                old.abort();
+            }
+            logging::info("PUBSUB", "shared pubsub connection established");
+        }
+
+        let subscribed = inner.listeners.lock().unwrap().get(channel).is_some_and(|l| !l.is_empty());
+        if !subscribed {
+            sink.as_mut().unwrap().subscribe(channel).await.with_context(|| format!("SUBSCRIBE {}", channel))?;
+        }
+
+        let id = inner.next_id.fetch_add(1, Ordering::Relaxed);
+        let (tx, events) = mpsc::unbounded_channel();
+        inner.listeners.lock().unwrap().entry(channel.to_string()).or_default().insert(id, tx);
+        Ok(Subscription { id, channel: channel.to_string(), events, inner: inner.clone() })
+    }
+
+    /// 当前在服务端订阅的频道数量
+    pub fn channel_count(&self) -> usize {
+        self.inner.listeners.lock().unwrap().len()
+    }
+
+    /// 指定频道的订阅者数量
+    pub fn listener_count(&self, channel: &str) -> usize {
+        self.inner.listeners.lock().unwrap().get(channel).map_or(0, HashMap::len)
+    }
+}
+
+impl Drop for PubSubManager {
+    fn drop(&mut self) {
+        if let Some(handle) = self.inner.dispatcher.lock().unwrap().take() {
+            handle.abort();
+        }
+        // 关闭事件通道，订阅者的 `recv` 随即返回 `None`
+        self.inner.listeners.lock().unwrap().clear();
+    }
+}
+
+impl Inner {
+    /// 建立 Pub/Sub 连接并订阅 `channels`，返回发送端与消息流
+    ///
+    /// 无法解码为字符串的负载记录日志后跳过；连接断开时消息流结束。
+    async fn connect(&self, channels: Vec<String>) -> Result<(PubSubSink, MessageStream)> {
+        let (mut sink, stream) = self.client.get_async_pubsub().await?.split();
+        if !channels.is_empty() {
+            sink.subscribe(&channels).await.context("SUBSCRIBE")?;
+        }
+        let stream = stream.filter_map(|msg| async move {
+            match msg.get_payload::<String>() {
+                Ok(payload) => Some((msg.get_channel_name().to_string(), payload)),
+                Err(e) => {
+                    logging::error("PUBSUB", &format!("Payload error: {}", e));
+                    None
+                }
+            }
+        });
+        Ok((sink, stream.boxed()))
+    }
+
+    /// 重建共享连接，重新订阅所有仍有订阅者的频道
+    async fn reconnect(&self) -> Result<MessageStream> {
+        let mut sink = self.sink.lock().await;
+        let channels: Vec<String> = self.listeners.lock().unwrap().keys().cloned().collect();
+        let (new_sink, stream) = self.connect(channels).await?;
+        *sink = Some(new_sink);
+        Ok(stream)
+    }
+
+    /// 消息分发任务：按频道转发消息，断线后自动重连
+    async fn dispatch(inner: Arc<Inner>, stream: MessageStream) {
+        let reconnect = {
+            let inner = inner.clone();
+            move || {
+                let inner = inner.clone();
+                async move { inner.reconnect().await }
+            }
+        };
+        let deliver = {
+            let inner = inner.clone();
+            move |(channel, payload): (String, String)| {
+                if let Some(listeners) = inner.listeners.lock().unwrap().get(&channel) {
+                    for tx in listeners.values() {
+                        let _ = tx.send(PubSubEvent::Message(payload.clone()));
+                    }
+                }
+                true
+            }
+        };
+        let notify = {
+            let inner = inner.clone();
+            move || {
+                for tx in inner.listeners.lock().unwrap().values().flat_map(HashMap::values) {
+                    let _ = tx.send(PubSubEvent::Resubscribed);
+                }
+            }
+        };
+        run_subscription(stream, reconnect, inner.retry_delay, deliver, notify).await;
+    }
+
+    /// 移除订阅者，频道没有订阅者后向服务端发送 UNSUBSCRIBE
+    async fn release(&self, channel: &str, id: u64) {
+        let mut sink = self.sink.lock().await;
+        let last = {
+            let mut listeners = self.listeners.lock().unwrap();
+            match listeners.get_mut(channel) {
+                Some(l) if l.remove(&id).is_some() && l.is_empty() => {
+                    listeners.remove(channel);
+                    true
+                }
+                _ => false,
+            }
+        };
+        if let (true, Some(sink)) = (last, sink.as_mut()) {
+            if let Err(e) = sink.unsubscribe(channel).await {
+                logging::warn("PUBSUB", &format!("UNSUBSCRIBE {} failed: {}", channel, e));
+            }
+        }
+    }
+}
+
+impl Subscription {
+    /// 等待下一个事件，管理器被丢弃后返回 `None`
+    pub async fn recv(&mut self) -> Option<PubSubEvent> {
+        self.events.recv().await
+    }
+
+    /// 订阅的频道名
+    pub fn channel(&self) -> &str {
+        &self.channel
+    }
+
+    /// 转换为 `(频道, 事件)` 流，便于用 `select_all` 合并多个订阅
+    ///
+    /// 流被丢弃时订阅随之丢弃并自动退订。
+    pub fn into_stream(self) -> BoxStream<'static, (String, PubSubEvent)> {
+        futures::stream::unfold(self, |mut sub| async move {
+            let event = sub.recv().await?;
+            Some(((sub.channel.clone(), event), sub))
+        }).boxed()
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        let inner = self.inner.clone();
+        let channel = std::mem::take(&mut self.channel);
+        let id = self.id;
+        // 运行时已关闭（应用退出）时连接随之关闭，无需退订
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            runtime.spawn(async move { inner.release(&channel, id).await });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    /// 生成唯一的测试频道名
+    fn gen_channel(name: &str) -> String {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+        format!("{}-{}", name, nanos)
+    }
+
+    /// 查询服务端频道的订阅连接数（PUBSUB NUMSUB）
+    async fn numsub(client: &redis::Client, channel: &str) -> i64 {
+        let mut conn = client.get_multiplexed_async_connection().await.unwrap();
+        let reply: (String, i64) = redis::cmd("PUBSUB").arg("NUMSUB").arg(channel).query_async(&mut conn).await.unwrap();
+        reply.1
+    }
+
+    /// 测试同一频道的多个订阅者共享一次服务端订阅（需要本地 Redis）
+    #[tokio::test]
+    #[ignore]
+    async fn test_shared_subscription() {
+        let client = redis::Client::open("redis://127.0.0.1:6379").unwrap();
+        let manager = PubSubManager::new(client.clone(), Duration::from_millis(100));
+        let channel = gen_channel("shared");
+
+        let mut first = manager.subscribe(&channel).await.unwrap();
+        let mut second = manager.subscribe(&channel).await.unwrap();
+        assert_eq!(manager.channel_count(), 1);
+        assert_eq!(manager.listener_count(&channel), 2);
+        assert_eq!(numsub(&client, &channel).await, 1);
+
+        let mut conn = client.get_multiplexed_async_connection().await.unwrap();
+        let _: i64 = redis::cmd("PUBLISH").arg(&channel).arg("hello").query_async(&mut conn).await.unwrap();
+        for sub in [&mut first, &mut second] {
+            let event = tokio::time::timeout(Duration::from_secs(2), sub.recv()).await.unwrap();
+            assert_eq!(event, Some(PubSubEvent::Message("hello".into())));
+        }
+
+        // 仍有订阅者时不退订
+        drop(first);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(manager.listener_count(&channel), 1);
+        assert_eq!(numsub(&client, &channel).await, 1);
+
+        // 最后一个订阅者离开后退订
+        drop(second);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(manager.channel_count(), 0);
+        assert_eq!(numsub(&client, &channel).await, 0);
+    }
+}
@@ -0,0 +1,205 @@
+//! 读缓存模块
+//!
+//! 为 `RedisService` 提供一个可选的小型 LRU 缓存，在短时间内复用相同读取（`GET`、`HGETALL`、`TYPE`）的结果，
+//! 避免浏览键时反复读取同一个键。由 `RedisConfig::read_cache_ttl_ms` 启用，默认关闭。
+//!
+//! # 功能特性
+//!
+//! - **短期缓存**：条目在 TTL 到期后失效，限制读取到旧数据的时间
+//! - **写入失效**：通过同一个服务写入某个键后，该键的所有缓存条目立即失效
+//! - **容量限制**：条目数量达到上限时淘汰最久未使用的条目
+//! - **命中统计**：记录命中与未命中次数，随连接指标一起返回
+//!
+//! # 注意事项
+//!
+//! 其他客户端或原始命令对键的修改无法感知，只能等待条目过期。
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// 缓存的读取类型
+///
+/// `Get` 与 `HGetAll` 携带结果类型的 `TypeId`，以不同类型读取同一个键时互不干扰。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ReadKind {
+    Get(TypeId),
+    HGetAll(TypeId),
+    Type,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct CacheKey {
+    db: u32,
+    key: String,
+    kind: ReadKind,
+}
+
+struct Entry {
+    value: Arc<dyn Any + Send + Sync>,
+    expires_at: Instant,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct State {
+    entries: HashMap<CacheKey, Entry>,
+    /// 单调递增的访问计数，用于 LRU 淘汰
+    tick: u64,
+    /// 每次失效时递增；读取开始后发生过失效的结果不再写入缓存
+    epoch: u64,
+}
+
+/// 读结果缓存
+pub struct ReadCache {
+    ttl: Duration,
+    capacity: usize,
+    state: Mutex<State>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ReadCache {
+    /// 创建缓存
+    ///
+    /// # 参数
+    ///
+    /// - `ttl`: 条目的有效期
+    /// - `capacity`: 最多保留的条目数量（至少 1）
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        ReadCache {
+            ttl,
+            capacity: capacity.max(1),
+            state: Mutex::new(State::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// 查询缓存，未命中或条目已过期时返回 `None`
+    pub fn get<V: Clone + 'static>(&self, db: u32, key: &str, kind: ReadKind) -> Option<V> {
+        let cache_key = CacheKey { db, key: key.to_string(), kind };
+        let mut state = self.state.lock().unwrap();
+        state.tick += 1;
+        let tick = state.tick;
+        let value = match state.entries.get_mut(&cache_key) {
+            Some(entry) if entry.expires_at > Instant::now() => {
+                entry.last_used = tick;
+                entry.value.downcast_ref::<V>().cloned()
+            }
+            Some(_) => {
+                state.entries.remove(&cache_key);
+                None
+            }
+            None => None,
+        };
+        let counter = if value.is_some() { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+        value
+    }
+
+    /// 当前的失效纪元，读取前获取并在写入缓存时传回
+    pub fn epoch(&self) -> u64 {
+        self.state.lock().unwrap().epoch
+    }
+
+    /// 写入读取结果
+    ///
+    /// `epoch` 为读取开始前通过 `epoch` 获取的值；读取期间发生过失效时丢弃该结果，
+    /// 避免把写入之前读到的旧值放回缓存。
+    pub fn insert<V: Send + Sync + 'static>(&self, db: u32, key: &str, kind: ReadKind, epoch: u64, value: V) {
+        let mut state = self.state.lock().unwrap();
+        if state.epoch != epoch {
+            return;
+        }
+        let cache_key = CacheKey { db, key: key.to_string(), kind };
+        if state.entries.len() >= self.capacity && !state.entries.contains_key(&cache_key) {
+            let oldest = state.entries.iter().min_by_key(|(_, e)| e.last_used).map(|(k, _)| k.clone());
+            if let Some(oldest) = oldest {
+                state.entries.remove(&oldest);
+            }
+        }
+        state.tick += 1;
+        let entry = Entry { value: Arc::new(value), expires_at: Instant::now() + self.ttl, last_used: state.tick };
+        state.entries.insert(cache_key, entry);
+    }
+
+    /// 使某个键的所有缓存条目失效
+    pub fn invalidate(&self, db: u32, key: &str) {
+        let mut state = self.state.lock().unwrap();
+        state.epoch += 1;
+        state.entries.retain(|k, _| k.db != db || k.key != key);
+    }
+
+    /// 清空所有缓存条目
+    pub fn clear(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.epoch += 1;
+        state.entries.clear();
+    }
+
+    /// 返回 `(命中次数, 未命中次数)`
+    pub fn stats(&self) -> (u64, u64) {
+        (self.hits.load(Ordering::Relaxed), self.misses.load(Ordering::Relaxed))
+    }
+
+    /// 清零命中统计
+    pub fn reset_stats(&self) {
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试命中、失效与过期
+    #[test]
+    fn test_read_cache() {
+        let cache = ReadCache::new(Duration::from_millis(50), 8);
+        assert_eq!(cache.get::<String>(0, "k", ReadKind::Type), None);
+
+        let epoch = cache.epoch();
+        cache.insert(0, "k", ReadKind::Type, epoch, "string".to_string());
+        assert_eq!(cache.get::<String>(0, "k", ReadKind::Type).as_deref(), Some("string"));
+        // 不同的数据库互不影响
+        assert_eq!(cache.get::<String>(1, "k", ReadKind::Type), None);
+        assert_eq!(cache.stats(), (1, 2));
+
+        cache.invalidate(0, "k");
+        assert_eq!(cache.get::<String>(0, "k", ReadKind::Type), None);
+
+        // 读取期间发生失效时不写入
+        let epoch = cache.epoch();
+        cache.invalidate(0, "other");
+        cache.insert(0, "k", ReadKind::Type, epoch, "hash".to_string());
+        assert_eq!(cache.get::<String>(0, "k", ReadKind::Type), None);
+
+        let epoch = cache.epoch();
+        cache.insert(0, "k", ReadKind::Type, epoch, "hash".to_string());
+        std::thread::sleep(Duration::from_millis(60));
+        assert_eq!(cache.get::<String>(0, "k", ReadKind::Type), None);
+
+        cache.reset_stats();
+        assert_eq!(cache.stats(), (0, 0));
+    }
+
+    /// 测试达到容量后淘汰最久未使用的条目
+    #[test]
+    fn test_read_cache_eviction() {
+        let cache = ReadCache::new(Duration::from_secs(60), 2);
+        let epoch = cache.epoch();
+        cache.insert(0, "a", ReadKind::Type, epoch, 1i64);
+        cache.insert(0, "b", ReadKind::Type, epoch, 2i64);
+        // 访问 a 后 b 成为最久未使用的条目
+        assert_eq!(cache.get::<i64>(0, "a", ReadKind::Type), Some(1));
+        cache.insert(0, "c", ReadKind::Type, epoch, 3i64);
+
+        assert_eq!(cache.get::<i64>(0, "a", ReadKind::Type), Some(1));
+        assert_eq!(cache.get::<i64>(0, "b", ReadKind::Type), None);
+        assert_eq!(cache.get::<i64>(0, "c", ReadKind::Type), Some(3));
+    }
+}
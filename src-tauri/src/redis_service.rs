@@ -134,13 +134,20 @@
 //! - `futures`: 异步工具
 
 use anyhow::{anyhow, Context, Result};
-use redis::aio::ConnectionManager;
-use redis::{AsyncCommands, Cmd, Pipeline};
+use redis::aio::{ConnectionManager, MultiplexedConnection};
+use redis::{AsyncCommands, Cmd, ConnectionInfo, IntoConnectionInfo, Pipeline, ProtocolVersion};
 use redis::cluster::ClusterClient;
+use redis::cluster_routing::{Route, RoutingInfo, SingleNodeRoutingInfo, SlotAddr};
 use crate::logging;
-use std::time::Duration;
-use std::collections::HashMap;
+use crate::pubsub::{PubSubEvent, PubSubManager, Subscription};
+use crate::read_cache::{ReadCache, ReadKind};
+use std::any::TypeId;
+use std::time::{Duration, Instant};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use futures::StreamExt;
+use base64::Engine;
 
 /// Redis 连接配置结构
 /// 
@@ -162,10 +169,26 @@ use futures::StreamExt;
 /// - `sentinel_urls`: 哨兵节点地址列表（哨兵模式必需）
 /// 
 /// ## 性能配置
-/// - `pool_size`: 连接池大小（底层管理器实际处理连接数）
+/// - `pool_size`: 单机/哨兵模式下建立的多路复用连接数量，命令轮询分发到各连接
 /// - `retries`: 操作失败时的自动重试次数
 /// - `retry_delay_ms`: 重试之间的延迟时间（毫秒）
 /// 
+/// ## 协议配置
+/// - `resp3`: 使用 RESP3 协议进行握手（默认 RESP2）
+/// 
+/// ## 数据库配置
+/// - `default_db`: 命令未显式指定数据库时使用的默认数据库索引
+/// 
+/// ## 读写路由
+/// - `read_from_replicas`: 集群模式下将只读命令路由到副本节点（写命令仍发往主节点）
+/// 
+/// ## 读缓存
+/// - `read_cache_ttl_ms`: 短期缓存 GET/HGETALL/TYPE 的结果（默认关闭）
+/// 
+/// ## 命令策略
+/// - `command_policy`: 限制该连接可执行的危险命令（默认不限制）
+/// - `readonly`: 只读连接，拒绝所有写操作，以及发布消息、保存快照等改变服务端状态的命令（默认关闭）
+/// 
 /// # 配置示例
 /// 
 /// ```rust
@@ -218,8 +241,10 @@ pub struct RedisConfig {
     
     /// 连接池大小
     /// 
-    /// 指定连接池中保持的最大连接数。虽然 Redis 客户端内部管理实际连接，
-    /// 但这个参数会影响并发操作的性能。
+    /// 单机/哨兵模式下，创建服务时会建立 `pool_size` 个独立的 `ConnectionManager`
+    /// （每个对应一条多路复用连接），0 号库上的命令按轮询方式分发到各连接。
+    /// 小于 1 时按 1 处理。集群模式下该值不生效，连接由集群客户端按节点管理。
+    /// 测试连接（`probe`）时总是只建立 1 条连接。
     /// 
     /// 推荐值：
     /// - 低并发应用：4-8
@@ -230,6 +255,7 @@ pub struct RedisConfig {
     /// 自动重试次数
     /// 
     /// 当 Redis 操作因网络问题或临时故障失败时，自动重试的次数。
+    /// 类型错误、认证失败、语法错误等不可恢复的错误不会重试。
     /// 增加此值可以提高连接的稳定性，但会增加操作延迟。
     /// 
     /// 推荐值：2-5 次
@@ -266,9 +292,117 @@ pub struct RedisConfig {
     /// 
     /// 哨兵模式必需字段。
     pub sentinel_urls: Vec<String>,
+
+    /// 是否使用 RESP3 协议
+    /// 
+    /// 客户端缓存、属性（attributes）、推送消息等服务端特性需要 RESP3。
+    /// 默认为 `false`，使用兼容性最好的 RESP2。
+    /// 
+    /// 需要 Redis 6.0 或更高版本。
+    pub resp3: bool,
+
+    /// 默认数据库索引
+    /// 
+    /// 前端调用命令时未传入 `db` 参数，则使用此数据库，而不是固定的 0 号库。
+    /// 集群模式只支持 0 号库，此字段应保持为 0。
+    pub default_db: u32,
+
+    /// 集群模式下是否从副本节点读取
+    /// 
+    /// 启用后只读命令（如 GET、HGETALL）会被路由到副本节点，以分担主节点的读压力；
+    /// 写命令始终发往主节点。由于主从复制是异步的，读取到的数据可能略有延迟。
+    /// 
+    /// 仅对集群模式生效，单机和哨兵模式忽略此字段。
+    pub read_from_replicas: bool,
+
+    /// 建立连接的超时时间（毫秒）
+    /// 
+    /// 创建服务时若在此时间内未能建立连接（例如主机不可达），返回 `ConnectTimeout` 错误，
+    /// 避免添加或测试连接时界面长时间无响应。集群模式下作为集群客户端连接各节点的超时时间。
+    /// 
+    /// 默认 3000 毫秒，`0` 表示不限制。
+    pub connect_timeout_ms: u64,
+
+    /// 读缓存的有效期（毫秒）
+    /// 
+    /// 设置后 `get`、`hgetall` 和类型查询的结果会在此时间内被复用，通过同一个连接写入某个键时
+    /// 该键的缓存立即失效；其他客户端的修改要等缓存过期后才可见。
+    /// 
+    /// 默认为 `None`，不启用缓存；`0` 同样视为不启用。
+    pub read_cache_ttl_ms: Option<u64>,
+
+    /// 命令策略
+    /// 
+    /// 用于只读或共享的连接，阻止执行 FLUSHALL、DEL、CONFIG 等危险命令。
+    /// 在 `run_raw_command_debug` 以及删除键、切换/移动数据库、修改配置、ACL、集群与哨兵管理等
+    /// 破坏性方法发送命令之前检查，被拒绝时返回 `PolicyBlocked` 错误。
+    /// 
+    /// 默认为 `None`，不做限制。
+    pub command_policy: Option<CommandPolicy>,
+
+    /// 只读连接
+    /// 
+    /// 连接生产环境时的总开关：启用后所有写入数据或修改服务端状态的方法（写入、删除、过期、
+    /// 切换数据库、修改配置、ACL、集群管理等）以及原始命令中的写命令，
+    /// 都会在发送之前返回 `ReadOnlyConnection` 错误；读取不受影响。
+    /// 原始命令只放行已知的读命令，未知命令一律按写命令拒绝。
+    /// 
+    /// 默认为 `false`。
+    pub readonly: bool,
 }
 
-#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+/// 命令策略的模式
+/// 
+/// - `Allow`: 白名单，只允许执行列表中的命令
+/// - `Deny`: 黑名单，禁止执行列表中的命令
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PolicyMode {
+    Allow,
+    Deny,
+}
+
+/// 连接级别的命令白名单/黑名单
+/// 
+/// `commands` 中的名称不区分大小写，可以是命令名（如 `CONFIG`，匹配其所有子命令），
+/// 也可以是带子命令的名称（如 `CONFIG SET`，只匹配该子命令）。
+/// 删除键的方法（包括按模式删除）统一按 `DEL` 检查。
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CommandPolicy {
+    pub mode: PolicyMode,
+    pub commands: Vec<String>,
+}
+
+impl CommandPolicy {
+    /// 判断策略是否允许执行命令
+    /// 
+    /// `command` 为命令名，可带子命令，如 `FLUSHALL`、`CONFIG SET`。
+    pub fn permits(&self, command: &str) -> bool {
+        let command = command.trim().to_ascii_uppercase();
+        let listed = self.commands.iter().any(|entry| {
+            let entry = entry.trim().to_ascii_uppercase();
+            !entry.is_empty() && (command == entry || command.starts_with(&format!("{} ", entry)))
+        });
+        match self.mode {
+            PolicyMode::Allow => listed,
+            PolicyMode::Deny => !listed,
+        }
+    }
+}
+
+/// 集群节点角色
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeRole {
+    Master,
+    Slave,
+}
+
+/// `CLUSTER NODES` 中的一个节点
+/// 
+/// `addr`、`flags`、`slots` 等字段保留原始文本；`ip`、`port`、`cport`、`role`、`slot_ranges`
+/// 为解析后的结构化字段，前端无需再次解析。
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct ClusterNodeInfo {
     pub id: String,
     pub addr: String,
@@ -279,6 +413,473 @@ pub struct ClusterNodeInfo {
     pub config_epoch: String,
     pub link_state: String,
     pub slots: Vec<String>,
+    /// 节点 IP，`noaddr` 节点为空字符串
+    pub ip: String,
+    /// 客户端端口，无法解析时为 0
+    pub port: u16,
+    /// 集群总线端口，无法解析时为 0
+    pub cport: u16,
+    /// 由 `flags` 推导的角色，握手中等未确定角色的节点为 `None`
+    pub role: Option<NodeRole>,
+    /// 负责的槽位区间（闭区间），不含 `[slot->-id]` / `[slot-<-id]` 迁移标记
+    pub slot_ranges: Vec<(u16, u16)>,
+}
+
+impl ClusterNodeInfo {
+    /// 解析 `CLUSTER NODES` 的一行
+    /// 
+    /// 格式：`<id> <ip:port@cport[,hostname]> <flags> <master> <ping-sent> <pong-recv> <config-epoch> <link-state> <slot> ...`
+    /// 
+    /// 字段不足 8 个时返回 `None`。
+    pub fn parse_line(line: &str) -> Option<Self> {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 8 {
+            return None;
+        }
+        let (ip, port, cport) = parse_node_addr(parts[1]);
+        let role = parts[2].split(',').find_map(|flag| match flag {
+            "master" => Some(NodeRole::Master),
+            "slave" => Some(NodeRole::Slave),
+            _ => None,
+        });
+        let slots: Vec<String> = parts[8..].iter().map(|s| s.to_string()).collect();
+        let slot_ranges = slots.iter().filter_map(|s| parse_slot_range(s)).collect();
+
+        Some(ClusterNodeInfo {
+            id: parts[0].to_string(),
+            addr: parts[1].to_string(),
+            flags: parts[2].to_string(),
+            master_id: parts[3].to_string(),
+            ping_sent: parts[4].to_string(),
+            pong_recv: parts[5].to_string(),
+            config_epoch: parts[6].to_string(),
+            link_state: parts[7].to_string(),
+            slots,
+            ip,
+            port,
+            cport,
+            role,
+            slot_ranges,
+        })
+    }
+}
+
+/// EXPIRE 的条件标志（Redis 7.0+）
+/// 
+/// - `Nx`: 仅当键没有过期时间时设置
+/// - `Xx`: 仅当键已有过期时间时设置
+/// - `Gt`: 仅当新的过期时间大于当前值时设置（永不过期视为无穷大）
+/// - `Lt`: 仅当新的过期时间小于当前值时设置（永不过期视为无穷大）
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ExpireFlag {
+    Nx,
+    Xx,
+    Gt,
+    Lt,
+}
+
+impl ExpireFlag {
+    /// 返回命令参数中的标志名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExpireFlag::Nx => "NX",
+            ExpireFlag::Xx => "XX",
+            ExpireFlag::Gt => "GT",
+            ExpireFlag::Lt => "LT",
+        }
+    }
+}
+
+/// GETEX 的过期时间选项
+/// 
+/// - `Ex(秒)`: 设置相对过期时间（秒）
+/// - `Px(毫秒)`: 设置相对过期时间（毫秒）
+/// - `ExAt(Unix 秒)`: 设置绝对过期时间戳
+/// - `Persist`: 移除过期时间
+/// 
+/// 序列化为 `{"EX": 60}` 或 `"PERSIST"`。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum SetExpiry {
+    Ex(u64),
+    Px(u64),
+    ExAt(u64),
+    Persist,
+}
+
+impl SetExpiry {
+    /// 将选项追加为命令参数
+    fn write_args(&self, cmd: &mut Cmd) {
+        match self {
+            SetExpiry::Ex(secs) => cmd.arg("EX").arg(*secs),
+            SetExpiry::Px(ms) => cmd.arg("PX").arg(*ms),
+            SetExpiry::ExAt(ts) => cmd.arg("EXAT").arg(*ts),
+            SetExpiry::Persist => cmd.arg("PERSIST"),
+        };
+    }
+}
+
+/// 键的过期信息
+/// 
+/// 由 `PTTL` 的返回值转换而来，避免前端直接处理 `-1`/`-2` 这类哨兵值：
+/// - 键不存在：`exists = false`，`has_expiry = false`，`ttl_ms = None`
+/// - 键存在但永不过期：`exists = true`，`has_expiry = false`，`ttl_ms = None`
+/// - 键存在且设置了过期时间：`exists = true`，`has_expiry = true`，`ttl_ms = Some(剩余毫秒)`
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct KeyTtlInfo {
+    pub exists: bool,
+    pub has_expiry: bool,
+    pub ttl_ms: Option<i64>,
+}
+
+impl KeyTtlInfo {
+    /// 根据 `PTTL` 的返回值构造过期信息
+    pub fn from_pttl(pttl: i64) -> Self {
+        match pttl {
+            -2 => KeyTtlInfo { exists: false, has_expiry: false, ttl_ms: None },
+            -1 => KeyTtlInfo { exists: true, has_expiry: false, ttl_ms: None },
+            ms => KeyTtlInfo { exists: true, has_expiry: true, ttl_ms: Some(ms.max(0)) },
+        }
+    }
+}
+
+/// `del_if_volatile` 的执行结果
+/// 
+/// - `Deleted`: 键设置了过期时间，已删除
+/// - `SkippedPersistent`: 键永不过期，未删除
+/// - `NotFound`: 键不存在
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VolatileDeleteStatus {
+    Deleted,
+    SkippedPersistent,
+    NotFound,
+}
+
+/// 键预览
+/// 
+/// 返回类型、TTL 与元素数量，`preview_keys` 还会附带少量样本：
+/// - `key_type`：`TYPE` 的结果，键不存在时为 `none`（序列化为 `type`）
+/// - `ttl`：`TTL` 的结果（秒），`-1` 表示永不过期，`-2` 表示键不存在
+/// - `element_count`：字符串为字节长度，列表/集合/有序集合/哈希/流为元素数量，其他类型为 `None`
+/// - `sample`：字符串为截断后的值，列表/集合/有序集合为前几个成员，哈希为 `field: value` 形式的前几个字段；
+///   `preview_key` 不读取值，始终为空
+/// - `error`：读取该键失败时的错误信息，此时其余字段（类型除外）没有意义
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct KeyPreview {
+    #[serde(rename = "type")]
+    pub key_type: String,
+    pub ttl: i64,
+    pub element_count: Option<u64>,
+    pub sample: Vec<String>,
+    pub error: Option<String>,
+}
+
+/// 键检查面板所需的元数据
+/// 
+/// 由一次服务端脚本调用得到，各字段来自同一时刻的键状态：
+/// - `key_type`：`TYPE` 的结果，键不存在时为 `none`（序列化为 `type`）
+/// - `ttl_ms`：`PTTL` 的结果（毫秒），`-1` 表示永不过期，`-2` 表示键不存在
+/// - `encoding`：`OBJECT ENCODING` 的结果，如 `listpack`、`skiplist`
+/// - `memory_usage`：`MEMORY USAGE` 的结果（字节），服务端禁用该命令时为 `None`
+/// - `element_count`：字符串为字节长度，列表/集合/有序集合/哈希/流为元素数量，其他类型为 `None`
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct KeyInspection {
+    #[serde(rename = "type")]
+    pub key_type: String,
+    pub ttl_ms: i64,
+    pub encoding: Option<String>,
+    pub memory_usage: Option<u64>,
+    pub element_count: Option<u64>,
+}
+
+/// 哈希字段值及其 JSON 解析结果
+/// 
+/// 供前端以树形结构展示存放 JSON 的字段：
+/// - `raw`：字段的原始值
+/// - `is_json`：原始值是否为合法的 JSON 对象或数组；数字、布尔等标量不视为 JSON，按普通字符串展示
+/// - `json`：`is_json` 为 `true` 时的解析结果
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ParsedHashField {
+    pub field: String,
+    pub raw: String,
+    pub is_json: bool,
+    pub json: Option<serde_json::Value>,
+}
+
+impl ParsedHashField {
+    /// 尝试将字段值解析为 JSON 对象或数组
+    pub fn parse(field: String, raw: String) -> Self {
+        let json = serde_json::from_str::<serde_json::Value>(raw.trim())
+            .ok()
+            .filter(|v| v.is_object() || v.is_array());
+        ParsedHashField { field, raw, is_json: json.is_some(), json }
+    }
+}
+
+/// 流中的一条消息
+/// 
+/// - `id`：消息 ID，如 `1700000000000-0`
+/// - `fields`：按写入顺序排列的 `(字段, 值)` 列表；消息已被 `XDEL` 删除但仍在待确认列表中时为空
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct StreamEntry {
+    pub id: String,
+    pub fields: Vec<(String, String)>,
+}
+
+/// 按类型描述的键值，供 `write_key` 整体替换键的值
+/// 
+/// 序列化为带 `type` 标签的对象，结构与导出文件中的值保持一致：
+/// - `{ "type": "string", "value": "..." }`
+/// - `{ "type": "hash", "fields": { "f": "v" } }`
+/// - `{ "type": "list", "items": ["a", "b"] }`
+/// - `{ "type": "set", "members": ["a", "b"] }`
+/// - `{ "type": "zset", "members": [["a", 1.0]] }`
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum KeyPayload {
+    String { value: String },
+    Hash { fields: BTreeMap<String, String> },
+    List { items: Vec<String> },
+    Set { members: Vec<String> },
+    Zset { members: Vec<(String, f64)> },
+}
+
+impl KeyPayload {
+    /// 对应 `TYPE` 命令返回的类型名
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            KeyPayload::String { .. } => "string",
+            KeyPayload::Hash { .. } => "hash",
+            KeyPayload::List { .. } => "list",
+            KeyPayload::Set { .. } => "set",
+            KeyPayload::Zset { .. } => "zset",
+        }
+    }
+
+    /// 集合类型的值是否为空
+    /// 
+    /// Redis 不保存空的集合类型，写入空集合等同于删除键。字符串始终返回 `false`。
+    pub fn is_empty(&self) -> bool {
+        match self {
+            KeyPayload::String { .. } => false,
+            KeyPayload::Hash { fields } => fields.is_empty(),
+            KeyPayload::List { items } => items.is_empty(),
+            KeyPayload::Set { members } => members.is_empty(),
+            KeyPayload::Zset { members } => members.is_empty(),
+        }
+    }
+
+    /// 将写入命令追加到管道中，不包含删除旧值的 `DEL`
+    fn append_writes(&self, pipe: &mut Pipeline, key: &str) {
+        match self {
+            KeyPayload::String { value } => {
+                pipe.cmd("SET").arg(key).arg(value).ignore();
+            }
+            KeyPayload::Hash { fields } => {
+                let mut cmd = redis::cmd("HSET");
+                cmd.arg(key);
+                for (field, value) in fields {
+                    cmd.arg(field).arg(value);
+                }
+                pipe.add_command(cmd).ignore();
+            }
+            KeyPayload::List { items } => {
+                pipe.cmd("RPUSH").arg(key).arg(items).ignore();
+            }
+            KeyPayload::Set { members } => {
+                pipe.cmd("SADD").arg(key).arg(members).ignore();
+            }
+            KeyPayload::Zset { members } => {
+                let mut cmd = redis::cmd("ZADD");
+                cmd.arg(key);
+                for (member, score) in members {
+                    cmd.arg(*score).arg(member);
+                }
+                pipe.add_command(cmd).ignore();
+            }
+        }
+    }
+}
+
+/// 任意命令的执行信息
+/// 
+/// 由 `run_raw_command_timed` 返回，命令层原样附加到响应中（见 `command::ResponseMeta`）：
+/// - `duration_ms`: 命令在服务端往返的耗时（毫秒，含小数）
+/// - `node`: 集群模式下处理该命令的节点地址（`host:port`）；单机、哨兵模式或命令不涉及键时为 `None`
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct ResponseMeta {
+    pub duration_ms: f64,
+    pub node: Option<String>,
+}
+
+/// 字符串值的分页读取结果
+/// 
+/// 由 `STRLEN` 与 `GETRANGE` 组合得到，供前端对大字符串进行分页展示：
+/// - `total_len`：整个值的字节长度
+/// - `chunk`：本页内容；若本页不是合法的 UTF-8，则为 Base64 编码后的字符串
+/// - `base64`：`chunk` 是否经过 Base64 编码
+/// - `next_offset`：下一页的起始偏移量。文本页的末尾若截断了多字节字符，该字符留到下一页，
+///   因此可能小于 `offset + length`；前端应以它请求下一页，拼接的文本才完整
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ValuePage {
+    pub total_len: u64,
+    pub chunk: String,
+    pub base64: bool,
+    pub next_offset: u64,
+}
+
+/// 计算分页读取的最后一个字节位置（含）
+/// 
+/// `length` 为 0 或 `offset` 超出值的长度时返回 `None`；`offset + length` 溢出时按值的末尾截断。
+fn page_end(offset: u64, length: u64, total_len: u64) -> Option<u64> {
+    if length == 0 || offset >= total_len {
+        return None;
+    }
+    Some(offset.saturating_add(length - 1).min(total_len - 1))
+}
+
+impl ValuePage {
+    /// 根据总长度、本页起始偏移量和原始字节构造分页结果
+    /// 
+    /// 合法的 UTF-8 数据原样返回，二进制数据使用标准 Base64 编码。
+    /// 本页不是最后一页且只在末尾截断了一个多字节字符时，去掉这几个字节并相应减小 `next_offset`，
+    /// 使每一页都在字符边界结束，不会因分页位置把文本页误判为二进制。
+    pub fn from_bytes(total_len: u64, offset: u64, mut bytes: Vec<u8>) -> Self {
+        let is_last = offset.saturating_add(bytes.len() as u64) >= total_len;
+        if let Err(e) = std::str::from_utf8(&bytes) {
+            if !is_last && e.error_len().is_none() && e.valid_up_to() > 0 {
+                bytes.truncate(e.valid_up_to());
+            }
+        }
+        let next_offset = offset.saturating_add(bytes.len() as u64).min(total_len.max(offset));
+        match String::from_utf8(bytes) {
+            Ok(chunk) => ValuePage { total_len, chunk, base64: false, next_offset },
+            Err(e) => ValuePage {
+                total_len,
+                chunk: base64::engine::general_purpose::STANDARD.encode(e.into_bytes()),
+                base64: true,
+                next_offset,
+            },
+        }
+    }
+}
+
+/// 建立连接超时
+/// 
+/// 由 `RedisService::new` 在 `connect_timeout_ms` 内未能建立连接时返回，
+/// 命令层据此映射为 `CONNECT_TIMEOUT` 错误代码。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConnectTimeout {
+    pub timeout_ms: u64,
+}
+
+impl std::fmt::Display for ConnectTimeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "connection not established within {} ms", self.timeout_ms)
+    }
+}
+
+impl std::error::Error for ConnectTimeout {}
+
+/// 参数校验失败
+/// 
+/// 由 `RedisService` 在发送命令之前检查参数时返回（如模式为空、缺少键、超出范围），
+/// 命令层据此映射为 `INVALID_ARGUMENT` 错误代码，而不是与服务端错误混在一起。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidArgument {
+    pub message: String,
+}
+
+impl std::fmt::Display for InvalidArgument {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for InvalidArgument {}
+
+/// 构造 `InvalidArgument` 错误
+fn invalid_argument(message: impl Into<String>) -> anyhow::Error {
+    anyhow::Error::new(InvalidArgument { message: message.into() })
+}
+
+/// 命令被连接的命令策略拒绝
+/// 
+/// 由 `RedisService` 在发送命令之前根据 `RedisConfig::command_policy` 返回，
+/// 命令层据此映射为 `POLICY_BLOCKED` 错误代码。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PolicyBlocked {
+    pub command: String,
+}
+
+impl std::fmt::Display for PolicyBlocked {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} is blocked by this connection's command policy", self.command)
+    }
+}
+
+impl std::error::Error for PolicyBlocked {}
+
+/// 在只读连接上尝试写入
+/// 
+/// 由 `RedisService` 在 `RedisConfig::readonly` 为 `true` 时、发送写命令之前返回，
+/// 命令层据此映射为 `READONLY_CONNECTION` 错误代码。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReadOnlyConnection {
+    pub command: String,
+}
+
+impl std::fmt::Display for ReadOnlyConnection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} is not allowed, this connection is read-only", self.command)
+    }
+}
+
+impl std::error::Error for ReadOnlyConnection {}
+
+/// 键的实际类型与命令不匹配
+/// 
+/// 由 `get` 或哈希/列表/集合/有序集合的写入方法在服务端返回 `WRONGTYPE` 时构造，携带键的实际类型，
+/// 写入时还携带尝试执行的命令（`operation`）。
+/// 命令层据此映射为 `WRONGTYPE` 错误代码，前端可引导用户切换到对应的查看器。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WrongType {
+    pub key: String,
+    pub actual: String,
+    pub operation: Option<String>,
+}
+
+impl std::fmt::Display for WrongType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.operation {
+            Some(op) => write!(f, "cannot {} key '{}', it already holds a {}", op, self.key, self.actual),
+            None => write!(f, "key '{}' is a {}, use the {} viewer", self.key, self.actual, self.actual),
+        }
+    }
+}
+
+impl std::error::Error for WrongType {}
+
+/// Pub/Sub 收到的一条消息
+/// 
+/// 多频道订阅时通过 `channel` 区分消息来源。
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PubSubMessage {
+    pub channel: String,
+    pub payload: String,
+}
+
+impl RedisConfig {
+    /// 根据配置返回连接握手使用的协议版本
+    pub fn protocol(&self) -> ProtocolVersion {
+        if self.resp3 {
+            ProtocolVersion::RESP3
+        } else {
+            ProtocolVersion::RESP2
+        }
+    }
 }
 
 impl Default for RedisConfig {
@@ -301,61 +902,522 @@ impl Default for RedisConfig {
             sentinel: false,
             sentinel_master_name: None,
             sentinel_urls: vec![],
+
+            // 默认使用 RESP2 协议
+            resp3: false,
+
+            // 默认使用 0 号数据库
+            default_db: 0,
+
+            // 默认所有读写都发往主节点
+            read_from_replicas: false,
+
+            // 默认 3 秒连接超时
+            connect_timeout_ms: 3000,
+
+            // 默认不启用读缓存
+            read_cache_ttl_ms: None,
+
+            // 默认不限制命令
+            command_policy: None,
+
+            // 默认允许写入
+            readonly: false,
         }
     }
 }
 
-/// Redis 服务实例
+/// 持久化状态
 /// 
-/// 主要的 Redis 操作接口，封装了底层连接管理和重试逻辑。
-/// 支持单机、哨兵、集群三种部署模式的统一操作接口。
+/// 由 `INFO persistence` 解析得到，供持久化面板展示 RDB/AOF 的当前状态：
+/// - `loading`: 服务端是否正在加载持久化文件
+/// - `rdb_changes_since_last_save`: 上次快照以来的修改次数
+/// - `rdb_bgsave_in_progress`: 是否正在执行后台快照
+/// - `rdb_last_save_time`: 最近一次成功快照的 Unix 时间戳（秒）
+/// - `rdb_last_bgsave_status`: 最近一次后台快照的结果（`ok` / `err`）
+/// - `aof_enabled`: 是否开启 AOF
+/// - `aof_rewrite_in_progress`: 是否正在执行 AOF 重写
+/// - `aof_rewrite_scheduled`: 是否有等待当前快照完成后执行的 AOF 重写
+/// - `aof_last_bgrewrite_status`: 最近一次 AOF 重写的结果（`ok` / `err`）
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PersistenceStatus {
+    pub loading: bool,
+    pub rdb_changes_since_last_save: u64,
+    pub rdb_bgsave_in_progress: bool,
+    pub rdb_last_save_time: i64,
+    pub rdb_last_bgsave_status: String,
+    pub aof_enabled: bool,
+    pub aof_rewrite_in_progress: bool,
+    pub aof_rewrite_scheduled: bool,
+    pub aof_last_bgrewrite_status: String,
+}
+
+impl PersistenceStatus {
+    /// 解析 `INFO persistence` 的文本回复
+    /// 
+    /// 忽略空行、`# Persistence` 等注释行以及未知字段，缺失或无法解析的字段保持默认值。
+    pub fn from_info(info: &str) -> Self {
+        let mut status = PersistenceStatus::default();
+        for line in info.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once(':') else { continue };
+            let flag = value == "1";
+            match key {
+                "loading" => status.loading = flag,
+                "rdb_changes_since_last_save" => status.rdb_changes_since_last_save = value.parse().unwrap_or_default(),
+                "rdb_bgsave_in_progress" => status.rdb_bgsave_in_progress = flag,
+                "rdb_last_save_time" => status.rdb_last_save_time = value.parse().unwrap_or_default(),
+                "rdb_last_bgsave_status" => status.rdb_last_bgsave_status = value.to_string(),
+                "aof_enabled" => status.aof_enabled = flag,
+                "aof_rewrite_in_progress" => status.aof_rewrite_in_progress = flag,
+                "aof_rewrite_scheduled" => status.aof_rewrite_scheduled = flag,
+                "aof_last_bgrewrite_status" => status.aof_last_bgrewrite_status = value.to_string(),
+                _ => {}
+            }
+        }
+        status
+    }
+}
+
+/// 集群整体状态
 /// 
-/// # 线程安全
+/// 由 `CLUSTER INFO` 解析得到，供集群面板展示：
+/// - `cluster_enabled`: 服务端是否以集群模式运行，为 `false` 时其余字段均为默认值
+/// - `cluster_state`: 集群状态（`ok` / `fail`）
+/// - `cluster_slots_assigned`: 已分配的槽位数量，正常时为 16384
+/// - `cluster_slots_ok` / `cluster_slots_pfail` / `cluster_slots_fail`: 各状态下的槽位数量
+/// - `cluster_known_nodes`: 已知节点数量（含握手中的节点）
+/// - `cluster_size`: 至少负责一个槽位的主节点数量
+/// - `cluster_current_epoch`: 当前纪元
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ClusterStatus {
+    pub cluster_enabled: bool,
+    pub cluster_state: String,
+    pub cluster_slots_assigned: u32,
+    pub cluster_slots_ok: u32,
+    pub cluster_slots_pfail: u32,
+    pub cluster_slots_fail: u32,
+    pub cluster_known_nodes: u32,
+    pub cluster_size: u32,
+    pub cluster_current_epoch: u64,
+}
+
+impl ClusterStatus {
+    /// 解析 `CLUSTER INFO` 的文本回复
+    /// 
+    /// 忽略空行及未知字段，缺失或无法解析的字段保持默认值；`cluster_enabled` 固定为 `true`。
+    pub fn from_info(info: &str) -> Self {
+        let mut status = ClusterStatus { cluster_enabled: true, ..ClusterStatus::default() };
+        for line in info.lines() {
+            let Some((key, value)) = line.trim().split_once(':') else { continue };
+            match key {
+                "cluster_state" => status.cluster_state = value.to_string(),
+                "cluster_slots_assigned" => status.cluster_slots_assigned = value.parse().unwrap_or_default(),
+                "cluster_slots_ok" => status.cluster_slots_ok = value.parse().unwrap_or_default(),
+                "cluster_slots_pfail" => status.cluster_slots_pfail = value.parse().unwrap_or_default(),
+                "cluster_slots_fail" => status.cluster_slots_fail = value.parse().unwrap_or_default(),
+                "cluster_known_nodes" => status.cluster_known_nodes = value.parse().unwrap_or_default(),
+                "cluster_size" => status.cluster_size = value.parse().unwrap_or_default(),
+                "cluster_current_epoch" => status.cluster_current_epoch = value.parse().unwrap_or_default(),
+                _ => {}
+            }
+        }
+        status
+    }
+}
+
+/// 连接测试结果
 /// 
-/// `RedisService` 实现了 `Clone`，可以安全地在多个线程间共享：
-/// - 克隆操作不会创建新的连接，只是增加引用计数
-/// - 内部使用连接管理器自动处理连接复用
-/// - 所有操作都是异步的，不会阻塞线程
+/// 由 `RedisService::probe` 返回，除连通性外还会对比服务端的运行模式与配置：
+/// - `ok`: 是否成功建立连接并通过健康检查
+/// - `server_version`: `INFO server` 中的 `redis_version`
+/// - `redis_mode`: `INFO server` 中的 `redis_mode`（`standalone` / `cluster` / `sentinel`）
+/// - `latency_ms`: 健康检查（PING）的往返耗时（毫秒）
+/// - `warnings`: 配置与服务端不一致等提示信息
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TestResult {
+    pub ok: bool,
+    pub server_version: String,
+    pub redis_mode: String,
+    pub latency_ms: f64,
+    pub warnings: Vec<String>,
+}
+
+impl TestResult {
+    /// 根据 `INFO server` 的文本回复构造测试结果，并检查运行模式是否与配置一致
+    /// 
+    /// 哨兵模式下实际连接的是主节点，其 `redis_mode` 应为 `standalone`。
+    pub fn from_server_info(info: &str, cfg: &RedisConfig, latency_ms: f64) -> Self {
+        let mut result = TestResult { ok: true, latency_ms, ..Default::default() };
+        for line in info.lines() {
+            let Some((key, value)) = line.trim().split_once(':') else { continue };
+            match key {
+                "redis_version" => result.server_version = value.to_string(),
+                "redis_mode" => result.redis_mode = value.to_string(),
+                _ => {}
+            }
+        }
+        let expected = if cfg.cluster { "cluster" } else { "standalone" };
+        if !result.redis_mode.is_empty() && result.redis_mode != expected {
+            result.warnings.push(format!(
+                "Mode mismatch: configured as {} but server reports redis_mode={}",
+                if cfg.cluster { "cluster" } else if cfg.sentinel { "sentinel" } else { "standalone" },
+                result.redis_mode,
+            ));
+        }
+        result
+    }
+}
+
+/// 服务端支持的功能
 /// 
-/// # 重试机制
+/// 由 `RedisService::detect_capabilities` 根据 `INFO server` 的版本号与 `MODULE LIST` 得到，
+/// 前端据此隐藏服务端不支持的操作：
+/// - `server_version` / `modules`：服务端版本与已加载的模块名称
+/// - `has_json` / `has_search`：是否加载了 RedisJSON（`ReJSON`）/ RediSearch（`search`）模块
+/// - `supports_resp3`：是否支持 RESP3 协议（6.0+）
+/// - `supports_getdel`：是否支持 `GETDEL`、`GETEX`（6.2+）
+/// - `supports_expire_flags`：`EXPIRE` 是否支持 `NX`/`XX`/`GT`/`LT` 选项（7.0+）
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Capabilities {
+    pub server_version: String,
+    pub modules: Vec<String>,
+    pub has_json: bool,
+    pub has_search: bool,
+    pub supports_resp3: bool,
+    pub supports_getdel: bool,
+    pub supports_expire_flags: bool,
+}
+
+impl Capabilities {
+    /// 根据 `INFO server` 的文本回复与模块名称列表构造能力信息
+    /// 
+    /// 无法解析版本号时按 0.0.0 处理，所有依赖版本的功能均视为不支持。
+    pub fn from_info(info: &str, modules: Vec<String>) -> Self {
+        let server_version = info
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("redis_version:"))
+            .unwrap_or_default()
+            .to_string();
+        let version = parse_version(&server_version);
+        let has_module = |name: &str| modules.iter().any(|m| m.eq_ignore_ascii_case(name));
+        Capabilities {
+            has_json: has_module("ReJSON"),
+            has_search: has_module("search"),
+            supports_resp3: version >= (6, 0, 0),
+            supports_getdel: version >= (6, 2, 0),
+            supports_expire_flags: version >= (7, 0, 0),
+            server_version,
+            modules,
+        }
+    }
+}
+
+/// 服务端时间与本地时钟的偏差
 /// 
-/// 所有 Redis 操作都通过 `with_retry` 方法包装，提供：
-/// - 自动重试失败的连接
-/// - 可配置的重试次数和延迟
-/// - 详细的错误日志记录
-/// - 智能的错误分类和处理
+/// - `seconds` / `microseconds`: `TIME` 命令返回的服务端时间
+/// - `skew_ms`: 服务端时间减去本地时间（毫秒），正数表示服务端时钟较快。
+///   本地时间取请求发出与收到回复的中点，以抵消网络往返的影响
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ServerTime {
+    pub seconds: i64,
+    pub microseconds: i64,
+    pub skew_ms: f64,
+}
+
+impl ServerTime {
+    /// 根据服务端时间与本地时间（Unix 毫秒）计算偏差
+    pub fn new(seconds: i64, microseconds: i64, local_ms: f64) -> Self {
+        let server_ms = seconds as f64 * 1000.0 + microseconds as f64 / 1000.0;
+        ServerTime { seconds, microseconds, skew_ms: server_ms - local_ms }
+    }
+}
+
+/// 按模式统计键数量的结果
 /// 
-/// # 使用建议
+/// 由 `RedisService::count_keys` 返回：
+/// - `count`: 已统计到的匹配键数量
+/// - `complete`: 是否完整遍历了键空间；达到扫描次数上限时为 `false`，此时 `count` 只是部分结果
+/// - `iterations`: 实际执行的 SCAN 次数
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct KeyCount {
+    pub count: u64,
+    pub complete: bool,
+    pub iterations: u64,
+}
+
+/// 性能测试结果
 /// 
-/// - 长期应用中保持 `RedisService` 实例，避免频繁创建
-/// - 在集群模式下注意键的分布，避免跨槽位操作
-/// - 合理配置重试参数，平衡稳定性和响应速度
-/// - 监控连接状态，及时发现和处理问题
-#[derive(Clone)]
-pub struct RedisService {
-    /// 连接类型枚举，存储实际的连接对象
-    kind: ConnectionKind,
-    
-    /// 连接配置，用于重连和日志记录
-    cfg: RedisConfig,
+/// 由 `RedisService::benchmark` 返回，每个操作为一次 SET + GET：
+/// - `total_ops`: 实际完成的操作数
+/// - `duration_ms`: 总耗时（毫秒）
+/// - `ops_per_sec`: 每秒完成的操作数
+/// - `p50_ms` / `p99_ms`: 单次操作耗时的 P50 / P99（毫秒）
+/// - `cancelled`: 是否被 `cancel_benchmark` 提前终止
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct BenchmarkResult {
+    pub total_ops: u64,
+    pub duration_ms: f64,
+    pub ops_per_sec: f64,
+    pub p50_ms: f64,
+    pub p99_ms: f64,
+    pub cancelled: bool,
 }
 
-/// Redis 连接类型枚举
+/// 性能测试的操作数上限
+pub const BENCHMARK_MAX_OPS: u64 = 1_000_000;
+
+/// 性能测试的并发任务数上限
+pub const BENCHMARK_MAX_CONCURRENCY: usize = 256;
+
+/// 性能测试的单个值大小上限（字节）
+pub const BENCHMARK_MAX_VALUE_SIZE: usize = 1024 * 1024;
+
+/// 性能测试临时键的过期时间（秒），清理被跳过时由 Redis 自动删除
+const BENCHMARK_KEY_TTL: u64 = 300;
+
+/// DEBUG SLEEP 允许的最长休眠时间（秒）
+pub const DEBUG_SLEEP_MAX_SECS: f64 = 60.0;
+
+/// 无法读取 `databases` 配置时假定的数据库数量（Redis 默认值）
+const DEFAULT_DATABASE_COUNT: u32 = 16;
+
+/// `count_keys` 每次 SCAN 的 COUNT 提示
+const COUNT_KEYS_BATCH: usize = 1000;
+
+/// `count_keys` 的 SCAN 次数上限（所有节点合计）
+const COUNT_KEYS_MAX_ITERATIONS: u64 = 10_000;
+
+/// `find_idle_keys` 每次 SCAN 的 COUNT 提示
+const IDLE_SCAN_COUNT: usize = 500;
+
+/// `find_idle_keys` 最多检查的键数量
+const IDLE_MAX_EXAMINED: usize = 100_000;
+
+/// `preview_keys` 中每个集合类键最多读取的样本数量
+const PREVIEW_SAMPLE_SIZE: usize = 10;
+
+/// `preview_keys` 中字符串值最多读取的字节数
+const PREVIEW_STRING_BYTES: usize = 256;
+
+/// `preview_keys` 同时读取样本的键数量
+const PREVIEW_CONCURRENCY: usize = 16;
+
+/// 连接延迟指标快照
 /// 
-/// 封装不同部署模式的连接对象：
-/// - `Standalone`: 单机或哨兵模式的连接管理器和原始客户端（用于特定 DB 操作）
+/// 由 `RedisService::metrics_snapshot` 返回，统计自连接建立以来每次命令尝试（含重试）的耗时：
+/// - `count`: 命令尝试总次数
+/// - `avg_ms`: 平均耗时（毫秒）
+/// - `p95_ms`: 最近采样窗口内的 P95 耗时（毫秒）
+/// - `errors`: 失败的尝试次数
+/// - `cache_hits` / `cache_misses`: 读缓存的命中与未命中次数，未启用读缓存时为 0
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ConnMetrics {
+    pub count: u64,
+    pub avg_ms: f64,
+    pub p95_ms: f64,
+    pub errors: u64,
+    pub cache_hits: u64,
+    pub cache_misses: u64,
+}
+
+/// 延迟采样窗口大小，用于计算 P95
+const METRICS_SAMPLE_CAPACITY: usize = 1024;
+
+/// 读缓存最多保留的条目数量
+const READ_CACHE_CAPACITY: usize = 256;
+
+/// 集群槽位归属缓存的有效期
+const SLOT_OWNERS_TTL: Duration = Duration::from_secs(30);
+
+/// 集群槽位到主节点地址的缓存，供 `serving_node` 使用
+struct SlotOwners {
+    fetched_at: Instant,
+    /// `(起始槽位, 结束槽位, 主节点地址)`
+    ranges: Vec<(u16, u16, String)>,
+}
+
+impl SlotOwners {
+    /// 返回负责 `slot` 的主节点地址
+    fn lookup(&self, slot: u16) -> Option<String> {
+        self.ranges.iter()
+            .find(|(start, end, _)| (*start..=*end).contains(&slot))
+            .map(|(_, _, addr)| addr.clone())
+    }
+}
+
+/// 延迟指标收集器
+/// 
+/// 计数与总耗时使用原子变量，记录开销很低；
+/// 分位数基于一个有界的最近采样窗口计算。
+#[derive(Default)]
+struct MetricsRecorder {
+    count: AtomicU64,
+    errors: AtomicU64,
+    total_us: AtomicU64,
+    samples: std::sync::Mutex<VecDeque<u64>>,
+}
+
+impl MetricsRecorder {
+    /// 记录一次命令尝试
+    fn record(&self, elapsed: Duration, ok: bool) {
+        let us = elapsed.as_micros() as u64;
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.total_us.fetch_add(us, Ordering::Relaxed);
+        if !ok {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+        }
+        let mut samples = self.samples.lock().unwrap();
+        if samples.len() >= METRICS_SAMPLE_CAPACITY {
+            samples.pop_front();
+        }
+        samples.push_back(us);
+    }
+
+    /// 生成当前指标快照
+    fn snapshot(&self) -> ConnMetrics {
+        let count = self.count.load(Ordering::Relaxed);
+        let total_us = self.total_us.load(Ordering::Relaxed);
+        let errors = self.errors.load(Ordering::Relaxed);
+        let mut sorted: Vec<u64> = self.samples.lock().unwrap().iter().copied().collect();
+        sorted.sort_unstable();
+        let p95_us = percentile(&sorted, 0.95);
+        ConnMetrics {
+            count,
+            avg_ms: if count == 0 { 0.0 } else { total_us as f64 / count as f64 / 1000.0 },
+            p95_ms: p95_us as f64 / 1000.0,
+            errors,
+            cache_hits: 0,
+            cache_misses: 0,
+        }
+    }
+
+    /// 清零所有计数并清空采样窗口
+    fn reset(&self) {
+        let mut samples = self.samples.lock().unwrap();
+        self.count.store(0, Ordering::Relaxed);
+        self.total_us.store(0, Ordering::Relaxed);
+        self.errors.store(0, Ordering::Relaxed);
+        samples.clear();
+    }
+}
+
+/// Redis 服务实例
+/// 
+/// 主要的 Redis 操作接口，封装了底层连接管理和重试逻辑。
+/// 支持单机、哨兵、集群三种部署模式的统一操作接口。
+/// 
+/// # 线程安全
+/// 
+/// `RedisService` 实现了 `Clone`，可以安全地在多个线程间共享：
+/// - 克隆操作不会创建新的连接，只是增加引用计数
+/// - 内部使用连接管理器自动处理连接复用
+/// - 所有操作都是异步的，不会阻塞线程
+/// 
+/// # 重试机制
+/// 
+/// 所有 Redis 操作都通过 `with_retry` 方法包装，提供：
+/// - 自动重试失败的连接
+/// - 可配置的重试次数和延迟
+/// - 详细的错误日志记录
+/// - 智能的错误分类和处理
+/// 
+/// # 使用建议
+/// 
+/// - 长期应用中保持 `RedisService` 实例，避免频繁创建
+/// - 在集群模式下注意键的分布，避免跨槽位操作
+/// - 合理配置重试参数，平衡稳定性和响应速度
+/// - 监控连接状态，及时发现和处理问题
+#[derive(Clone)]
+pub struct RedisService {
+    /// 连接类型枚举，存储实际的连接对象
+    kind: ConnectionKind,
+    
+    /// 连接配置，用于重连和日志记录
+    /// 
+    /// 保持调用方传入的原样，`AppState::reload_from_db` 依靠它判断配置是否变化。
+    cfg: RedisConfig,
+
+    /// 实际使用的默认数据库：`cfg.default_db`，未设置时取地址路径中的数据库索引
+    default_db: u32,
+
+    /// 延迟指标，克隆的实例共享同一份统计
+    metrics: Arc<MetricsRecorder>,
+
+    /// 连接建立的时间点，用于计算运行时长
+    connected_since: Instant,
+
+    /// 正在运行的性能测试的取消标志，克隆的实例共享
+    /// 
+    /// 每次运行创建自己的标志，结束时移除；没有测试运行时为 `None`，此时的取消请求不会影响之后的运行。
+    benchmark_cancel: Arc<std::sync::Mutex<Option<Arc<AtomicBool>>>>,
+
+    /// 共享的 Pub/Sub 连接，首次 `subscribe` 时建立，克隆的实例共享
+    pubsub: Arc<tokio::sync::OnceCell<PubSubManager>>,
+
+    /// 服务端配置的数据库数量，首次查询后缓存，克隆的实例共享
+    database_count: Arc<tokio::sync::OnceCell<u32>>,
+
+    /// 服务端支持的功能，首次检测成功后缓存，克隆的实例共享
+    capabilities: Arc<tokio::sync::OnceCell<Capabilities>>,
+
+    /// 读缓存，`read_cache_ttl_ms` 未设置时为 `None`，克隆的实例共享
+    read_cache: Option<Arc<ReadCache>>,
+
+    /// 集群槽位归属，`serving_node` 首次使用时查询，过期或未覆盖目标槽位时刷新，克隆的实例共享
+    slot_owners: Arc<std::sync::Mutex<Option<SlotOwners>>>,
+}
+
+/// 单机/哨兵模式下的连接管理器池
+/// 
+/// 持有 `pool_size` 个 `ConnectionManager`，每个对应一条独立的多路复用连接，
+/// `get` 按轮询顺序返回。克隆得到的池共享同一组连接和同一个游标。
+#[derive(Clone)]
+struct ManagerPool {
+    managers: Arc<Vec<ConnectionManager>>,
+    next: Arc<AtomicUsize>,
+}
+
+impl ManagerPool {
+    /// 并发建立 `size` 个连接管理器（至少 1 个）
+    async fn new(client: &redis::Client, size: usize) -> Result<Self> {
+        let managers = futures::future::try_join_all(
+            (0..size.max(1)).map(|_| client.get_connection_manager())
+        ).await.context("create connection pool")?;
+        Ok(Self { managers: Arc::new(managers), next: Arc::new(AtomicUsize::new(0)) })
+    }
+
+    /// 按轮询顺序取出下一个连接管理器
+    fn get(&self) -> ConnectionManager {
+        let i = self.next.fetch_add(1, Ordering::Relaxed) % self.managers.len();
+        self.managers[i].clone()
+    }
+
+    /// 池中所有连接管理器
+    fn all(&self) -> &[ConnectionManager] {
+        &self.managers
+    }
+}
+
+/// Redis 连接类型枚举
+/// 
+/// 封装不同部署模式的连接对象：
+/// - `Standalone`: 单机或哨兵模式的连接管理器池和原始客户端（用于特定 DB 操作）
     /// - `Cluster`: 集群模式的客户端连接
     #[derive(Clone)]
     enum ConnectionKind {
-        /// 单机模式连接管理器
+        /// 单机模式连接管理器池
         /// 
-        /// 使用 `ConnectionManager` 提供：
+        /// 由 `pool_size` 个 `ConnectionManager` 组成，每个提供：
         /// - 自动连接复用
         /// - 连接断开自动重连
         /// - 线程安全的并发访问
         /// 
         /// `Client` 用于创建特定数据库的临时连接
-        Standalone(ConnectionManager, redis::Client),
+        Standalone(ManagerPool, redis::Client),
         
         /// 集群模式客户端
         /// 
@@ -363,6 +1425,9 @@ pub struct RedisService {
         /// - 自动路由到正确的节点
         /// - 槽位感知的命令分发
         /// - 集群拓扑的自动更新
+        /// 
+        /// 命令方法通过 `client.get_connection()` 得到的是 `ClusterConnection`，会自动跟随
+        /// `MOVED`/`ASK` 重定向；重定向次数耗尽仍返回的 `MOVED`/`ASK` 由 `with_retry` 在刷新拓扑后重试。
         Cluster(ClusterClient),
     }
 
@@ -402,14 +1467,39 @@ impl RedisService {
     /// let redis = RedisService::new(config).await?;
     /// ```
     pub async fn new(cfg: RedisConfig) -> Result<Self> {
+        let read_cache = cfg.read_cache_ttl_ms
+            .filter(|&ms| ms > 0)
+            .map(|ms| Arc::new(ReadCache::new(Duration::from_millis(ms), READ_CACHE_CAPACITY)));
         if cfg.cluster {
             // 集群模式初始化
-            logging::info("REDIS_INIT", &format!("cluster mode urls={:?}", cfg.urls));
-            let client = ClusterClient::new(cfg.urls.clone())?;
-            return Ok(Self { kind: ConnectionKind::Cluster(client), cfg });
+            let routing = if cfg.read_from_replicas { "replicas" } else { "masters" };
+            logging::info("REDIS_INIT", &format!("cluster mode urls={:?} protocol={:?} reads={}", cfg.urls, cfg.protocol(), routing));
+            let mut urls = Vec::with_capacity(cfg.urls.len());
+            for url in &cfg.urls {
+                let (url, db) = split_url_db(url)?;
+                if let Some(db) = db.filter(|&db| db != 0) {
+                    logging::warn("REDIS_INIT", &format!("cluster mode does not support database selection, ignoring db {} in url", db));
+                }
+                urls.push(url);
+            }
+            let mut builder = ClusterClient::builder(urls)
+                .use_protocol(cfg.protocol());
+            if cfg.connect_timeout_ms > 0 {
+                builder = builder.connection_timeout(Duration::from_millis(cfg.connect_timeout_ms));
+            }
+            if cfg.read_from_replicas {
+                // 较新的 redis 版本建议改用 read_routing_strategy，锁定版本中仍使用此接口
+                #[allow(deprecated)]
+                {
+                    builder = builder.read_from_replicas();
+                }
+            }
+            let client = builder.build()?;
+            return Ok(Self { kind: ConnectionKind::Cluster(client), default_db: cfg.default_db, cfg, metrics: Arc::default(), connected_since: Instant::now(), benchmark_cancel: Arc::default(), pubsub: Arc::default(), database_count: Arc::default(), capabilities: Arc::default(), read_cache, slot_owners: Arc::default() });
         }
 
         // 解析连接地址
+        let mut default_db = cfg.default_db;
         let url = if cfg.sentinel {
             // 哨兵模式：通过 redis+sentinel 协议自动处理
             let master = cfg.sentinel_master_name.as_ref()
@@ -421,18 +1511,35 @@ impl RedisService {
             url
         } else {
             // 单机模式：直接使用配置的地址
-            cfg.urls.get(0)
+            let url = cfg.urls.get(0)
                 .ok_or_else(|| anyhow!("no redis url provided"))?
-                .clone()
+                .clone();
+            // 地址路径中的数据库索引（如 redis://host:6379/3）作为默认数据库，显式配置的 default_db 优先
+            if let (_, Some(db)) = split_url_db(&url)? {
+                logging::info("REDIS_INIT", &format!("database index {} detected in url", db));
+                if cfg.default_db == 0 {
+                    default_db = db;
+                } else if cfg.default_db != db {
+                    logging::warn("REDIS_INIT", &format!("default_db {} overrides db {} in url", cfg.default_db, db));
+                }
+            }
+            url
         };
         
-        logging::info("REDIS_INIT", &format!("connecting to url={}", url));
+        logging::info("REDIS_INIT", &format!("connecting to url={} protocol={:?}", url, cfg.protocol()));
         
         // 创建 Redis 客户端和连接管理器
-        let client = redis::Client::open(url)?;
-        let manager = client.get_connection_manager().await?;
+        let client = redis::Client::open(connection_info(&url, &cfg)?)?;
+        let pool = if cfg.connect_timeout_ms > 0 {
+            tokio::time::timeout(Duration::from_millis(cfg.connect_timeout_ms), ManagerPool::new(&client, cfg.pool_size))
+                .await
+                .map_err(|_| anyhow::Error::new(ConnectTimeout { timeout_ms: cfg.connect_timeout_ms }).context(format!("connect to {}", url)))??
+        } else {
+            ManagerPool::new(&client, cfg.pool_size).await?
+        };
+        logging::info("REDIS_INIT", &format!("connection pool ready size={}", pool.all().len()));
         
-        Ok(Self { kind: ConnectionKind::Standalone(manager, client), cfg })
+        Ok(Self { kind: ConnectionKind::Standalone(pool, client), cfg, default_db, metrics: Arc::default(), connected_since: Instant::now(), benchmark_cancel: Arc::default(), pubsub: Arc::default(), database_count: Arc::default(), capabilities: Arc::default(), read_cache, slot_owners: Arc::default() })
     }
 
     /// 带自动重试的操作执行包装器
@@ -461,8 +1568,8 @@ impl RedisService {
     /// 
     /// 当前实现使用固定延迟重试：
     /// 1. 执行操作
-    /// 2. 如果失败且未达到重试上限，等待 `retry_delay_ms`
-    /// 3. 记录警告日志
+    /// 2. 如果失败，由 `is_retryable` 判断错误是否可恢复，不可恢复时立即返回
+    /// 3. 如果未达到重试上限，等待 `retry_delay_ms` 并记录警告日志
     /// 4. 重新执行操作
     /// 5. 重复直到成功或达到重试上限
     /// 
@@ -470,35 +1577,227 @@ impl RedisService {
     /// 
     /// ```rust
     /// self.with_retry(|| async {
-    ///     let mut conn = manager.clone();
+    ///     let mut conn = pool.get();
     ///     conn.set("key", "value").await
     /// }).await
     /// ```
-    async fn with_retry<F, Fut, T>(&self, mut f: F) -> Result<T>
+    async fn with_retry<F, Fut, T>(&self, f: F) -> Result<T>
     where
         F: FnMut() -> Fut,
         Fut: std::future::Future<Output = Result<T>>,
     {
-        let mut attempts = 0;
-        
-        loop {
-            match f().await {
-                Ok(v) => return Ok(v),
-                Err(e) => {
-                    attempts += 1;
-                    
-                    // 检查是否超过重试次数
-                    if attempts > self.cfg.retries {
-                        return Err(e);
-                    }
-                    
-                    // 等待重试延迟
-                    let delay = Duration::from_millis(self.cfg.retry_delay_ms);
-                    logging::warn("REDIS_RETRY", &format!("attempt {} failed: {}", attempts, e));
-                    tokio::time::sleep(delay).await;
+        run_with_retry(self.cfg.retries, Duration::from_millis(self.cfg.retry_delay_ms), &self.metrics, f).await
+    }
+
+    /// 获取指定数据库的异步连接
+    /// 
+    /// 非 0 号数据库的操作不能复用共享的 `ConnectionManager`（它固定在 0 号库），
+    /// 因此为每次操作建立一个独立的多路复用异步连接并执行 `SELECT`。
+    /// 
+    /// 相比同步连接 + `spawn_blocking` 的方式，不会占用阻塞线程池。
+    /// 
+    /// # 参数
+    /// 
+    /// - `client`: 单机/哨兵模式下的原始客户端
+    /// - `db`: 目标数据库索引
+    async fn db_connection(client: &redis::Client, db: u32) -> Result<MultiplexedConnection> {
+        let mut conn = client.get_multiplexed_async_connection().await.context("get dedicated connection")?;
+        redis::cmd("SELECT").arg(db).query_async::<()>(&mut conn).await.context("select db")?;
+        Ok(conn)
+    }
+
+    /// 在指定数据库上执行单条命令（带重试）
+    /// 
+    /// 统一处理三种连接路径：
+    /// - 单机 0 号库：复用 `ConnectionManager`
+    /// - 单机非 0 号库：通过 `db_connection` 建立独立连接
+    /// - 集群：仅支持 0 号库，在阻塞线程中使用集群连接执行
+    /// 
+    /// # 参数
+    /// 
+    /// - `db`: 目标数据库索引
+    /// - `cmd`: 已构造好的命令
+    /// - `label`: 错误上下文标签，通常为命令名
+    async fn query_cmd<T: redis::FromRedisValue + Send + 'static>(&self, db: u32, cmd: Cmd, label: &'static str) -> Result<T> {
+        self.with_retry(|| self.exec_cmd(db, &cmd, label)).await
+    }
+
+    /// 在指定数据库上执行单条命令，不自动重试
+    /// 
+    /// 用于非幂等的命令（弹出元素、GETDEL、带上限的自增等）：命令已在服务端执行但回复因网络错误丢失时，
+    /// 重试会再执行一次，导致第一次的结果丢失或重复计数。失败时由调用方决定是否重新执行。
+    async fn query_cmd_once<T: redis::FromRedisValue + Send + 'static>(&self, db: u32, cmd: Cmd, label: &'static str) -> Result<T> {
+        self.without_retry(|| self.exec_cmd(db, &cmd, label)).await
+    }
+
+    /// 只执行一次操作，与 `with_retry` 一样记录连接指标
+    async fn without_retry<F, Fut, T>(&self, f: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        run_with_retry(0, Duration::ZERO, &self.metrics, f).await
+    }
+
+    /// `query_cmd` 与 `query_cmd_once` 的单次执行
+    async fn exec_cmd<T: redis::FromRedisValue + Send + 'static>(&self, db: u32, cmd: &Cmd, label: &'static str) -> Result<T> {
+        match &self.kind {
+            ConnectionKind::Standalone(pool, client) => {
+                if db == 0 {
+                    let mut conn = pool.get();
+                    let v: T = cmd.query_async(&mut conn).await.context(label)?;
+                    Ok(v)
+                } else {
+                    let mut conn = Self::db_connection(client, db).await?;
+                    let v: T = cmd.query_async(&mut conn).await.context(label)?;
+                    Ok(v)
+                }
+            }
+            ConnectionKind::Cluster(client) => {
+                if db != 0 {
+                    return Err(anyhow!("Cluster mode does not support multiple databases"));
+                }
+                let cmd = cmd.clone();
+                let client = client.clone();
+
+                tokio::task::spawn_blocking(move || -> Result<T> {
+                    let mut conn = client.get_connection().context("get cluster connection")?;
+                    let v: T = cmd.query(&mut conn).context(label)?;
+                    Ok(v)
+                }).await.unwrap()
+            }
+        }
+    }
+
+    /// 按连接的命令策略检查是否允许执行命令
+    /// 
+    /// 未配置策略时总是通过；被拒绝时返回 `PolicyBlocked` 错误，命令不会发送到服务端。
+    fn check_policy(&self, command: &str) -> Result<()> {
+        match &self.cfg.command_policy {
+            Some(policy) if !policy.permits(command) => {
+                logging::warn("POLICY", &format!("blocked {}", command));
+                Err(anyhow::Error::new(PolicyBlocked { command: command.to_ascii_uppercase() }))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// 只读连接上拒绝写操作
+    /// 
+    /// `readonly` 为 `false` 时总是通过；否则返回 `ReadOnlyConnection` 错误，命令不会发送到服务端。
+    fn check_writable(&self, command: &str) -> Result<()> {
+        if self.cfg.readonly {
+            return Err(anyhow::Error::new(ReadOnlyConnection { command: command.to_string() }));
+        }
+        Ok(())
+    }
+
+    /// 集群模式下校验多个键位于同一槽位
+    /// 
+    /// 多键命令在集群中要求所有键映射到同一个槽位，否则服务端会返回 `CROSSSLOT` 错误。
+    /// 提前在客户端校验可以给出更明确的错误信息。单机模式下直接通过。
+    fn ensure_same_slot<'a>(&self, keys: impl IntoIterator<Item = &'a str>) -> Result<()> {
+        if !matches!(self.kind, ConnectionKind::Cluster(_)) {
+            return Ok(());
+        }
+        let mut slot = None;
+        for key in keys {
+            let s = key_slot(key);
+            match slot {
+                None => slot = Some(s),
+                Some(prev) if prev != s => {
+                    return Err(invalid_argument("Keys must hash to the same slot in cluster mode (use a {hash_tag})"));
                 }
+                _ => {}
             }
         }
+        Ok(())
+    }
+
+    /// 返回创建此服务时使用的连接配置
+    pub fn config(&self) -> &RedisConfig {
+        &self.cfg
+    }
+
+    /// 判断两个句柄是否来自同一次 `RedisService::new`
+    /// 
+    /// 克隆得到的句柄共享底层连接，返回 `true`；重新创建的服务返回 `false`。
+    pub fn same_instance(&self, other: &RedisService) -> bool {
+        Arc::ptr_eq(&self.metrics, &other.metrics)
+    }
+
+    /// 返回实际使用的默认数据库索引（含地址路径中的数据库索引）
+    pub fn default_db(&self) -> u32 {
+        self.default_db
+    }
+
+    /// 解析命令使用的数据库索引
+    /// 
+    /// 显式传入的 `db` 优先，未传入时回退到默认数据库（见 `default_db`）。
+    pub fn resolve_db(&self, db: Option<u32>) -> u32 {
+        db.unwrap_or(self.default_db)
+    }
+
+    /// 获取连接的延迟指标快照
+    /// 
+    /// 统计所有经过 `with_retry` 的命令尝试，每次重试单独计数；启用读缓存时附带命中统计。
+    pub fn metrics_snapshot(&self) -> ConnMetrics {
+        let mut metrics = self.metrics.snapshot();
+        if let Some(cache) = &self.read_cache {
+            (metrics.cache_hits, metrics.cache_misses) = cache.stats();
+        }
+        metrics
+    }
+
+    /// 重置连接的延迟指标
+    /// 
+    /// 克隆的实例共享同一份统计，因此会一并被清零。
+    pub fn reset_metrics(&self) {
+        self.metrics.reset();
+        if let Some(cache) = &self.read_cache {
+            cache.reset_stats();
+        }
+    }
+
+    /// 查询读缓存，未启用缓存或未命中时返回 `None`
+    fn cache_lookup<V: Clone + 'static>(&self, db: u32, key: &str, kind: ReadKind) -> Option<V> {
+        self.read_cache.as_ref()?.get(db, key, kind)
+    }
+
+    /// 读取开始前的缓存失效纪元，未启用缓存时为 0
+    fn cache_epoch(&self) -> u64 {
+        self.read_cache.as_ref().map_or(0, |cache| cache.epoch())
+    }
+
+    /// 将成功的读取结果写入缓存
+    fn cache_store<V: Clone + Send + Sync + 'static>(&self, db: u32, key: &str, kind: ReadKind, epoch: u64, result: &Result<V>) {
+        if let (Some(cache), Ok(value)) = (&self.read_cache, result) {
+            cache.insert(db, key, kind, epoch, value.clone());
+        }
+    }
+
+    /// 写入键后使其读缓存失效
+    fn invalidate_cached(&self, db: u32, key: &str) {
+        if let Some(cache) = &self.read_cache {
+            cache.invalidate(db, key);
+        }
+    }
+
+    /// 无法确定被修改的键时（按模式删除、事务等）清空读缓存
+    fn clear_cached(&self) {
+        if let Some(cache) = &self.read_cache {
+            cache.clear();
+        }
+    }
+
+    /// 返回连接建立的时间点
+    pub fn connected_since(&self) -> Instant {
+        self.connected_since
+    }
+
+    /// 返回连接自建立以来的运行时长
+    pub fn uptime(&self) -> Duration {
+        self.connected_since.elapsed()
     }
 
     /// 健康检查
@@ -534,6 +1833,54 @@ impl RedisService {
         Ok(())
     }
 
+    /// 测试连接配置并检查服务端运行模式
+    /// 
+    /// 建立连接、执行健康检查并读取 `INFO server`，返回版本、模式、延迟以及配置不一致的警告。
+    /// 
+    /// # 注意事项
+    /// 
+    /// 集群配置指向单机服务器时集群客户端无法工作，此时会改用单机方式连接第一个地址读取 `INFO`：
+    /// 若确认服务端不是集群模式，返回 `ok = false` 并附带模式不一致的警告，而不是底层的连接错误。
+    /// 
+    /// 测试只需一条连接，因此忽略 `pool_size`，始终以 1 建立连接。
+    pub async fn probe(cfg: RedisConfig) -> Result<TestResult> {
+        match Self::probe_with(&cfg, RedisConfig { pool_size: 1, ..cfg.clone() }).await {
+            Ok(result) => Ok(result),
+            Err(e) if cfg.cluster && !cfg.urls.is_empty() => {
+                let fallback = RedisConfig { cluster: false, pool_size: 1, ..cfg.clone() };
+                match Self::probe_with(&cfg, fallback).await {
+                    Ok(mut result) if result.redis_mode != "cluster" => {
+                        result.ok = false;
+                        Ok(result)
+                    }
+                    _ => Err(e),
+                }
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// 使用 `connect` 建立连接并按 `cfg` 构造测试结果
+    /// 
+    /// 集群客户端在首次执行命令时才会连接节点，因此连接错误可能出现在健康检查阶段。
+    async fn probe_with(cfg: &RedisConfig, connect: RedisConfig) -> Result<TestResult> {
+        let svc = RedisService::new(connect).await?;
+        let result = svc.server_info_result(cfg).await;
+        svc.disconnect().await;
+        result
+    }
+
+    /// 执行健康检查并读取 `INFO server`，构造 `TestResult`
+    async fn server_info_result(&self, cfg: &RedisConfig) -> Result<TestResult> {
+        let started = Instant::now();
+        self.check_health().await?;
+        let latency_ms = started.elapsed().as_secs_f64() * 1000.0;
+        let mut cmd = redis::cmd("INFO");
+        cmd.arg("server");
+        let info: String = self.query_cmd(0, cmd, "INFO server").await?;
+        Ok(TestResult::from_server_info(&info, cfg, latency_ms))
+    }
+
     /// 显式断开连接
     /// 
     /// 注意：Redis 客户端使用引用计数管理连接，调用此方法并不会立即关闭连接。
@@ -564,88 +1911,188 @@ impl RedisService {
     /// - `cursor`: 游标，开始时为 0
     /// - `pattern`: 匹配模式（可选）
     /// - `count`: 每次扫描的建议数量（可选）
+    /// - `type_filter`: 只返回指定类型的键（可选），如 `string`、`hash`，由服务端过滤，需要 Redis 6.0 及以上版本
     ///
     /// # 返回值
     ///
     /// 返回 `(u64, Vec<String>)`：
     /// - `u64`: 下次迭代的游标，为 0 表示结束
     /// - `Vec<String>`: 扫描到的键列表
-    pub async fn scan(&self, db: u32, cursor: u64, pattern: Option<String>, count: Option<usize>) -> Result<(u64, Vec<String>)> {
+    pub async fn scan(&self, db: u32, cursor: u64, pattern: Option<String>, count: Option<usize>, type_filter: Option<String>) -> Result<(u64, Vec<String>)> {
+        let cmd = scan_cmd(cursor, pattern, count, type_filter)?;
+        self.query_cmd(db, cmd, "SCAN").await
+    }
+
+    /// 以原始字节扫描当前数据库的键
+    ///
+    /// 与 `scan` 相同，但键名不要求是合法 UTF-8，适合导出等需要处理任意键名的场景。
+    pub async fn scan_bytes(&self, db: u32, cursor: u64, pattern: Option<String>, count: Option<usize>) -> Result<(u64, Vec<Vec<u8>>)> {
+        let cmd = scan_cmd(cursor, pattern, count, None)?;
+        self.query_cmd(db, cmd, "SCAN").await
+    }
+
+    /// 扫描一页键并同时获取每个键的类型
+    ///
+    /// 先执行一次 `scan`，再通过管道为本页的每个键发送 `TYPE`，
+    /// 只需一次额外的往返即可得到 `(键, 类型)`，避免逐个调用 `get_type`。
+    ///
+    /// # 返回值
+    ///
+    /// 返回 `(下次迭代的游标, [(键, 类型)])`。类型为 `string`、`list`、`hash`、`set`、`zset`、`stream` 等，
+    /// 键在 SCAN 与 TYPE 之间被删除时为 `none`。
+    pub async fn scan_with_types(&self, db: u32, cursor: u64, pattern: Option<String>, count: Option<usize>) -> Result<(u64, Vec<(String, String)>)> {
+        let (next_cursor, keys) = self.scan(db, cursor, pattern, count, None).await?;
+        if keys.is_empty() {
+            return Ok((next_cursor, Vec::new()));
+        }
+        let types: Vec<String> = self.pipe_each(db, &keys, &["TYPE"], "TYPE").await?;
+        Ok((next_cursor, keys.into_iter().zip(types).collect()))
+    }
+
+    /// 以原始字节扫描一页键并同时获取每个键的类型
+    ///
+    /// 与 `scan_with_types` 相同，但键名不要求是合法 UTF-8。
+    pub async fn scan_with_types_bytes(&self, db: u32, cursor: u64, pattern: Option<String>, count: Option<usize>) -> Result<(u64, Vec<(Vec<u8>, String)>)> {
+        let (next_cursor, keys) = self.scan_bytes(db, cursor, pattern, count).await?;
+        if keys.is_empty() {
+            return Ok((next_cursor, Vec::new()));
+        }
+        let types: Vec<String> = self.pipe_each(db, &keys, &["TYPE"], "TYPE").await?;
+        Ok((next_cursor, keys.into_iter().zip(types).collect()))
+    }
+
+    /// 查找空闲时间超过阈值的键
+    ///
+    /// 逐页 SCAN，并通过管道为每页的键发送 `OBJECT IDLETIME`，收集空闲时间不少于 `min_idle_secs` 的键，
+    /// 用于定位长期未访问、可以清理的数据。
+    ///
+    /// # 参数
+    ///
+    /// - `min_idle_secs`: 空闲时间阈值（秒）
+    /// - `pattern`: 匹配模式（可选）
+    /// - `limit`: 最多返回的键数量，达到后停止扫描
+    ///
+    /// # 返回值
+    ///
+    /// 返回 `(键, 空闲秒数)` 列表。最多检查 100000 个键，超过后返回已找到的结果。
+    ///
+    /// # 注意事项
+    ///
+    /// `maxmemory-policy` 为 LFU 策略时服务端不记录空闲时间，`OBJECT IDLETIME` 会返回错误。
+    pub async fn find_idle_keys(&self, db: u32, min_idle_secs: i64, pattern: Option<String>, limit: usize) -> Result<Vec<(String, i64)>> {
+        let mut found = Vec::new();
+        if limit == 0 {
+            return Ok(found);
+        }
+        let mut examined = 0usize;
+        let mut cursor = 0u64;
+        loop {
+            let (next, keys) = self.scan(db, cursor, pattern.clone(), Some(IDLE_SCAN_COUNT), None).await?;
+            if !keys.is_empty() {
+                examined += keys.len();
+                let idles: Vec<Option<i64>> = self.pipe_each(db, &keys, &["OBJECT", "IDLETIME"], "OBJECT IDLETIME").await?;
+                for (key, idle) in keys.into_iter().zip(idles) {
+                    if let Some(idle) = idle.filter(|&idle| idle >= min_idle_secs) {
+                        found.push((key, idle));
+                        if found.len() >= limit {
+                            return Ok(found);
+                        }
+                    }
+                }
+            }
+            cursor = next;
+            if cursor == 0 || examined >= IDLE_MAX_EXAMINED {
+                return Ok(found);
+            }
+        }
+    }
+
+    /// 执行一个非事务管道，返回各条命令的结果
+    /// 
+    /// 集群模式下管道中的命令应只涉及同一个键（或同一槽位的键）。
+    async fn query_pipe<T: redis::FromRedisValue + Send + 'static>(&self, db: u32, pipe: Pipeline, label: &'static str) -> Result<T> {
         self.with_retry(|| async {
             match &self.kind {
-                ConnectionKind::Standalone(manager, client) => {
+                ConnectionKind::Standalone(pool, client) => {
                     if db == 0 {
-                        let mut conn = manager.clone();
-                        let mut cmd = redis::cmd("SCAN");
-                        cmd.arg(cursor);
-                        if let Some(p) = &pattern {
-                            if !p.is_empty() {
-                                cmd.arg("MATCH").arg(p);
-                            }
-                        }
-                        if let Some(c) = count {
-                            if c > 0 {
-                                cmd.arg("COUNT").arg(c);
-                            }
-                        }
-                        let (next_cursor, keys): (u64, Vec<String>) = cmd.query_async(&mut conn).await.context("SCAN")?;
-                        Ok((next_cursor, keys))
+                        let mut conn = pool.get();
+                        let v: T = pipe.query_async(&mut conn).await.context(label)?;
+                        Ok(v)
                     } else {
-                         let client = client.clone();
-                         let pattern = pattern.clone();
-                         tokio::task::spawn_blocking(move || -> Result<(u64, Vec<String>)> {
-                             let mut conn = client.get_connection().context("get dedicated connection")?;
-                             redis::cmd("SELECT").arg(db).query::<()>(&mut conn).context("select db")?;
-                             let mut cmd = redis::cmd("SCAN");
-                             cmd.arg(cursor);
-                             if let Some(p) = &pattern {
-                                 if !p.is_empty() {
-                                     cmd.arg("MATCH").arg(p);
-                                 }
-                             }
-                             if let Some(c) = count {
-                                 if c > 0 {
-                                     cmd.arg("COUNT").arg(c);
-                                 }
-                             }
-                             let (next_cursor, keys): (u64, Vec<String>) = cmd.query(&mut conn).context("SCAN")?;
-                             Ok((next_cursor, keys))
-                         }).await.unwrap()
+                        let mut conn = Self::db_connection(client, db).await?;
+                        let v: T = pipe.query_async(&mut conn).await.context(label)?;
+                        Ok(v)
                     }
                 }
                 ConnectionKind::Cluster(client) => {
                     if db != 0 {
                         return Err(anyhow!("Cluster mode does not support multiple databases"));
                     }
+                    let pipe = pipe.clone();
                     let client = client.clone();
-                    let pattern = pattern.clone();
-                    
-                    tokio::task::spawn_blocking(move || -> Result<(u64, Vec<String>)> {
+
+                    tokio::task::spawn_blocking(move || -> Result<T> {
                         let mut conn = client.get_connection().context("get cluster connection")?;
-                        let mut cmd = redis::cmd("SCAN");
-                        cmd.arg(cursor);
-                        if let Some(p) = &pattern {
-                            if !p.is_empty() {
-                                cmd.arg("MATCH").arg(p);
-                            }
-                        }
-                        if let Some(c) = count {
-                            if c > 0 {
-                                cmd.arg("COUNT").arg(c);
-                            }
-                        }
-                        let (next_cursor, keys): (u64, Vec<String>) = cmd.query(&mut conn).context("SCAN")?;
-                        Ok((next_cursor, keys))
+                        let v: T = pipe.query(&mut conn).context(label)?;
+                        Ok(v)
                     }).await.unwrap()
                 }
             }
         }).await
     }
-    /// 获取当前数据库的键数量（DBSIZE 命令）
-    ///
-    /// # 参数
-    ///
-    /// - `db`: 数据库索引
+
+    /// 通过管道为每个键执行同一条命令
+    ///
+    /// 命令形如 `prefix... key`，返回值与 `keys` 顺序一致，只需一次往返。
+    /// 集群模式使用集群管道，按键所在的节点拆分发送。
+    async fn pipe_each<K, T>(&self, db: u32, keys: &[K], prefix: &'static [&'static str], label: &'static str) -> Result<Vec<T>>
+    where
+        K: redis::ToRedisArgs + Clone + Send + Sync + 'static,
+        T: redis::FromRedisValue + Send + 'static,
+    {
+        self.with_retry(|| async {
+            match &self.kind {
+                ConnectionKind::Standalone(pool, client) => {
+                    let mut pipe = redis::pipe();
+                    for key in keys {
+                        pipe.cmd(prefix[0]).arg(&prefix[1..]).arg(key);
+                    }
+                    if db == 0 {
+                        let mut conn = pool.get();
+                        let values: Vec<T> = pipe.query_async(&mut conn).await.context(label)?;
+                        Ok(values)
+                    } else {
+                        let mut conn = Self::db_connection(client, db).await?;
+                        let values: Vec<T> = pipe.query_async(&mut conn).await.context(label)?;
+                        Ok(values)
+                    }
+                }
+                ConnectionKind::Cluster(client) => {
+                    if db != 0 {
+                        return Err(anyhow!("Cluster mode does not support multiple databases"));
+                    }
+                    let client = client.clone();
+                    let keys = keys.to_vec();
+
+                    tokio::task::spawn_blocking(move || -> Result<Vec<T>> {
+                        let mut conn = client.get_connection().context("get cluster connection")?;
+                        let mut pipe = redis::cluster::cluster_pipe();
+                        for key in &keys {
+                            pipe.cmd(prefix[0]).arg(&prefix[1..]).arg(key);
+                        }
+                        let values: Vec<T> = pipe.query(&mut conn).context(label)?;
+                        Ok(values)
+                    }).await.unwrap()
+                }
+            }
+        }).await
+    }
+
+    /// 获取当前数据库的键数量（DBSIZE 命令）
+    ///
+    /// # 参数
+    ///
+    /// - `db`: 数据库索引
     ///
     /// # 返回值
     ///
@@ -653,19 +2100,15 @@ impl RedisService {
     pub async fn dbsize(&self, db: u32) -> Result<u64> {
         self.with_retry(|| async {
             match &self.kind {
-                ConnectionKind::Standalone(manager, client) => {
+                ConnectionKind::Standalone(pool, client) => {
                     if db == 0 {
-                        let mut conn = manager.clone();
+                        let mut conn = pool.get();
                         let size: u64 = redis::cmd("DBSIZE").query_async(&mut conn).await.context("DBSIZE")?;
                         Ok(size)
                     } else {
-                        let client = client.clone();
-                        tokio::task::spawn_blocking(move || -> Result<u64> {
-                            let mut conn = client.get_connection().context("get dedicated connection")?;
-                            redis::cmd("SELECT").arg(db).query::<()>(&mut conn).context("select db")?;
-                            let size: u64 = redis::cmd("DBSIZE").query(&mut conn).context("DBSIZE")?;
-                            Ok(size)
-                        }).await.unwrap()
+                        let mut conn = Self::db_connection(client, db).await?;
+                        let size: u64 = redis::cmd("DBSIZE").query_async(&mut conn).await.context("DBSIZE")?;
+                        Ok(size)
                     }
                 }
                 ConnectionKind::Cluster(client) => {
@@ -683,6 +2126,348 @@ impl RedisService {
         }).await
     }
 
+    /// 一次性获取所有数据库的键数量
+    /// 
+    /// 单机模式下复用同一个独立连接，依次 `SELECT` 每个库（数量见 `database_count`）并执行 DBSIZE，
+    /// 某个库查询失败时跳过该库。
+    /// 集群模式只有 0 号库，返回单个 `(0, 总键数)`。
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回 `(数据库索引, 键数量)` 列表，按索引升序排列。
+    pub async fn all_dbsizes(&self) -> Result<Vec<(u32, u64)>> {
+        match &self.kind {
+            ConnectionKind::Standalone(_, client) => {
+                let count = self.database_count().await?;
+                self.with_retry(|| async {
+                    let mut conn = client.get_multiplexed_async_connection().await.context("get dedicated connection")?;
+                    let mut sizes = Vec::new();
+                    for db in 0..count {
+                        if let Err(e) = redis::cmd("SELECT").arg(db).query_async::<()>(&mut conn).await {
+                            logging::warn("REDIS_DBSIZE", &format!("skip db {}: {}", db, e));
+                            continue;
+                        }
+                        match redis::cmd("DBSIZE").query_async::<u64>(&mut conn).await {
+                            Ok(size) => sizes.push((db, size)),
+                            Err(e) => logging::warn("REDIS_DBSIZE", &format!("skip db {}: {}", db, e)),
+                        }
+                    }
+                    Ok(sizes)
+                }).await
+            }
+            ConnectionKind::Cluster(_) => {
+                let total = self.dbsize(0).await?;
+                Ok(vec![(0, total)])
+            }
+        }
+    }
+
+    /// 获取服务端配置的数据库数量（`CONFIG GET databases`）
+    /// 
+    /// 首次查询成功后缓存结果，之后不再发送 CONFIG 命令。
+    /// 集群模式、CONFIG 命令被禁用或查询失败时返回默认值 16；查询失败时不缓存，下次调用重新查询，
+    /// 避免一次网络抖动让之后的结果一直停留在默认值。
+    pub async fn database_count(&self) -> Result<u32> {
+        let count = self.database_count.get_or_try_init(|| async {
+            if matches!(self.kind, ConnectionKind::Cluster(_)) {
+                return Ok(DEFAULT_DATABASE_COUNT);
+            }
+            let mut cmd = redis::cmd("CONFIG");
+            cmd.arg("GET").arg("databases");
+            let config: HashMap<String, String> = self.query_cmd(0, cmd, "CONFIG GET").await?;
+            Ok::<u32, anyhow::Error>(match config.get("databases").and_then(|v| v.parse::<u32>().ok()) {
+                Some(n) => n.max(1),
+                None => DEFAULT_DATABASE_COUNT,
+            })
+        }).await;
+        match count {
+            Ok(n) => Ok(*n),
+            Err(e) => {
+                logging::warn("REDIS_CONFIG", &format!("CONFIG GET databases failed, assuming {}: {:#}", DEFAULT_DATABASE_COUNT, e));
+                Ok(DEFAULT_DATABASE_COUNT)
+            }
+        }
+    }
+
+    /// 检测服务端支持的功能
+    /// 
+    /// 读取 `INFO server` 中的版本号，并通过 `MODULE LIST` 获取已加载的模块。
+    /// 首次检测成功后缓存结果；`MODULE LIST` 被禁用（部分云服务）时按未加载任何模块处理。
+    pub async fn detect_capabilities(&self) -> Result<Capabilities> {
+        let caps = self.capabilities.get_or_try_init(|| async {
+            let mut cmd = redis::cmd("INFO");
+            cmd.arg("server");
+            let info: String = self.query_cmd(0, cmd, "INFO server").await?;
+            let mut cmd = redis::cmd("MODULE");
+            cmd.arg("LIST");
+            let modules = match self.query_cmd::<redis::Value>(0, cmd, "MODULE LIST").await {
+                Ok(reply) => parse_module_names(reply)?,
+                Err(e) => {
+                    logging::warn("REDIS_CONFIG", &format!("MODULE LIST failed, assuming no modules: {}", e));
+                    Vec::new()
+                }
+            };
+            Ok::<_, anyhow::Error>(Capabilities::from_info(&info, modules))
+        }).await?;
+        Ok(caps.clone())
+    }
+
+    /// 统计匹配模式的键数量
+    /// 
+    /// 使用 `SCAN ... MATCH pattern COUNT 1000` 逐页遍历键空间，每页只累加匹配数量，
+    /// 不会在内存中保留完整的键列表。
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回 `KeyCount`。SCAN 次数超过上限（所有节点合计 10000 次）时停止遍历，
+    /// `complete` 为 `false`，`count` 为已统计的部分结果。
+    /// 
+    /// # 注意事项
+    /// 
+    /// - 单机模式使用独立连接遍历，不占用共享连接池
+    /// - 集群模式依次遍历每个主节点并累加
+    /// - 遍历期间键被修改时，SCAN 可能重复返回同一个键，结果为近似值
+    pub async fn count_keys(&self, db: u32, pattern: String) -> Result<KeyCount> {
+        match &self.kind {
+            ConnectionKind::Standalone(_, client) => {
+                self.with_retry(|| async {
+                    let mut conn = Self::db_connection(client, db).await?;
+                    let mut result = KeyCount::default();
+                    let mut cursor = 0u64;
+                    loop {
+                        let reply: redis::Value = scan_count_cmd(cursor, &pattern).query_async(&mut conn).await.context("SCAN")?;
+                        let (next, matched) = parse_scan_reply(reply)?;
+                        result.count += matched as u64;
+                        result.iterations += 1;
+                        cursor = next;
+                        if cursor == 0 {
+                            result.complete = true;
+                            break;
+                        }
+                        if result.iterations >= COUNT_KEYS_MAX_ITERATIONS {
+                            break;
+                        }
+                    }
+                    Ok(result)
+                }).await
+            }
+            ConnectionKind::Cluster(client) => {
+                if db != 0 {
+                    return Err(anyhow!("Cluster mode does not support multiple databases"));
+                }
+                let masters = self.master_addrs().await?;
+
+                self.with_retry(|| async {
+                    let client = client.clone();
+                    let masters = masters.clone();
+                    let pattern = pattern.clone();
+
+                    tokio::task::spawn_blocking(move || -> Result<KeyCount> {
+                        let mut conn = client.get_connection().context("get cluster connection")?;
+                        let mut result = KeyCount { complete: true, ..Default::default() };
+                        'nodes: for (host, port) in masters {
+                            let mut cursor = 0u64;
+                            loop {
+                                if result.iterations >= COUNT_KEYS_MAX_ITERATIONS {
+                                    result.complete = false;
+                                    break 'nodes;
+                                }
+                                let routing = RoutingInfo::SingleNode(SingleNodeRoutingInfo::ByAddress { host: host.clone(), port });
+                                let reply = conn.route_command(&scan_count_cmd(cursor, &pattern), routing).context("SCAN")?;
+                                let (next, matched) = parse_scan_reply(reply)?;
+                                result.count += matched as u64;
+                                result.iterations += 1;
+                                cursor = next;
+                                if cursor == 0 {
+                                    break;
+                                }
+                            }
+                        }
+                        Ok(result)
+                    }).await.unwrap()
+                }).await
+            }
+        }
+    }
+
+    /// 按模式批量删除键（SCAN + UNLINK）
+    /// 
+    /// 分页扫描匹配的键，每页使用一次 UNLINK 在后台释放内存，返回实际删除的键数量。
+    /// 
+    /// # 注意事项
+    /// 
+    /// - `pattern` 不能为空；是否允许 `*` 这类匹配全部键的模式由调用方决定
+    /// - 集群模式依次遍历每个主节点，并按槽位分组发送 UNLINK
+    /// - 删除中途失败时不会自动重试，已删除的键无法恢复
+    /// - 键名全程以原始字节传递，二进制键名同样会被正确删除
+    pub async fn delete_by_pattern(&self, db: u32, pattern: String, batch_size: usize) -> Result<u64> {
+        self.check_writable("DEL")?;
+        self.check_policy("DEL")?;
+        let result = self.unlink_by_pattern(db, pattern, batch_size).await;
+        self.clear_cached();
+        result
+    }
+
+    /// `delete_by_pattern` 的实现，删除后由调用方清空读缓存
+    async fn unlink_by_pattern(&self, db: u32, pattern: String, batch_size: usize) -> Result<u64> {
+        if pattern.is_empty() {
+            return Err(invalid_argument("Pattern must not be empty"));
+        }
+        let batch_size = batch_size.max(1);
+        match &self.kind {
+            ConnectionKind::Standalone(_, client) => {
+                let mut conn = Self::db_connection(client, db).await?;
+                let mut deleted = 0u64;
+                let mut cursor = 0u64;
+                loop {
+                    let (next, keys): (u64, Vec<Vec<u8>>) = redis::cmd("SCAN")
+                        .arg(cursor).arg("MATCH").arg(&pattern).arg("COUNT").arg(batch_size)
+                        .query_async(&mut conn).await.context("SCAN")?;
+                    if !keys.is_empty() {
+                        let n: u64 = redis::cmd("UNLINK").arg(&keys).query_async(&mut conn).await.context("UNLINK")?;
+                        deleted += n;
+                    }
+                    cursor = next;
+                    if cursor == 0 {
+                        return Ok(deleted);
+                    }
+                }
+            }
+            ConnectionKind::Cluster(client) => {
+                if db != 0 {
+                    return Err(anyhow!("Cluster mode does not support multiple databases"));
+                }
+                let masters = self.master_addrs().await?;
+                let client = client.clone();
+
+                tokio::task::spawn_blocking(move || -> Result<u64> {
+                    let mut conn = client.get_connection().context("get cluster connection")?;
+                    let mut deleted = 0u64;
+                    for (host, port) in masters {
+                        let mut cursor = 0u64;
+                        loop {
+                            let mut scan = redis::cmd("SCAN");
+                            scan.arg(cursor).arg("MATCH").arg(&pattern).arg("COUNT").arg(batch_size);
+                            let routing = RoutingInfo::SingleNode(SingleNodeRoutingInfo::ByAddress { host: host.clone(), port });
+                            let reply = conn.route_command(&scan, routing).context("SCAN")?;
+                            let (next, keys) = parse_scan_keys(reply)?;
+
+                            let mut by_slot: HashMap<u16, Vec<Vec<u8>>> = HashMap::new();
+                            for key in keys {
+                                by_slot.entry(key_slot_bytes(&key)).or_default().push(key);
+                            }
+                            for group in by_slot.into_values() {
+                                let n: u64 = redis::cmd("UNLINK").arg(&group).query(&mut conn).context("UNLINK")?;
+                                deleted += n;
+                            }
+
+                            cursor = next;
+                            if cursor == 0 {
+                                break;
+                            }
+                        }
+                    }
+                    Ok(deleted)
+                }).await.unwrap()
+            }
+        }
+    }
+
+    /// 按模式批量设置过期时间（SCAN + EXPIRE）
+    /// 
+    /// 分页扫描匹配的键，每页使用一次 pipeline 发送 EXPIRE，返回成功设置过期时间的键数量。
+    /// 
+    /// # 注意事项
+    /// 
+    /// - `pattern` 不能为空
+    /// - 扫描与设置之间被删除的键不计入结果
+    /// - 集群模式依次遍历每个主节点，逐个键发送 EXPIRE
+    pub async fn expire_by_pattern(&self, db: u32, pattern: String, seconds: u64, batch_size: usize) -> Result<u64> {
+        self.check_writable("EXPIRE")?;
+        if pattern.is_empty() {
+            return Err(invalid_argument("Pattern must not be empty"));
+        }
+        let batch_size = batch_size.max(1);
+        let sec = i64::try_from(seconds).context("seconds out of range")?;
+        let result: Result<u64> = async {
+            match &self.kind {
+                ConnectionKind::Standalone(_, client) => {
+                    let mut conn = Self::db_connection(client, db).await?;
+                    let mut applied = 0u64;
+                    let mut cursor = 0u64;
+                    loop {
+                        let (next, keys): (u64, Vec<Vec<u8>>) = redis::cmd("SCAN")
+                            .arg(cursor).arg("MATCH").arg(&pattern).arg("COUNT").arg(batch_size)
+                            .query_async(&mut conn).await.context("SCAN")?;
+                        if !keys.is_empty() {
+                            let mut pipe = redis::pipe();
+                            for key in &keys {
+                                pipe.cmd("EXPIRE").arg(key).arg(sec);
+                            }
+                            let results: Vec<bool> = pipe.query_async(&mut conn).await.context("EXPIRE")?;
+                            applied += results.into_iter().filter(|&ok| ok).count() as u64;
+                        }
+                        cursor = next;
+                        if cursor == 0 {
+                            return Ok(applied);
+                        }
+                    }
+                }
+                ConnectionKind::Cluster(client) => {
+                    if db != 0 {
+                        return Err(anyhow!("Cluster mode does not support multiple databases"));
+                    }
+                    let masters = self.master_addrs().await?;
+                    let client = client.clone();
+
+                    tokio::task::spawn_blocking(move || -> Result<u64> {
+                        let mut conn = client.get_connection().context("get cluster connection")?;
+                        let mut applied = 0u64;
+                        for (host, port) in masters {
+                            let mut cursor = 0u64;
+                            loop {
+                                let mut scan = redis::cmd("SCAN");
+                                scan.arg(cursor).arg("MATCH").arg(&pattern).arg("COUNT").arg(batch_size);
+                                let routing = RoutingInfo::SingleNode(SingleNodeRoutingInfo::ByAddress { host: host.clone(), port });
+                                let reply = conn.route_command(&scan, routing).context("SCAN")?;
+                                let (next, keys) = parse_scan_keys(reply)?;
+
+                                for key in keys {
+                                    let ok: bool = redis::cmd("EXPIRE").arg(&key).arg(sec).query(&mut conn).context("EXPIRE")?;
+                                    if ok {
+                                        applied += 1;
+                                    }
+                                }
+
+                                cursor = next;
+                                if cursor == 0 {
+                                    break;
+                                }
+                            }
+                        }
+                        Ok(applied)
+                    }).await.unwrap()
+                }
+            }
+        }.await;
+        // 过期时间变化后缓存的值可能已不存在，按模式无法逐个定位，直接清空读缓存
+        self.clear_cached();
+        result
+    }
+
+    /// 获取集群中在线主节点的 `(host, port)` 列表
+    async fn master_addrs(&self) -> Result<Vec<(String, u16)>> {
+        Ok(self.get_cluster_nodes().await?
+            .into_iter()
+            .filter(|n| n.flags.contains("master") && !n.flags.contains("fail"))
+            .filter_map(|n| {
+                let addr = n.addr.split('@').next()?;
+                let (host, port) = addr.rsplit_once(':')?;
+                Some((host.to_string(), port.parse().ok()?))
+            })
+            .collect())
+    }
+
     // --- 批量操作 ---
 
     /// 批量获取多个键的值（MGET 命令）
@@ -697,11 +2482,12 @@ impl RedisService {
     /// 
     /// # 参数
     /// 
+    /// - `db`: 数据库索引（仅单机模式有效）
     /// - `keys`: 要获取的键列表
     /// 
     /// # 返回值
     /// 
-    /// 返回与键列表对应的值列表，不存在的键对应 `None`。
+    /// 返回与键列表顺序一致的值列表，不存在的键对应 `None`。
     /// 
     /// # 性能考虑
     /// 
@@ -713,17 +2499,26 @@ impl RedisService {
     /// 
     /// ```rust
     /// let keys = vec!["user:1", "user:2", "user:3"];
-    /// let values: Vec<Option<String>> = redis.mget(&keys).await?;
+    /// let values: Vec<Option<String>> = redis.mget(0, &keys).await?;
     /// ```
-    pub async fn mget<K: redis::ToRedisArgs + Send + Sync, T: redis::FromRedisValue + Send + 'static>(&self, keys: &[K]) -> Result<Vec<Option<T>>> {
+    pub async fn mget<K: redis::ToRedisArgs + Send + Sync, T: redis::FromRedisValue + Send + 'static>(&self, db: u32, keys: &[K]) -> Result<Vec<Option<T>>> {
         self.with_retry(|| async {
             match &self.kind {
-                ConnectionKind::Standalone(manager, _) => {
-                    let mut conn = manager.clone();
-                    let v: Vec<Option<T>> = conn.mget(keys).await.context("MGET")?;
-                    Ok(v)
+                ConnectionKind::Standalone(pool, client) => {
+                    if db == 0 {
+                        let mut conn = pool.get();
+                        let v: Vec<Option<T>> = conn.mget(keys).await.context("MGET")?;
+                        Ok(v)
+                    } else {
+                        let mut conn = Self::db_connection(client, db).await?;
+                        let v: Vec<Option<T>> = redis::cmd("MGET").arg(keys).query_async(&mut conn).await.context("MGET")?;
+                        Ok(v)
+                    }
                 }
                 ConnectionKind::Cluster(client) => {
+                    if db != 0 {
+                        return Err(anyhow!("Cluster mode does not support multiple databases"));
+                    }
                     // 集群模式下的 MGET 处理
                     let keys: Vec<String> = keys.iter()
                         .map(|k| redis::ToRedisArgs::to_redis_args(k).get(0)
@@ -754,6 +2549,7 @@ impl RedisService {
     /// 
     /// # 参数
     /// 
+    /// - `db`: 数据库索引（仅单机模式有效）
     /// - `items`: 键值对列表
     /// 
     /// # 性能考虑
@@ -770,17 +2566,27 @@ impl RedisService {
     ///     ("key2", "value2"),
     ///     ("key3", "value3"),
     /// ];
-    /// redis.mset(&items).await?;
+    /// redis.mset(0, &items).await?;
     /// ```
-    pub async fn mset<K: redis::ToRedisArgs + Send + Sync + 'static, V: redis::ToRedisArgs + Send + Sync + 'static>(&self, items: &[(K, V)]) -> Result<()> {
-        self.with_retry(|| async {
+    pub async fn mset<K: redis::ToRedisArgs + Send + Sync + 'static, V: redis::ToRedisArgs + Send + Sync + 'static>(&self, db: u32, items: &[(K, V)]) -> Result<()> {
+        self.check_writable("MSET")?;
+        let result = self.with_retry(|| async {
             match &self.kind {
-                ConnectionKind::Standalone(manager, _) => {
-                    let mut conn = manager.clone();
-                    conn.mset::<_, _, ()>(items).await.context("MSET")?;
-                    Ok(())
+                ConnectionKind::Standalone(pool, client) => {
+                    if db == 0 {
+                        let mut conn = pool.get();
+                        conn.mset::<_, _, ()>(items).await.context("MSET")?;
+                        Ok(())
+                    } else {
+                        let mut conn = Self::db_connection(client, db).await?;
+                        conn.mset::<_, _, ()>(items).await.context("MSET")?;
+                        Ok(())
+                    }
                 }
                 ConnectionKind::Cluster(client) => {
+                    if db != 0 {
+                        return Err(anyhow!("Cluster mode does not support multiple databases"));
+                    }
                     // 集群模式下的 MSET 处理
                     let items_vec: Vec<(String, Vec<u8>)> = items.iter().map(|(k, v)| {
                         let k_str = redis::ToRedisArgs::to_redis_args(k).get(0)
@@ -800,7 +2606,9 @@ impl RedisService {
                     }).await.unwrap()
                 }
             }
-        }).await
+        }).await;
+        self.clear_cached();
+        result
     }
 
     // --- 事务 ---
@@ -838,12 +2646,13 @@ impl RedisService {
     pub async fn transaction<F>(&self, f: F) -> Result<()> 
     where F: Fn(&mut Pipeline) + Send + Sync + Clone + 'static
     {
-        self.with_retry(|| {
+        self.check_writable("MULTI")?;
+        let result = self.with_retry(|| {
             let f = f.clone();
             async move {
                 match &self.kind {
-                    ConnectionKind::Standalone(manager, _) => {
-                        let mut conn = manager.clone();
+                    ConnectionKind::Standalone(pool, _) => {
+                        let mut conn = pool.get();
                         let mut pipe = redis::pipe();
                         pipe.atomic(); // 设置原子模式
                         f(&mut pipe);
@@ -864,27 +2673,111 @@ impl RedisService {
                     }
                 }
             }
-        }).await
+        }).await;
+        self.clear_cached();
+        result
+    }
+
+    /// 执行带 WATCH 的乐观事务（WATCH/MULTI/EXEC）
+    /// 
+    /// 先 WATCH 指定的键并读取其中字符串键的当前值，再由闭包基于这些值构建事务管道，
+    /// 最后通过 EXEC 提交。如果在 WATCH 之后、EXEC 之前任一被监视的键被其他客户端修改，
+    /// 事务会被服务端放弃，此时返回 `Ok(false)`，调用方可以重新读取并重试。
+    /// 
+    /// # 参数
+    /// 
+    /// - `db`: 数据库索引
+    /// - `watch_keys`: 需要监视的键列表
+    /// - `f`: 闭包，接收被监视键的当前值（按 `watch_keys` 顺序，不存在或不是字符串类型时为 `None`）并构建事务管道
+    /// 
+    /// # 返回值
+    /// 
+    /// - `Ok(true)`: 事务已提交
+    /// - `Ok(false)`: 被监视的键发生变化，事务被放弃
+    /// 
+    /// # 使用示例
+    /// 
+    /// ```rust
+    /// // 比较并设置：仅当余额未被他人修改时才扣减
+    /// loop {
+    ///     let committed = redis.transaction_watched(0, vec!["balance".into()], |values, pipe| {
+    ///         let balance: i64 = values[0].as_deref().unwrap_or("0").parse().unwrap_or(0);
+    ///         pipe.set("balance", balance - 10);
+    ///     }).await?;
+    ///     if committed { break; }
+    /// }
+    /// ```
+    /// 
+    /// # 注意事项
+    /// 
+    /// - WATCH 状态绑定在连接上，因此每次调用都会建立独立连接，不复用共享的连接管理器
+    /// - 当前值通过一次 MGET 读取，MGET 对非字符串类型的键返回 nil，因此这些键的值为 `None`，
+    ///   但它们同样受 WATCH 保护
+    /// - 事务被放弃不视为错误，也不会触发 `with_retry` 的自动重试
+    /// - 集群模式暂不支持：WATCH 与 EXEC 必须发送到同一节点的同一连接，
+    ///   而集群客户端会按键路由命令，无法保证这一点
+    pub async fn transaction_watched<F>(&self, db: u32, watch_keys: Vec<String>, f: F) -> Result<bool>
+    where F: FnOnce(&[Option<String>], &mut Pipeline)
+    {
+        self.check_writable("MULTI")?;
+        if watch_keys.is_empty() {
+            return Err(invalid_argument("transaction_watched requires at least one key to watch"));
+        }
+        let result = match &self.kind {
+            ConnectionKind::Standalone(_, client) => {
+                let mut conn = Self::db_connection(client, db).await?;
+                redis::cmd("WATCH").arg(&watch_keys).query_async::<()>(&mut conn).await.context("WATCH")?;
+                // 不存在或不是字符串类型的键，MGET 均返回 nil
+                let values: Vec<Option<String>> = redis::cmd("MGET").arg(&watch_keys).query_async(&mut conn).await.context("MGET")?;
+
+                let mut pipe = redis::pipe();
+                pipe.atomic();
+                f(&values, &mut pipe);
+
+                // 事务被放弃时 EXEC 返回 nil
+                let result: Option<redis::Value> = pipe.query_async(&mut conn).await.context("TRANSACTION")?;
+                if result.is_none() {
+                    logging::warn("REDIS_TX", &format!("watched transaction aborted, keys={:?}", watch_keys));
+                }
+                Ok(result.is_some())
+            }
+            ConnectionKind::Cluster(_) => {
+                Err(anyhow!("WATCH transactions are not supported in cluster mode"))
+            }
+        };
+        self.clear_cached();
+        result
     }
 
     // --- 发布订阅 ---
 
     /// 订阅 Redis 频道并处理消息
     /// 
-    /// 创建独立的订阅连接，避免阻塞主要业务连接。
+    /// 所有订阅共享同一个专用 Pub/Sub 连接（见 `pubsub` 模块），避免阻塞主要业务连接。
     /// 为每个收到的消息执行回调函数，当回调返回 `false` 时停止订阅。
     /// 
+    /// 订阅连接断开（例如服务器重启）后，每隔 `retry_delay_ms` 尝试重建连接并重新订阅，
+    /// 成功后调用 `on_resubscribed`。只有回调返回 `false` 或任务被 `abort` 时才会结束，
+    /// 结束后该订阅者自动退订。
+    /// 
     /// # 参数
     /// 
     /// - `channel`: 要订阅的频道名称
     /// - `callback`: 消息处理回调，返回 `false` 时停止订阅
+    /// - `on_resubscribed`: 断线后重新订阅成功时调用
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回后台消息处理任务的 `JoinHandle`，调用方可以保存它以便在关闭时 `abort`。
     /// 
     /// # 实现细节
     /// 
-    /// - 使用专用的 Pub/Sub 连接，不影响其他操作
+    /// - 使用专用的 Pub/Sub 连接，不影响其他操作；多次订阅复用这一条连接
+    /// - 同一频道的多个订阅者共享一次服务端订阅，最后一个订阅者结束时才发送 UNSUBSCRIBE
     /// - 集群模式下连接到种子节点（传统 Pub/Sub 是节点局部的）
     /// - 异步消息处理循环，出现错误时记录日志并继续
     /// - 支持优雅停止（通过回调返回值）
+    /// - 首次订阅失败直接返回错误，之后的断线由后台任务自动重连
     /// 
     /// # 使用示例
     /// 
@@ -892,7 +2785,7 @@ impl RedisService {
     /// redis.subscribe("notifications", |message| {
     ///     println!("Received: {}", message);
     ///     true // 继续订阅
-    /// }).await?;
+    /// }, || println!("resubscribed")).await?;
     /// ```
     /// 
     /// # 注意事项
@@ -901,105 +2794,233 @@ impl RedisService {
     /// - 集群模式下 Pub/Sub 是节点局部的
     /// - 分片 Pub/Sub 请使用 `ssubscribe` 和 `spublish`
     /// - 回调函数应该是快速执行的，避免阻塞消息处理
-    pub async fn subscribe<F>(&self, channel: String, mut callback: F) -> Result<()> 
-    where F: FnMut(String) -> bool + Send + 'static // Returns false to stop
+    pub async fn subscribe<F, R>(&self, channel: String, mut callback: F, mut on_resubscribed: R) -> Result<tokio::task::JoinHandle<()>> 
+    where
+        F: FnMut(String) -> bool + Send + 'static, // Returns false to stop
+        R: FnMut() + Send + 'static,
     {
-        // 根据模式确定连接地址
-        let url = if self.cfg.cluster {
-            // 集群模式：连接到种子节点
-            self.cfg.urls.get(0)
-                .ok_or_else(|| anyhow!("no cluster seed url"))?
-                .clone()
-        } else if self.cfg.sentinel {
-            // 哨兵模式：构建 Sentinel URL
-            let master = self.cfg.sentinel_master_name.as_ref()
-                .ok_or_else(|| anyhow!("no master name"))?;
-            build_sentinel_url(master, &self.cfg.sentinel_urls)?
-        } else {
-            // 单机模式：直接使用配置地址
-            self.cfg.urls.get(0)
-                .ok_or_else(|| anyhow!("no url"))?
-                .clone()
-        };
-
-        // 创建专用的 Pub/Sub 连接
-        let client = redis::Client::open(url)?;
-        let mut pubsub_conn = client.get_async_pubsub().await?;
-        pubsub_conn.subscribe(channel.clone()).await?;
+        let mut subscription = self.pubsub_manager().await?.subscribe(&channel).await?;
         
-        // 启动消息处理任务
-        tokio::spawn(async move {
-            let mut stream = pubsub_conn.on_message();
-            while let Some(msg) = stream.next().await {
-                let payload: String = match msg.get_payload() {
-                    Ok(s) => s,
-                    Err(e) => {
-                        logging::error("PUBSUB", &format!("Payload error: {}", e));
-                        continue;
+        // 启动消息处理任务，任务结束时 `subscription` 被丢弃并自动退订
+        let handle = tokio::spawn(async move {
+            while let Some(event) = subscription.recv().await {
+                match event {
+                    PubSubEvent::Message(payload) => {
+                        // 执行回调，如果返回 false 则停止订阅
+                        if !callback(payload) {
+                            break;
+                        }
                     }
-                };
-                
-                // 执行回调，如果返回 false 则停止订阅
-                if !callback(payload) {
-                    break;
+                    PubSubEvent::Resubscribed => on_resubscribed(),
                 }
             }
         });
         
-        Ok(())
+        Ok(handle)
     }
 
-    /// 发布消息到指定频道
+    /// 获取共享的 Pub/Sub 连接管理器，首次调用时创建
+    async fn pubsub_manager(&self) -> Result<&PubSubManager> {
+        self.pubsub.get_or_try_init(|| async {
+            let client = redis::Client::open(connection_info(&self.pubsub_url()?, &self.cfg)?)?;
+            // 重连间隔至少 100 毫秒，避免服务器不可用时空转
+            let delay = Duration::from_millis(self.cfg.retry_delay_ms.max(100));
+            Ok::<_, anyhow::Error>(PubSubManager::new(client, delay))
+        }).await
+    }
+
+    /// 同时订阅多个频道
     /// 
-    /// 向指定频道发布消息，返回订阅该频道的客户端数量。
-    /// 支持普通 Pub/Sub 模式。
+    /// 与逐个调用 `subscribe` 不同，所有频道共享同一个后台任务，
+    /// 终止返回的任务即可一次性取消全部频道的订阅。订阅建立在共享的 Pub/Sub 连接上（见 `subscribe`），
+    /// 断线后由连接管理器自动重连并重新订阅。
     /// 
     /// # 参数
     /// 
-    /// - `channel`: 频道名称
-    /// - `message`: 要发布的消息内容
+    /// - `channels`: 要订阅的频道列表，不能为空，重复的频道只订阅一次
+    /// - `callback`: 消息处理回调，参数为 `PubSubMessage { channel, payload }`，返回 `false` 时停止订阅
+    /// - `on_resubscribed`: 断线后重新订阅成功时按频道调用，参数为频道名
     /// 
     /// # 返回值
     /// 
-    /// 返回接收到消息的订阅者数量。
-    /// 
-    /// # 使用示例
-    /// 
-    /// ```rust
-    /// let subscribers = redis.publish("news", "Hello, World!").await?;
-    /// println!("Message sent to {} subscribers", subscribers);
-    /// ```
-    pub async fn publish(&self, channel: &str, message: &str) -> Result<i64> {
-        self.with_retry(|| async {
-            match &self.kind {
-                ConnectionKind::Standalone(manager, _) => {
-                    let mut conn = manager.clone();
-                    let n: i64 = conn.publish(channel, message).await.context("PUBLISH")?;
-                    Ok(n)
-                }
-                ConnectionKind::Cluster(client) => {
-                    let channel = channel.to_string();
-                    let message = message.to_string();
-                    let client = client.clone();
-                    
-                    tokio::task::spawn_blocking(move || -> Result<i64> {
-                        let mut conn = client.get_connection().context("get cluster connection")?;
-                        let n: i64 = redis::cmd("PUBLISH").arg(&channel).arg(&message).query(&mut conn).context("PUBLISH")?;
-                        Ok(n)
-                    }).await.unwrap()
+    /// 返回后台消息处理任务的 `JoinHandle`。任一频道订阅失败时返回错误，已订阅的频道随即退订。
+    pub async fn subscribe_many<F, R>(&self, mut channels: Vec<String>, mut callback: F, mut on_resubscribed: R) -> Result<tokio::task::JoinHandle<()>>
+    where
+        F: FnMut(PubSubMessage) -> bool + Send + 'static,
+        R: FnMut(String) + Send + 'static,
+    {
+        if channels.is_empty() {
+            return Err(invalid_argument("at least one channel is required"));
+        }
+        channels.sort();
+        channels.dedup();
+
+        let manager = self.pubsub_manager().await?;
+        let mut subscriptions = Vec::with_capacity(channels.len());
+        for channel in &channels {
+            subscriptions.push(manager.subscribe(channel).await?);
+        }
+        logging::info("PUBSUB", &format!("subscribed to {} channel(s) on the shared connection", channels.len()));
+
+        // 任务结束时合并的流被丢弃，各频道的订阅随之退订
+        let mut events = futures::stream::select_all(subscriptions.into_iter().map(Subscription::into_stream));
+        let handle = tokio::spawn(async move {
+            while let Some((channel, event)) = events.next().await {
+                match event {
+                    PubSubEvent::Message(payload) => {
+                        if !callback(PubSubMessage { channel, payload }) {
+                            break;
+                        }
+                    }
+                    PubSubEvent::Resubscribed => on_resubscribed(channel),
                 }
             }
-        }).await
+        });
+
+        Ok(handle)
     }
 
-    /// 发布消息到指定分片频道
+    /// 订阅频道并按批次回调消息
     /// 
-    /// Redis 7.0+ 的分片 Pub/Sub 功能，将消息路由到特定的分片。
-    /// 相比普通 Pub/Sub，具有更好的扩展性。
+    /// 高频频道逐条回调会产生大量事件，这里先把消息放入缓冲区，
+    /// 每隔 `flush_ms` 毫秒或缓冲区达到 `max_batch` 条时整体回调一次。
     /// 
     /// # 参数
     /// 
-    /// - `channel`: 分片频道名称
+    /// - `channel`: 要订阅的频道名称
+    /// - `flush_ms`: 刷新间隔（毫秒），必须大于 0
+    /// - `max_batch`: 单批最多包含的消息数，必须大于 0
+    /// - `callback`: 批量消息回调，返回 `false` 时停止订阅
+    /// - `on_resubscribed`: 断线后重新订阅成功时调用
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回后台消息处理任务的 `JoinHandle`。与 `subscribe` 相同，订阅建立在共享的 Pub/Sub 连接上，
+    /// 断线后自动重连；连接管理器被丢弃时会先回调剩余的消息再结束。
+    pub async fn subscribe_batched<F, R>(&self, channel: String, flush_ms: u64, max_batch: usize, mut callback: F, mut on_resubscribed: R) -> Result<tokio::task::JoinHandle<()>>
+    where
+        F: FnMut(Vec<String>) -> bool + Send + 'static,
+        R: FnMut() + Send + 'static,
+    {
+        if flush_ms == 0 || max_batch == 0 {
+            return Err(invalid_argument("flush_ms and max_batch must be greater than 0"));
+        }
+
+        let mut subscription = self.pubsub_manager().await?.subscribe(&channel).await?;
+
+        // 任务结束时 `subscription` 被丢弃并自动退订
+        let handle = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(Duration::from_millis(flush_ms));
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            let mut buffer: Vec<String> = Vec::with_capacity(max_batch);
+            loop {
+                tokio::select! {
+                    event = subscription.recv() => {
+                        match event {
+                            Some(PubSubEvent::Message(payload)) => buffer.push(payload),
+                            Some(PubSubEvent::Resubscribed) => {
+                                on_resubscribed();
+                                continue;
+                            }
+                            None => {
+                                if !buffer.is_empty() {
+                                    callback(buffer);
+                                }
+                                break;
+                            }
+                        }
+                        if buffer.len() >= max_batch {
+                            ticker.reset();
+                            if !callback(std::mem::replace(&mut buffer, Vec::with_capacity(max_batch))) {
+                                break;
+                            }
+                        }
+                    }
+                    _ = ticker.tick() => {
+                        if !buffer.is_empty() && !callback(std::mem::replace(&mut buffer, Vec::with_capacity(max_batch))) {
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+
+        Ok(handle)
+    }
+
+    /// 根据连接模式确定 Pub/Sub 专用连接的地址
+    /// 
+    /// - 集群模式：连接到第一个种子节点
+    /// - 哨兵模式：构建 Sentinel URL
+    /// - 单机模式：直接使用配置地址
+    fn pubsub_url(&self) -> Result<String> {
+        if self.cfg.cluster {
+            Ok(self.cfg.urls.get(0)
+                .ok_or_else(|| anyhow!("no cluster seed url"))?
+                .clone())
+        } else if self.cfg.sentinel {
+            let master = self.cfg.sentinel_master_name.as_ref()
+                .ok_or_else(|| anyhow!("no master name"))?;
+            build_sentinel_url(master, &self.cfg.sentinel_urls)
+        } else {
+            Ok(self.cfg.urls.get(0)
+                .ok_or_else(|| anyhow!("no url"))?
+                .clone())
+        }
+    }
+
+    /// 发布消息到指定频道
+    /// 
+    /// 向指定频道发布消息，返回订阅该频道的客户端数量。
+    /// 支持普通 Pub/Sub 模式。
+    /// 
+    /// # 参数
+    /// 
+    /// - `channel`: 频道名称
+    /// - `message`: 要发布的消息内容
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回接收到消息的订阅者数量。
+    /// 
+    /// # 使用示例
+    /// 
+    /// ```rust
+    /// let subscribers = redis.publish("news", "Hello, World!").await?;
+    /// println!("Message sent to {} subscribers", subscribers);
+    /// ```
+    pub async fn publish(&self, channel: &str, message: &str) -> Result<i64> {
+        self.check_writable("PUBLISH")?;
+        self.with_retry(|| async {
+            match &self.kind {
+                ConnectionKind::Standalone(pool, _) => {
+                    let mut conn = pool.get();
+                    let n: i64 = conn.publish(channel, message).await.context("PUBLISH")?;
+                    Ok(n)
+                }
+                ConnectionKind::Cluster(client) => {
+                    let channel = channel.to_string();
+                    let message = message.to_string();
+                    let client = client.clone();
+                    
+                    tokio::task::spawn_blocking(move || -> Result<i64> {
+                        let mut conn = client.get_connection().context("get cluster connection")?;
+                        let n: i64 = redis::cmd("PUBLISH").arg(&channel).arg(&message).query(&mut conn).context("PUBLISH")?;
+                        Ok(n)
+                    }).await.unwrap()
+                }
+            }
+        }).await
+    }
+
+    /// 发布消息到指定分片频道
+    /// 
+    /// Redis 7.0+ 的分片 Pub/Sub 功能，将消息路由到特定的分片。
+    /// 相比普通 Pub/Sub，具有更好的扩展性。
+    /// 
+    /// # 参数
+    /// 
+    /// - `channel`: 分片频道名称
     /// - `message`: 要发布的消息内容
     /// 
     /// # 返回值
@@ -1019,8 +3040,8 @@ impl RedisService {
     pub async fn spublish(&self, channel: &str, message: &str) -> Result<i64> {
         self.with_retry(|| async {
             match &self.kind {
-                ConnectionKind::Standalone(manager, _) => {
-                    let mut conn = manager.clone();
+                ConnectionKind::Standalone(pool, _) => {
+                    let mut conn = pool.get();
                     let n: i64 = redis::cmd("SPUBLISH").arg(channel).arg(message).query_async(&mut conn).await.context("SPUBLISH")?;
                     Ok(n)
                 }
@@ -1081,10 +3102,11 @@ impl RedisService {
     /// }
     /// ```
     pub async fn try_lock(&self, resource: &str, token: &str, ttl_ms: u64) -> Result<bool> {
+        self.check_writable("SET")?;
         let result: Option<String> = self.with_retry(|| async {
             match &self.kind {
-                ConnectionKind::Standalone(manager, _) => {
-                    let mut conn = manager.clone();
+                ConnectionKind::Standalone(pool, _) => {
+                    let mut conn = pool.get();
                     let res: Option<String> = redis::cmd("SET")
                         .arg(resource)
                         .arg(token)
@@ -1113,6 +3135,7 @@ impl RedisService {
                 }
             }
         }).await?;
+        self.invalidate_cached(0, resource);
         
         Ok(result.is_some())
     }
@@ -1159,6 +3182,7 @@ impl RedisService {
     /// }
     /// ```
     pub async fn unlock(&self, resource: &str, token: &str) -> Result<bool> {
+        self.check_writable("DEL")?;
         // Lua 脚本确保原子性
         let script = r#"
             if redis.call("get", KEYS[1]) == ARGV[1] then
@@ -1168,10 +3192,10 @@ impl RedisService {
             end
         "#;
         
-        self.with_retry(|| async {
+        let result = self.with_retry(|| async {
             match &self.kind {
-                ConnectionKind::Standalone(manager, _) => {
-                    let mut conn = manager.clone();
+                ConnectionKind::Standalone(pool, _) => {
+                    let mut conn = pool.get();
                     let n: i64 = redis::Script::new(script)
                         .key(resource)
                         .arg(token)
@@ -1191,10 +3215,82 @@ impl RedisService {
                     }).await.unwrap()
                 }
             }
-        }).await
+        }).await;
+        self.invalidate_cached(0, resource);
+        result
     }
     
 
+    /// 带上限的原子自增（限流计数器）
+    /// 
+    /// 通过 Lua 脚本在服务端原子地执行：自增后不超过 `max` 时执行 INCR 并返回新值，
+    /// 否则不修改计数器并返回 `None`。计数器首次创建（自增后为 1）时按 `ttl_secs` 设置过期时间，
+    /// 之后的自增不会延长过期时间，适合固定窗口限流。
+    /// 
+    /// # 参数
+    /// 
+    /// - `max`: 计数器允许的最大值
+    /// - `ttl_secs`: 计数器首次创建时设置的过期时间（秒），为 `None` 或 0 时不设置
+    /// 
+    /// # 返回值
+    /// 
+    /// - `Some(n)`: 自增成功，`n` 为新值
+    /// - `None`: 自增后会超过 `max`，计数器保持不变
+    /// 
+    /// # 注意事项
+    /// 
+    /// 不自动重试：脚本已执行但回复丢失时，重试会再自增一次，破坏上限语义。
+    pub async fn incr_capped(&self, db: u32, key: &str, max: i64, ttl_secs: Option<u64>) -> Result<Option<i64>> {
+        self.check_writable("INCRBY")?;
+        let script = r#"
+            local current = tonumber(redis.call("get", KEYS[1]) or "0")
+            if not current then
+                return redis.error_reply("ERR value is not an integer or out of range")
+            end
+            if current + 1 > tonumber(ARGV[1]) then
+                return false
+            end
+            local n = redis.call("incr", KEYS[1])
+            local ttl = tonumber(ARGV[2])
+            if n == 1 and ttl > 0 then
+                redis.call("expire", KEYS[1], ttl)
+            end
+            return n
+        "#;
+        let ttl = ttl_secs.unwrap_or(0);
+
+        let result = self.without_retry(|| async {
+            match &self.kind {
+                ConnectionKind::Standalone(pool, client) => {
+                    let n: Option<i64> = if db == 0 {
+                        let mut conn = pool.get();
+                        redis::Script::new(script).key(key).arg(max).arg(ttl).invoke_async(&mut conn).await.context("INCR CAPPED")?
+                    } else {
+                        let mut conn = Self::db_connection(client, db).await?;
+                        redis::Script::new(script).key(key).arg(max).arg(ttl).invoke_async(&mut conn).await.context("INCR CAPPED")?
+                    };
+                    Ok(n)
+                }
+                ConnectionKind::Cluster(client) => {
+                    if db != 0 {
+                        return Err(anyhow!("Cluster mode does not support multiple databases"));
+                    }
+                    let key = key.to_string();
+                    let client = client.clone();
+                    let s = redis::Script::new(script);
+
+                    tokio::task::spawn_blocking(move || -> Result<Option<i64>> {
+                        let mut conn = client.get_connection().context("get cluster connection")?;
+                        let n: Option<i64> = s.key(&key).arg(max).arg(ttl).invoke(&mut conn).context("INCR CAPPED")?;
+                        Ok(n)
+                    }).await.unwrap()
+                }
+            }
+        }).await;
+        self.invalidate_cached(db, key);
+        result
+    }
+
     // --- 高级功能 ---
     
     /// 移除键的过期时间
@@ -1217,22 +3313,18 @@ impl RedisService {
     /// let removed = redis.persist("temp_key").await?;  // 移除过期时间
     /// ```
     pub async fn persist(&self, db: u32, key: &str) -> Result<bool> {
+        self.check_writable("PERSIST")?;
         self.with_retry(|| async {
             match &self.kind {
-                ConnectionKind::Standalone(manager, client) => {
+                ConnectionKind::Standalone(pool, client) => {
                     if db == 0 {
-                        let mut conn = manager.clone();
+                        let mut conn = pool.get();
                         let n: i64 = conn.persist(key).await.context("PERSIST")?;
                         Ok(n > 0)
                     } else {
-                        let client = client.clone();
-                        let key = key.to_string();
-                        tokio::task::spawn_blocking(move || -> Result<bool> {
-                            let mut conn = client.get_connection().context("get dedicated connection")?;
-                            redis::cmd("SELECT").arg(db).query::<()>(&mut conn).context("select db")?;
-                            let n: i64 = redis::cmd("PERSIST").arg(&key).query(&mut conn).context("PERSIST")?;
-                            Ok(n > 0)
-                        }).await.unwrap()
+                        let mut conn = Self::db_connection(client, db).await?;
+                        let n: i64 = redis::cmd("PERSIST").arg(key).query_async(&mut conn).await.context("PERSIST")?;
+                        Ok(n > 0)
                     }
                 }
                 ConnectionKind::Cluster(client) => {
@@ -1265,22 +3357,22 @@ impl RedisService {
     /// 
     /// 返回键的类型字符串（如 "string", "list", "set", "zset", "hash", "stream", "none"）。
     pub async fn get_type(&self, db: u32, key: &str) -> Result<String> {
-        self.with_retry(|| async {
+        let kind = ReadKind::Type;
+        if let Some(v) = self.cache_lookup(db, key, kind) {
+            return Ok(v);
+        }
+        let epoch = self.cache_epoch();
+        let result = self.with_retry(|| async {
             match &self.kind {
-                ConnectionKind::Standalone(manager, client) => {
+                ConnectionKind::Standalone(pool, client) => {
                     if db == 0 {
-                        let mut conn = manager.clone();
+                        let mut conn = pool.get();
                         let t: String = redis::cmd("TYPE").arg(key).query_async(&mut conn).await.context("TYPE")?;
                         Ok(t)
                     } else {
-                        let client = client.clone();
-                        let key = key.to_string();
-                        tokio::task::spawn_blocking(move || -> Result<String> {
-                            let mut conn = client.get_connection().context("get dedicated connection")?;
-                            redis::cmd("SELECT").arg(db).query::<()>(&mut conn).context("select db")?;
-                            let t: String = redis::cmd("TYPE").arg(&key).query(&mut conn).context("TYPE")?;
-                            Ok(t)
-                        }).await.unwrap()
+                        let mut conn = Self::db_connection(client, db).await?;
+                        let t: String = redis::cmd("TYPE").arg(key).query_async(&mut conn).await.context("TYPE")?;
+                        Ok(t)
                     }
                 }
                 ConnectionKind::Cluster(client) => {
@@ -1297,7 +3389,9 @@ impl RedisService {
                     }).await.unwrap()
                 }
             }
-        }).await
+        }).await;
+        self.cache_store(db, key, kind, epoch, &result);
+        result
     }
 
     /// 存储 JSON 数据
@@ -1329,8 +3423,9 @@ impl RedisService {
     /// redis.set_json(0, "user:1", &user, Some(3600)).await?;
     /// ```
     pub async fn set_json<V: serde::Serialize + Send + Sync + Clone + 'static>(&self, db: u32, key: &str, value: &V, expire_seconds: Option<u64>) -> Result<()> {
+        self.check_writable("SET")?;
         let json_str = serde_json::to_string(value).context("serialize json")?;
-        self.set(db, key, json_str, expire_seconds).await
+        self.set(db, key, json_str, expire_seconds, false).await
     }
 
     /// 获取并反序列化 JSON 数据
@@ -1391,43 +3486,46 @@ impl RedisService {
     /// - `key`: 键名
     /// - `value`: 要存储的值
     /// - `expire_seconds`: 可选的过期时间（秒）
+    /// - `keep_ttl`: 为 `true` 时保留键原有的过期时间（`SET ... KEEPTTL`，需要 Redis 6.0+）；
+    ///   为 `false` 时按普通 SET 语义清除过期时间。设置了 `expire_seconds` 时忽略此参数
     /// 
     /// # 使用示例
     /// 
     /// ```rust
     /// // 永久存储
-    /// redis.set("key", "value", None).await?;
+    /// redis.set(0, "key", "value", None, false).await?;
     /// 
     /// // 60秒后过期
-    /// redis.set("temp_key", "temp_value", Some(60)).await?;
+    /// redis.set(0, "temp_key", "temp_value", Some(60), false).await?;
+    /// 
+    /// // 修改值但保留剩余的过期时间
+    /// redis.set(0, "temp_key", "new_value", None, true).await?;
     /// ```
-    pub async fn set<V: redis::ToRedisArgs + redis::ToSingleRedisArg + Send + Sync + Clone + 'static>(&self, db: u32, key: &str, value: V, expire_seconds: Option<u64>) -> Result<()> {
-        self.with_retry(|| async {
+    pub async fn set<V: redis::ToRedisArgs + redis::ToSingleRedisArg + Send + Sync + Clone + 'static>(&self, db: u32, key: &str, value: V, expire_seconds: Option<u64>, keep_ttl: bool) -> Result<()> {
+        self.check_writable("SET")?;
+        let result = self.with_retry(|| async {
             match &self.kind {
-                ConnectionKind::Standalone(manager, client) => {
+                ConnectionKind::Standalone(pool, client) => {
                     if db == 0 {
-                        let mut conn = manager.clone();
+                        let mut conn = pool.get();
                         if let Some(exp) = expire_seconds {
                             conn.set_ex(key, value.clone(), exp).await.context("SETEX")?
+                        } else if keep_ttl {
+                            redis::cmd("SET").arg(key).arg(&value).arg("KEEPTTL").query_async::<()>(&mut conn).await.context("SET KEEPTTL")?
                         } else {
                             conn.set(key, value.clone()).await.context("SET")?
                         }
                         Ok(())
                     } else {
-                        let client = client.clone();
-                        let key = key.to_string();
-                        let val = value.clone();
-                        let exp = expire_seconds;
-                        tokio::task::spawn_blocking(move || -> Result<()> {
-                            let mut conn = client.get_connection().context("get dedicated connection")?;
-                            redis::cmd("SELECT").arg(db).query::<()>(&mut conn).context("select db")?;
-                            if let Some(e) = exp {
-                                redis::cmd("SETEX").arg(&key).arg(e).arg(&val).query::<()>(&mut conn).context("SETEX")?;
-                            } else {
-                                redis::cmd("SET").arg(&key).arg(&val).query::<()>(&mut conn).context("SET")?;
-                            }
-                            Ok(())
-                        }).await.unwrap()
+                        let mut conn = Self::db_connection(client, db).await?;
+                        if let Some(e) = expire_seconds {
+                            redis::cmd("SETEX").arg(key).arg(e).arg(&value).query_async::<()>(&mut conn).await.context("SETEX")?;
+                        } else if keep_ttl {
+                            redis::cmd("SET").arg(key).arg(&value).arg("KEEPTTL").query_async::<()>(&mut conn).await.context("SET KEEPTTL")?;
+                        } else {
+                            redis::cmd("SET").arg(key).arg(&value).query_async::<()>(&mut conn).await.context("SET")?;
+                        }
+                        Ok(())
                     }
                 }
                 ConnectionKind::Cluster(client) => {
@@ -1443,6 +3541,8 @@ impl RedisService {
                         let mut conn = client.get_connection().context("get cluster connection")?;
                         if let Some(e) = exp {
                             redis::cmd("SETEX").arg(&key).arg(e).arg(&val).query::<()>(&mut conn).context("SETEX")?;
+                        } else if keep_ttl {
+                            redis::cmd("SET").arg(&key).arg(&val).arg("KEEPTTL").query::<()>(&mut conn).context("SET KEEPTTL")?;
                         } else {
                             redis::cmd("SET").arg(&key).arg(&val).query::<()>(&mut conn).context("SET")?;
                         }
@@ -1450,7 +3550,9 @@ impl RedisService {
                     }).await.unwrap()
                 }
             }
-        }).await
+        }).await;
+        self.invalidate_cached(db, key);
+        result
     }
 
     /// 获取键的值
@@ -1475,23 +3577,23 @@ impl RedisService {
     ///     println!("Key not found");
     /// }
     /// ```
-    pub async fn get<T: redis::FromRedisValue + Send + 'static>(&self, db: u32, key: &str) -> Result<Option<T>> {
-        self.with_retry(|| async {
+    pub async fn get<T: redis::FromRedisValue + Clone + Send + Sync + 'static>(&self, db: u32, key: &str) -> Result<Option<T>> {
+        let kind = ReadKind::Get(TypeId::of::<T>());
+        if let Some(v) = self.cache_lookup(db, key, kind) {
+            return Ok(v);
+        }
+        let epoch = self.cache_epoch();
+        let result = self.with_retry(|| async {
             match &self.kind {
-                ConnectionKind::Standalone(manager, client) => {
+                ConnectionKind::Standalone(pool, client) => {
                     if db == 0 {
-                        let mut conn = manager.clone();
+                        let mut conn = pool.get();
                         let v: Option<T> = conn.get(key).await.context("GET")?;
                         Ok(v)
                     } else {
-                        let client = client.clone();
-                        let key = key.to_string();
-                        tokio::task::spawn_blocking(move || -> Result<Option<T>> {
-                            let mut conn = client.get_connection().context("get dedicated connection")?;
-                            redis::cmd("SELECT").arg(db).query::<()>(&mut conn).context("select db")?;
-                            let v: Option<T> = redis::cmd("GET").arg(key).query(&mut conn).context("GET")?;
-                            Ok(v)
-                        }).await.unwrap()
+                        let mut conn = Self::db_connection(client, db).await?;
+                        let v: Option<T> = redis::cmd("GET").arg(key).query_async(&mut conn).await.context("GET")?;
+                        Ok(v)
                     }
                 }
                 ConnectionKind::Cluster(client) => {
@@ -1508,7 +3610,70 @@ impl RedisService {
                     }).await.unwrap()
                 }
             }
-        }).await
+        }).await;
+        self.cache_store(db, key, kind, epoch, &result);
+        match result {
+            Err(e) if is_wrong_type(&e) => Err(self.wrong_type_error(db, key, None, e).await),
+            other => other,
+        }
+    }
+
+    /// 以原始字节获取字符串键的值
+    /// 
+    /// 与 `get` 不同，键名与值都不要求是合法 UTF-8，适合导出等需要处理任意键名的场景；结果不经过读缓存。
+    pub async fn get_bytes(&self, db: u32, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        let mut cmd = redis::cmd("GET");
+        cmd.arg(key);
+        self.query_cmd(db, cmd, "GET").await
+    }
+
+    /// 将 WRONGTYPE 错误转换为携带实际类型的 `WrongType`
+    /// 
+    /// 查询类型失败时保留原错误。
+    async fn wrong_type_error(&self, db: u32, key: &str, operation: Option<&str>, e: anyhow::Error) -> anyhow::Error {
+        match self.get_type(db, key).await {
+            Ok(actual) => anyhow::Error::new(WrongType { key: key.to_string(), actual, operation: operation.map(str::to_string) }),
+            Err(_) => e,
+        }
+    }
+
+    /// 写入哈希/列表/集合/有序集合时的类型检查
+    /// 
+    /// 写入结果为 WRONGTYPE 时转换为携带实际类型和 `operation` 的 `WrongType`，其余结果原样返回。
+    async fn guard_write_type<T>(&self, db: u32, key: &str, operation: &str, result: Result<T>) -> Result<T> {
+        match result {
+            Err(e) if is_wrong_type(&e) => Err(self.wrong_type_error(db, key, Some(operation), e).await),
+            other => other,
+        }
+    }
+
+    /// 原子地获取并删除字符串键（GETDEL 命令，Redis 6.2+）
+    /// 
+    /// 适用于一次性令牌等读取后即失效的场景，避免 GET + DEL 之间的竞态。
+    /// 键不存在时返回 `None`。不自动重试，避免回复丢失后重试读到已删除的键而丢失值。
+    pub async fn getdel(&self, db: u32, key: &str) -> Result<Option<String>> {
+        self.check_writable("GETDEL")?;
+        self.check_policy("GETDEL")?;
+        let mut cmd = redis::cmd("GETDEL");
+        cmd.arg(key);
+        let result = self.query_cmd_once(db, cmd, "GETDEL").await;
+        self.invalidate_cached(db, key);
+        result
+    }
+
+    /// 获取字符串值并同时更新过期时间（GETEX 命令，Redis 6.2+）
+    /// 
+    /// `expiry` 为 `None` 时等同于 GET。键不存在时返回 `None`，且不会创建键。
+    pub async fn getex(&self, db: u32, key: &str, expiry: Option<SetExpiry>) -> Result<Option<String>> {
+        if expiry.is_some() {
+            self.check_writable("GETEX")?;
+        }
+        let mut cmd = redis::cmd("GETEX");
+        cmd.arg(key);
+        if let Some(expiry) = expiry {
+            expiry.write_args(&mut cmd);
+        }
+        self.query_cmd(db, cmd, "GETEX").await
     }
 
     /// 获取集群节点信息
@@ -1527,45 +3692,7 @@ impl RedisService {
                         let mut conn = client.get_connection().context("get cluster connection")?;
                         let info: String = redis::cmd("CLUSTER").arg("NODES").query(&mut conn).context("CLUSTER NODES")?;
                         
-                        let mut nodes = Vec::new();
-                        for line in info.lines() {
-                            let parts: Vec<&str> = line.split_whitespace().collect();
-                            if parts.len() < 8 {
-                                continue;
-                            }
-                            
-                            // 格式: <id> <ip:port@cport[,hostname]> <flags> <master> <ping-sent> <pong-recv> <config-epoch> <link-state> <slot> <slot> ...
-                            // id: parts[0]
-                            // addr: parts[1]
-                            // flags: parts[2]
-                            // master: parts[3]
-                            // ping: parts[4]
-                            // pong: parts[5]
-                            // epoch: parts[6]
-                            // state: parts[7]
-                            // slots: parts[8..]
-                            
-                            let mut slots = Vec::new();
-                            if parts.len() > 8 {
-                                for i in 8..parts.len() {
-                                    slots.push(parts[i].to_string());
-                                }
-                            }
-                            
-                            nodes.push(ClusterNodeInfo {
-                                id: parts[0].to_string(),
-                                addr: parts[1].to_string(),
-                                flags: parts[2].to_string(),
-                                master_id: parts[3].to_string(),
-                                ping_sent: parts[4].to_string(),
-                                pong_recv: parts[5].to_string(),
-                                config_epoch: parts[6].to_string(),
-                                link_state: parts[7].to_string(),
-                                slots,
-                            });
-                        }
-                        
-                        Ok(nodes)
+                        Ok(parse_cluster_nodes(&info))
                     }).await.unwrap()
                 }
             }
@@ -1594,22 +3721,19 @@ impl RedisService {
     /// }
     /// ```
     pub async fn del(&self, db: u32, key: &str) -> Result<bool> {
-        self.with_retry(|| async {
+        self.check_writable("DEL")?;
+        self.check_policy("DEL")?;
+        let result = self.with_retry(|| async {
             match &self.kind {
-                ConnectionKind::Standalone(manager, client) => {
+                ConnectionKind::Standalone(pool, client) => {
                     if db == 0 {
-                        let mut conn = manager.clone();
+                        let mut conn = pool.get();
                         let n: i64 = conn.del(key).await.context("DEL")?;
                         Ok(n > 0)
                     } else {
-                        let client = client.clone();
-                        let key = key.to_string();
-                        tokio::task::spawn_blocking(move || -> Result<bool> {
-                            let mut conn = client.get_connection().context("get dedicated connection")?;
-                            redis::cmd("SELECT").arg(db).query::<()>(&mut conn).context("select db")?;
-                            let n: i64 = redis::cmd("DEL").arg(&key).query(&mut conn).context("DEL")?;
-                            Ok(n > 0)
-                        }).await.unwrap()
+                        let mut conn = Self::db_connection(client, db).await?;
+                        let n: i64 = redis::cmd("DEL").arg(key).query_async(&mut conn).await.context("DEL")?;
+                        Ok(n > 0)
                     }
                 }
                 ConnectionKind::Cluster(client) => {
@@ -1626,7 +3750,9 @@ impl RedisService {
                     }).await.unwrap()
                 }
             }
-        }).await
+        }).await;
+        self.invalidate_cached(db, key);
+        result
     }
 
     /// 检查键是否存在
@@ -1654,20 +3780,15 @@ impl RedisService {
     pub async fn exists(&self, db: u32, key: &str) -> Result<bool> {
         self.with_retry(|| async {
             match &self.kind {
-                ConnectionKind::Standalone(manager, client) => {
+                ConnectionKind::Standalone(pool, client) => {
                     if db == 0 {
-                        let mut conn = manager.clone();
+                        let mut conn = pool.get();
                         let n: i64 = conn.exists(key).await.context("EXISTS")?;
                         Ok(n > 0)
                     } else {
-                        let client = client.clone();
-                        let key = key.to_string();
-                        tokio::task::spawn_blocking(move || -> Result<bool> {
-                            let mut conn = client.get_connection().context("get dedicated connection")?;
-                            redis::cmd("SELECT").arg(db).query::<()>(&mut conn).context("select db")?;
-                            let n: i64 = redis::cmd("EXISTS").arg(&key).query(&mut conn).context("EXISTS")?;
-                            Ok(n > 0)
-                        }).await.unwrap()
+                        let mut conn = Self::db_connection(client, db).await?;
+                        let n: i64 = redis::cmd("EXISTS").arg(key).query_async(&mut conn).await.context("EXISTS")?;
+                        Ok(n > 0)
                     }
                 }
                 ConnectionKind::Cluster(client) => {
@@ -1687,105 +3808,237 @@ impl RedisService {
         }).await
     }
 
-    /// 设置键的过期时间
+    /// 随机返回一个键（RANDOMKEY 命令）
     /// 
-    /// 使用 EXPIRE 命令为已存在的键设置过期时间。
+    /// 用于在大型键空间中抽样浏览。
     /// 
-    /// # 参数
+    /// # 返回值
     /// 
-    /// - `key`: 要设置过期时间的键名
-    /// - `seconds`: 过期时间（秒）
+    /// 数据库为空时返回 `None`。
     /// 
-    /// # 返回值
+    /// # 集群模式
     /// 
-    /// - `true`: 成功设置过期时间
-    /// - `false`: 键不存在
+    /// 不带键的命令由集群客户端随机路由到一个主节点，因此结果只来自该节点。
+    /// 若选中的节点恰好为空，会换一个节点重试几次。
+    pub async fn randomkey(&self, db: u32) -> Result<Option<String>> {
+        let attempts = match &self.kind {
+            ConnectionKind::Standalone(..) => 1,
+            ConnectionKind::Cluster(_) => 3,
+        };
+        for _ in 0..attempts {
+            let key: Option<String> = self.query_cmd(db, redis::cmd("RANDOMKEY"), "RANDOMKEY").await?;
+            if key.is_some() {
+                return Ok(key);
+            }
+        }
+        Ok(None)
+    }
+
+    /// 更新键的最后访问时间（TOUCH 命令）
     /// 
-    /// # 使用示例
+    /// 不修改键的值，只刷新 LRU/空闲时间，常用于防止键被淘汰。
     /// 
-    /// ```rust
-    /// redis.set("my_key", "value", None).await?;
-    /// redis.expire("my_key", 3600).await?; // 1小时后过期
-    /// ```
-    pub async fn expire(&self, db: u32, key: &str, seconds: u64) -> Result<bool> {
-        self.with_retry(|| async {
-            match &self.kind {
-                ConnectionKind::Standalone(manager, client) => {
-                    if db == 0 {
-                        let mut conn = manager.clone();
-                        let res: bool = conn.expire(key, i64::try_from(seconds).unwrap()).await.context("EXPIRE")?;
-                        Ok(res)
-                    } else {
-                        let client = client.clone();
-                        let key = key.to_string();
-                        let sec = i64::try_from(seconds).unwrap();
-                        tokio::task::spawn_blocking(move || -> Result<bool> {
-                            let mut conn = client.get_connection().context("get dedicated connection")?;
-                            redis::cmd("SELECT").arg(db).query::<()>(&mut conn).context("select db")?;
-                            let res: bool = redis::cmd("EXPIRE").arg(&key).arg(sec).query(&mut conn).context("EXPIRE")?;
-                            Ok(res)
-                        }).await.unwrap()
-                    }
-                }
-                ConnectionKind::Cluster(client) => {
-                    if db != 0 {
-                        return Err(anyhow!("Cluster mode does not support multiple databases"));
-                    }
-                    let key = key.to_string();
-                    let sec = i64::try_from(seconds).unwrap();
-                    let client = client.clone();
-                    
-                    tokio::task::spawn_blocking(move || -> Result<bool> {
-                        let mut conn = client.get_connection().context("get cluster connection")?;
-                        let res: bool = redis::cmd("EXPIRE").arg(&key).arg(sec).query(&mut conn).context("EXPIRE")?;
-                        Ok(res)
-                    }).await.unwrap()
+    /// # 返回值
+    /// 
+    /// 返回实际存在并被更新的键数量。集群模式下逐个键执行，键可以分布在不同槽位。
+    pub async fn touch(&self, db: u32, keys: &[String]) -> Result<i64> {
+        if keys.is_empty() {
+            return Ok(0);
+        }
+        match &self.kind {
+            ConnectionKind::Standalone(..) => {
+                let mut cmd = redis::cmd("TOUCH");
+                cmd.arg(keys);
+                self.query_cmd(db, cmd, "TOUCH").await
+            }
+            ConnectionKind::Cluster(_) => {
+                let mut total = 0;
+                for key in keys {
+                    let mut cmd = redis::cmd("TOUCH");
+                    cmd.arg(key);
+                    let n: i64 = self.query_cmd(db, cmd, "TOUCH").await?;
+                    total += n;
                 }
+                Ok(total)
             }
-        }).await
+        }
     }
 
-    /// 获取键的剩余过期时间
+    /// 将键移动到同一实例的另一个数据库（MOVE 命令）
     /// 
-    /// 使用 TTL 命令查询键的剩余生存时间。
+    /// # 返回值
     /// 
-    /// # 参数
+    /// - `true`: 移动成功
+    /// - `false`: 源库中不存在该键，或目标库中已存在同名键
     /// 
-    /// - `key`: 要查询的键名
+    /// # 注意事项
     /// 
-    /// # 返回值
+    /// 集群模式只有 0 号库，不支持 MOVE，直接返回错误。
+    pub async fn move_key(&self, db: u32, key: &str, dest_db: u32) -> Result<bool> {
+        self.check_writable("MOVE")?;
+        self.check_policy("MOVE")?;
+        if matches!(self.kind, ConnectionKind::Cluster(_)) {
+            return Err(anyhow!("MOVE is not supported in cluster mode"));
+        }
+        if db == dest_db {
+            return Err(invalid_argument("Source and destination databases must differ"));
+        }
+        let mut cmd = redis::cmd("MOVE");
+        cmd.arg(key).arg(dest_db);
+        let result = self.query_cmd(db, cmd, "MOVE").await;
+        self.invalidate_cached(db, key);
+        self.invalidate_cached(dest_db, key);
+        result
+    }
+
+    /// 原子交换两个数据库的全部数据（SWAPDB 命令）
     /// 
-    /// - `> 0`: 剩余过期时间（秒）
-    /// - `-1`: 键存在但没有设置过期时间
-    /// - `-2`: 键不存在
+    /// 交换对所有客户端立即可见，常用于将预发布数据库与线上数据库整体切换。
     /// 
-    /// # 使用示例
+    /// # 注意事项
     /// 
-    /// ```rust
-    /// let ttl = redis.ttl("my_key").await?;
-    /// match ttl {
-    ///     -2 => println!("Key does not exist"),
-    ///     -1 => println!("Key has no expiration"),
-    ///     t  => println!("Key will expire in {} seconds", t),
-    /// }
+    /// - 集群模式只有 0 号库，不支持 SWAPDB，直接返回错误
+    /// - SWAPDB 不是幂等的：回复丢失后重试会把两个库再换回去，因此只发送一次，不自动重试
+    pub async fn swapdb(&self, db1: u32, db2: u32) -> Result<()> {
+        self.check_writable("SWAPDB")?;
+        self.check_policy("SWAPDB")?;
+        if matches!(self.kind, ConnectionKind::Cluster(_)) {
+            return Err(anyhow!("SWAPDB is not supported in cluster mode"));
+        }
+        if db1 == db2 {
+            return Err(invalid_argument("Databases to swap must differ"));
+        }
+        let mut cmd = redis::cmd("SWAPDB");
+        cmd.arg(db1).arg(db2);
+        let _: () = self.query_cmd_once(0, cmd, "SWAPDB").await?;
+        self.clear_cached();
+        Ok(())
+    }
+
+    /// 序列化键的值并读取剩余过期时间（DUMP + PTTL）
+    /// 
+    /// # 返回值
+    /// 
+    /// - `Some((payload, ttl_ms))`: 序列化数据与剩余毫秒数，`ttl_ms` 为 `0` 表示永不过期
+    /// - `None`: 键不存在
+    /// 
+    /// # 注意事项
+    /// 
+    /// DUMP 的序列化数据不包含过期时间，因此在同一管道中执行 PTTL 一并返回，
+    /// 供 `restore` 在目标端恢复。
+    pub async fn dump_with_ttl(&self, db: u32, key: &str) -> Result<Option<(Vec<u8>, u64)>> {
+        let mut pipe = redis::pipe();
+        pipe.cmd("DUMP").arg(key).cmd("PTTL").arg(key);
+        let (payload, pttl): (Option<Vec<u8>>, i64) = self.query_pipe(db, pipe, "DUMP").await?;
+        match payload {
+            // PTTL 为 -2 说明键在两条命令之间过期
+            Some(_) if pttl == -2 => Ok(None),
+            Some(payload) => Ok(Some((payload, pttl.max(0) as u64))),
+            None => Ok(None),
+        }
+    }
+
+    /// 从序列化数据恢复键（RESTORE 命令）
+    /// 
+    /// # 参数
+    /// 
+    /// - `ttl_ms`: 过期时间（毫秒），`0` 表示永不过期
+    /// - `payload`: `DUMP` 返回的序列化数据
+    /// - `replace`: 目标键已存在时是否覆盖；为 `false` 时服务端返回 `BUSYKEY` 错误
+    /// 
+    /// # 注意事项
+    /// 
+    /// - 序列化数据与 RDB 版本绑定，高版本 Redis 导出的数据可能无法在低版本恢复
+    /// - 命令只发送一次，不自动重试：回复丢失后重试时键已由第一次写入，
+    ///   不覆盖时会得到 `BUSYKEY`，被误报为目标键已存在
+    pub async fn restore(&self, db: u32, key: &str, ttl_ms: u64, payload: &[u8], replace: bool) -> Result<()> {
+        self.check_writable("RESTORE")?;
+        let mut cmd = redis::cmd("RESTORE");
+        cmd.arg(key).arg(ttl_ms).arg(payload);
+        if replace {
+            cmd.arg("REPLACE");
+        }
+        let _: () = self.query_cmd_once(db, cmd, "RESTORE").await?;
+        self.invalidate_cached(db, key);
+        Ok(())
+    }
+
+    /// 按类型整体替换键的值
+    /// 
+    /// 先读取剩余过期时间，再在一个事务（MULTI/EXEC）中执行 `DEL` 与对应类型的写入命令
+    /// （`SET`、`HSET`、`RPUSH`、`SADD`、`ZADD`），其他客户端不会看到只写入了一部分的值。
+    /// 
+    /// # 参数
+    /// 
+    /// - `key`: 要写入的键名
+    /// - `payload`: 新的值，其类型可以与键当前的类型不同
+    /// 
+    /// # 覆盖语义
+    /// 
+    /// - 旧值被完全替换而不是合并：`payload` 中没有的哈希字段、列表元素、集合成员都会被删除
+    /// - 键原有的过期时间会被保留；键不存在时新写入的键永不过期
+    /// - 集合类型的值为空时只删除键，因为 Redis 不保存空的集合类型
+    /// 
+    /// # 注意事项
+    /// 
+    /// - 读取过期时间与事务之间键的过期时间被其他客户端修改时，以读取到的值为准
+    /// - 集群模式不支持事务，命令以普通管道发送，写入过程中其他客户端可能读到中间状态
+    pub async fn write_key(&self, db: u32, key: &str, payload: &KeyPayload) -> Result<()> {
+        self.check_writable("DEL")?;
+        self.check_policy("DEL")?;
+        let pttl: i64 = self.query_cmd(db, redis::cmd("PTTL").arg(key).clone(), "PTTL").await?;
+
+        let mut pipe = redis::pipe();
+        if !matches!(self.kind, ConnectionKind::Cluster(_)) {
+            pipe.atomic();
+        }
+        pipe.cmd("DEL").arg(key).ignore();
+        if !payload.is_empty() {
+            payload.append_writes(&mut pipe, key);
+            if pttl > 0 {
+                pipe.cmd("PEXPIRE").arg(key).arg(pttl).ignore();
+            }
+        }
+        let _: () = self.query_pipe(db, pipe, "WRITE_KEY").await?;
+        self.invalidate_cached(db, key);
+        logging::debug("REDIS", &format!("write_key key={} type={} ttl_ms={}", key, payload.type_name(), pttl));
+        Ok(())
+    }
+
+    /// 设置键的过期时间
+    /// 
+    /// 使用 EXPIRE 命令为已存在的键设置过期时间。
+    /// 
+    /// # 参数
+    /// 
+    /// - `key`: 要设置过期时间的键名
+    /// - `seconds`: 过期时间（秒）
+    /// 
+    /// # 返回值
+    /// 
+    /// - `true`: 成功设置过期时间
+    /// - `false`: 键不存在
+    /// 
+    /// # 使用示例
+    /// 
+    /// ```rust
+    /// redis.set("my_key", "value", None).await?;
+    /// redis.expire("my_key", 3600).await?; // 1小时后过期
     /// ```
-    pub async fn ttl(&self, db: u32, key: &str) -> Result<i64> {
+    pub async fn expire(&self, db: u32, key: &str, seconds: u64) -> Result<bool> {
+        self.check_writable("EXPIRE")?;
         self.with_retry(|| async {
             match &self.kind {
-                ConnectionKind::Standalone(manager, client) => {
+                ConnectionKind::Standalone(pool, client) => {
                     if db == 0 {
-                        let mut conn = manager.clone();
-                        let res: i64 = conn.ttl(key).await.context("TTL")?;
+                        let mut conn = pool.get();
+                        let res: bool = conn.expire(key, i64::try_from(seconds).unwrap()).await.context("EXPIRE")?;
                         Ok(res)
                     } else {
-                        let client = client.clone();
-                        let key = key.to_string();
-                        tokio::task::spawn_blocking(move || -> Result<i64> {
-                            let mut conn = client.get_connection().context("get dedicated connection")?;
-                            redis::cmd("SELECT").arg(db).query::<()>(&mut conn).context("select db")?;
-                            let res: i64 = redis::cmd("TTL").arg(&key).query(&mut conn).context("TTL")?;
-                            Ok(res)
-                        }).await.unwrap()
+                        let sec = i64::try_from(seconds).unwrap();
+                        let mut conn = Self::db_connection(client, db).await?;
+                        let res: bool = redis::cmd("EXPIRE").arg(key).arg(sec).query_async(&mut conn).await.context("EXPIRE")?;
+                        Ok(res)
                     }
                 }
                 ConnectionKind::Cluster(client) => {
@@ -1793,11 +4046,12 @@ impl RedisService {
                         return Err(anyhow!("Cluster mode does not support multiple databases"));
                     }
                     let key = key.to_string();
+                    let sec = i64::try_from(seconds).unwrap();
                     let client = client.clone();
                     
-                    tokio::task::spawn_blocking(move || -> Result<i64> {
+                    tokio::task::spawn_blocking(move || -> Result<bool> {
                         let mut conn = client.get_connection().context("get cluster connection")?;
-                        let res: i64 = redis::cmd("TTL").arg(&key).query(&mut conn).context("TTL")?;
+                        let res: bool = redis::cmd("EXPIRE").arg(&key).arg(sec).query(&mut conn).context("EXPIRE")?;
                         Ok(res)
                     }).await.unwrap()
                 }
@@ -1805,95 +4059,94 @@ impl RedisService {
         }).await
     }
 
-    /// 获取键的数据类型
-    ///
-    /// 使用 TYPE 命令获取键的数据类型。
-    ///
-    /// # 参数
-    ///
-    /// - `key`: 键名
-    ///
+    /// 按条件设置键的过期时间（EXPIRE NX/XX/GT/LT）
+    /// 
+    /// `flag` 为 `None` 时等同于 `expire`。
+    /// 
     /// # 返回值
-    ///
-    /// 返回类型字符串，如 "string", "list", "set", "zset", "hash", "stream", "none"。
-    pub async fn key_type(&self, db: u32, key: &str) -> Result<String> {
-        self.with_retry(|| async {
-            match &self.kind {
-                ConnectionKind::Standalone(manager, client) => {
-                    if db == 0 {
-                        let mut conn = manager.clone();
-                        let t: String = redis::cmd("TYPE").arg(key).query_async(&mut conn).await.context("TYPE")?;
-                        Ok(t)
-                    } else {
-                        let client = client.clone();
-                        let key = key.to_string();
-                        tokio::task::spawn_blocking(move || -> Result<String> {
-                            let mut conn = client.get_connection().context("get dedicated connection")?;
-                            redis::cmd("SELECT").arg(db).query::<()>(&mut conn).context("select db")?;
-                            let t: String = redis::cmd("TYPE").arg(&key).query(&mut conn).context("TYPE")?;
-                            Ok(t)
-                        }).await.unwrap()
-                    }
-                }
-                ConnectionKind::Cluster(client) => {
-                    if db != 0 {
-                        return Err(anyhow!("Cluster mode does not support multiple databases"));
-                    }
-                    let key = key.to_string();
-                    let client = client.clone();
-                    
-                    tokio::task::spawn_blocking(move || -> Result<String> {
-                        let mut conn = client.get_connection().context("get cluster connection")?;
-                        let t: String = redis::cmd("TYPE").arg(&key).query(&mut conn).context("TYPE")?;
-                        Ok(t)
-                    }).await.unwrap()
-                }
+    /// 
+    /// - `true`: 成功设置过期时间
+    /// - `false`: 键不存在，或条件不满足而未修改
+    /// 
+    /// # 注意事项
+    /// 
+    /// 条件标志需要 Redis 7.0 或更高版本，旧版本会以参数个数错误拒绝命令，
+    /// 此时返回的错误中会注明版本要求。
+    pub async fn expire_with_flag(&self, db: u32, key: &str, seconds: u64, flag: Option<ExpireFlag>) -> Result<bool> {
+        self.check_writable("EXPIRE")?;
+        let Some(flag) = flag else { return self.expire(db, key, seconds).await };
+        let mut cmd = redis::cmd("EXPIRE");
+        cmd.arg(key).arg(seconds).arg(flag.as_str());
+        self.query_cmd(db, cmd, "EXPIRE").await.map_err(|e| {
+            let unsupported = e.chain().any(|cause| {
+                cause.downcast_ref::<redis::RedisError>()
+                    .is_some_and(|re| re.to_string().contains("wrong number of arguments"))
+            });
+            if unsupported {
+                e.context("EXPIRE NX/XX/GT/LT flags require Redis 7.0 or later")
+            } else {
+                e
             }
-        }).await
+        })
     }
 
-    // --- 哈希操作 ---
+    /// 设置键在指定时刻过期（PEXPIREAT 命令）
+    /// 
+    /// # 参数
+    /// 
+    /// - `unix_ms`: 过期时刻（Unix 毫秒时间戳）
+    /// 
+    /// # 返回值
+    /// 
+    /// - `true`: 成功设置过期时间
+    /// - `false`: 键不存在
+    /// 
+    /// # 注意事项
+    /// 
+    /// 时刻早于当前时间时服务端会立即删除该键，同样返回 `true`。
+    pub async fn pexpire_at(&self, db: u32, key: &str, unix_ms: i64) -> Result<bool> {
+        self.check_writable("PEXPIREAT")?;
+        let mut cmd = redis::cmd("PEXPIREAT");
+        cmd.arg(key).arg(unix_ms);
+        self.query_cmd(db, cmd, "PEXPIREAT").await
+    }
 
-    /// 设置哈希字段
+    /// 获取键的剩余过期时间
     /// 
-    /// 使用 HSET 命令设置哈希表中的字段值。
+    /// 使用 TTL 命令查询键的剩余生存时间。
     /// 
     /// # 参数
     /// 
-    /// - `key`: 哈希表的键名
-    /// - `field`: 字段名
-    /// - `value`: 字段值
+    /// - `key`: 要查询的键名
     /// 
     /// # 返回值
     /// 
-    /// - `true`: 字段是新增的
-    /// - `false`: 字段已存在并被更新
+    /// - `> 0`: 剩余过期时间（秒）
+    /// - `-1`: 键存在但没有设置过期时间
+    /// - `-2`: 键不存在
     /// 
     /// # 使用示例
     /// 
     /// ```rust
-    /// redis.hset("user:1", "name", "Alice").await?;
-    /// redis.hset("user:1", "age", 25).await?;
+    /// let ttl = redis.ttl("my_key").await?;
+    /// match ttl {
+    ///     -2 => println!("Key does not exist"),
+    ///     -1 => println!("Key has no expiration"),
+    ///     t  => println!("Key will expire in {} seconds", t),
+    /// }
     /// ```
-    pub async fn hset<V: redis::ToRedisArgs + redis::ToSingleRedisArg + Send + Sync + Clone + 'static>(&self, db: u32, key: &str, field: &str, value: V) -> Result<bool> {
+    pub async fn ttl(&self, db: u32, key: &str) -> Result<i64> {
         self.with_retry(|| async {
             match &self.kind {
-                ConnectionKind::Standalone(manager, client) => {
+                ConnectionKind::Standalone(pool, client) => {
                     if db == 0 {
-                        let mut conn = manager.clone();
-                        let n: i64 = conn.hset(key, field, value.clone()).await.context("HSET")?;
-                        Ok(n > 0)
+                        let mut conn = pool.get();
+                        let res: i64 = conn.ttl(key).await.context("TTL")?;
+                        Ok(res)
                     } else {
-                        let client = client.clone();
-                        let key = key.to_string();
-                        let field = field.to_string();
-                        let value = value.clone();
-                        tokio::task::spawn_blocking(move || -> Result<bool> {
-                            let mut conn = client.get_connection().context("get dedicated connection")?;
-                            redis::cmd("SELECT").arg(db).query::<()>(&mut conn).context("select db")?;
-                            let n: i64 = redis::cmd("HSET").arg(&key).arg(&field).arg(&value).query(&mut conn).context("HSET")?;
-                            Ok(n > 0)
-                        }).await.unwrap()
+                        let mut conn = Self::db_connection(client, db).await?;
+                        let res: i64 = redis::cmd("TTL").arg(key).query_async(&mut conn).await.context("TTL")?;
+                        Ok(res)
                     }
                 }
                 ConnectionKind::Cluster(client) => {
@@ -1901,118 +4154,511 @@ impl RedisService {
                         return Err(anyhow!("Cluster mode does not support multiple databases"));
                     }
                     let key = key.to_string();
-                    let field = field.to_string();
-                    let value = value.clone();
                     let client = client.clone();
                     
-                    tokio::task::spawn_blocking(move || -> Result<bool> {
+                    tokio::task::spawn_blocking(move || -> Result<i64> {
                         let mut conn = client.get_connection().context("get cluster connection")?;
-                        let n: i64 = redis::cmd("HSET").arg(&key).arg(&field).arg(&value).query(&mut conn).context("HSET")?;
-                        Ok(n > 0)
+                        let res: i64 = redis::cmd("TTL").arg(&key).query(&mut conn).context("TTL")?;
+                        Ok(res)
                     }).await.unwrap()
                 }
             }
         }).await
     }
 
-    pub async fn hdel(&self, db: u32, key: &str, field: &str) -> Result<bool> {
-        self.with_retry(|| async {
+    /// 查询键的剩余生存时间（毫秒）
+    /// 
+    /// 使用 PTTL 命令，返回值约定与 `ttl` 相同：`-1` 表示永不过期，`-2` 表示键不存在。
+    pub async fn pttl(&self, db: u32, key: &str) -> Result<i64> {
+        let mut cmd = redis::cmd("PTTL");
+        cmd.arg(key);
+        self.query_cmd(db, cmd, "PTTL").await
+    }
+
+    /// 查询键的过期信息
+    /// 
+    /// 基于 `pttl` 返回结构化的 `KeyTtlInfo`，区分"键不存在"、"永不过期"和"剩余时间"三种情况。
+    pub async fn ttl_info(&self, db: u32, key: &str) -> Result<KeyTtlInfo> {
+        let pttl = self.pttl(db, key).await?;
+        Ok(KeyTtlInfo::from_pttl(pttl))
+    }
+
+    /// 仅当键设置了过期时间时删除（软删除）
+    /// 
+    /// 通过 Lua 脚本在服务端原子地检查 `PTTL` 并删除，避免检查与删除之间键的过期时间被修改。
+    /// 永不过期的键不会被删除，用于防止误删重要的持久键。
+    pub async fn del_if_volatile(&self, db: u32, key: &str) -> Result<VolatileDeleteStatus> {
+        self.check_writable("DEL")?;
+        self.check_policy("DEL")?;
+        let script = r#"
+            local ttl = redis.call("pttl", KEYS[1])
+            if ttl > 0 then
+                redis.call("del", KEYS[1])
+                return 1
+            elseif ttl == -1 then
+                return 0
+            else
+                return -1
+            end
+        "#;
+        let to_status = |n: i64| match n {
+            1 => VolatileDeleteStatus::Deleted,
+            0 => VolatileDeleteStatus::SkippedPersistent,
+            _ => VolatileDeleteStatus::NotFound,
+        };
+
+        let result = self.with_retry(|| async {
             match &self.kind {
-                ConnectionKind::Standalone(manager, client) => {
-                    if db == 0 {
-                        let mut conn = manager.clone();
-                        let n: i64 = redis::Cmd::new().arg("HDEL").arg(key).arg(field).query_async(&mut conn).await.context("HDEL")?;
-                        Ok(n > 0)
+                ConnectionKind::Standalone(pool, client) => {
+                    let n: i64 = if db == 0 {
+                        let mut conn = pool.get();
+                        redis::Script::new(script).key(key).invoke_async(&mut conn).await.context("DEL IF VOLATILE")?
                     } else {
-                        let client = client.clone();
-                        let key = key.to_string();
-                        let field = field.to_string();
-                        tokio::task::spawn_blocking(move || -> Result<bool> {
-                            let mut conn = client.get_connection().context("get dedicated connection")?;
-                            redis::cmd("SELECT").arg(db).query::<()>(&mut conn).context("select db")?;
-                            let n: i64 = redis::cmd("HDEL").arg(&key).arg(&field).query(&mut conn).context("HDEL")?;
-                            Ok(n > 0)
-                        }).await.unwrap()
-                    }
+                        let mut conn = Self::db_connection(client, db).await?;
+                        redis::Script::new(script).key(key).invoke_async(&mut conn).await.context("DEL IF VOLATILE")?
+                    };
+                    Ok(to_status(n))
                 }
                 ConnectionKind::Cluster(client) => {
                     if db != 0 {
                         return Err(anyhow!("Cluster mode does not support multiple databases"));
                     }
                     let key = key.to_string();
-                    let field = field.to_string();
                     let client = client.clone();
-                    
-                    tokio::task::spawn_blocking(move || -> Result<bool> {
+                    let s = redis::Script::new(script);
+
+                    tokio::task::spawn_blocking(move || -> Result<VolatileDeleteStatus> {
                         let mut conn = client.get_connection().context("get cluster connection")?;
-                        let n: i64 = redis::cmd("HDEL").arg(&key).arg(&field).query(&mut conn).context("HDEL")?;
-                        Ok(n > 0)
+                        let n: i64 = s.key(&key).invoke(&mut conn).context("DEL IF VOLATILE")?;
+                        Ok(to_status(n))
                     }).await.unwrap()
                 }
             }
-        }).await
+        }).await;
+        self.invalidate_cached(db, key);
+        result
     }
 
-    /// 获取哈希字段值
-    /// 
-    /// 使用 HGET 命令获取哈希表中指定字段的值。
-    /// 
-    /// # 参数
+    /// 预览键的类型、TTL 与元素数量
+    /// 
+    /// 先执行 `TYPE`，再通过一次管道按类型发送 `STRLEN`/`LLEN`/`SCARD`/`ZCARD`/`HLEN`/`XLEN` 和 `TTL`，
+    /// 不会读取值的内容，适合在删除前展示。键不存在时类型为 `none`。
+    pub async fn preview_key(&self, db: u32, key: &str) -> Result<KeyPreview> {
+        let key_type = self.get_type(db, key).await?;
+        if key_type == "none" {
+            return Ok(KeyPreview { key_type, ttl: -2, element_count: None, sample: Vec::new(), error: None });
+        }
+
+        let mut pipe = redis::pipe();
+        let size_cmd = size_command(&key_type);
+        if let Some(size_cmd) = size_cmd {
+            pipe.cmd(size_cmd).arg(key);
+        }
+        pipe.cmd("TTL").arg(key);
+        let values: Vec<i64> = self.query_pipe(db, pipe, "PREVIEW").await?;
+
+        let (element_count, ttl) = match (size_cmd, values.as_slice()) {
+            (Some(_), [count, ttl]) => (Some((*count).max(0) as u64), *ttl),
+            (None, [ttl]) => (None, *ttl),
+            _ => return Err(anyhow!("Unexpected preview reply: {:?}", values)),
+        };
+        Ok(KeyPreview { key_type, ttl, element_count, sample: Vec::new(), error: None })
+    }
+
+    /// 批量预览多个类型未知的键
     /// 
-    /// - `key`: 哈希表的键名
-    /// - `field`: 要获取的字段名
+    /// 先通过管道获取所有键的 `TYPE`，再按类型为每个键读取元素数量、`TTL` 和有限的样本：
+    /// 字符串最多 256 字节，列表/有序集合取前 10 个成员，集合随机取 10 个成员，哈希取约 10 个字段。
     /// 
     /// # 返回值
     /// 
-    /// - `Some(T)`: 字段存在，返回对应的值
-    /// - `None`: 字段不存在
-    /// 
-    /// # 使用示例
+    /// 与 `keys` 顺序一致的预览列表。单个键读取失败（如读取期间类型被修改）时只在该键的 `error` 中记录，
+    /// 不影响其他键；只有获取类型失败时整个调用返回错误。
+    pub async fn preview_keys(&self, db: u32, keys: Vec<String>) -> Result<Vec<KeyPreview>> {
+        if keys.is_empty() {
+            return Ok(Vec::new());
+        }
+        let types: Vec<String> = self.pipe_each(db, &keys, &["TYPE"], "TYPE").await?;
+        let previews: Vec<KeyPreview> = futures::stream::iter(keys.iter().zip(types))
+            .map(|(key, key_type)| async move {
+                if key_type == "none" {
+                    return KeyPreview { key_type, ttl: -2, element_count: None, sample: Vec::new(), error: None };
+                }
+                match self.sample_key(db, key, &key_type).await {
+                    Ok(preview) => preview,
+                    Err(e) => KeyPreview { key_type, ttl: -2, element_count: None, sample: Vec::new(), error: Some(format!("{:#}", e)) },
+                }
+            })
+            .buffered(PREVIEW_CONCURRENCY)
+            .collect()
+            .await;
+        Ok(previews)
+    }
+
+    /// 按已知类型读取单个键的元素数量、TTL 与样本，三者在同一个管道中发送
+    async fn sample_key(&self, db: u32, key: &str, key_type: &str) -> Result<KeyPreview> {
+        let n = PREVIEW_SAMPLE_SIZE;
+        let mut pipe = redis::pipe();
+        let size_cmd = size_command(key_type);
+        if let Some(size_cmd) = size_cmd {
+            pipe.cmd(size_cmd).arg(key);
+        }
+        pipe.cmd("TTL").arg(key);
+        let sampled = match key_type {
+            "string" => { pipe.cmd("GETRANGE").arg(key).arg(0).arg(PREVIEW_STRING_BYTES - 1); true }
+            "list" => { pipe.cmd("LRANGE").arg(key).arg(0).arg(n - 1); true }
+            "set" => { pipe.cmd("SRANDMEMBER").arg(key).arg(n); true }
+            "zset" => { pipe.cmd("ZRANGE").arg(key).arg(0).arg(n - 1); true }
+            "hash" => { pipe.cmd("HSCAN").arg(key).arg(0).arg("COUNT").arg(n); true }
+            _ => false,
+        };
+        let values: Vec<redis::Value> = self.query_pipe(db, pipe, "PREVIEW").await?;
+
+        let mut values = values.into_iter();
+        let mut int = || match values.next() {
+            Some(redis::Value::Int(v)) => Ok(v),
+            other => Err(anyhow!("Unexpected preview reply: {:?}", other)),
+        };
+        let element_count = match size_cmd {
+            Some(_) => Some(int()?.max(0) as u64),
+            None => None,
+        };
+        let ttl = int()?;
+        let sample = match (sampled, values.next()) {
+            (true, Some(v)) => parse_sample(key_type, v),
+            _ => Vec::new(),
+        };
+        Ok(KeyPreview { key_type: key_type.to_string(), ttl, element_count, sample, error: None })
+    }
+
+    /// 一次性获取键检查面板所需的元数据
     /// 
-    /// ```rust
-    /// if let Some(name) = redis.hget::<String>("user:1", "name").await? {
-    ///     println!("User name: {}", name);
-    /// }
-    /// ```
-    pub async fn hget<T: redis::FromRedisValue + Send + 'static>(&self, db: u32, key: &str, field: &str) -> Result<Option<T>> {
+    /// 通过 Lua 脚本在服务端依次执行 `TYPE`、`PTTL`、`OBJECT ENCODING`、`MEMORY USAGE`
+    /// 以及按类型选择的 `STRLEN`/`LLEN`/`SCARD`/`ZCARD`/`HLEN`/`XLEN`，只需一次往返，
+    /// 且结果不会因为多次调用之间键被修改而互相矛盾。键不存在时类型为 `none`，其余字段为空。
+    pub async fn inspect_key(&self, db: u32, key: &str) -> Result<KeyInspection> {
+        let script = r#"
+            local key = KEYS[1]
+            local t = redis.call("type", key)["ok"]
+            if t == "none" then
+                return {t, -2, false, false, false}
+            end
+            local pttl = redis.call("pttl", key)
+            local enc = redis.pcall("object", "encoding", key)
+            if type(enc) ~= "string" then enc = false end
+            local mem = redis.pcall("memory", "usage", key)
+            if type(mem) ~= "number" then mem = false end
+            local sizes = {string = "strlen", list = "llen", set = "scard", zset = "zcard", hash = "hlen", stream = "xlen"}
+            local size = false
+            if sizes[t] then size = redis.call(sizes[t], key) end
+            return {t, pttl, enc, mem, size}
+        "#;
+        type Reply = (String, i64, Option<String>, Option<u64>, Option<u64>);
+        let to_inspection = |(key_type, ttl_ms, encoding, memory_usage, element_count): Reply| KeyInspection {
+            key_type,
+            ttl_ms,
+            encoding,
+            memory_usage,
+            element_count,
+        };
+
         self.with_retry(|| async {
             match &self.kind {
-                ConnectionKind::Standalone(manager, client) => {
-                    if db == 0 {
-                        let mut conn = manager.clone();
-                        let v: Option<T> = conn.hget(key, field).await.context("HGET")?;
-                        Ok(v)
+                ConnectionKind::Standalone(pool, client) => {
+                    let reply: Reply = if db == 0 {
+                        let mut conn = pool.get();
+                        redis::Script::new(script).key(key).invoke_async(&mut conn).await.context("INSPECT")?
                     } else {
-                        let client = client.clone();
-                        let key = key.to_string();
-                        let field = field.to_string();
-                        tokio::task::spawn_blocking(move || -> Result<Option<T>> {
-                            let mut conn = client.get_connection().context("get dedicated connection")?;
-                            redis::cmd("SELECT").arg(db).query::<()>(&mut conn).context("select db")?;
-                            let v: Option<T> = redis::cmd("HGET").arg(&key).arg(&field).query(&mut conn).context("HGET")?;
-                            Ok(v)
-                        }).await.unwrap()
-                    }
+                        let mut conn = Self::db_connection(client, db).await?;
+                        redis::Script::new(script).key(key).invoke_async(&mut conn).await.context("INSPECT")?
+                    };
+                    Ok(to_inspection(reply))
                 }
                 ConnectionKind::Cluster(client) => {
                     if db != 0 {
                         return Err(anyhow!("Cluster mode does not support multiple databases"));
                     }
                     let key = key.to_string();
-                    let field = field.to_string();
                     let client = client.clone();
-                    
-                    tokio::task::spawn_blocking(move || -> Result<Option<T>> {
+                    let s = redis::Script::new(script);
+
+                    tokio::task::spawn_blocking(move || -> Result<KeyInspection> {
                         let mut conn = client.get_connection().context("get cluster connection")?;
-                        let v: Option<T> = redis::cmd("HGET").arg(&key).arg(&field).query(&mut conn).context("HGET")?;
-                        Ok(v)
+                        let reply: Reply = s.key(&key).invoke(&mut conn).context("INSPECT")?;
+                        Ok(to_inspection(reply))
                     }).await.unwrap()
                 }
             }
         }).await
     }
 
-    /// 批量设置哈希字段
+    /// 分页读取字符串值
+    /// 
+    /// 先使用 STRLEN 获取值的总长度，再使用 GETRANGE 读取 `[offset, offset + length)` 范围内的字节。
+    /// 文本页在字符边界结束，末尾被截断的多字节字符留到下一页，见 `ValuePage::next_offset`。
+    /// 
+    /// # 参数
+    /// 
+    /// - `db`: 数据库编号
+    /// - `key`: 键名
+    /// - `offset`: 起始字节偏移量
+    /// - `length`: 本页读取的最大字节数，为 0 时返回空片段
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回 `ValuePage`，包含值的总长度和本页内容（二进制内容会进行 Base64 编码）。
+    pub async fn get_value_page(&self, db: u32, key: &str, offset: u64, length: u64) -> Result<ValuePage> {
+        let mut cmd = redis::cmd("STRLEN");
+        cmd.arg(key);
+        let total_len: u64 = self.query_cmd(db, cmd, "STRLEN").await?;
+        let Some(end) = page_end(offset, length, total_len) else {
+            return Ok(ValuePage::from_bytes(total_len, offset, Vec::new()));
+        };
+        let mut cmd = redis::cmd("GETRANGE");
+        cmd.arg(key).arg(offset).arg(end);
+        let bytes: Vec<u8> = self.query_cmd(db, cmd, "GETRANGE").await?;
+        Ok(ValuePage::from_bytes(total_len, offset, bytes))
+    }
+
+    /// 查看键的底层调试信息（DEBUG OBJECT 命令）
+    ///
+    /// 将 `Value at:0x... refcount:1 encoding:embstr serializedlength:6 ...` 形式的回复
+    /// 解析为字段映射，常用字段包括 `encoding`、`refcount`、`serializedlength`、`lru_seconds_idle`。
+    ///
+    /// # 注意事项
+    ///
+    /// - Redis 7 起默认禁用 DEBUG 命令，需要在服务端配置 `enable-debug-command`
+    /// - 键不存在时服务端返回错误
+    pub async fn debug_object(&self, db: u32, key: &str) -> Result<HashMap<String, String>> {
+        let mut cmd = redis::cmd("DEBUG");
+        cmd.arg("OBJECT").arg(key);
+        let reply: String = self.query_cmd(db, cmd, "DEBUG OBJECT").await.map_err(|e| {
+            if format!("{:#}", e).contains("enable-debug-command") {
+                e.context("DEBUG command is disabled on the server (enable-debug-command)")
+            } else {
+                e
+            }
+        })?;
+        Ok(parse_debug_object(&reply))
+    }
+
+    /// 查询键的 LFU 访问频率计数（OBJECT FREQ 命令）
+    ///
+    /// 仅当服务端的 `maxmemory-policy` 为 LFU 策略（如 `allkeys-lfu`）时可用，
+    /// 否则服务端返回错误，错误信息中会注明需要 LFU 策略。
+    ///
+    /// # 返回值
+    ///
+    /// 键不存在时返回 `None`。
+    pub async fn object_freq(&self, db: u32, key: &str) -> Result<Option<i64>> {
+        let mut cmd = redis::cmd("OBJECT");
+        cmd.arg("FREQ").arg(key);
+        self.query_cmd(db, cmd, "OBJECT FREQ").await.map_err(|e| {
+            if format!("{:#}", e).contains("LFU") {
+                e.context("OBJECT FREQ requires an LFU maxmemory-policy (e.g. allkeys-lfu)")
+            } else {
+                e
+            }
+        })
+    }
+
+    /// 获取键的数据类型
+    ///
+    /// 使用 TYPE 命令获取键的数据类型。
+    ///
+    /// # 参数
+    ///
+    /// - `key`: 键名
+    ///
+    /// # 返回值
+    ///
+    /// 返回类型字符串，如 "string", "list", "set", "zset", "hash", "stream", "none"。
+    pub async fn key_type(&self, db: u32, key: &str) -> Result<String> {
+        let kind = ReadKind::Type;
+        if let Some(v) = self.cache_lookup(db, key, kind) {
+            return Ok(v);
+        }
+        let epoch = self.cache_epoch();
+        let result = self.with_retry(|| async {
+            match &self.kind {
+                ConnectionKind::Standalone(pool, client) => {
+                    if db == 0 {
+                        let mut conn = pool.get();
+                        let t: String = redis::cmd("TYPE").arg(key).query_async(&mut conn).await.context("TYPE")?;
+                        Ok(t)
+                    } else {
+                        let mut conn = Self::db_connection(client, db).await?;
+                        let t: String = redis::cmd("TYPE").arg(key).query_async(&mut conn).await.context("TYPE")?;
+                        Ok(t)
+                    }
+                }
+                ConnectionKind::Cluster(client) => {
+                    if db != 0 {
+                        return Err(anyhow!("Cluster mode does not support multiple databases"));
+                    }
+                    let key = key.to_string();
+                    let client = client.clone();
+                    
+                    tokio::task::spawn_blocking(move || -> Result<String> {
+                        let mut conn = client.get_connection().context("get cluster connection")?;
+                        let t: String = redis::cmd("TYPE").arg(&key).query(&mut conn).context("TYPE")?;
+                        Ok(t)
+                    }).await.unwrap()
+                }
+            }
+        }).await;
+        self.cache_store(db, key, kind, epoch, &result);
+        result
+    }
+
+    // --- 哈希操作 ---
+
+    /// 设置哈希字段
+    /// 
+    /// 使用 HSET 命令设置哈希表中的字段值。
+    /// 
+    /// # 参数
+    /// 
+    /// - `key`: 哈希表的键名
+    /// - `field`: 字段名
+    /// - `value`: 字段值
+    /// 
+    /// # 返回值
+    /// 
+    /// - `true`: 字段是新增的
+    /// - `false`: 字段已存在并被更新
+    /// 
+    /// # 使用示例
+    /// 
+    /// ```rust
+    /// redis.hset("user:1", "name", "Alice").await?;
+    /// redis.hset("user:1", "age", 25).await?;
+    /// ```
+    pub async fn hset<V: redis::ToRedisArgs + redis::ToSingleRedisArg + Send + Sync + Clone + 'static>(&self, db: u32, key: &str, field: &str, value: V) -> Result<bool> {
+        self.check_writable("HSET")?;
+        let result = self.with_retry(|| async {
+            match &self.kind {
+                ConnectionKind::Standalone(pool, client) => {
+                    if db == 0 {
+                        let mut conn = pool.get();
+                        let n: i64 = conn.hset(key, field, value.clone()).await.context("HSET")?;
+                        Ok(n > 0)
+                    } else {
+                        let mut conn = Self::db_connection(client, db).await?;
+                        let n: i64 = redis::cmd("HSET").arg(key).arg(field).arg(&value).query_async(&mut conn).await.context("HSET")?;
+                        Ok(n > 0)
+                    }
+                }
+                ConnectionKind::Cluster(client) => {
+                    if db != 0 {
+                        return Err(anyhow!("Cluster mode does not support multiple databases"));
+                    }
+                    let key = key.to_string();
+                    let field = field.to_string();
+                    let value = value.clone();
+                    let client = client.clone();
+                    
+                    tokio::task::spawn_blocking(move || -> Result<bool> {
+                        let mut conn = client.get_connection().context("get cluster connection")?;
+                        let n: i64 = redis::cmd("HSET").arg(&key).arg(&field).arg(&value).query(&mut conn).context("HSET")?;
+                        Ok(n > 0)
+                    }).await.unwrap()
+                }
+            }
+        }).await;
+        self.invalidate_cached(db, key);
+        self.guard_write_type(db, key, "HSET", result).await
+    }
+
+    pub async fn hdel(&self, db: u32, key: &str, field: &str) -> Result<bool> {
+        self.check_writable("HDEL")?;
+        let result = self.with_retry(|| async {
+            match &self.kind {
+                ConnectionKind::Standalone(pool, client) => {
+                    if db == 0 {
+                        let mut conn = pool.get();
+                        let n: i64 = redis::Cmd::new().arg("HDEL").arg(key).arg(field).query_async(&mut conn).await.context("HDEL")?;
+                        Ok(n > 0)
+                    } else {
+                        let mut conn = Self::db_connection(client, db).await?;
+                        let n: i64 = redis::cmd("HDEL").arg(key).arg(field).query_async(&mut conn).await.context("HDEL")?;
+                        Ok(n > 0)
+                    }
+                }
+                ConnectionKind::Cluster(client) => {
+                    if db != 0 {
+                        return Err(anyhow!("Cluster mode does not support multiple databases"));
+                    }
+                    let key = key.to_string();
+                    let field = field.to_string();
+                    let client = client.clone();
+                    
+                    tokio::task::spawn_blocking(move || -> Result<bool> {
+                        let mut conn = client.get_connection().context("get cluster connection")?;
+                        let n: i64 = redis::cmd("HDEL").arg(&key).arg(&field).query(&mut conn).context("HDEL")?;
+                        Ok(n > 0)
+                    }).await.unwrap()
+                }
+            }
+        }).await;
+        self.invalidate_cached(db, key);
+        self.guard_write_type(db, key, "HDEL", result).await
+    }
+
+    /// 获取哈希字段值
+    /// 
+    /// 使用 HGET 命令获取哈希表中指定字段的值。
+    /// 
+    /// # 参数
+    /// 
+    /// - `key`: 哈希表的键名
+    /// - `field`: 要获取的字段名
+    /// 
+    /// # 返回值
+    /// 
+    /// - `Some(T)`: 字段存在，返回对应的值
+    /// - `None`: 字段不存在
+    /// 
+    /// # 使用示例
+    /// 
+    /// ```rust
+    /// if let Some(name) = redis.hget::<String>("user:1", "name").await? {
+    ///     println!("User name: {}", name);
+    /// }
+    /// ```
+    pub async fn hget<T: redis::FromRedisValue + Send + 'static>(&self, db: u32, key: &str, field: &str) -> Result<Option<T>> {
+        self.with_retry(|| async {
+            match &self.kind {
+                ConnectionKind::Standalone(pool, client) => {
+                    if db == 0 {
+                        let mut conn = pool.get();
+                        let v: Option<T> = conn.hget(key, field).await.context("HGET")?;
+                        Ok(v)
+                    } else {
+                        let mut conn = Self::db_connection(client, db).await?;
+                        let v: Option<T> = redis::cmd("HGET").arg(key).arg(field).query_async(&mut conn).await.context("HGET")?;
+                        Ok(v)
+                    }
+                }
+                ConnectionKind::Cluster(client) => {
+                    if db != 0 {
+                        return Err(anyhow!("Cluster mode does not support multiple databases"));
+                    }
+                    let key = key.to_string();
+                    let field = field.to_string();
+                    let client = client.clone();
+                    
+                    tokio::task::spawn_blocking(move || -> Result<Option<T>> {
+                        let mut conn = client.get_connection().context("get cluster connection")?;
+                        let v: Option<T> = redis::cmd("HGET").arg(&key).arg(&field).query(&mut conn).context("HGET")?;
+                        Ok(v)
+                    }).await.unwrap()
+                }
+            }
+        }).await
+    }
+
+    /// 批量设置哈希字段
     /// 
     /// 使用 HMSET 命令（新版 Redis 中用 HSET 的多参数形式）批量设置哈希字段。
     /// 等价于历史上的 HMSET 命令。
@@ -2033,53 +4679,18 @@ impl RedisService {
     /// redis.hmset("user:1", &items).await?;
     /// ```
     pub async fn hmset<K: redis::ToRedisArgs + Send + Sync + 'static, V: redis::ToRedisArgs + Send + Sync + 'static>(&self, db: u32, key: &str, items: &[(K, V)]) -> Result<()> {
-        self.with_retry(|| async {
+        self.check_writable("HSET")?;
+        let result = self.with_retry(|| async {
             match &self.kind {
-                ConnectionKind::Standalone(manager, client) => {
+                ConnectionKind::Standalone(pool, client) => {
                     if db == 0 {
-                        let mut conn = manager.clone();
+                        let mut conn = pool.get();
                         conn.hset_multiple::<_, _, _, ()>(key, items).await.context("HSET MULTIPLE")?;
                         Ok(())
                     } else {
-                        let client = client.clone();
-                        let key = key.to_string();
-                        // 序列化 items 以便在 blocking task 中使用
-                        // 这里我们不能直接传递泛型 K, V，因为它们可能不是 Clone 的
-                        // 但是 ToRedisArgs 也不容易序列化。
-                        // 这是一个棘手的问题。
-                        // 既然 K, V 是 ToRedisArgs，我们可以尝试转换为 Vec<(Vec<u8>, Vec<u8>)>?
-                        // Redis crate 的 ToRedisArgs trait 实际上是用来追加参数的。
-                        
-                        // 为了简化，我们假设 K 和 V 实现了 Clone。
-                        // 但是函数签名里没有 Clone。
-                        // 我们可能需要修改函数签名或者在此处做一些转换。
-                        // 考虑到这只是一个示例代码，我们可以要求 K, V 必须是 Clone。
-                        // 或者我们直接在外部调用多次 HSET？不，那样效率低。
-                        
-                        // 让我们尝试把 arguments 转换成 Vec<Vec<u8>> 在这里。
-                        let mut args = Vec::new();
-                        for (k, v) in items {
-                            let mut k_args = Vec::new();
-                            k.write_redis_args(&mut k_args);
-                            args.extend(k_args);
-                            
-                            let mut v_args = Vec::new();
-                            v.write_redis_args(&mut v_args);
-                            args.extend(v_args);
-                        }
-                        
-                        tokio::task::spawn_blocking(move || -> Result<()> {
-                            let mut conn = client.get_connection().context("get dedicated connection")?;
-                            redis::cmd("SELECT").arg(db).query::<()>(&mut conn).context("select db")?;
-                            
-                            let mut cmd = redis::cmd("HSET");
-                            cmd.arg(&key);
-                            for arg in args {
-                                cmd.arg(arg);
-                            }
-                            cmd.query::<()>(&mut conn).context("HSET MULTIPLE")?;
-                            Ok(())
-                        }).await.unwrap()
+                        let mut conn = Self::db_connection(client, db).await?;
+                        conn.hset_multiple::<_, _, _, ()>(key, items).await.context("HSET MULTIPLE")?;
+                        Ok(())
                     }
                 }
                 ConnectionKind::Cluster(client) => {
@@ -2116,7 +4727,9 @@ impl RedisService {
                     }).await.unwrap()
                 }
             }
-        }).await
+        }).await;
+        self.invalidate_cached(db, key);
+        self.guard_write_type(db, key, "HSET", result).await
     }
 
     /// 获取整个哈希表
@@ -2144,23 +4757,23 @@ impl RedisService {
     ///     println!("{}: {}", field, value);
     /// }
     /// ```
-    pub async fn hgetall<T: redis::FromRedisValue + Send + 'static>(&self, db: u32, key: &str) -> Result<HashMap<String, T>> {
-        self.with_retry(|| async {
+    pub async fn hgetall<T: redis::FromRedisValue + Clone + Send + Sync + 'static>(&self, db: u32, key: &str) -> Result<HashMap<String, T>> {
+        let kind = ReadKind::HGetAll(TypeId::of::<T>());
+        if let Some(v) = self.cache_lookup(db, key, kind) {
+            return Ok(v);
+        }
+        let epoch = self.cache_epoch();
+        let result = self.with_retry(|| async {
             match &self.kind {
-                ConnectionKind::Standalone(manager, client) => {
+                ConnectionKind::Standalone(pool, client) => {
                     if db == 0 {
-                        let mut conn = manager.clone();
+                        let mut conn = pool.get();
                         let m: HashMap<String, T> = conn.hgetall(key).await.context("HGETALL")?;
                         Ok(m)
                     } else {
-                        let client = client.clone();
-                        let key = key.to_string();
-                        tokio::task::spawn_blocking(move || -> Result<HashMap<String, T>> {
-                            let mut conn = client.get_connection().context("get dedicated connection")?;
-                            redis::cmd("SELECT").arg(db).query::<()>(&mut conn).context("select db")?;
-                            let m: HashMap<String, T> = redis::cmd("HGETALL").arg(&key).query(&mut conn).context("HGETALL")?;
-                            Ok(m)
-                        }).await.unwrap()
+                        let mut conn = Self::db_connection(client, db).await?;
+                        let m: HashMap<String, T> = redis::cmd("HGETALL").arg(key).query_async(&mut conn).await.context("HGETALL")?;
+                        Ok(m)
                     }
                 }
                 ConnectionKind::Cluster(client) => {
@@ -2177,7 +4790,29 @@ impl RedisService {
                     }).await.unwrap()
                 }
             }
-        }).await
+        }).await;
+        self.cache_store(db, key, kind, epoch, &result);
+        result
+    }
+
+    /// 以原始字节获取哈希表所有字段和值
+    /// 
+    /// 与 `hgetall` 不同，键名与字段名不要求是合法 UTF-8，适合导出等需要保留二进制内容的场景；结果不经过读缓存。
+    pub async fn hgetall_bytes(&self, db: u32, key: &[u8]) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        let mut cmd = redis::cmd("HGETALL");
+        cmd.arg(key);
+        self.query_cmd(db, cmd, "HGETALL").await
+    }
+
+    /// 获取哈希表所有字段，并尝试将每个值解析为 JSON
+    /// 
+    /// 基于 `hgetall`，返回按字段名排序的 `ParsedHashField` 列表。
+    /// 值为 JSON 对象或数组时附带解析结果，其余值保留为原始字符串。
+    pub async fn hgetall_parsed(&self, db: u32, key: &str) -> Result<Vec<ParsedHashField>> {
+        let map: HashMap<String, String> = self.hgetall(db, key).await?;
+        let mut fields: Vec<ParsedHashField> = map.into_iter().map(|(f, v)| ParsedHashField::parse(f, v)).collect();
+        fields.sort_by(|a, b| a.field.cmp(&b.field));
+        Ok(fields)
     }
 
     // --- 列表操作 ---
@@ -2201,23 +4836,18 @@ impl RedisService {
     /// let length = redis.lpush("my_list", "hello").await?; // [hello, world]
     /// ```
     pub async fn lpush<V: redis::ToRedisArgs + Send + Sync + Clone + 'static>(&self, db: u32, key: &str, value: V) -> Result<i64> {
-        self.with_retry(|| async {
+        self.check_writable("LPUSH")?;
+        let result = self.with_retry(|| async {
             match &self.kind {
-                ConnectionKind::Standalone(manager, client) => {
+                ConnectionKind::Standalone(pool, client) => {
                     if db == 0 {
-                        let mut conn = manager.clone();
+                        let mut conn = pool.get();
                         let n: i64 = conn.lpush(key, value.clone()).await.context("LPUSH")?;
                         Ok(n)
                     } else {
-                        let client = client.clone();
-                        let key = key.to_string();
-                        let value = value.clone();
-                        tokio::task::spawn_blocking(move || -> Result<i64> {
-                            let mut conn = client.get_connection().context("get dedicated connection")?;
-                            redis::cmd("SELECT").arg(db).query::<()>(&mut conn).context("select db")?;
-                            let n: i64 = redis::cmd("LPUSH").arg(&key).arg(&value).query(&mut conn).context("LPUSH")?;
-                            Ok(n)
-                        }).await.unwrap()
+                        let mut conn = Self::db_connection(client, db).await?;
+                        let n: i64 = redis::cmd("LPUSH").arg(key).arg(&value).query_async(&mut conn).await.context("LPUSH")?;
+                        Ok(n)
                     }
                 }
                 ConnectionKind::Cluster(client) => {
@@ -2235,7 +4865,9 @@ impl RedisService {
                     }).await.unwrap()
                 }
             }
-        }).await
+        }).await;
+        self.invalidate_cached(db, key);
+        self.guard_write_type(db, key, "LPUSH", result).await
     }
 
     /// 从右侧弹出元素
@@ -2261,22 +4893,18 @@ impl RedisService {
     /// }
     /// ```
     pub async fn rpop<T: redis::FromRedisValue + Send + 'static>(&self, db: u32, key: &str) -> Result<Option<T>> {
-        self.with_retry(|| async {
+        self.check_writable("RPOP")?;
+        let result = self.with_retry(|| async {
             match &self.kind {
-                ConnectionKind::Standalone(manager, client) => {
+                ConnectionKind::Standalone(pool, client) => {
                     if db == 0 {
-                        let mut conn = manager.clone();
+                        let mut conn = pool.get();
                         let v: Option<T> = conn.rpop(key, None).await.context("RPOP")?;
                         Ok(v)
                     } else {
-                        let client = client.clone();
-                        let key = key.to_string();
-                        tokio::task::spawn_blocking(move || -> Result<Option<T>> {
-                            let mut conn = client.get_connection().context("get dedicated connection")?;
-                            redis::cmd("SELECT").arg(db).query::<()>(&mut conn).context("select db")?;
-                            let v: Option<T> = redis::cmd("RPOP").arg(&key).query(&mut conn).context("RPOP")?;
-                            Ok(v)
-                        }).await.unwrap()
+                        let mut conn = Self::db_connection(client, db).await?;
+                        let v: Option<T> = redis::cmd("RPOP").arg(key).query_async(&mut conn).await.context("RPOP")?;
+                        Ok(v)
                     }
                 }
                 ConnectionKind::Cluster(client) => {
@@ -2293,7 +4921,9 @@ impl RedisService {
                     }).await.unwrap()
                 }
             }
-        }).await
+        }).await;
+        self.invalidate_cached(db, key);
+        result
     }
 
     /// 获取列表范围 (LRANGE)
@@ -2310,20 +4940,15 @@ impl RedisService {
     pub async fn lrange<T: redis::FromRedisValue + Send + 'static>(&self, db: u32, key: &str, start: isize, stop: isize) -> Result<Vec<T>> {
         self.with_retry(|| async {
             match &self.kind {
-                ConnectionKind::Standalone(manager, client) => {
+                ConnectionKind::Standalone(pool, client) => {
                     if db == 0 {
-                        let mut conn = manager.clone();
+                        let mut conn = pool.get();
                         let v: Vec<T> = conn.lrange(key, start, stop).await.context("LRANGE")?;
                         Ok(v)
                     } else {
-                        let client = client.clone();
-                        let key = key.to_string();
-                        tokio::task::spawn_blocking(move || -> Result<Vec<T>> {
-                            let mut conn = client.get_connection().context("get dedicated connection")?;
-                            redis::cmd("SELECT").arg(db).query::<()>(&mut conn).context("select db")?;
-                            let v: Vec<T> = redis::cmd("LRANGE").arg(&key).arg(start).arg(stop).query(&mut conn).context("LRANGE")?;
-                            Ok(v)
-                        }).await.unwrap()
+                        let mut conn = Self::db_connection(client, db).await?;
+                        let v: Vec<T> = redis::cmd("LRANGE").arg(key).arg(start).arg(stop).query_async(&mut conn).await.context("LRANGE")?;
+                        Ok(v)
                     }
                 }
                 ConnectionKind::Cluster(client) => {
@@ -2343,48 +4968,258 @@ impl RedisService {
         }).await
     }
 
-    // --- 集合操作 ---
+    /// 以原始字节获取列表索引区间内的元素
+    /// 
+    /// 与 `lrange` 相同，但键名不要求是合法 UTF-8。
+    pub async fn lrange_bytes(&self, db: u32, key: &[u8], start: isize, stop: isize) -> Result<Vec<Vec<u8>>> {
+        let mut cmd = redis::cmd("LRANGE");
+        cmd.arg(key).arg(start).arg(stop);
+        self.query_cmd(db, cmd, "LRANGE").await
+    }
 
-    /// 添加集合成员
+    /// 查找元素在列表中的位置（LPOS 命令）
     /// 
-    /// 使用 SADD 命令向集合中添加一个或多个成员。
-    /// 集合中的成员是唯一的，重复添加不会产生效果。
+    /// # 参数
+    /// 
+    /// - `value`: 要查找的元素
+    /// - `rank`: 从第几个匹配开始返回，负数表示从尾部向头部查找（可选）
+    /// - `count`: 最多返回的匹配数量，`0` 表示返回全部（可选，默认只返回第一个）
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回匹配元素的索引列表（从 0 开始），没有匹配时为空。需要 Redis 6.0.6 及以上版本。
+    pub async fn lpos(&self, db: u32, key: &str, value: &str, rank: Option<i64>, count: Option<usize>) -> Result<Vec<i64>> {
+        let mut cmd = redis::cmd("LPOS");
+        cmd.arg(key).arg(value);
+        if let Some(rank) = rank {
+            cmd.arg("RANK").arg(rank);
+        }
+        match count {
+            Some(count) => {
+                cmd.arg("COUNT").arg(count);
+                self.query_cmd(db, cmd, "LPOS").await
+            }
+            None => {
+                // 不带 COUNT 时返回单个索引或 nil
+                let pos: Option<i64> = self.query_cmd(db, cmd, "LPOS").await?;
+                Ok(pos.into_iter().collect())
+            }
+        }
+    }
+
+    /// 从列表中移除元素（LREM 命令）
     /// 
     /// # 参数
     /// 
-    /// - `key`: 集合的键名
-    /// - `member`: 要添加的成员
+    /// - `count`: 移除数量及方向
+    ///   - `> 0`: 从头部向尾部移除最多 `count` 个
+    ///   - `< 0`: 从尾部向头部移除最多 `|count|` 个
+    ///   - `= 0`: 移除所有匹配的元素
+    /// - `value`: 要移除的元素
     /// 
     /// # 返回值
     /// 
-    /// - `true`: 成员是新增的
-    /// - `false`: 成员已存在
+    /// 返回实际移除的元素数量。
+    pub async fn lrem(&self, db: u32, key: &str, count: i64, value: &str) -> Result<i64> {
+        self.check_writable("LREM")?;
+        let mut cmd = redis::cmd("LREM");
+        cmd.arg(key).arg(count).arg(value);
+        let result = self.query_cmd(db, cmd, "LREM").await;
+        self.invalidate_cached(db, key);
+        result
+    }
+
+    /// 从多个列表中第一个非空的列表弹出元素（LMPOP 命令）
     /// 
-    /// # 使用示例
+    /// # 参数
     /// 
-    /// ```rust
-    /// redis.sadd("my_set", "apple").await?;   // 新增，返回 true
-    /// redis.sadd("my_set", "banana").await?;  // 新增，返回 true
-    /// redis.sadd("my_set", "apple").await?;   // 已存在，返回 false
-    /// ```
+    /// - `keys`: 按顺序检查的列表键名
+    /// - `from_left`: `true` 从头部弹出（LEFT），`false` 从尾部弹出（RIGHT）
+    /// - `count`: 最多弹出的元素数量
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回 `(键名, 弹出的元素)`，所有列表都为空时返回 `None`。需要 Redis 7.0 及以上版本。
+    /// 集群模式下所有键必须位于同一槽位。不自动重试，避免回复丢失后重试再弹出一批元素。
+    pub async fn lmpop(&self, db: u32, keys: &[String], from_left: bool, count: usize) -> Result<Option<(String, Vec<String>)>> {
+        self.check_writable("LMPOP")?;
+        if keys.is_empty() {
+            return Err(invalid_argument("LMPOP requires at least one key"));
+        }
+        self.ensure_same_slot(keys.iter().map(String::as_str))?;
+        let mut cmd = redis::cmd("LMPOP");
+        cmd.arg(keys.len()).arg(keys)
+            .arg(if from_left { "LEFT" } else { "RIGHT" })
+            .arg("COUNT").arg(count.max(1));
+        let result = self.query_cmd_once(db, cmd, "LMPOP").await;
+        for key in keys {
+            self.invalidate_cached(db, key);
+        }
+        result
+    }
+
+    /// 原子地取出整个列表并删除该键
+    /// 
+    /// 通过 Lua 脚本在服务端执行 `LRANGE key 0 -1` 与 `DEL`，两者之间不会有其他客户端写入，
+    /// 适用于"一次性取走队列中所有任务"的消费模式。
+    /// 
+    /// # 返回值
+    /// 
+    /// 按列表顺序（从头到尾）返回所有元素，键不存在时返回空列表。
+    /// 非 UTF-8 的元素以 `U+FFFD` 替换无法解码的字节。
+    /// 
+    /// # 注意事项
+    /// 
+    /// - 脚本只访问一个键，集群模式下按该键路由，不存在跨槽问题
+    /// - 不自动重试：脚本执行成功但回复丢失时，重试会得到空列表，元素将无法找回
+    pub async fn drain_list(&self, db: u32, key: &str) -> Result<Vec<String>> {
+        self.check_writable("DEL")?;
+        self.check_policy("DEL")?;
+        let script = r#"
+            local items = redis.call("lrange", KEYS[1], 0, -1)
+            redis.call("del", KEYS[1])
+            return items
+        "#;
+
+        let result = match &self.kind {
+            ConnectionKind::Standalone(pool, client) => {
+                let items: Vec<Vec<u8>> = if db == 0 {
+                    let mut conn = pool.get();
+                    redis::Script::new(script).key(key).invoke_async(&mut conn).await.context("DRAIN LIST")?
+                } else {
+                    let mut conn = Self::db_connection(client, db).await?;
+                    redis::Script::new(script).key(key).invoke_async(&mut conn).await.context("DRAIN LIST")?
+                };
+                Ok(items)
+            }
+            ConnectionKind::Cluster(client) => {
+                if db != 0 {
+                    return Err(anyhow!("Cluster mode does not support multiple databases"));
+                }
+                let key = key.to_string();
+                let client = client.clone();
+                let s = redis::Script::new(script);
+
+                tokio::task::spawn_blocking(move || -> Result<Vec<Vec<u8>>> {
+                    let mut conn = client.get_connection().context("get cluster connection")?;
+                    let items: Vec<Vec<u8>> = s.key(&key).invoke(&mut conn).context("DRAIN LIST")?;
+                    Ok(items)
+                }).await.unwrap()
+            }
+        };
+        self.invalidate_cached(db, key);
+        // 脚本执行后键已被删除，按字节取回再转换，避免非 UTF-8 元素导致解码失败而丢失数据
+        Ok(result?.iter().map(|item| String::from_utf8_lossy(item).into_owned()).collect())
+    }
+
+    /// 阻塞式从多个列表头部弹出元素（BLPOP 命令）
+    /// 
+    /// 返回 `(键名, 元素)`，在 `timeout_secs` 秒内所有列表都为空时返回 `None`。
+    /// 详见 `blocking_pop`。
+    pub async fn blpop(&self, db: u32, keys: &[String], timeout_secs: f64) -> Result<Option<(String, String)>> {
+        self.check_writable("BLPOP")?;
+        let result = self.blocking_pop(db, "BLPOP", keys, timeout_secs).await;
+        if let Ok(Some((key, _))) = &result {
+            self.invalidate_cached(db, key);
+        }
+        result
+    }
+
+    /// 阻塞式从多个列表尾部弹出元素（BRPOP 命令）
+    /// 
+    /// 返回 `(键名, 元素)`，在 `timeout_secs` 秒内所有列表都为空时返回 `None`。
+    /// 详见 `blocking_pop`。
+    pub async fn brpop(&self, db: u32, keys: &[String], timeout_secs: f64) -> Result<Option<(String, String)>> {
+        self.check_writable("BRPOP")?;
+        let result = self.blocking_pop(db, "BRPOP", keys, timeout_secs).await;
+        if let Ok(Some((key, _))) = &result {
+            self.invalidate_cached(db, key);
+        }
+        result
+    }
+
+    /// 执行 BLPOP / BRPOP
+    /// 
+    /// # 注意事项
+    /// 
+    /// - 阻塞命令会占住所在连接，因此总是使用独立连接执行，不经过共享的连接池，
+    ///   避免阻塞期间其他命令排队等待
+    /// - `timeout_secs` 必须大于 0（服务端的 `0` 表示无限等待，会让界面一直挂起）
+    /// - 客户端额外设置了比服务端超时多 1 秒的截止时间，网络异常时返回超时错误而不是无限等待
+    /// - 不自动重试，避免重试时重复弹出元素
+    async fn blocking_pop(&self, db: u32, name: &'static str, keys: &[String], timeout_secs: f64) -> Result<Option<(String, String)>> {
+        if keys.is_empty() {
+            return Err(invalid_argument(format!("{} requires at least one key", name)));
+        }
+        if timeout_secs.is_nan() || timeout_secs <= 0.0 {
+            return Err(invalid_argument(format!("{} timeout must be greater than 0", name)));
+        }
+        self.ensure_same_slot(keys.iter().map(String::as_str))?;
+        let mut cmd = redis::cmd(name);
+        cmd.arg(keys).arg(timeout_secs);
+        let deadline = Duration::from_secs_f64(timeout_secs) + Duration::from_secs(1);
+
+        let pop = async {
+            match &self.kind {
+                ConnectionKind::Standalone(_, client) => {
+                    let mut conn = Self::db_connection(client, db).await?;
+                    let v: Option<(String, String)> = cmd.query_async(&mut conn).await.context(name)?;
+                    Ok(v)
+                }
+                ConnectionKind::Cluster(client) => {
+                    if db != 0 {
+                        return Err(anyhow!("Cluster mode does not support multiple databases"));
+                    }
+                    let client = client.clone();
+
+                    tokio::task::spawn_blocking(move || -> Result<Option<(String, String)>> {
+                        let mut conn = client.get_connection().context("get cluster connection")?;
+                        let v: Option<(String, String)> = cmd.query(&mut conn).context(name)?;
+                        Ok(v)
+                    }).await.unwrap()
+                }
+            }
+        };
+        tokio::time::timeout(deadline, pop).await.with_context(|| format!("{} timed out", name))?
+    }
+
+    // --- 集合操作 ---
+
+    /// 添加集合成员
+    /// 
+    /// 使用 SADD 命令向集合中添加一个或多个成员。
+    /// 集合中的成员是唯一的，重复添加不会产生效果。
+    /// 
+    /// # 参数
+    /// 
+    /// - `key`: 集合的键名
+    /// - `member`: 要添加的成员
+    /// 
+    /// # 返回值
+    /// 
+    /// - `true`: 成员是新增的
+    /// - `false`: 成员已存在
+    /// 
+    /// # 使用示例
+    /// 
+    /// ```rust
+    /// redis.sadd("my_set", "apple").await?;   // 新增，返回 true
+    /// redis.sadd("my_set", "banana").await?;  // 新增，返回 true
+    /// redis.sadd("my_set", "apple").await?;   // 已存在，返回 false
+    /// ```
     pub async fn sadd<V: redis::ToRedisArgs + Send + Sync + Clone + 'static>(&self, db: u32, key: &str, member: V) -> Result<bool> {
-        self.with_retry(|| async {
+        self.check_writable("SADD")?;
+        let result = self.with_retry(|| async {
             match &self.kind {
-                ConnectionKind::Standalone(manager, client) => {
+                ConnectionKind::Standalone(pool, client) => {
                     if db == 0 {
-                        let mut conn = manager.clone();
+                        let mut conn = pool.get();
                         let n: i64 = conn.sadd(key, member.clone()).await.context("SADD")?;
                         Ok(n > 0)
                     } else {
-                        let client = client.clone();
-                        let key = key.to_string();
-                        let member = member.clone();
-                        tokio::task::spawn_blocking(move || -> Result<bool> {
-                            let mut conn = client.get_connection().context("get dedicated connection")?;
-                            redis::cmd("SELECT").arg(db).query::<()>(&mut conn).context("select db")?;
-                            let n: i64 = redis::cmd("SADD").arg(&key).arg(&member).query(&mut conn).context("SADD")?;
-                            Ok(n > 0)
-                        }).await.unwrap()
+                        let mut conn = Self::db_connection(client, db).await?;
+                        let n: i64 = redis::cmd("SADD").arg(key).arg(&member).query_async(&mut conn).await.context("SADD")?;
+                        Ok(n > 0)
                     }
                 }
                 ConnectionKind::Cluster(client) => {
@@ -2402,7 +5237,9 @@ impl RedisService {
                     }).await.unwrap()
                 }
             }
-        }).await
+        }).await;
+        self.invalidate_cached(db, key);
+        self.guard_write_type(db, key, "SADD", result).await
     }
 
     /// 获取所有集合成员
@@ -2433,20 +5270,15 @@ impl RedisService {
     pub async fn smembers<T: redis::FromRedisValue + Send + 'static>(&self, db: u32, key: &str) -> Result<Vec<T>> {
         self.with_retry(|| async {
             match &self.kind {
-                ConnectionKind::Standalone(manager, client) => {
+                ConnectionKind::Standalone(pool, client) => {
                     if db == 0 {
-                        let mut conn = manager.clone();
+                        let mut conn = pool.get();
                         let v: Vec<T> = conn.smembers(key).await.context("SMEMBERS")?;
                         Ok(v)
                     } else {
-                        let client = client.clone();
-                        let key = key.to_string();
-                        tokio::task::spawn_blocking(move || -> Result<Vec<T>> {
-                            let mut conn = client.get_connection().context("get dedicated connection")?;
-                            redis::cmd("SELECT").arg(db).query::<()>(&mut conn).context("select db")?;
-                            let v: Vec<T> = redis::cmd("SMEMBERS").arg(&key).query(&mut conn).context("SMEMBERS")?;
-                            Ok(v)
-                        }).await.unwrap()
+                        let mut conn = Self::db_connection(client, db).await?;
+                        let v: Vec<T> = redis::cmd("SMEMBERS").arg(key).query_async(&mut conn).await.context("SMEMBERS")?;
+                        Ok(v)
                     }
                 }
                 ConnectionKind::Cluster(client) => {
@@ -2466,24 +5298,28 @@ impl RedisService {
         }).await
     }
 
+    /// 以原始字节获取集合的所有成员
+    /// 
+    /// 与 `smembers` 相同，但键名与成员不要求是合法 UTF-8。
+    pub async fn smembers_bytes(&self, db: u32, key: &[u8]) -> Result<Vec<Vec<u8>>> {
+        let mut cmd = redis::cmd("SMEMBERS");
+        cmd.arg(key);
+        self.query_cmd(db, cmd, "SMEMBERS").await
+    }
+
     pub async fn srem<V: redis::ToRedisArgs + Send + Sync + Clone + 'static>(&self, db: u32, key: &str, member: V) -> Result<bool> {
-        self.with_retry(|| async {
+        self.check_writable("SREM")?;
+        let result = self.with_retry(|| async {
             match &self.kind {
-                ConnectionKind::Standalone(manager, client) => {
+                ConnectionKind::Standalone(pool, client) => {
                     if db == 0 {
-                        let mut conn = manager.clone();
+                        let mut conn = pool.get();
                         let n: i64 = redis::Cmd::new().arg("SREM").arg(key).arg(member.clone()).query_async(&mut conn).await.context("SREM")?;
                         Ok(n > 0)
                     } else {
-                        let client = client.clone();
-                        let key = key.to_string();
-                        let member = member.clone();
-                        tokio::task::spawn_blocking(move || -> Result<bool> {
-                            let mut conn = client.get_connection().context("get dedicated connection")?;
-                            redis::cmd("SELECT").arg(db).query::<()>(&mut conn).context("select db")?;
-                            let n: i64 = redis::cmd("SREM").arg(&key).arg(&member).query(&mut conn).context("SREM")?;
-                            Ok(n > 0)
-                        }).await.unwrap()
+                        let mut conn = Self::db_connection(client, db).await?;
+                        let n: i64 = redis::cmd("SREM").arg(key).arg(&member).query_async(&mut conn).await.context("SREM")?;
+                        Ok(n > 0)
                     }
                 }
                 ConnectionKind::Cluster(client) => {
@@ -2501,29 +5337,91 @@ impl RedisService {
                     }).await.unwrap()
                 }
             }
-        }).await
+        }).await;
+        self.invalidate_cached(db, key);
+        self.guard_write_type(db, key, "SREM", result).await
+    }
+
+    /// 将多个集合的交集保存到目标键（SINTERSTORE 命令）
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回结果集合的元素数量。集群模式下目标键与所有源键必须位于同一槽位。
+    pub async fn sinterstore(&self, db: u32, dest: &str, keys: &[String]) -> Result<i64> {
+        self.check_writable("SINTERSTORE")?;
+        self.set_store("SINTERSTORE", db, dest, keys).await
+    }
+
+    /// 将多个集合的并集保存到目标键（SUNIONSTORE 命令）
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回结果集合的元素数量。集群模式下目标键与所有源键必须位于同一槽位。
+    pub async fn sunionstore(&self, db: u32, dest: &str, keys: &[String]) -> Result<i64> {
+        self.check_writable("SUNIONSTORE")?;
+        self.set_store("SUNIONSTORE", db, dest, keys).await
+    }
+
+    /// 将第一个集合与其余集合的差集保存到目标键（SDIFFSTORE 命令）
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回结果集合的元素数量。集群模式下目标键与所有源键必须位于同一槽位。
+    pub async fn sdiffstore(&self, db: u32, dest: &str, keys: &[String]) -> Result<i64> {
+        self.check_writable("SDIFFSTORE")?;
+        self.set_store("SDIFFSTORE", db, dest, keys).await
+    }
+
+    /// 集合 *STORE 命令的公共实现
+    async fn set_store(&self, command: &'static str, db: u32, dest: &str, keys: &[String]) -> Result<i64> {
+        if keys.is_empty() {
+            return Err(invalid_argument(format!("{} requires at least one source key", command)));
+        }
+        self.ensure_same_slot(std::iter::once(dest).chain(keys.iter().map(String::as_str)))?;
+        let mut cmd = redis::cmd(command);
+        cmd.arg(dest).arg(keys);
+        let result = self.query_cmd(db, cmd, command).await;
+        self.invalidate_cached(db, dest);
+        result
+    }
+
+    /// 计算多个集合交集的元素数量（SINTERCARD 命令）
+    /// 
+    /// 只返回数量，不传输交集成员。`limit` 为 `Some(n)` 时数量达到 `n` 后提前停止计算。
+    /// 需要 Redis 7.0 及以上版本，集群模式下所有键必须位于同一槽位。
+    pub async fn sintercard(&self, db: u32, keys: &[String], limit: Option<usize>) -> Result<i64> {
+        self.intercard("SINTERCARD", db, keys, limit).await
+    }
+
+    /// SINTERCARD/ZINTERCARD 的公共实现
+    async fn intercard(&self, command: &'static str, db: u32, keys: &[String], limit: Option<usize>) -> Result<i64> {
+        if keys.is_empty() {
+            return Err(invalid_argument(format!("{} requires at least one key", command)));
+        }
+        self.ensure_same_slot(keys.iter().map(String::as_str))?;
+        let mut cmd = redis::cmd(command);
+        cmd.arg(keys.len()).arg(keys);
+        if let Some(limit) = limit {
+            cmd.arg("LIMIT").arg(limit);
+        }
+        self.query_cmd(db, cmd, command).await
     }
 
     // --- 有序集合操作 ---
 
     pub async fn zadd<V: redis::ToRedisArgs + Send + Sync + Clone + 'static>(&self, db: u32, key: &str, member: V, score: f64) -> Result<i64> {
-        self.with_retry(|| async {
+        self.check_writable("ZADD")?;
+        let result = self.with_retry(|| async {
             match &self.kind {
-                ConnectionKind::Standalone(manager, client) => {
+                ConnectionKind::Standalone(pool, client) => {
                     if db == 0 {
-                        let mut conn = manager.clone();
+                        let mut conn = pool.get();
                         let n: i64 = redis::Cmd::new().arg("ZADD").arg(key).arg(score).arg(member.clone()).query_async(&mut conn).await.context("ZADD")?;
                         Ok(n)
                     } else {
-                        let client = client.clone();
-                        let key = key.to_string();
-                        let member = member.clone();
-                        tokio::task::spawn_blocking(move || -> Result<i64> {
-                            let mut conn = client.get_connection().context("get dedicated connection")?;
-                            redis::cmd("SELECT").arg(db).query::<()>(&mut conn).context("select db")?;
-                            let n: i64 = redis::cmd("ZADD").arg(&key).arg(score).arg(&member).query(&mut conn).context("ZADD")?;
-                            Ok(n)
-                        }).await.unwrap()
+                        let mut conn = Self::db_connection(client, db).await?;
+                        let n: i64 = redis::cmd("ZADD").arg(key).arg(score).arg(&member).query_async(&mut conn).await.context("ZADD")?;
+                        Ok(n)
                     }
                 }
                 ConnectionKind::Cluster(client) => {
@@ -2541,27 +5439,24 @@ impl RedisService {
                     }).await.unwrap()
                 }
             }
-        }).await
+        }).await;
+        self.invalidate_cached(db, key);
+        self.guard_write_type(db, key, "ZADD", result).await
     }
 
     pub async fn zrem<V: redis::ToRedisArgs + Send + Sync + Clone + 'static>(&self, db: u32, key: &str, member: V) -> Result<bool> {
-        self.with_retry(|| async {
+        self.check_writable("ZREM")?;
+        let result = self.with_retry(|| async {
             match &self.kind {
-                ConnectionKind::Standalone(manager, client) => {
+                ConnectionKind::Standalone(pool, client) => {
                     if db == 0 {
-                        let mut conn = manager.clone();
+                        let mut conn = pool.get();
                         let n: i64 = redis::Cmd::new().arg("ZREM").arg(key).arg(member.clone()).query_async(&mut conn).await.context("ZREM")?;
                         Ok(n > 0)
                     } else {
-                        let client = client.clone();
-                        let key = key.to_string();
-                        let member = member.clone();
-                        tokio::task::spawn_blocking(move || -> Result<bool> {
-                            let mut conn = client.get_connection().context("get dedicated connection")?;
-                            redis::cmd("SELECT").arg(db).query::<()>(&mut conn).context("select db")?;
-                            let n: i64 = redis::cmd("ZREM").arg(&key).arg(&member).query(&mut conn).context("ZREM")?;
-                            Ok(n > 0)
-                        }).await.unwrap()
+                        let mut conn = Self::db_connection(client, db).await?;
+                        let n: i64 = redis::cmd("ZREM").arg(key).arg(&member).query_async(&mut conn).await.context("ZREM")?;
+                        Ok(n > 0)
                     }
                 }
                 ConnectionKind::Cluster(client) => {
@@ -2579,26 +5474,23 @@ impl RedisService {
                     }).await.unwrap()
                 }
             }
-        }).await
+        }).await;
+        self.invalidate_cached(db, key);
+        self.guard_write_type(db, key, "ZREM", result).await
     }
 
     pub async fn zrange_withscores(&self, db: u32, key: &str, start: isize, stop: isize) -> Result<Vec<(String, f64)>> {
         self.with_retry(|| async {
             match &self.kind {
-                ConnectionKind::Standalone(manager, client) => {
+                ConnectionKind::Standalone(pool, client) => {
                     if db == 0 {
-                        let mut conn = manager.clone();
+                        let mut conn = pool.get();
                         let v: Vec<(String, f64)> = redis::cmd("ZRANGE").arg(key).arg(start).arg(stop).arg("WITHSCORES").query_async(&mut conn).await.context("ZRANGE WITHSCORES")?;
                         Ok(v)
                     } else {
-                        let client = client.clone();
-                        let key = key.to_string();
-                        tokio::task::spawn_blocking(move || -> Result<Vec<(String, f64)>> {
-                            let mut conn = client.get_connection().context("get dedicated connection")?;
-                            redis::cmd("SELECT").arg(db).query::<()>(&mut conn).context("select db")?;
-                            let v: Vec<(String, f64)> = redis::cmd("ZRANGE").arg(&key).arg(start).arg(stop).arg("WITHSCORES").query(&mut conn).context("ZRANGE WITHSCORES")?;
-                            Ok(v)
-                        }).await.unwrap()
+                        let mut conn = Self::db_connection(client, db).await?;
+                        let v: Vec<(String, f64)> = redis::cmd("ZRANGE").arg(key).arg(start).arg(stop).arg("WITHSCORES").query_async(&mut conn).await.context("ZRANGE WITHSCORES")?;
+                        Ok(v)
                     }
                 }
                 ConnectionKind::Cluster(client) => {
@@ -2618,752 +5510,5179 @@ impl RedisService {
         }).await
     }
 
-    // --- RedisJSON 操作 ---
-
-    pub async fn json_set<V: serde::Serialize + Send + Sync + Clone + 'static>(&self, db: u32, key: &str, path: &str, value: &V) -> Result<()> {
-        let json_str = serde_json::to_string(value).context("serialize json value")?;
-        self.with_retry(|| async {
-            match &self.kind {
-                ConnectionKind::Standalone(manager, client) => {
-                    if db == 0 {
-                        let mut conn = manager.clone();
-                        redis::Cmd::new().arg("JSON.SET").arg(key).arg(path).arg(json_str.clone()).query_async::<()>(&mut conn).await.context("JSON.SET")?;
-                        Ok(())
-                    } else {
-                        let client = client.clone();
-                        let key = key.to_string();
-                        let path = path.to_string();
-                        let json_str = json_str.clone();
-                        tokio::task::spawn_blocking(move || -> Result<()> {
-                            let mut conn = client.get_connection().context("get dedicated connection")?;
-                            redis::cmd("SELECT").arg(db).query::<()>(&mut conn).context("select db")?;
-                            redis::cmd("JSON.SET").arg(&key).arg(&path).arg(json_str).query::<()>(&mut conn).context("JSON.SET")?;
-                            Ok(())
-                        }).await.unwrap()
-                    }
-                }
-                ConnectionKind::Cluster(client) => {
-                    if db != 0 {
-                        return Err(anyhow!("Cluster mode does not support multiple databases"));
-                    }
-                    let key = key.to_string();
-                    let path = path.to_string();
-                    let json_str = json_str.clone();
-                    let client = client.clone();
-                    
-                    tokio::task::spawn_blocking(move || -> Result<()> {
-                        let mut conn = client.get_connection().context("get cluster connection")?;
-                        redis::cmd("JSON.SET").arg(&key).arg(&path).arg(json_str).query::<()>(&mut conn).context("JSON.SET")?;
-                        Ok(())
-                    }).await.unwrap()
-                }
-            }
-        }).await
-    }
-
-    pub async fn json_get(&self, db: u32, key: &str, path: &str) -> Result<Option<serde_json::Value>> {
-        self.with_retry(|| async {
-            match &self.kind {
-                ConnectionKind::Standalone(manager, client) => {
-                    if db == 0 {
-                        let mut conn = manager.clone();
-                        let s: Option<String> = redis::Cmd::new().arg("JSON.GET").arg(key).arg(path).query_async(&mut conn).await.context("JSON.GET")?;
-                        if let Some(js) = s { Ok(Some(serde_json::from_str(&js).context("parse json")?)) } else { Ok(None) }
-                    } else {
-                        let client = client.clone();
-                        let key = key.to_string();
-                        let path = path.to_string();
-                        tokio::task::spawn_blocking(move || -> Result<Option<serde_json::Value>> {
-                            let mut conn = client.get_connection().context("get dedicated connection")?;
-                            redis::cmd("SELECT").arg(db).query::<()>(&mut conn).context("select db")?;
-                            let s: Option<String> = redis::cmd("JSON.GET").arg(&key).arg(&path).query(&mut conn).context("JSON.GET")?;
-                            if let Some(js) = s { Ok(Some(serde_json::from_str(&js).context("parse json")?)) } else { Ok(None) }
-                        }).await.unwrap()
-                    }
-                }
-                ConnectionKind::Cluster(client) => {
-                    if db != 0 {
-                        return Err(anyhow!("Cluster mode does not support multiple databases"));
-                    }
-                    let key = key.to_string();
-                    let path = path.to_string();
-                    let client = client.clone();
-                    
-                    tokio::task::spawn_blocking(move || -> Result<Option<serde_json::Value>> {
-                        let mut conn = client.get_connection().context("get cluster connection")?;
-                        let s: Option<String> = redis::cmd("JSON.GET").arg(&key).arg(&path).query(&mut conn).context("JSON.GET")?;
-                        if let Some(js) = s { Ok(Some(serde_json::from_str(&js).context("parse json")?)) } else { Ok(None) }
-                    }).await.unwrap()
-                }
-            }
-        }).await
+    /// 以原始字节获取有序集合索引区间内的成员及分数
+    /// 
+    /// 与 `zrange_withscores` 相同，但键名与成员不要求是合法 UTF-8。
+    pub async fn zrange_withscores_bytes(&self, db: u32, key: &[u8], start: isize, stop: isize) -> Result<Vec<(Vec<u8>, f64)>> {
+        let mut cmd = redis::cmd("ZRANGE");
+        cmd.arg(key).arg(start).arg(stop).arg("WITHSCORES");
+        self.query_cmd(db, cmd, "ZRANGE WITHSCORES").await
     }
 
-    // --- 集群管理命令 ---
-
-    /// 获取集群节点信息
+    /// 将有序集合的索引区间保存到目标键（ZRANGESTORE 命令）
     /// 
-    /// 使用 CLUSTER NODES 命令获取集群中所有节点的信息。
+    /// # 参数
+    /// 
+    /// - `dest`: 目标键
+    /// - `src`: 源有序集合
+    /// - `start`/`stop`: 索引区间，支持负数索引
     /// 
     /// # 返回值
     /// 
-    /// 返回包含节点信息的字符串，每行代表一个节点。
+    /// 返回目标有序集合的元素数量。需要 Redis 6.2 及以上版本，
+    /// 集群模式下 `dest` 与 `src` 必须位于同一槽位。
+    pub async fn zrangestore(&self, db: u32, dest: &str, src: &str, start: isize, stop: isize) -> Result<i64> {
+        self.check_writable("ZRANGESTORE")?;
+        self.ensure_same_slot([dest, src])?;
+        let mut cmd = redis::cmd("ZRANGESTORE");
+        cmd.arg(dest).arg(src).arg(start).arg(stop);
+        let result = self.query_cmd(db, cmd, "ZRANGESTORE").await;
+        self.invalidate_cached(db, dest);
+        result
+    }
+
+    /// 从多个有序集合中第一个非空的集合弹出成员（ZMPOP 命令）
     /// 
-    /// # 信息格式
+    /// # 参数
     /// 
-    /// 每行包含：节点ID、地址、标志、主节点ID、最后 ping 时间、最后 pong 时间、
-    /// 配置纪元、连接状态、节点端口、总线端口、槽位范围。
+    /// - `keys`: 按顺序检查的有序集合键名
+    /// - `min`: `true` 弹出分数最小的成员（MIN），`false` 弹出分数最大的成员（MAX）
+    /// - `count`: 最多弹出的成员数量
     /// 
-    /// # 使用示例
+    /// # 返回值
     /// 
-    /// ```rust
-    /// let nodes = redis.cluster_nodes().await?;
-    /// println!("Cluster nodes:\n{}", nodes);
-    /// ```
-    pub async fn cluster_nodes(&self) -> Result<String> {
-        self.with_retry(|| async {
-            match &self.kind {
-                ConnectionKind::Standalone(manager, _) => {
-                    let mut conn = manager.clone();
-                    let out: String = Cmd::new().arg("CLUSTER").arg("NODES").query_async(&mut conn).await.context("CLUSTER NODES")?;
-                    Ok(out)
-                }
-                ConnectionKind::Cluster(client) => {
-                    let client = client.clone();
-                    
-                    tokio::task::spawn_blocking(move || -> Result<String> {
-                        let mut conn = client.get_connection().context("get cluster connection")?;
-                        let out: String = Cmd::new().arg("CLUSTER").arg("NODES").query(&mut conn).context("CLUSTER NODES")?;
-                        Ok(out)
-                    }).await.unwrap()
-                }
-            }
-        }).await
+    /// 返回 `(键名, [(成员, 分数)])`，所有集合都为空时返回 `None`。需要 Redis 7.0 及以上版本。
+    /// 不自动重试，避免回复丢失后重试再弹出一批成员。
+    pub async fn zmpop(&self, db: u32, keys: &[String], min: bool, count: usize) -> Result<Option<(String, Vec<(String, f64)>)>> {
+        self.check_writable("ZMPOP")?;
+        if keys.is_empty() {
+            return Err(invalid_argument("ZMPOP requires at least one key"));
+        }
+        self.ensure_same_slot(keys.iter().map(String::as_str))?;
+        let mut cmd = redis::cmd("ZMPOP");
+        cmd.arg(keys.len()).arg(keys)
+            .arg(if min { "MIN" } else { "MAX" })
+            .arg("COUNT").arg(count.max(1));
+        let result = self.query_cmd_once(db, cmd, "ZMPOP").await;
+        for key in keys {
+            self.invalidate_cached(db, key);
+        }
+        result
     }
 
-    /// 获取集群槽位分布
+    /// 计算多个有序集合交集的元素数量（ZINTERCARD 命令）
     /// 
-    /// 使用 CLUSTER SLOTS 命令获取集群中槽位的分布情况。
+    /// 只返回数量，不传输交集成员。`limit` 为 `Some(n)` 时数量达到 `n` 后提前停止计算。
+    /// 需要 Redis 7.0 及以上版本，集群模式下所有键必须位于同一槽位。
+    pub async fn zintercard(&self, db: u32, keys: &[String], limit: Option<usize>) -> Result<i64> {
+        self.intercard("ZINTERCARD", db, keys, limit).await
+    }
+
+    // --- HyperLogLog 操作 ---
+
+    /// 添加元素到 HyperLogLog（PFADD 命令）
     /// 
     /// # 返回值
     /// 
-    /// 返回槽位分布信息的原始 Redis 值。
+    /// 内部寄存器发生变化（基数估计可能改变）时返回 `true`。
+    pub async fn pfadd(&self, db: u32, key: &str, elements: Vec<String>) -> Result<bool> {
+        self.check_writable("PFADD")?;
+        let mut cmd = redis::cmd("PFADD");
+        cmd.arg(key).arg(&elements);
+        let n: Result<i64> = self.query_cmd(db, cmd, "PFADD").await;
+        self.invalidate_cached(db, key);
+        Ok(n? > 0)
+    }
+
+    /// 获取 HyperLogLog 的基数估计（PFCOUNT 命令）
     /// 
-    /// # 信息格式
+    /// 传入多个键时返回它们并集的基数估计，标准误差约 0.81%。
+    /// 集群模式下多个键必须位于同一槽位（可使用 `{hash_tag}`）。
+    pub async fn pfcount(&self, db: u32, keys: &[String]) -> Result<i64> {
+        if keys.is_empty() {
+            return Err(invalid_argument("PFCOUNT requires at least one key"));
+        }
+        self.ensure_same_slot(keys.iter().map(String::as_str))?;
+        let mut cmd = redis::cmd("PFCOUNT");
+        cmd.arg(keys);
+        self.query_cmd(db, cmd, "PFCOUNT").await
+    }
+
+    /// 合并多个 HyperLogLog 到目标键（PFMERGE 命令）
+    /// 
+    /// 集群模式下目标键与所有源键必须位于同一槽位。
+    pub async fn pfmerge(&self, db: u32, dest: &str, sources: &[String]) -> Result<()> {
+        self.check_writable("PFMERGE")?;
+        self.ensure_same_slot(std::iter::once(dest).chain(sources.iter().map(String::as_str)))?;
+        let mut cmd = redis::cmd("PFMERGE");
+        cmd.arg(dest).arg(sources);
+        let result = self.query_cmd(db, cmd, "PFMERGE").await;
+        self.invalidate_cached(db, dest);
+        result
+    }
+
+    // --- 位图操作 ---
+
+    /// 设置位图中指定偏移的位（SETBIT 命令）
     /// 
-    /// 返回一个数组，每个元素包含：
-    /// - 起始槽位
-    /// - 结束槽位  
-    /// - 主节点信息
-    /// - 副本节点信息（可选）
+    /// # 返回值
     /// 
-    /// # 使用示例
-    /// 
-    /// ```rust
-    /// let slots = redis.cluster_slots().await?;
-    /// // 解析槽位分布信息
-    /// ```
-    pub async fn cluster_slots(&self) -> Result<redis::Value> {
-        self.with_retry(|| async {
-            match &self.kind {
-                ConnectionKind::Standalone(manager, _) => {
-                    let mut conn = manager.clone();
-                    let out: redis::Value = Cmd::new().arg("CLUSTER").arg("SLOTS").query_async(&mut conn).await.context("CLUSTER SLOTS")?;
-                    Ok(out)
-                }
-                ConnectionKind::Cluster(client) => {
-                    let client = client.clone();
-                    
-                    tokio::task::spawn_blocking(move || -> Result<redis::Value> {
-                        let mut conn = client.get_connection().context("get cluster connection")?;
-                        let out: redis::Value = Cmd::new().arg("CLUSTER").arg("SLOTS").query(&mut conn).context("CLUSTER SLOTS")?;
-                        Ok(out)
-                    }).await.unwrap()
-                }
-            }
-        }).await
+    /// 返回该位原来的值。
+    pub async fn setbit(&self, db: u32, key: &str, offset: u64, value: bool) -> Result<bool> {
+        self.check_writable("SETBIT")?;
+        let mut cmd = redis::cmd("SETBIT");
+        cmd.arg(key).arg(offset).arg(value as u8);
+        let prev: u8 = self.query_cmd(db, cmd, "SETBIT").await?;
+        self.invalidate_cached(db, key);
+        Ok(prev == 1)
     }
 
-    /// 邀请节点加入集群
-    /// 
-    /// 使用 CLUSTER MEET 命令邀请指定节点加入当前集群。
+    /// 获取位图中指定偏移的位（GETBIT 命令）
+    /// 
+    /// 超出字符串长度或键不存在时返回 `false`。
+    pub async fn getbit(&self, db: u32, key: &str, offset: u64) -> Result<bool> {
+        let mut cmd = redis::cmd("GETBIT");
+        cmd.arg(key).arg(offset);
+        let bit: u8 = self.query_cmd(db, cmd, "GETBIT").await?;
+        Ok(bit == 1)
+    }
+
+    /// 统计被置为 1 的位数（BITCOUNT 命令）
     /// 
     /// # 参数
     /// 
-    /// - `ip`: 新节点的 IP 地址
-    /// - `port`: 新节点的客户端端口
+    /// - `range`: 可选的字节范围 `(start, end)`，支持负数索引
+    pub async fn bitcount(&self, db: u32, key: &str, range: Option<(i64, i64)>) -> Result<i64> {
+        let mut cmd = redis::cmd("BITCOUNT");
+        cmd.arg(key);
+        if let Some((start, end)) = range {
+            cmd.arg(start).arg(end);
+        }
+        self.query_cmd(db, cmd, "BITCOUNT").await
+    }
+
+    /// 对多个位图执行位运算并保存到目标键（BITOP 命令）
     /// 
-    /// # 使用示例
+    /// # 参数
     /// 
-    /// ```rust
-    /// redis.cluster_meet("192.168.1.100", 6379).await?;
-    /// ```
+    /// - `op`: 运算类型，支持 `AND`、`OR`、`XOR`、`NOT`（不区分大小写）
+    /// - `dest`: 结果保存的目标键
+    /// - `keys`: 源键列表，`NOT` 只接受一个源键
     /// 
-    /// # 注意事项
+    /// # 返回值
     /// 
-    /// - 新节点必须能够访问当前节点
-    /// - 端口应该是客户端端口，不是集群总线端口
-    /// - 需要适当的权限配置
-    pub async fn cluster_meet(&self, ip: &str, port: u16) -> Result<()> {
-        self.with_retry(|| async {
-            match &self.kind {
-                ConnectionKind::Standalone(manager, _) => {
-                    let mut conn = manager.clone();
-                    Cmd::new().arg("CLUSTER").arg("MEET").arg(ip).arg(port).query_async::<()>(&mut conn).await.context("CLUSTER MEET")?;
-                    Ok(())
+    /// 返回目标键中字符串的长度（字节）。集群模式下所有键必须位于同一槽位。
+    pub async fn bitop(&self, db: u32, op: &str, dest: &str, keys: &[String]) -> Result<i64> {
+        self.check_writable("BITOP")?;
+        let op = op.to_ascii_uppercase();
+        match op.as_str() {
+            "AND" | "OR" | "XOR" => {
+                if keys.is_empty() {
+                    return Err(invalid_argument(format!("BITOP {} requires at least one source key", op)));
                 }
-                ConnectionKind::Cluster(client) => {
-                    let ip = ip.to_string();
-                    let port = port;
-                    let client = client.clone();
-                    
-                    tokio::task::spawn_blocking(move || -> Result<()> {
-                        let mut conn = client.get_connection().context("get cluster connection")?;
-                        Cmd::new().arg("CLUSTER").arg("MEET").arg(&ip).arg(port).query::<()>(&mut conn).context("CLUSTER MEET")?;
-                        Ok(())
-                    }).await.unwrap()
+            }
+            "NOT" => {
+                if keys.len() != 1 {
+                    return Err(invalid_argument("BITOP NOT requires exactly one source key"));
                 }
             }
-        }).await
+            _ => return Err(invalid_argument(format!("Invalid BITOP operation: {}", op))),
+        }
+        self.ensure_same_slot(std::iter::once(dest).chain(keys.iter().map(String::as_str)))?;
+        let mut cmd = redis::cmd("BITOP");
+        cmd.arg(&op).arg(dest).arg(keys);
+        let result = self.query_cmd(db, cmd, "BITOP").await;
+        self.invalidate_cached(db, dest);
+        result
     }
 
-    /// 从集群中移除节点
-    /// 
-    /// 使用 CLUSTER FORGET 命令从集群中移除指定节点。
+    // --- 地理位置操作 ---
+
+    /// 添加地理位置成员（GEOADD 命令）
     /// 
     /// # 参数
     /// 
-    /// - `node_id`: 要移除的节点 ID
+    /// - `db`: 数据库索引（仅单机模式有效）
+    /// - `key`: 地理位置集合的键名
+    /// - `members`: `(经度, 纬度, 成员名)` 列表
     /// 
-    /// # 使用示例
+    /// # 返回值
     /// 
-    /// ```rust
-    /// redis.cluster_forget("3c3a0c74aae0b2717c740bb3f2c8a6a71d0d8c00").await?;
-    /// ```
+    /// 返回新添加的成员数量（已存在成员的坐标更新不计入）。
+    pub async fn geoadd(&self, db: u32, key: &str, members: Vec<(f64, f64, String)>) -> Result<i64> {
+        self.check_writable("GEOADD")?;
+        if members.is_empty() {
+            return Ok(0);
+        }
+        let mut cmd = redis::cmd("GEOADD");
+        cmd.arg(key);
+        for (lon, lat, member) in &members {
+            cmd.arg(*lon).arg(*lat).arg(member);
+        }
+        let result = self.query_cmd(db, cmd, "GEOADD").await;
+        self.invalidate_cached(db, key);
+        result
+    }
+
+    /// 获取成员坐标（GEOPOS 命令）
     /// 
-    /// # 注意事项
+    /// # 返回值
     /// 
-    /// - 节点 ID 是 40 字符的十六进制字符串
-    /// - 移除节点前应该确保没有数据分配给该节点
-    /// - 需要在集群的每个节点上执行此命令
-    pub async fn cluster_forget(&self, node_id: &str) -> Result<()> {
-        self.with_retry(|| async {
-            match &self.kind {
-                ConnectionKind::Standalone(manager, _) => {
-                    let mut conn = manager.clone();
-                    Cmd::new().arg("CLUSTER").arg("FORGET").arg(node_id).query_async::<()>(&mut conn).await.context("CLUSTER FORGET")?;
-                    Ok(())
-                }
-                ConnectionKind::Cluster(client) => {
-                    let node_id = node_id.to_string();
-                    let client = client.clone();
-                    
-                    tokio::task::spawn_blocking(move || -> Result<()> {
-                        let mut conn = client.get_connection().context("get cluster connection")?;
-                        Cmd::new().arg("CLUSTER").arg("FORGET").arg(&node_id).query::<()>(&mut conn).context("CLUSTER FORGET")?;
-                        Ok(())
-                    }).await.unwrap()
-                }
-            }
-        }).await
+    /// 与 `members` 顺序一致的坐标列表，不存在的成员对应 `None`。
+    /// 坐标格式为 `(经度, 纬度)`。
+    pub async fn geopos(&self, db: u32, key: &str, members: &[String]) -> Result<Vec<Option<(f64, f64)>>> {
+        if members.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut cmd = redis::cmd("GEOPOS");
+        cmd.arg(key).arg(members);
+        let v: redis::Value = self.query_cmd(db, cmd, "GEOPOS").await?;
+        parse_geopos(v)
     }
 
-    /// 发起集群故障转移
-    /// 
-    /// 使用 CLUSTER FAILOVER 命令在副本节点上发起故障转移，
-    /// 使副本成为新的主节点。
+    /// 计算两个成员之间的距离（GEODIST 命令）
     /// 
     /// # 参数
     /// 
-    /// - `hard`: 是否使用强制故障转移
-    ///   - `false`: 正常故障转移（TAKEOVER）
-    ///   - `true`: 强制故障转移（FORCE）
+    /// - `unit`: 距离单位，支持 `m`、`km`、`mi`、`ft`
     /// 
-    /// # 故障转移模式
+    /// # 返回值
     /// 
-    /// - **TAKEOVER**: 副本会通知主节点停止处理客户端请求
-    /// - **FORCE**: 副本不等主节点响应，直接开始故障转移
+    /// 任一成员不存在时返回 `None`。
+    pub async fn geodist(&self, db: u32, key: &str, m1: &str, m2: &str, unit: &str) -> Result<Option<f64>> {
+        let unit = geo_unit(unit)?;
+        let mut cmd = redis::cmd("GEODIST");
+        cmd.arg(key).arg(m1).arg(m2).arg(unit);
+        self.query_cmd(db, cmd, "GEODIST").await
+    }
+
+    /// 按半径搜索成员（GEOSEARCH ... FROMLONLAT ... BYRADIUS 命令）
     /// 
-    /// # 使用示例
+    /// # 参数
     /// 
-    /// ```rust
-    /// // 正常故障转移
-    /// redis.cluster_failover(false).await?;
+    /// - `center`: 圆心坐标 `(经度, 纬度)`
+    /// - `radius`: 搜索半径
+    /// - `unit`: 距离单位，支持 `m`、`km`、`mi`、`ft`
     /// 
-    /// // 强制故障转移（主节点无响应时使用）
-    /// redis.cluster_failover(true).await?;
-    /// ```
-    pub async fn cluster_failover(&self, hard: bool) -> Result<()> {
-        self.with_retry(|| async {
-            match &self.kind {
-                ConnectionKind::Standalone(manager, _) => {
-                    let mut conn = manager.clone();
-                    let mode = if hard { "FORCE" } else { "TAKEOVER" };
-                    Cmd::new().arg("CLUSTER").arg("FAILOVER").arg(mode).query_async::<()>(&mut conn).await.context("CLUSTER FAILOVER")?;
-                    Ok(())
-                }
-                ConnectionKind::Cluster(client) => {
-                    let hard = hard;
-                    let client = client.clone();
-                    
-                    tokio::task::spawn_blocking(move || -> Result<()> {
-                        let mut conn = client.get_connection().context("get cluster connection")?;
-                        let mode = if hard { "FORCE" } else { "TAKEOVER" };
-                        Cmd::new().arg("CLUSTER").arg("FAILOVER").arg(mode).query::<()>(&mut conn).context("CLUSTER FAILOVER")?;
-                        Ok(())
-                    }).await.unwrap()
-                }
-            }
-        }).await
+    /// # 返回值
+    /// 
+    /// 按距离由近到远排序的成员列表。需要 Redis 6.2 及以上版本。
+    pub async fn geosearch(&self, db: u32, key: &str, center: (f64, f64), radius: f64, unit: &str) -> Result<Vec<String>> {
+        let unit = geo_unit(unit)?;
+        let mut cmd = redis::cmd("GEOSEARCH");
+        cmd.arg(key)
+            .arg("FROMLONLAT").arg(center.0).arg(center.1)
+            .arg("BYRADIUS").arg(radius).arg(unit)
+            .arg("ASC");
+        self.query_cmd(db, cmd, "GEOSEARCH").await
     }
 
-    // --- 服务器配置命令 ---
+    // --- 排序 ---
 
-    /// 设置 Redis 服务器配置参数
-    /// 
-    /// 使用 CONFIG SET 命令动态修改 Redis 服务器的配置参数。
+    /// 在服务端对列表、集合或有序集合的元素排序（SORT 命令）
     /// 
     /// # 参数
     /// 
-    /// - `key`: 配置参数名称
-    /// - `value`: 配置参数值
-    /// 
-    /// # 常用配置参数
-    /// 
-    /// - `timeout`: 客户端超时时间
-    /// - `maxmemory`: 最大内存限制
-    /// - `save`: RDB 快照保存策略
-    /// - `loglevel`: 日志级别
+    /// - `by`: 按外部键排序的模式，如 `weight_*`；`nosort` 表示不排序（通常与 `get` 配合使用）
+    /// - `limit`: `(偏移量, 数量)`，只返回排序后的一部分
+    /// - `get`: 返回外部键的值而不是元素本身，如 `object_*`，`#` 表示元素本身；可指定多个
+    /// - `alpha`: 按字典序排序；为 `false` 时按数值排序
+    /// - `desc`: 降序排列
     /// 
-    /// # 使用示例
+    /// # 返回值
     /// 
-    /// ```rust
-    /// redis.config_set("timeout", "300").await?;
-    /// redis.config_set("loglevel", "notice").await?;
-    /// ```
+    /// 排序后的元素（或 `get` 取得的值），`get` 指向的键不存在时对应空字符串。
     /// 
-    /// # 注意事项
+    /// # 错误
     /// 
-    /// - 某些参数需要重启 Redis 才能生效
-    /// - 部署环境可能限制 CONFIG 命令的使用
-    /// - 修改配置前应该了解参数的影响
-    pub async fn config_set(&self, key: &str, value: &str) -> Result<()> {
-        self.with_retry(|| async {
-            match &self.kind {
-                ConnectionKind::Standalone(manager, _) => {
-                    let mut conn = manager.clone();
-                    Cmd::new().arg("CONFIG").arg("SET").arg(key).arg(value).query_async::<()>(&mut conn).await.context("CONFIG SET")?;
-                    Ok(())
-                }
-                ConnectionKind::Cluster(client) => {
-                    let key = key.to_string();
-                    let value = value.to_string();
-                    let client = client.clone();
-                    
-                    tokio::task::spawn_blocking(move || -> Result<()> {
-                        let mut conn = client.get_connection().context("get cluster connection")?;
-                        Cmd::new().arg("CONFIG").arg("SET").arg(&key).arg(&value).query::<()>(&mut conn).context("CONFIG SET")?;
-                        Ok(())
-                    }).await.unwrap()
-                }
+    /// 按数值排序时遇到无法转换为数字的元素，服务端会拒绝执行，返回的错误会提示改用 `alpha`。
+    #[allow(clippy::too_many_arguments)]
+    pub async fn sort(&self, db: u32, key: &str, by: Option<String>, limit: Option<(i64, i64)>, get: Vec<String>, alpha: bool, desc: bool) -> Result<Vec<String>> {
+        let mut cmd = redis::cmd("SORT");
+        cmd.arg(key);
+        if let Some(by) = &by {
+            cmd.arg("BY").arg(by);
+        }
+        if let Some((offset, count)) = limit {
+            cmd.arg("LIMIT").arg(offset).arg(count);
+        }
+        for pattern in &get {
+            cmd.arg("GET").arg(pattern);
+        }
+        if desc {
+            cmd.arg("DESC");
+        }
+        if alpha {
+            cmd.arg("ALPHA");
+        }
+        let values: Vec<redis::Value> = match self.query_cmd(db, cmd, "SORT").await {
+            Err(e) if !alpha && is_not_numeric(&e) => {
+                return Err(e.context("SORT found non-numeric values, set alpha to sort them as strings"));
             }
-        }).await
+            other => other?,
+        };
+        Ok(values.iter().map(|v| value_string(v).unwrap_or_default()).collect())
     }
 
-    /// 触发后台保存快照
+    // --- 流消费者组操作 ---
+
+    /// 创建消费者组（XGROUP CREATE 命令）
     /// 
-    /// 使用 BGSAVE 命令在后台创建 RDB 快照文件。
-    /// 这个命令不会阻塞服务器，会立即返回。
+    /// # 参数
+    /// 
+    /// - `key`: 流的键名
+    /// - `group`: 消费者组名称
+    /// - `id`: 组的起始消息 ID，`$` 表示只消费之后写入的消息，`0` 表示从头消费
+    /// - `mkstream`: 流不存在时自动创建空流；为 `false` 且流不存在时返回错误
     /// 
     /// # 返回值
     /// 
-    /// 成功时返回 `Ok(())`。
+    /// - `true`: 新建了消费者组
+    /// - `false`: 同名消费者组已存在（`BUSYGROUP`），保持原样，可安全重复调用
+    pub async fn xgroup_create(&self, db: u32, key: &str, group: &str, id: &str, mkstream: bool) -> Result<bool> {
+        self.check_writable("XGROUP CREATE")?;
+        let mut cmd = redis::cmd("XGROUP");
+        cmd.arg("CREATE").arg(key).arg(group).arg(id);
+        if mkstream {
+            cmd.arg("MKSTREAM");
+        }
+        let result = match self.query_cmd::<()>(db, cmd, "XGROUP CREATE").await {
+            Ok(()) => Ok(true),
+            Err(e) if is_server_error(&e, "BUSYGROUP") => {
+                logging::debug("STREAM", &format!("consumer group {} already exists on {}", group, key));
+                Ok(false)
+            }
+            Err(e) => Err(e),
+        };
+        if mkstream {
+            self.invalidate_cached(db, key);
+        }
+        result
+    }
+
+    /// 以消费者身份读取消息（XREADGROUP 命令）
     /// 
-    /// # 使用场景
+    /// # 参数
     /// 
-    /// - 定期数据备份
-    /// - 手动触发持久化
-    /// - 数据迁移前的快照
+    /// - `group` / `consumer`: 消费者组与消费者名称，消费者不存在时由服务端自动创建
+    /// - `streams`: 要读取的流，集群模式下必须位于同一槽位
+    /// - `ids`: 与 `streams` 一一对应的起始 ID，`>` 表示读取从未投递给其他消费者的新消息，
+    ///   其他 ID 表示重新读取本消费者待确认列表中该 ID 之后的消息
+    /// - `count`: 每个流最多返回的消息数量
+    /// - `block_ms`: 没有消息时最多阻塞等待的毫秒数，`Some(0)` 表示一直等待；
+    ///   阻塞读取使用独立连接，不会阻塞同一连接上的其他命令
     /// 
-    /// # 使用示例
+    /// # 返回值
     /// 
-    /// ```rust
-    /// redis.bgsave().await?;
-    /// println!("Background save started");
-    /// ```
+    /// `(流的键名, 消息列表)` 列表，只包含有消息的流；阻塞超时时返回空列表。
     /// 
     /// # 注意事项
     /// 
-    /// - 快照创建是异步的，命令立即返回
-    /// - 大型数据库可能需要较长时间完成
-    /// - 可以通过 LASTSAVE 命令检查最后一次保存时间
-    pub async fn bgsave(&self) -> Result<()> {
-        self.with_retry(|| async {
-            match &self.kind {
-                ConnectionKind::Standalone(manager, _) => {
-                    let mut conn = manager.clone();
-                    Cmd::new().arg("BGSAVE").query_async::<()>(&mut conn).await.context("BGSAVE")?;
-                    Ok(())
-                }
-                ConnectionKind::Cluster(client) => {
-                    let client = client.clone();
-                    
-                    tokio::task::spawn_blocking(move || -> Result<()> {
-                        let mut conn = client.get_connection().context("get cluster connection")?;
-                        Cmd::new().arg("BGSAVE").query::<()>(&mut conn).context("BGSAVE")?;
-                        Ok(())
-                    }).await.unwrap()
-                }
+    /// 以 `>` 读取会把消息移入本消费者的待确认列表，回复丢失后重试会跳过这批消息，因此任何情况下都只发送一次。
+    /// 调用返回错误时，可能已有消息被投递但未返回，应通过 `XPENDING` 查看并以 `XCLAIM`（或以 `0` 为 ID 重新读取）取回。
+    #[allow(clippy::too_many_arguments)]
+    pub async fn xreadgroup(&self, db: u32, group: &str, consumer: &str, streams: &[String], ids: &[String], count: Option<usize>, block_ms: Option<u64>) -> Result<Vec<(String, Vec<StreamEntry>)>> {
+        self.check_writable("XREADGROUP")?;
+        if streams.is_empty() || streams.len() != ids.len() {
+            return Err(invalid_argument("XREADGROUP requires one id per stream"));
+        }
+        self.ensure_same_slot(streams.iter().map(|s| s.as_str()))?;
+        let mut cmd = redis::cmd("XREADGROUP");
+        cmd.arg("GROUP").arg(group).arg(consumer);
+        if let Some(count) = count {
+            cmd.arg("COUNT").arg(count);
+        }
+        if let Some(block_ms) = block_ms {
+            cmd.arg("BLOCK").arg(block_ms);
+        }
+        cmd.arg("STREAMS").arg(streams).arg(ids);
+
+        let reply: redis::Value = match (&self.kind, block_ms) {
+            (ConnectionKind::Standalone(_, client), Some(_)) => {
+                // 阻塞命令会占住多路复用连接，使用独立连接执行
+                let mut conn = Self::db_connection(client, db).await?;
+                cmd.query_async(&mut conn).await.context("XREADGROUP")?
             }
-        }).await
+            _ => self.query_cmd_once(db, cmd, "XREADGROUP").await?,
+        };
+        parse_xread_reply(reply)
     }
 
-    // --- 健康检查 ---
-
-    /// Ping 命令健康检查
-    /// 
-    /// 使用 PING 命令检查 Redis 服务器的连接状态。
+    /// 确认消息已处理（XACK 命令）
     /// 
     /// # 返回值
     /// 
-    /// 返回 "PONG" 响应字符串。
-    /// 
-    /// # 实现细节
+    /// 实际从待确认列表中移除的消息数量；已确认或不存在的 ID 不计入。
+    pub async fn xack(&self, db: u32, key: &str, group: &str, ids: &[String]) -> Result<i64> {
+        self.check_writable("XACK")?;
+        if ids.is_empty() {
+            return Ok(0);
+        }
+        let mut cmd = redis::cmd("XACK");
+        cmd.arg(key).arg(group).arg(ids);
+        self.query_cmd(db, cmd, "XACK").await
+    }
+
+    /// 删除流中的消息（XDEL 命令）
     /// 
-    /// - 单机模式：通过设置测试键来验证连接
-    /// - 集群模式：使用标准的 PING 命令
+    /// # 返回值
+    /// 
+    /// 实际删除的消息数量，不存在的 ID 不计入。
+    pub async fn xdel(&self, db: u32, key: &str, ids: Vec<String>) -> Result<i64> {
+        self.check_writable("XDEL")?;
+        if ids.is_empty() {
+            return Ok(0);
+        }
+        let mut cmd = redis::cmd("XDEL");
+        cmd.arg(key).arg(&ids);
+        self.query_cmd(db, cmd, "XDEL").await
+    }
+
+    /// 裁剪流（XTRIM 命令）
+    /// 
+    /// # 参数
+    /// 
+    /// - `maxlen`: 只保留最新的若干条消息（`MAXLEN` 策略）
+    /// - `minid`: 删除 ID 小于该值的消息（`MINID` 策略，需要 Redis 6.2 及以上版本）
+    /// - `approximate`: 使用 `~` 近似裁剪，只删除整个宏节点，效率更高，但可能保留略多于目标数量的消息
+    /// 
+    /// `maxlen` 与 `minid` 必须且只能指定一个。
+    /// 
+    /// # 返回值
+    /// 
+    /// 被删除的消息数量。
+    pub async fn xtrim(&self, db: u32, key: &str, maxlen: Option<u64>, minid: Option<String>, approximate: bool) -> Result<i64> {
+        self.check_writable("XTRIM")?;
+        let mut cmd = redis::cmd("XTRIM");
+        cmd.arg(key);
+        match (maxlen, minid) {
+            (Some(maxlen), None) => { cmd.arg("MAXLEN"); if approximate { cmd.arg("~"); } cmd.arg(maxlen); }
+            (None, Some(minid)) => { cmd.arg("MINID"); if approximate { cmd.arg("~"); } cmd.arg(minid); }
+            _ => return Err(invalid_argument("XTRIM requires exactly one of maxlen or minid")),
+        }
+        self.query_cmd(db, cmd, "XTRIM").await
+    }
+
+    // --- RedisJSON 操作 ---
+
+    pub async fn json_set<V: serde::Serialize + Send + Sync + Clone + 'static>(&self, db: u32, key: &str, path: &str, value: &V) -> Result<()> {
+        self.check_writable("JSON.SET")?;
+        let json_str = serde_json::to_string(value).context("serialize json value")?;
+        let result = self.with_retry(|| async {
+            match &self.kind {
+                ConnectionKind::Standalone(pool, client) => {
+                    if db == 0 {
+                        let mut conn = pool.get();
+                        redis::Cmd::new().arg("JSON.SET").arg(key).arg(path).arg(json_str.clone()).query_async::<()>(&mut conn).await.context("JSON.SET")?;
+                        Ok(())
+                    } else {
+                        let mut conn = Self::db_connection(client, db).await?;
+                        redis::cmd("JSON.SET").arg(key).arg(path).arg(&json_str).query_async::<()>(&mut conn).await.context("JSON.SET")?;
+                        Ok(())
+                    }
+                }
+                ConnectionKind::Cluster(client) => {
+                    if db != 0 {
+                        return Err(anyhow!("Cluster mode does not support multiple databases"));
+                    }
+                    let key = key.to_string();
+                    let path = path.to_string();
+                    let json_str = json_str.clone();
+                    let client = client.clone();
+                    
+                    tokio::task::spawn_blocking(move || -> Result<()> {
+                        let mut conn = client.get_connection().context("get cluster connection")?;
+                        redis::cmd("JSON.SET").arg(&key).arg(&path).arg(json_str).query::<()>(&mut conn).context("JSON.SET")?;
+                        Ok(())
+                    }).await.unwrap()
+                }
+            }
+        }).await;
+        self.invalidate_cached(db, key);
+        result
+    }
+
+    pub async fn json_get(&self, db: u32, key: &str, path: &str) -> Result<Option<serde_json::Value>> {
+        self.with_retry(|| async {
+            match &self.kind {
+                ConnectionKind::Standalone(pool, client) => {
+                    if db == 0 {
+                        let mut conn = pool.get();
+                        let s: Option<String> = redis::Cmd::new().arg("JSON.GET").arg(key).arg(path).query_async(&mut conn).await.context("JSON.GET")?;
+                        if let Some(js) = s { Ok(Some(serde_json::from_str(&js).context("parse json")?)) } else { Ok(None) }
+                    } else {
+                        let mut conn = Self::db_connection(client, db).await?;
+                        let s: Option<String> = redis::cmd("JSON.GET").arg(key).arg(path).query_async(&mut conn).await.context("JSON.GET")?;
+                        if let Some(js) = s { Ok(Some(serde_json::from_str(&js).context("parse json")?)) } else { Ok(None) }
+                    }
+                }
+                ConnectionKind::Cluster(client) => {
+                    if db != 0 {
+                        return Err(anyhow!("Cluster mode does not support multiple databases"));
+                    }
+                    let key = key.to_string();
+                    let path = path.to_string();
+                    let client = client.clone();
+                    
+                    tokio::task::spawn_blocking(move || -> Result<Option<serde_json::Value>> {
+                        let mut conn = client.get_connection().context("get cluster connection")?;
+                        let s: Option<String> = redis::cmd("JSON.GET").arg(&key).arg(&path).query(&mut conn).context("JSON.GET")?;
+                        if let Some(js) = s { Ok(Some(serde_json::from_str(&js).context("parse json")?)) } else { Ok(None) }
+                    }).await.unwrap()
+                }
+            }
+        }).await
+    }
+
+    /// 删除 JSON 文档中路径匹配的值（JSON.DEL 命令）
+    /// 
+    /// 路径为根路径 `$` 时删除整个键。返回被删除的值数量。
+    pub async fn json_del(&self, db: u32, key: &str, path: &str) -> Result<i64> {
+        self.check_writable("JSON.DEL")?;
+        let mut cmd = redis::cmd("JSON.DEL");
+        cmd.arg(key).arg(path);
+        let result = self.query_cmd(db, cmd, "JSON.DEL").await;
+        self.invalidate_cached(db, key);
+        result
+    }
+
+    /// 向 JSON 数组末尾追加元素（JSON.ARRAPPEND 命令）
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回追加后数组的长度。路径匹配多个值时取第一个匹配，
+    /// 路径不存在或匹配的值不是数组时返回 `None`。
+    /// 
+    /// 追加不是幂等的，命令只发送一次，不自动重试。
+    pub async fn json_arrappend(&self, db: u32, key: &str, path: &str, values: &[serde_json::Value]) -> Result<Option<i64>> {
+        self.check_writable("JSON.ARRAPPEND")?;
+        if values.is_empty() {
+            return Err(invalid_argument("JSON.ARRAPPEND requires at least one value"));
+        }
+        let mut cmd = redis::cmd("JSON.ARRAPPEND");
+        cmd.arg(key).arg(path);
+        for v in values {
+            cmd.arg(serde_json::to_string(v).context("serialize json value")?);
+        }
+        let reply: Result<redis::Value> = self.query_cmd_once(db, cmd, "JSON.ARRAPPEND").await;
+        self.invalidate_cached(db, key);
+        first_json_int(reply?)
+    }
+
+    /// 对 JSON 中的数值执行增量（JSON.NUMINCRBY 命令）
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回增加后的值。路径匹配多个值时取第一个匹配，匹配的值不是数字时返回 `None`。
+    /// 
+    /// 增量不是幂等的，命令只发送一次，不自动重试。
+    pub async fn json_numincrby(&self, db: u32, key: &str, path: &str, delta: f64) -> Result<Option<f64>> {
+        self.check_writable("JSON.NUMINCRBY")?;
+        let mut cmd = redis::cmd("JSON.NUMINCRBY");
+        cmd.arg(key).arg(path).arg(delta);
+        let reply: Result<String> = self.query_cmd_once(db, cmd, "JSON.NUMINCRBY").await;
+        self.invalidate_cached(db, key);
+        let reply = reply?;
+        // JSONPath 返回 "[3]" 形式的数组，旧式路径返回 "3"
+        let value: serde_json::Value = serde_json::from_str(&reply).context("parse JSON.NUMINCRBY reply")?;
+        Ok(match value {
+            serde_json::Value::Array(items) => items.first().and_then(serde_json::Value::as_f64),
+            other => other.as_f64(),
+        })
+    }
+
+    /// 获取 JSON 路径上值的类型（JSON.TYPE 命令）
+    /// 
+    /// 返回 `object`、`array`、`string`、`integer`、`number`、`boolean`、`null` 之一。
+    /// 路径匹配多个值时取第一个匹配，键或路径不存在时返回 `None`。
+    pub async fn json_type(&self, db: u32, key: &str, path: &str) -> Result<Option<String>> {
+        let mut cmd = redis::cmd("JSON.TYPE");
+        cmd.arg(key).arg(path);
+        let reply: redis::Value = self.query_cmd(db, cmd, "JSON.TYPE").await?;
+        parse_json_type(reply)
+    }
+
+    /// 获取 JSON 对象的键列表（JSON.OBJKEYS 命令）
+    /// 
+    /// 路径匹配多个值时取第一个匹配；键、路径不存在或匹配的值不是对象时返回空列表。
+    pub async fn json_objkeys(&self, db: u32, key: &str, path: &str) -> Result<Vec<String>> {
+        let mut cmd = redis::cmd("JSON.OBJKEYS");
+        cmd.arg(key).arg(path);
+        let reply: redis::Value = self.query_cmd(db, cmd, "JSON.OBJKEYS").await?;
+        parse_json_objkeys(reply)
+    }
+
+    /// 批量写入 JSON 文档（JSON.MSET 命令）
+    /// 
+    /// 优先使用 RedisJSON 2.6 的 `JSON.MSET` 原子写入；服务端不支持该命令时回退为在一个管道中
+    /// 逐个执行 `JSON.SET`，此时不保证原子性。集群模式下所有键必须位于同一槽位。
+    /// 
+    /// # 参数
+    /// 
+    /// - `items`: `(键, 路径, 值)` 三元组列表，不能为空
+    pub async fn json_mset(&self, db: u32, items: Vec<(String, String, serde_json::Value)>) -> Result<()> {
+        self.check_writable("JSON.MSET")?;
+        if items.is_empty() {
+            return Err(invalid_argument("JSON.MSET requires at least one item"));
+        }
+        self.ensure_same_slot(items.iter().map(|(k, _, _)| k.as_str()))?;
+        let mut encoded = Vec::with_capacity(items.len());
+        for (key, path, value) in &items {
+            encoded.push((key.as_str(), path.as_str(), serde_json::to_string(value).context("serialize json value")?));
+        }
+
+        let mut cmd = redis::cmd("JSON.MSET");
+        for (key, path, json) in &encoded {
+            cmd.arg(*key).arg(*path).arg(json);
+        }
+        let result = match self.query_cmd::<()>(db, cmd, "JSON.MSET").await {
+            Err(e) if is_unknown_command(&e) => {
+                logging::debug("JSON", &format!("JSON.MSET unavailable, falling back to pipelined JSON.SET: {}", e));
+                let mut pipe = redis::pipe();
+                for (key, path, json) in &encoded {
+                    pipe.cmd("JSON.SET").arg(*key).arg(*path).arg(json).ignore();
+                }
+                self.query_pipe::<()>(db, pipe, "JSON.SET").await
+            }
+            other => other,
+        };
+        for (key, _, _) in &items {
+            self.invalidate_cached(db, key);
+        }
+        result
+    }
+
+    // --- 集群管理命令 ---
+
+    /// 获取集群节点信息
+    /// 
+    /// 使用 CLUSTER NODES 命令获取集群中所有节点的信息。
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回包含节点信息的字符串，每行代表一个节点。
+    /// 
+    /// # 信息格式
+    /// 
+    /// 每行包含：节点ID、地址、标志、主节点ID、最后 ping 时间、最后 pong 时间、
+    /// 配置纪元、连接状态、节点端口、总线端口、槽位范围。
     /// 
     /// # 使用示例
     /// 
     /// ```rust
-    /// let pong = redis.ping().await?;
-    /// assert_eq!(pong, "PONG");
+    /// let nodes = redis.cluster_nodes().await?;
+    /// println!("Cluster nodes:\n{}", nodes);
     /// ```
-    pub async fn ping(&self) -> Result<String> {
+    pub async fn cluster_nodes(&self) -> Result<String> {
         self.with_retry(|| async {
             match &self.kind {
-                ConnectionKind::Standalone(manager, _) => {
-                    // 单机模式通过设置测试键来验证连接
-                    let mut conn = manager.clone();
-                    let _: () = conn.set("__ping__", "1").await.context("PING_SET")?;
-                    Ok("PONG".to_string())
+                ConnectionKind::Standalone(pool, _) => {
+                    let mut conn = pool.get();
+                    let out: String = Cmd::new().arg("CLUSTER").arg("NODES").query_async(&mut conn).await.context("CLUSTER NODES")?;
+                    Ok(out)
                 }
                 ConnectionKind::Cluster(client) => {
-                    // 集群模式使用标准 PING 命令
                     let client = client.clone();
                     
                     tokio::task::spawn_blocking(move || -> Result<String> {
                         let mut conn = client.get_connection().context("get cluster connection")?;
-                        let res: String = Cmd::new().arg("PING").query(&mut conn).context("PING")?;
-                        Ok(res)
+                        let out: String = Cmd::new().arg("CLUSTER").arg("NODES").query(&mut conn).context("CLUSTER NODES")?;
+                        Ok(out)
                     }).await.unwrap()
                 }
             }
         }).await
     }
-}
 
-/// 构建 Sentinel 连接 URL
-/// 
-/// 格式: redis+sentinel://host1:port1,host2:port2/master_name
-fn build_sentinel_url(master: &str, urls: &[String]) -> Result<String> {
-    let hosts: Vec<String> = urls.iter().map(|u| {
-        u.trim_start_matches("redis://")
-         .trim_start_matches("http://")
-         .trim_end_matches('/')
-         .to_string()
-    }).collect();
-    
-    if hosts.is_empty() {
-         return Err(anyhow!("No sentinel URLs provided"));
+    /// 获取集群槽位分布
+    /// 
+    /// 使用 CLUSTER SLOTS 命令获取集群中槽位的分布情况。
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回槽位分布信息的原始 Redis 值。
+    /// 
+    /// # 信息格式
+    /// 
+    /// 返回一个数组，每个元素包含：
+    /// - 起始槽位
+    /// - 结束槽位  
+    /// - 主节点信息
+    /// - 副本节点信息（可选）
+    /// 
+    /// # 使用示例
+    /// 
+    /// ```rust
+    /// let slots = redis.cluster_slots().await?;
+    /// // 解析槽位分布信息
+    /// ```
+    pub async fn cluster_slots(&self) -> Result<redis::Value> {
+        self.with_retry(|| async {
+            match &self.kind {
+                ConnectionKind::Standalone(pool, _) => {
+                    let mut conn = pool.get();
+                    let out: redis::Value = Cmd::new().arg("CLUSTER").arg("SLOTS").query_async(&mut conn).await.context("CLUSTER SLOTS")?;
+                    Ok(out)
+                }
+                ConnectionKind::Cluster(client) => {
+                    let client = client.clone();
+                    
+                    tokio::task::spawn_blocking(move || -> Result<redis::Value> {
+                        let mut conn = client.get_connection().context("get cluster connection")?;
+                        let out: redis::Value = Cmd::new().arg("CLUSTER").arg("SLOTS").query(&mut conn).context("CLUSTER SLOTS")?;
+                        Ok(out)
+                    }).await.unwrap()
+                }
+            }
+        }).await
+    }
+
+    /// 获取集群整体状态（CLUSTER INFO 命令）
+    /// 
+    /// 单机或哨兵模式下先通过 `INFO cluster` 判断服务端是否开启集群，
+    /// 未开启时返回 `cluster_enabled` 为 `false` 的默认状态，而不是服务端错误。
+    pub async fn cluster_info(&self) -> Result<ClusterStatus> {
+        if !matches!(self.kind, ConnectionKind::Cluster(_)) {
+            let mut cmd = redis::cmd("INFO");
+            cmd.arg("cluster");
+            let info: String = self.query_cmd(0, cmd, "INFO cluster").await?;
+            if !info.lines().any(|line| line.trim() == "cluster_enabled:1") {
+                return Ok(ClusterStatus::default());
+            }
+        }
+        let mut cmd = redis::cmd("CLUSTER");
+        cmd.arg("INFO");
+        let info: String = self.query_cmd(0, cmd, "CLUSTER INFO").await?;
+        Ok(ClusterStatus::from_info(&info))
+    }
+
+    /// 邀请节点加入集群
+    /// 
+    /// 使用 CLUSTER MEET 命令邀请指定节点加入当前集群。
+    /// 
+    /// # 参数
+    /// 
+    /// - `ip`: 新节点的 IP 地址
+    /// - `port`: 新节点的客户端端口
+    /// 
+    /// # 使用示例
+    /// 
+    /// ```rust
+    /// redis.cluster_meet("192.168.1.100", 6379).await?;
+    /// ```
+    /// 
+    /// # 注意事项
+    /// 
+    /// - 新节点必须能够访问当前节点
+    /// - 端口应该是客户端端口，不是集群总线端口
+    /// - 需要适当的权限配置
+    pub async fn cluster_meet(&self, ip: &str, port: u16) -> Result<()> {
+        self.check_writable("CLUSTER MEET")?;
+        self.check_policy("CLUSTER MEET")?;
+        self.with_retry(|| async {
+            match &self.kind {
+                ConnectionKind::Standalone(pool, _) => {
+                    let mut conn = pool.get();
+                    Cmd::new().arg("CLUSTER").arg("MEET").arg(ip).arg(port).query_async::<()>(&mut conn).await.context("CLUSTER MEET")?;
+                    Ok(())
+                }
+                ConnectionKind::Cluster(client) => {
+                    let ip = ip.to_string();
+                    let port = port;
+                    let client = client.clone();
+                    
+                    tokio::task::spawn_blocking(move || -> Result<()> {
+                        let mut conn = client.get_connection().context("get cluster connection")?;
+                        Cmd::new().arg("CLUSTER").arg("MEET").arg(&ip).arg(port).query::<()>(&mut conn).context("CLUSTER MEET")?;
+                        Ok(())
+                    }).await.unwrap()
+                }
+            }
+        }).await
+    }
+
+    /// 从集群中移除节点
+    /// 
+    /// 使用 CLUSTER FORGET 命令从集群中移除指定节点。
+    /// 
+    /// # 参数
+    /// 
+    /// - `node_id`: 要移除的节点 ID
+    /// 
+    /// # 使用示例
+    /// 
+    /// ```rust
+    /// redis.cluster_forget("3c3a0c74aae0b2717c740bb3f2c8a6a71d0d8c00").await?;
+    /// ```
+    /// 
+    /// # 注意事项
+    /// 
+    /// - 节点 ID 是 40 字符的十六进制字符串
+    /// - 移除节点前应该确保没有数据分配给该节点
+    /// - 需要在集群的每个节点上执行此命令
+    pub async fn cluster_forget(&self, node_id: &str) -> Result<()> {
+        self.check_writable("CLUSTER FORGET")?;
+        self.check_policy("CLUSTER FORGET")?;
+        self.with_retry(|| async {
+            match &self.kind {
+                ConnectionKind::Standalone(pool, _) => {
+                    let mut conn = pool.get();
+                    Cmd::new().arg("CLUSTER").arg("FORGET").arg(node_id).query_async::<()>(&mut conn).await.context("CLUSTER FORGET")?;
+                    Ok(())
+                }
+                ConnectionKind::Cluster(client) => {
+                    let node_id = node_id.to_string();
+                    let client = client.clone();
+                    
+                    tokio::task::spawn_blocking(move || -> Result<()> {
+                        let mut conn = client.get_connection().context("get cluster connection")?;
+                        Cmd::new().arg("CLUSTER").arg("FORGET").arg(&node_id).query::<()>(&mut conn).context("CLUSTER FORGET")?;
+                        Ok(())
+                    }).await.unwrap()
+                }
+            }
+        }).await
+    }
+
+    /// 发起集群故障转移
+    /// 
+    /// 使用 CLUSTER FAILOVER 命令在副本节点上发起故障转移，
+    /// 使副本成为新的主节点。
+    /// 
+    /// # 参数
+    /// 
+    /// - `hard`: 是否使用强制故障转移
+    ///   - `false`: 正常故障转移（TAKEOVER）
+    ///   - `true`: 强制故障转移（FORCE）
+    /// 
+    /// # 故障转移模式
+    /// 
+    /// - **TAKEOVER**: 副本会通知主节点停止处理客户端请求
+    /// - **FORCE**: 副本不等主节点响应，直接开始故障转移
+    /// 
+    /// # 使用示例
+    /// 
+    /// ```rust
+    /// // 正常故障转移
+    /// redis.cluster_failover(false).await?;
+    /// 
+    /// // 强制故障转移（主节点无响应时使用）
+    /// redis.cluster_failover(true).await?;
+    /// ```
+    pub async fn cluster_failover(&self, hard: bool) -> Result<()> {
+        self.check_writable("CLUSTER FAILOVER")?;
+        self.check_policy("CLUSTER FAILOVER")?;
+        self.with_retry(|| async {
+            match &self.kind {
+                ConnectionKind::Standalone(pool, _) => {
+                    let mut conn = pool.get();
+                    let mode = if hard { "FORCE" } else { "TAKEOVER" };
+                    Cmd::new().arg("CLUSTER").arg("FAILOVER").arg(mode).query_async::<()>(&mut conn).await.context("CLUSTER FAILOVER")?;
+                    Ok(())
+                }
+                ConnectionKind::Cluster(client) => {
+                    let hard = hard;
+                    let client = client.clone();
+                    
+                    tokio::task::spawn_blocking(move || -> Result<()> {
+                        let mut conn = client.get_connection().context("get cluster connection")?;
+                        let mode = if hard { "FORCE" } else { "TAKEOVER" };
+                        Cmd::new().arg("CLUSTER").arg("FAILOVER").arg(mode).query::<()>(&mut conn).context("CLUSTER FAILOVER")?;
+                        Ok(())
+                    }).await.unwrap()
+                }
+            }
+        }).await
+    }
+
+    /// 计算键所属的槽位（等价于 CLUSTER KEYSLOT）
+    /// 
+    /// 在本地使用 CRC16 计算并遵循 hash tag 规则，结果与服务端 `CLUSTER KEYSLOT` 一致，
+    /// 因此无需往返服务器，单机模式下同样可用。
+    pub async fn cluster_keyslot(&self, key: &str) -> Result<u16> {
+        Ok(key_slot(key))
+    }
+
+    /// 统计指定槽位中的键数量（CLUSTER COUNTKEYSINSLOT 命令）
+    /// 
+    /// 该命令只统计接收节点本地的键，因此集群模式下会显式路由到负责该槽位的主节点。
+    /// 
+    /// # 参数
+    /// 
+    /// - `slot`: 槽位编号，范围 `0..16384`
+    pub async fn cluster_countkeysinslot(&self, slot: u16) -> Result<u64> {
+        check_slot(slot)?;
+        match &self.kind {
+            ConnectionKind::Standalone(..) => {
+                let mut cmd = redis::cmd("CLUSTER");
+                cmd.arg("COUNTKEYSINSLOT").arg(slot);
+                self.query_cmd(0, cmd, "CLUSTER COUNTKEYSINSLOT").await
+            }
+            ConnectionKind::Cluster(client) => {
+                self.with_retry(|| async {
+                    let client = client.clone();
+
+                    tokio::task::spawn_blocking(move || -> Result<u64> {
+                        let mut conn = client.get_connection().context("get cluster connection")?;
+                        let mut cmd = redis::cmd("CLUSTER");
+                        cmd.arg("COUNTKEYSINSLOT").arg(slot);
+                        let routing = RoutingInfo::SingleNode(SingleNodeRoutingInfo::SpecificNode(Route::new(slot, SlotAddr::Master)));
+                        match conn.route_command(&cmd, routing).context("CLUSTER COUNTKEYSINSLOT")? {
+                            redis::Value::Int(n) => Ok(n as u64),
+                            other => Err(anyhow!("Unexpected CLUSTER COUNTKEYSINSLOT reply: {:?}", other)),
+                        }
+                    }).await.unwrap()
+                }).await
+            }
+        }
+    }
+
+    /// 将槽位分配给当前节点（CLUSTER ADDSLOTS 命令）
+    /// 
+    /// 槽位必须尚未分配给任何节点，否则服务端返回错误。
+    /// 
+    /// # 注意事项
+    /// 
+    /// 槽位管理命令只作用于接收命令的节点。集群模式下命令发往集群客户端选中的节点，
+    /// 需要操作特定节点时，请使用直接指向该节点的单机连接。
+    pub async fn cluster_addslots(&self, slots: Vec<u16>) -> Result<()> {
+        self.check_writable("CLUSTER ADDSLOTS")?;
+        self.check_policy("CLUSTER ADDSLOTS")?;
+        self.slots_cmd("ADDSLOTS", slots).await
+    }
+
+    /// 从当前节点移除槽位分配（CLUSTER DELSLOTS 命令）
+    /// 
+    /// 移除后槽位处于未分配状态，集群在重新分配前无法服务该槽位的键。
+    /// 节点选择规则同 `cluster_addslots`。
+    pub async fn cluster_delslots(&self, slots: Vec<u16>) -> Result<()> {
+        self.check_writable("CLUSTER DELSLOTS")?;
+        self.check_policy("CLUSTER DELSLOTS")?;
+        self.slots_cmd("DELSLOTS", slots).await
+    }
+
+    /// 执行 ADDSLOTS/DELSLOTS，统一校验槽位范围
+    async fn slots_cmd(&self, sub: &'static str, slots: Vec<u16>) -> Result<()> {
+        if slots.is_empty() {
+            return Err(invalid_argument("at least one slot is required"));
+        }
+        for &slot in &slots {
+            check_slot(slot)?;
+        }
+        let mut cmd = redis::cmd("CLUSTER");
+        cmd.arg(sub).arg(&slots);
+        self.query_cmd(0, cmd, sub).await
+    }
+
+    /// 设置槽位的迁移状态（CLUSTER SETSLOT 命令）
+    /// 
+    /// 手动重新分片的步骤：
+    /// 1. 目标节点：`SETSLOT <slot> IMPORTING <源节点 ID>`
+    /// 2. 源节点：`SETSLOT <slot> MIGRATING <目标节点 ID>`
+    /// 3. 使用 MIGRATE 迁移键
+    /// 4. 在各主节点上执行 `SETSLOT <slot> NODE <目标节点 ID>`
+    /// 
+    /// # 参数
+    /// 
+    /// - `slot`: 槽位编号，范围 `0..16384`
+    /// - `state`: `IMPORTING`、`MIGRATING`、`STABLE` 或 `NODE`（不区分大小写）
+    /// - `node_id`: `STABLE` 时必须为 `None`，其他状态必须提供
+    /// 
+    /// 节点选择规则同 `cluster_addslots`。
+    pub async fn cluster_setslot(&self, slot: u16, state: &str, node_id: Option<&str>) -> Result<()> {
+        self.check_writable("CLUSTER SETSLOT")?;
+        self.check_policy("CLUSTER SETSLOT")?;
+        check_slot(slot)?;
+        let state = state.to_ascii_uppercase();
+        match (state.as_str(), node_id) {
+            ("IMPORTING" | "MIGRATING" | "NODE", Some(_)) | ("STABLE", None) => {}
+            ("IMPORTING" | "MIGRATING" | "NODE", None) => return Err(invalid_argument(format!("SETSLOT {} requires a node id", state))),
+            ("STABLE", Some(_)) => return Err(invalid_argument("SETSLOT STABLE does not take a node id")),
+            _ => return Err(invalid_argument(format!("Invalid SETSLOT state: {}", state))),
+        }
+        let mut cmd = redis::cmd("CLUSTER");
+        cmd.arg("SETSLOT").arg(slot).arg(&state);
+        if let Some(id) = node_id {
+            cmd.arg(id);
+        }
+        self.query_cmd(0, cmd, "CLUSTER SETSLOT").await
+    }
+
+    // --- 哨兵管理命令 ---
+
+    /// 连接到第一个可用的哨兵节点
+    /// 
+    /// 依次尝试 `sentinel_urls` 中的地址，返回首个连接成功的哨兵连接。
+    /// 地址可以省略 `redis://` 前缀。
+    async fn sentinel_connection(&self) -> Result<MultiplexedConnection> {
+        if self.cfg.sentinel_urls.is_empty() {
+            return Err(invalid_argument("No sentinel URLs provided"));
+        }
+        let mut last_err = None;
+        for url in &self.cfg.sentinel_urls {
+            let url = if url.contains("://") { url.clone() } else { format!("redis://{}", url) };
+            let conn = async {
+                let client = redis::Client::open(connection_info(&url, &self.cfg)?)?;
+                client.get_multiplexed_async_connection().await.context("connect sentinel")
+            }.await;
+            match conn {
+                Ok(conn) => return Ok(conn),
+                Err(e) => {
+                    logging::warn("REDIS_SENTINEL", &format!("sentinel {} unavailable: {}", url, e));
+                    last_err = Some(e);
+                }
+            }
+        }
+        Err(last_err.unwrap_or_else(|| anyhow!("No sentinel available")))
+    }
+
+    /// 列出哨兵监控的所有主节点（SENTINEL MASTERS 命令）
+    /// 
+    /// # 返回值
+    /// 
+    /// 每个主节点对应一个字段映射，包含 `name`、`ip`、`port`、`flags`、`num-slaves` 等字段。
+    pub async fn sentinel_masters(&self) -> Result<Vec<HashMap<String, String>>> {
+        self.with_retry(|| async {
+            let mut conn = self.sentinel_connection().await?;
+            let masters: Vec<HashMap<String, String>> = redis::cmd("SENTINEL").arg("MASTERS")
+                .query_async(&mut conn).await.context("SENTINEL MASTERS")?;
+            Ok(masters)
+        }).await
+    }
+
+    /// 查询指定主节点的当前地址（SENTINEL GET-MASTER-ADDR-BY-NAME 命令）
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回 `(ip, port)`，哨兵未监控该名称时返回 `None`。
+    pub async fn sentinel_get_master_addr(&self, name: &str) -> Result<Option<(String, u16)>> {
+        self.with_retry(|| async {
+            let mut conn = self.sentinel_connection().await?;
+            let addr: Option<(String, u16)> = redis::cmd("SENTINEL").arg("GET-MASTER-ADDR-BY-NAME").arg(name)
+                .query_async(&mut conn).await.context("SENTINEL GET-MASTER-ADDR-BY-NAME")?;
+            Ok(addr)
+        }).await
+    }
+
+    /// 手动触发主节点故障转移（SENTINEL FAILOVER 命令）
+    /// 
+    /// 哨兵会在不征求其他哨兵同意的情况下选出一个副本提升为新的主节点。
+    /// 命令立即返回，切换在后台完成，之后可通过 `sentinel_get_master_addr` 查看新地址。
+    /// 
+    /// # 注意事项
+    /// 
+    /// - 没有可用副本或已有故障转移在进行时，哨兵会返回错误
+    /// - 切换期间写入可能短暂失败，本服务的连接会在重连时自动指向新的主节点
+    pub async fn sentinel_failover(&self, name: &str) -> Result<()> {
+        self.check_writable("SENTINEL FAILOVER")?;
+        self.check_policy("SENTINEL FAILOVER")?;
+        let mut conn = self.sentinel_connection().await?;
+        redis::cmd("SENTINEL").arg("FAILOVER").arg(name)
+            .query_async::<()>(&mut conn).await.context("SENTINEL FAILOVER")?;
+        logging::info("REDIS_SENTINEL", &format!("failover triggered for master={}", name));
+        Ok(())
+    }
+
+    // --- 服务器配置命令 ---
+
+    /// 设置 Redis 服务器配置参数
+    /// 
+    /// 使用 CONFIG SET 命令动态修改 Redis 服务器的配置参数。
+    /// 
+    /// # 参数
+    /// 
+    /// - `key`: 配置参数名称
+    /// - `value`: 配置参数值
+    /// 
+    /// # 常用配置参数
+    /// 
+    /// - `timeout`: 客户端超时时间
+    /// - `maxmemory`: 最大内存限制
+    /// - `save`: RDB 快照保存策略
+    /// - `loglevel`: 日志级别
+    /// 
+    /// # 使用示例
+    /// 
+    /// ```rust
+    /// redis.config_set("timeout", "300").await?;
+    /// redis.config_set("loglevel", "notice").await?;
+    /// ```
+    /// 
+    /// # 注意事项
+    /// 
+    /// - 某些参数需要重启 Redis 才能生效
+    /// - 部署环境可能限制 CONFIG 命令的使用
+    /// - 修改配置前应该了解参数的影响
+    pub async fn config_set(&self, key: &str, value: &str) -> Result<()> {
+        self.check_writable("CONFIG SET")?;
+        self.check_policy("CONFIG SET")?;
+        self.with_retry(|| async {
+            match &self.kind {
+                ConnectionKind::Standalone(pool, _) => {
+                    let mut conn = pool.get();
+                    Cmd::new().arg("CONFIG").arg("SET").arg(key).arg(value).query_async::<()>(&mut conn).await.context("CONFIG SET")?;
+                    Ok(())
+                }
+                ConnectionKind::Cluster(client) => {
+                    let key = key.to_string();
+                    let value = value.to_string();
+                    let client = client.clone();
+                    
+                    tokio::task::spawn_blocking(move || -> Result<()> {
+                        let mut conn = client.get_connection().context("get cluster connection")?;
+                        Cmd::new().arg("CONFIG").arg("SET").arg(&key).arg(&value).query::<()>(&mut conn).context("CONFIG SET")?;
+                        Ok(())
+                    }).await.unwrap()
+                }
+            }
+        }).await
+    }
+
+    /// 触发后台保存快照
+    /// 
+    /// 使用 BGSAVE 命令在后台创建 RDB 快照文件。
+    /// 这个命令不会阻塞服务器，会立即返回。
+    /// 
+    /// # 返回值
+    /// 
+    /// 成功时返回 `Ok(())`。
+    /// 
+    /// # 使用场景
+    /// 
+    /// - 定期数据备份
+    /// - 手动触发持久化
+    /// - 数据迁移前的快照
+    /// 
+    /// # 使用示例
+    /// 
+    /// ```rust
+    /// redis.bgsave().await?;
+    /// println!("Background save started");
+    /// ```
+    /// 
+    /// # 注意事项
+    /// 
+    /// - 快照创建是异步的，命令立即返回
+    /// - 大型数据库可能需要较长时间完成
+    /// - 可以通过 LASTSAVE 命令检查最后一次保存时间
+    pub async fn bgsave(&self) -> Result<()> {
+        self.check_writable("BGSAVE")?;
+        self.with_retry(|| async {
+            match &self.kind {
+                ConnectionKind::Standalone(pool, _) => {
+                    let mut conn = pool.get();
+                    Cmd::new().arg("BGSAVE").query_async::<()>(&mut conn).await.context("BGSAVE")?;
+                    Ok(())
+                }
+                ConnectionKind::Cluster(client) => {
+                    let client = client.clone();
+                    
+                    tokio::task::spawn_blocking(move || -> Result<()> {
+                        let mut conn = client.get_connection().context("get cluster connection")?;
+                        Cmd::new().arg("BGSAVE").query::<()>(&mut conn).context("BGSAVE")?;
+                        Ok(())
+                    }).await.unwrap()
+                }
+            }
+        }).await
+    }
+
+    /// 同步保存快照
+    /// 
+    /// 使用 SAVE 命令在前台创建 RDB 快照文件，保存完成后才返回。
+    /// 
+    /// # 注意事项
+    /// 
+    /// - SAVE 会阻塞整个 Redis 服务器，期间不处理任何其他客户端的命令
+    /// - 大型数据库可能阻塞数秒甚至更久，生产环境应优先使用 `bgsave`
+    /// - 已有后台保存在进行时服务端会返回错误
+    pub async fn save(&self) -> Result<()> {
+        self.check_writable("SAVE")?;
+        self.query_cmd(0, redis::cmd("SAVE"), "SAVE").await
+    }
+
+    /// 获取最近一次成功保存快照的时间（LASTSAVE 命令）
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回 Unix 时间戳（秒）。可在 `bgsave` 后轮询此值，变化即表示后台保存已完成。
+    pub async fn lastsave(&self) -> Result<i64> {
+        self.query_cmd(0, redis::cmd("LASTSAVE"), "LASTSAVE").await
+    }
+
+    /// 获取服务端当前时间（TIME 命令）
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回 `(Unix 秒, 微秒)`。
+    pub async fn server_time(&self) -> Result<(i64, i64)> {
+        self.query_cmd(0, redis::cmd("TIME"), "TIME").await
+    }
+
+    /// 获取服务端时间并计算与本地时钟的偏差
+    pub async fn server_time_skew(&self) -> Result<ServerTime> {
+        let before = unix_millis_f64();
+        let (seconds, microseconds) = self.server_time().await?;
+        let after = unix_millis_f64();
+        Ok(ServerTime::new(seconds, microseconds, (before + after) / 2.0))
+    }
+
+    /// 让服务端休眠指定秒数（DEBUG SLEEP 命令）
+    /// 
+    /// 用于模拟慢服务器，便于测试界面的加载状态和超时处理。
+    /// 
+    /// # 参数
+    /// 
+    /// - `seconds`: 休眠时间（秒，可为小数），范围 `0..=DEBUG_SLEEP_MAX_SECS`
+    /// 
+    /// # 注意事项
+    /// 
+    /// - DEBUG SLEEP 会阻塞整个服务端，期间所有客户端的命令都无法执行，只应在测试实例上使用
+    /// - Redis 7 起默认禁用 DEBUG 命令（`enable-debug-command`），此时返回服务端错误
+    /// - 使用独立连接执行且不自动重试，避免重试时重复休眠
+    pub async fn debug_sleep(&self, seconds: f64) -> Result<()> {
+        self.check_policy("DEBUG SLEEP")?;
+        self.check_writable("DEBUG SLEEP")?;
+        if !seconds.is_finite() || !(0.0..=DEBUG_SLEEP_MAX_SECS).contains(&seconds) {
+            return Err(invalid_argument(format!("DEBUG SLEEP seconds must be between 0 and {}", DEBUG_SLEEP_MAX_SECS)));
+        }
+        let mut cmd = redis::cmd("DEBUG");
+        cmd.arg("SLEEP").arg(seconds);
+        match &self.kind {
+            ConnectionKind::Standalone(_, client) => {
+                let mut conn = Self::db_connection(client, 0).await?;
+                let _: () = cmd.query_async(&mut conn).await.context("DEBUG SLEEP")?;
+                Ok(())
+            }
+            ConnectionKind::Cluster(client) => {
+                let client = client.clone();
+                tokio::task::spawn_blocking(move || -> Result<()> {
+                    let mut conn = client.get_connection().context("get cluster connection")?;
+                    let _: () = cmd.query(&mut conn).context("DEBUG SLEEP")?;
+                    Ok(())
+                }).await.unwrap()
+            }
+        }
+    }
+
+    /// 触发后台 AOF 重写（BGREWRITEAOF 命令）
+    /// 
+    /// 在后台重写 AOF 文件以压缩体积，命令立即返回。
+    /// 若当前正在执行后台快照，服务端会将重写排期到快照完成之后，
+    /// 可通过 `persistence_status` 的 `aof_rewrite_scheduled` / `aof_rewrite_in_progress` 观察进度。
+    pub async fn bgrewriteaof(&self) -> Result<()> {
+        self.query_cmd(0, redis::cmd("BGREWRITEAOF"), "BGREWRITEAOF").await
+    }
+
+    /// 获取 RDB/AOF 持久化状态
+    /// 
+    /// 执行 `INFO persistence` 并解析为 `PersistenceStatus`。
+    /// 集群模式下结果来自命令被路由到的节点。
+    pub async fn persistence_status(&self) -> Result<PersistenceStatus> {
+        let mut cmd = redis::cmd("INFO");
+        cmd.arg("persistence");
+        let info: String = self.query_cmd(0, cmd, "INFO persistence").await?;
+        Ok(PersistenceStatus::from_info(&info))
+    }
+
+    /// 等待副本确认写入（WAIT 命令）
+    /// 
+    /// 阻塞直到此前通过当前连接执行的写命令被至少 `num_replicas` 个副本确认，
+    /// 或者超过 `timeout_ms` 毫秒。达到指定数量后会立即返回，不会等满超时时间。
+    /// 
+    /// # 参数
+    /// 
+    /// - `num_replicas`: 需要确认的副本数量
+    /// - `timeout_ms`: 超时时间（毫秒），`0` 表示无限等待
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回实际确认的副本数量，超时时可能小于 `num_replicas`。
+    /// 
+    /// # 使用示例
+    /// 
+    /// ```rust
+    /// redis.set(0, "order:1", "paid", None, false).await?;
+    /// let acked = redis.wait(1, 1000).await?;
+    /// ```
+    /// 
+    /// # 注意事项
+    /// 
+    /// - WAIT 只统计同一连接上的写入。单机模式下写入会轮询分布在连接池的各条连接上，
+    ///   因此在每条连接上并发执行 WAIT 并返回最小的确认数，覆盖 0 号库上通过本服务执行的写入
+    /// - 集群模式下命令由客户端路由，结果对应被路由到的主节点
+    pub async fn wait(&self, num_replicas: usize, timeout_ms: u64) -> Result<i64> {
+        let mut cmd = redis::cmd("WAIT");
+        cmd.arg(num_replicas).arg(timeout_ms);
+        match &self.kind {
+            ConnectionKind::Standalone(pool, _) => {
+                let acks = futures::future::try_join_all(pool.all().iter().map(|manager| {
+                    let cmd = cmd.clone();
+                    let mut conn = manager.clone();
+                    async move { cmd.query_async::<i64>(&mut conn).await.context("WAIT") }
+                })).await?;
+                Ok(acks.into_iter().min().unwrap_or(0))
+            }
+            ConnectionKind::Cluster(_) => self.query_cmd(0, cmd, "WAIT").await,
+        }
+    }
+
+    // --- 命令元信息 ---
+
+    /// 获取服务端支持的命令数量（COMMAND COUNT 命令）
+    pub async fn command_count(&self) -> Result<i64> {
+        let mut cmd = redis::cmd("COMMAND");
+        cmd.arg("COUNT");
+        self.query_cmd(0, cmd, "COMMAND COUNT").await
+    }
+
+    /// 获取服务端支持的命令名称列表
+    /// 
+    /// 优先使用 Redis 7.0 的 `COMMAND LIST`；旧版本不支持时回退到 `COMMAND`，取每条命令信息的名称。
+    /// 返回的名称为小写并按字母排序，可用于命令面板的自动补全。
+    pub async fn command_list(&self) -> Result<Vec<String>> {
+        let mut cmd = redis::cmd("COMMAND");
+        cmd.arg("LIST");
+        let reply: redis::Value = match self.query_cmd(0, cmd, "COMMAND LIST").await {
+            Ok(reply) => reply,
+            Err(e) => {
+                logging::debug("COMMAND", &format!("COMMAND LIST unavailable, falling back to COMMAND: {}", e));
+                self.query_cmd(0, redis::cmd("COMMAND"), "COMMAND").await?
+            }
+        };
+        let mut names = parse_command_names(reply)?;
+        names.sort();
+        names.dedup();
+        Ok(names)
+    }
+
+    /// 执行任意命令并返回回复结构的调试文本
+    /// 
+    /// 与转换为 JSON 不同，保留 RESP 的类型与嵌套，例如 `Array[BulkString("a"), Int(5), Nil]`，
+    /// 便于排查模块命令或非常规回复。
+    /// 
+    /// # 参数
+    /// 
+    /// - `args`: 命令及参数，如 `["HGETALL", "user:1"]`，不能为空
+    pub async fn run_raw_command_debug(&self, db: u32, args: &[String]) -> Result<String> {
+        let cmd = self.raw_command(args)?;
+        let reply: Result<redis::Value> = self.query_cmd(db, cmd, "RAW").await;
+        // 无法可靠地得知任意命令修改了哪些键，直接清空读缓存
+        self.clear_cached();
+        Ok(render_value(&reply?))
+    }
+
+    /// 执行任意命令，同时返回耗时与处理该命令的节点
+    /// 
+    /// 输出与 `run_raw_command_debug` 相同，`ResponseMeta` 供命令面板展示：
+    /// - `duration_ms` 只统计最后一次尝试的往返时间，不含策略检查、重试等待与节点查询
+    /// - 集群模式下根据命令的键计算槽位，再从缓存的槽位归属（见 `slot_owners`）中找到负责该槽位的主节点；
+    ///   命令不涉及键、开启 `read_from_replicas` 的只读命令（实际节点无法确定）或查询失败时为 `None`
+    pub async fn run_raw_command_timed(&self, db: u32, args: &[String]) -> Result<(String, ResponseMeta)> {
+        let cmd = self.raw_command(args)?;
+        let attempt_nanos = AtomicU64::new(0);
+        let reply: Result<redis::Value> = self.with_retry(|| async {
+            let started = Instant::now();
+            let reply = self.exec_cmd(db, &cmd, "RAW").await;
+            attempt_nanos.store(started.elapsed().as_nanos() as u64, Ordering::Relaxed);
+            reply
+        }).await;
+        let duration_ms = attempt_nanos.load(Ordering::Relaxed) as f64 / 1_000_000.0;
+        self.clear_cached();
+        let reply = reply?;
+
+        let node = match &self.kind {
+            ConnectionKind::Cluster(_) => self.serving_node(&cmd).await,
+            ConnectionKind::Standalone(..) => None,
+        };
+        Ok((render_value(&reply), ResponseMeta { duration_ms, node }))
+    }
+
+    /// 按命令策略与只读设置检查任意命令，通过后构造命令
+    fn raw_command(&self, args: &[String]) -> Result<Cmd> {
+        let Some((name, rest)) = args.split_first() else {
+            return Err(invalid_argument("command must not be empty"));
+        };
+        match rest.first() {
+            Some(sub) => self.check_policy(&format!("{} {}", name, sub))?,
+            None => self.check_policy(name)?,
+        }
+        if is_write_command(name, rest) {
+            self.check_writable(&name.to_ascii_uppercase())?;
+        }
+        let mut cmd = redis::cmd(name);
+        cmd.arg(rest);
+        Ok(cmd)
+    }
+
+    /// 集群模式下返回负责该命令所涉及槽位的主节点地址
+    /// 
+    /// 槽位归属缓存在 `slot_owners` 中，超过 `SLOT_OWNERS_TTL` 或不包含目标槽位时才重新执行 `CLUSTER NODES`，
+    /// 因此连续执行命令不会每次都多一次往返。
+    async fn serving_node(&self, cmd: &Cmd) -> Option<String> {
+        let Some(RoutingInfo::SingleNode(SingleNodeRoutingInfo::SpecificNode(route))) = RoutingInfo::for_routable(cmd) else {
+            return None;
+        };
+        if self.cfg.read_from_replicas && !matches!(route.slot_addr(), SlotAddr::Master) {
+            return None;
+        }
+        let slot = route.slot();
+        if let Some(owners) = self.slot_owners.lock().unwrap().as_ref() {
+            if owners.fetched_at.elapsed() < SLOT_OWNERS_TTL {
+                if let Some(addr) = owners.lookup(slot) {
+                    return Some(addr);
+                }
+            }
+        }
+
+        let nodes = match self.get_cluster_nodes().await {
+            Ok(nodes) => nodes,
+            Err(e) => {
+                logging::debug("RAW", &format!("resolve serving node failed: {}", e));
+                return None;
+            }
+        };
+        let owners = SlotOwners {
+            fetched_at: Instant::now(),
+            ranges: nodes.into_iter()
+                .filter(|n| n.role == Some(NodeRole::Master))
+                .flat_map(|n| {
+                    let addr = format!("{}:{}", n.ip, n.port);
+                    n.slot_ranges.into_iter().map(move |(start, end)| (start, end, addr.clone()))
+                })
+                .collect(),
+        };
+        let addr = owners.lookup(slot);
+        *self.slot_owners.lock().unwrap() = Some(owners);
+        addr
+    }
+
+    // --- 延迟监控 ---
+
+    /// 获取指定事件的延迟历史（LATENCY HISTORY 命令）
+    /// 
+    /// # 参数
+    /// 
+    /// - `event`: 事件名称，如 `command`、`fast-command`、`expire-cycle`
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回 `(Unix 时间戳秒, 延迟毫秒)` 列表，按时间先后排列。
+    /// 
+    /// # 注意事项
+    /// 
+    /// 只有 `latency-monitor-threshold` 大于 0 时服务端才会记录延迟事件，否则结果为空。
+    pub async fn latency_history(&self, event: &str) -> Result<Vec<(i64, i64)>> {
+        let mut cmd = redis::cmd("LATENCY");
+        cmd.arg("HISTORY").arg(event);
+        let reply: redis::Value = self.query_cmd(0, cmd, "LATENCY HISTORY").await?;
+        parse_latency_history(reply)
+    }
+
+    /// 清空全部事件的延迟记录（LATENCY RESET 命令）
+    /// 
+    /// 返回被清空的事件数量。
+    pub async fn latency_reset(&self) -> Result<i64> {
+        self.check_writable("LATENCY RESET")?;
+        let mut cmd = redis::cmd("LATENCY");
+        cmd.arg("RESET");
+        self.query_cmd(0, cmd, "LATENCY RESET").await
+    }
+
+    // --- ACL 用户管理 ---
+
+    /// 列出所有 ACL 用户规则（ACL LIST 命令）
+    /// 
+    /// 返回每个用户一行的规则描述，如 `user default on nopass ~* &* +@all`。需要 Redis 6.0 及以上版本。
+    /// 
+    /// # 注意事项
+    /// 
+    /// ACL 配置属于单个节点，集群模式下以下 ACL 命令只作用于命令被路由到的节点。
+    pub async fn acl_list(&self) -> Result<Vec<String>> {
+        let mut cmd = redis::cmd("ACL");
+        cmd.arg("LIST");
+        self.query_cmd(0, cmd, "ACL LIST").await
+    }
+
+    /// 获取单个用户的 ACL 详情（ACL GETUSER 命令）
+    /// 
+    /// 将嵌套的回复（`flags`、`passwords`、`commands`、`keys`、`channels`、`selectors` 等）
+    /// 解析为字段名到 JSON 值的映射。用户不存在时返回空映射。
+    pub async fn acl_getuser(&self, user: &str) -> Result<HashMap<String, serde_json::Value>> {
+        let mut cmd = redis::cmd("ACL");
+        cmd.arg("GETUSER").arg(user);
+        let reply: redis::Value = self.query_cmd(0, cmd, "ACL GETUSER").await?;
+        parse_acl_getuser(reply)
+    }
+
+    /// 创建或修改 ACL 用户（ACL SETUSER 命令）
+    /// 
+    /// # 参数
+    /// 
+    /// - `user`: 用户名，不存在时创建
+    /// - `rules`: 依次应用的规则，如 `["on", ">secret", "~cache:*", "+get"]`
+    pub async fn acl_setuser(&self, user: &str, rules: &[String]) -> Result<()> {
+        self.check_writable("ACL SETUSER")?;
+        self.check_policy("ACL SETUSER")?;
+        let mut cmd = redis::cmd("ACL");
+        cmd.arg("SETUSER").arg(user).arg(rules);
+        self.query_cmd(0, cmd, "ACL SETUSER").await
+    }
+
+    /// 删除 ACL 用户（ACL DELUSER 命令）
+    /// 
+    /// 返回实际删除的用户数量。`default` 用户不能被删除。
+    pub async fn acl_deluser(&self, user: &str) -> Result<i64> {
+        self.check_writable("ACL DELUSER")?;
+        self.check_policy("ACL DELUSER")?;
+        let mut cmd = redis::cmd("ACL");
+        cmd.arg("DELUSER").arg(user);
+        self.query_cmd(0, cmd, "ACL DELUSER").await
+    }
+
+    // --- 性能测试 ---
+
+    /// 对当前连接执行 SET/GET 性能测试
+    /// 
+    /// 启动 `concurrency` 个并发任务，共完成 `ops` 次操作（每次为一个 SET + GET），
+    /// 每个任务只读写自己的一个临时键，结束后删除这些键。
+    /// 
+    /// # 限制
+    /// 
+    /// - `ops`、`concurrency`、`value_size` 分别不能超过 `BENCHMARK_MAX_OPS`、
+    ///   `BENCHMARK_MAX_CONCURRENCY`、`BENCHMARK_MAX_VALUE_SIZE`
+    /// - 临时键带有 `BENCHMARK_KEY_TTL` 秒的过期时间，即使清理未执行也会自动删除
+    /// - 调用 `cancel_benchmark` 后各任务在当前操作完成后停止，结果中 `cancelled` 为 `true`；
+    ///   取消只作用于调用时正在运行的测试，没有测试运行时的取消请求不会留到下一次
+    /// - 同一连接上同时只应运行一个性能测试，`cancel_benchmark` 只能取消最近开始的一次
+    pub async fn benchmark(&self, db: u32, ops: u64, concurrency: usize, value_size: usize) -> Result<BenchmarkResult> {
+        self.check_writable("SET")?;
+        if ops == 0 || ops > BENCHMARK_MAX_OPS {
+            return Err(invalid_argument(format!("ops must be between 1 and {}", BENCHMARK_MAX_OPS)));
+        }
+        if concurrency == 0 || concurrency > BENCHMARK_MAX_CONCURRENCY {
+            return Err(invalid_argument(format!("concurrency must be between 1 and {}", BENCHMARK_MAX_CONCURRENCY)));
+        }
+        if value_size > BENCHMARK_MAX_VALUE_SIZE {
+            return Err(invalid_argument(format!("value_size must not exceed {} bytes", BENCHMARK_MAX_VALUE_SIZE)));
+        }
+
+        let concurrency = concurrency.min(ops as usize);
+        let value = "x".repeat(value_size);
+        let run_id = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or(0);
+        let keys: Vec<String> = (0..concurrency).map(|i| format!("__redis_mate_bench__:{}:{}", run_id, i)).collect();
+
+        // 本次运行独有的取消标志
+        let cancel = Arc::new(AtomicBool::new(false));
+        *self.benchmark_cancel.lock().unwrap() = Some(cancel.clone());
+
+        let started = Instant::now();
+        let mut workers = tokio::task::JoinSet::new();
+        for (i, key) in keys.iter().enumerate() {
+            // 前 ops % concurrency 个任务多执行一次
+            let count = ops / concurrency as u64 + u64::from((i as u64) < ops % concurrency as u64);
+            let svc = self.clone();
+            let cancel = cancel.clone();
+            let key = key.clone();
+            let value = value.clone();
+            workers.spawn(async move {
+                let mut latencies = Vec::with_capacity(count as usize);
+                for _ in 0..count {
+                    if cancel.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    let op_started = Instant::now();
+                    svc.set(db, &key, value.clone(), Some(BENCHMARK_KEY_TTL), false).await?;
+                    let _: Option<String> = svc.get(db, &key).await?;
+                    latencies.push(op_started.elapsed().as_micros() as u64);
+                }
+                Ok::<_, anyhow::Error>(latencies)
+            });
+        }
+
+        let mut latencies = Vec::with_capacity(ops as usize);
+        let mut failure = None;
+        while let Some(joined) = workers.join_next().await {
+            match joined.context("benchmark task") {
+                Ok(Ok(part)) => latencies.extend(part),
+                Ok(Err(e)) | Err(e) => {
+                    // 让其他任务尽快停止
+                    cancel.store(true, Ordering::Relaxed);
+                    failure.get_or_insert(e);
+                }
+            }
+        }
+        let elapsed = started.elapsed();
+        let cancelled = failure.is_none() && cancel.load(Ordering::Relaxed);
+        // 只移除自己的标志，之后开始的运行已替换了它
+        {
+            let mut current = self.benchmark_cancel.lock().unwrap();
+            if current.as_ref().is_some_and(|c| Arc::ptr_eq(c, &cancel)) {
+                *current = None;
+            }
+        }
+
+        for key in &keys {
+            if let Err(e) = self.del(db, key).await {
+                logging::warn("BENCHMARK", &format!("failed to clean up {}: {}", key, e));
+            }
+        }
+        if let Some(e) = failure {
+            return Err(e.context("benchmark"));
+        }
+
+        latencies.sort_unstable();
+        let secs = elapsed.as_secs_f64();
+        Ok(BenchmarkResult {
+            total_ops: latencies.len() as u64,
+            duration_ms: secs * 1000.0,
+            ops_per_sec: if secs > 0.0 { latencies.len() as f64 / secs } else { 0.0 },
+            p50_ms: percentile(&latencies, 0.50) as f64 / 1000.0,
+            p99_ms: percentile(&latencies, 0.99) as f64 / 1000.0,
+            cancelled,
+        })
+    }
+
+    /// 请求停止当前连接上正在运行的性能测试
+    /// 
+    /// 返回是否有测试正在运行；没有时不做任何事，不会影响之后开始的测试。
+    pub fn cancel_benchmark(&self) -> bool {
+        match self.benchmark_cancel.lock().unwrap().as_ref() {
+            Some(cancel) => {
+                cancel.store(true, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // --- 健康检查 ---
+
+    /// Ping 命令健康检查
+    /// 
+    /// 使用 PING 命令检查 Redis 服务器的连接状态。
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回 "PONG" 响应字符串。
+    /// 
+    /// 单机与集群模式均发送标准的 PING 命令，不会写入任何键。
+    /// 
+    /// # 使用示例
+    /// 
+    /// ```rust
+    /// let pong = redis.ping().await?;
+    /// assert_eq!(pong, "PONG");
+    /// ```
+    pub async fn ping(&self) -> Result<String> {
+        self.with_retry(|| async {
+            match &self.kind {
+                ConnectionKind::Standalone(pool, _) => {
+                    let mut conn = pool.get();
+                    let res: String = redis::cmd("PING").query_async(&mut conn).await.context("PING")?;
+                    Ok(res)
+                }
+                ConnectionKind::Cluster(client) => {
+                    let client = client.clone();
+                    
+                    tokio::task::spawn_blocking(move || -> Result<String> {
+                        let mut conn = client.get_connection().context("get cluster connection")?;
+                        let res: String = Cmd::new().arg("PING").query(&mut conn).context("PING")?;
+                        Ok(res)
+                    }).await.unwrap()
+                }
+            }
+        }).await
+    }
+
+    /// 测量一次 PING 的往返耗时
+    /// 
+    /// 返回毫秒数，包含连接获取与重试的耗时，可用于连接状态指示。
+    pub async fn ping_latency(&self) -> Result<f64> {
+        let start = Instant::now();
+        let pong = self.ping().await?;
+        let elapsed = start.elapsed().as_secs_f64() * 1000.0;
+        if pong != "PONG" {
+            return Err(anyhow!("Unexpected PING response: {}", pong));
+        }
+        Ok(elapsed)
+    }
+}
+
+/// `with_retry` 的重试循环
+/// 
+/// 每次尝试都计入 `metrics`；不可恢复的错误直接返回，可恢复的错误最多重试 `retries` 次。
+async fn run_with_retry<F, Fut, T>(retries: u32, delay: Duration, metrics: &MetricsRecorder, mut f: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    let mut attempts = 0;
+
+    loop {
+        let started = Instant::now();
+        let res = f().await;
+        metrics.record(started.elapsed(), res.is_ok());
+        match res {
+            Ok(v) => return Ok(v),
+            Err(e) => {
+                attempts += 1;
+
+                // 不可恢复的错误或超过重试次数时直接返回
+                if attempts > retries || !is_retryable(&e) {
+                    return Err(e);
+                }
+
+                // 等待重试延迟
+                logging::warn("REDIS_RETRY", &format!("attempt {} failed: {}", attempts, e));
+                tokio::time::sleep(delay).await;
+            }
+        }
+    }
+}
+
+/// 判断错误是否为服务端不认识该命令（旧版本或未加载对应模块）
+fn is_unknown_command(e: &anyhow::Error) -> bool {
+    e.chain().any(|cause| cause.to_string().to_lowercase().contains("unknown command"))
+}
+
+/// 判断错误是否为按数值排序时遇到非数字元素（`One or more scores can't be converted into double`）
+fn is_not_numeric(e: &anyhow::Error) -> bool {
+    e.chain().any(|cause| cause.to_string().to_lowercase().contains("converted into double"))
+}
+
+/// 原始命令中只读取数据的命令，只读连接上只允许执行这些命令
+/// 
+/// 采用允许列表而不是写命令列表：新版本或模块新增的命令默认视为写命令，宁可误拒也不误放。
+const READ_COMMANDS: &[&str] = &[
+    "BITCOUNT", "BITFIELD_RO", "BITPOS", "DBSIZE", "DUMP", "ECHO", "EVALSHA_RO", "EVAL_RO", "EXISTS", "EXPIRETIME",
+    "FCALL_RO", "GEODIST", "GEOHASH", "GEOPOS", "GEORADIUSBYMEMBER_RO", "GEORADIUS_RO", "GEOSEARCH", "GET", "GETBIT",
+    "GETRANGE", "HEXISTS", "HEXPIRETIME", "HGET", "HGETALL", "HKEYS", "HLEN", "HMGET", "HPEXPIRETIME", "HPTTL",
+    "HRANDFIELD", "HSCAN", "HSTRLEN", "HTTL", "HVALS", "INFO", "KEYS", "LASTSAVE", "LCS", "LINDEX", "LLEN", "LPOS",
+    "LRANGE", "MGET", "PEXPIRETIME", "PFCOUNT", "PING", "PTTL", "RANDOMKEY", "ROLE", "SCAN", "SCARD", "SDIFF", "SINTER",
+    "SINTERCARD", "SISMEMBER", "SMEMBERS", "SMISMEMBER", "SORT_RO", "SRANDMEMBER", "SSCAN", "STRLEN", "SUBSTR", "SUNION",
+    "TIME", "TOUCH", "TTL", "TYPE", "XLEN", "XPENDING", "XRANGE", "XREAD", "XREVRANGE", "ZCARD", "ZCOUNT", "ZDIFF",
+    "ZINTER", "ZINTERCARD", "ZLEXCOUNT", "ZMSCORE", "ZRANDMEMBER", "ZRANGE", "ZRANGEBYLEX", "ZRANGEBYSCORE", "ZRANK",
+    "ZREVRANGE", "ZREVRANGEBYLEX", "ZREVRANGEBYSCORE", "ZREVRANK", "ZSCAN", "ZSCORE", "ZUNION",
+    "COMMAND", "OBJECT", "PUBSUB", "XINFO",
+    "JSON.ARRINDEX", "JSON.ARRLEN", "JSON.DEBUG", "JSON.GET", "JSON.MGET", "JSON.OBJKEYS", "JSON.OBJLEN", "JSON.RESP",
+    "JSON.STRLEN", "JSON.TYPE",
+];
+
+/// 原始命令中只读取状态的子命令，如 `CONFIG GET`；同一命令的其他子命令视为写命令
+const READ_SUBCOMMANDS: &[(&str, &str)] = &[
+    ("ACL", "CAT"), ("ACL", "GETUSER"), ("ACL", "LIST"), ("ACL", "USERS"), ("ACL", "WHOAMI"),
+    ("CLIENT", "GETNAME"), ("CLIENT", "ID"), ("CLIENT", "INFO"), ("CLIENT", "LIST"),
+    ("CLUSTER", "COUNTKEYSINSLOT"), ("CLUSTER", "GETKEYSINSLOT"), ("CLUSTER", "INFO"), ("CLUSTER", "KEYSLOT"),
+    ("CLUSTER", "LINKS"), ("CLUSTER", "MYID"), ("CLUSTER", "NODES"), ("CLUSTER", "SHARDS"), ("CLUSTER", "SLOTS"),
+    ("CONFIG", "GET"), ("DEBUG", "OBJECT"), ("FUNCTION", "DUMP"), ("FUNCTION", "LIST"), ("FUNCTION", "STATS"),
+    ("LATENCY", "DOCTOR"), ("LATENCY", "HISTORY"), ("LATENCY", "LATEST"), ("MEMORY", "DOCTOR"), ("MEMORY", "STATS"),
+    ("MEMORY", "USAGE"), ("MODULE", "LIST"), ("SCRIPT", "EXISTS"), ("SENTINEL", "GET-MASTER-ADDR-BY-NAME"),
+    ("SENTINEL", "MASTER"), ("SENTINEL", "MASTERS"), ("SENTINEL", "REPLICAS"), ("SENTINEL", "SENTINELS"),
+    ("SENTINEL", "SLAVES"), ("SLOWLOG", "GET"), ("SLOWLOG", "LEN"),
+];
+
+/// 判断原始命令是否为写命令
+/// 
+/// `rest` 为命令名之后的参数。名称不区分大小写，不在 `READ_COMMANDS` / `READ_SUBCOMMANDS` 中的命令都视为写命令。
+/// 读写取决于参数的命令单独判断：带过期选项的 `GETEX`、带 `STORE` 的 `SORT`，
+/// 以及带 `STORE` / `STOREDIST` 的 `GEORADIUS` / `GEORADIUSBYMEMBER`。
+fn is_write_command(name: &str, rest: &[String]) -> bool {
+    let name = name.to_ascii_uppercase();
+    let has_arg = |wanted: &[&str]| rest.iter().any(|a| wanted.iter().any(|w| a.eq_ignore_ascii_case(w)));
+    match name.as_str() {
+        "GETEX" => return rest.len() > 1,
+        "SORT" => return has_arg(&["STORE"]),
+        "GEORADIUS" | "GEORADIUSBYMEMBER" => return has_arg(&["STORE", "STOREDIST"]),
+        _ => {}
+    }
+    if READ_COMMANDS.contains(&name.as_str()) {
+        return false;
+    }
+    let sub = rest.first().map(|s| s.to_ascii_uppercase()).unwrap_or_default();
+    !READ_SUBCOMMANDS.iter().any(|(n, s)| *n == name && *s == sub)
+}
+
+/// 判断错误链中是否包含指定错误前缀（如 `BUSYGROUP`）的服务端错误
+fn is_server_error(e: &anyhow::Error, code: &str) -> bool {
+    e.chain().any(|cause| cause.downcast_ref::<redis::RedisError>().and_then(|r| r.code()) == Some(code))
+}
+
+/// 判断错误是否为值得重试的临时故障
+/// 
+/// 只有连接、超时、IO、集群暂不可用（`TRYAGAIN`、`CLUSTERDOWN`、`LOADING`、`MASTERDOWN`）
+/// 以及槽位迁移中的重定向（`MOVED`、`ASK`，重试时集群连接会使用刷新后的槽位映射）才会重试；
+/// `WRONGTYPE`、认证失败、语法错误以及参数校验等错误重试也不会成功，立即返回。
+fn is_retryable(e: &anyhow::Error) -> bool {
+    for cause in e.chain() {
+        if let Some(re) = cause.downcast_ref::<redis::RedisError>() {
+            if re.kind() == redis::ErrorKind::AuthenticationFailed {
+                return false;
+            }
+            if re.is_io_error() || re.is_timeout() || re.is_connection_dropped() || re.is_connection_refusal() {
+                return true;
+            }
+            return matches!(re.code(), Some("TRYAGAIN" | "CLUSTERDOWN" | "LOADING" | "MASTERDOWN" | "MOVED" | "ASK"));
+        }
+        if cause.is::<std::io::Error>() || cause.is::<tokio::time::error::Elapsed>() || cause.is::<ConnectTimeout>() {
+            return true;
+        }
+    }
+    false
+}
+
+/// 订阅的消息处理循环
+/// 
+/// 逐条回调 `stream` 中的消息；流结束（连接断开）后等待 `delay`，通过 `reconnect` 重建订阅，
+/// 失败则继续等待重试，成功后调用 `on_resubscribed`。回调返回 `false` 时结束。
+pub(crate) async fn run_subscription<T, S, C, Fut, F, R>(mut stream: S, mut reconnect: C, delay: Duration, mut callback: F, mut on_resubscribed: R)
+where
+    S: futures::Stream<Item = T> + Unpin,
+    C: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<S>>,
+    F: FnMut(T) -> bool,
+    R: FnMut(),
+{
+    loop {
+        while let Some(payload) = stream.next().await {
+            // 执行回调，如果返回 false 则停止订阅
+            if !callback(payload) {
+                return;
+            }
+        }
+
+        logging::warn("PUBSUB", "subscription stream ended, reconnecting");
+        stream = loop {
+            tokio::time::sleep(delay).await;
+            match reconnect().await {
+                Ok(stream) => break stream,
+                Err(e) => logging::warn("PUBSUB", &format!("resubscribe failed: {}", e)),
+            }
+        };
+        logging::info("PUBSUB", "resubscribed");
+        on_resubscribed();
+    }
+}
+
+/// 将连接地址解析为 `ConnectionInfo`，并应用配置中的协议版本
+/// 
+/// 地址路径中的数据库索引会被去掉：连接池固定在 0 号库，其他数据库通过 `SELECT` 访问，
+/// 默认数据库由 `RedisConfig::default_db` 决定。
+fn connection_info(url: &str, cfg: &RedisConfig) -> Result<ConnectionInfo> {
+    let (url, _) = split_url_db(url)?;
+    let info = url.as_str().into_connection_info().context("parse redis url")?;
+    let redis = info.redis_settings().clone().set_protocol(cfg.protocol());
+    Ok(info.set_redis_settings(redis))
+}
+
+/// 拆分地址路径中的数据库索引
+/// 
+/// `redis://host:6379/3` 返回 `("redis://host:6379", Some(3))`，查询参数保留在返回的地址中；
+/// 没有路径、路径为空或不是 `redis`/`rediss` 地址（如 `unix://`）时原样返回，索引为 `None`。
+fn split_url_db(url: &str) -> Result<(String, Option<u32>)> {
+    let Some((scheme, rest)) = url.split_once("://") else { return Ok((url.to_string(), None)) };
+    if !matches!(scheme, "redis" | "rediss") {
+        return Ok((url.to_string(), None));
+    }
+    let (head, query) = rest.split_at(rest.find('?').unwrap_or(rest.len()));
+    let host_start = head.rfind('@').map_or(0, |at| at + 1);
+    let Some(slash) = head[host_start..].find('/') else { return Ok((url.to_string(), None)) };
+    let (authority, path) = head.split_at(host_start + slash);
+    let stripped = format!("{}://{}{}", scheme, authority, query);
+    let path = &path[1..];
+    if path.is_empty() {
+        return Ok((stripped, None));
+    }
+    let db = path.parse::<u32>().map_err(|_| anyhow!("Invalid database index in url: {}", path))?;
+    Ok((stripped, Some(db)))
+}
+
+/// 计算键所属的集群槽位（CRC16 % 16384）
+/// 
+/// 遵循 Redis 集群的 hash tag 规则：若键中包含非空的 `{...}`，
+/// 仅对第一对花括号内的内容计算哈希。
+pub(crate) fn key_slot(key: &str) -> u16 {
+    key_slot_bytes(key.as_bytes())
+}
+
+/// 计算二进制键名所属的集群槽位，规则同 `key_slot`
+fn key_slot_bytes(bytes: &[u8]) -> u16 {
+    let hashed = match bytes.iter().position(|&b| b == b'{') {
+        Some(open) => match bytes[open + 1..].iter().position(|&b| b == b'}') {
+            Some(len) if len > 0 => &bytes[open + 1..open + 1 + len],
+            _ => bytes,
+        },
+        None => bytes,
+    };
+    let mut crc: u16 = 0;
+    for &b in hashed {
+        crc ^= (b as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x1021 } else { crc << 1 };
+        }
+    }
+    crc % CLUSTER_SLOTS
+}
+
+/// 集群槽位总数
+const CLUSTER_SLOTS: u16 = 16384;
+
+/// 校验槽位编号位于 `0..16384` 范围内
+fn check_slot(slot: u16) -> Result<()> {
+    if slot >= CLUSTER_SLOTS {
+        return Err(invalid_argument(format!("Invalid slot {}: must be in 0..{}", slot, CLUSTER_SLOTS)));
+    }
+    Ok(())
+}
+
+/// 校验并规范化 GEO 距离单位
+fn geo_unit(unit: &str) -> Result<String> {
+    let u = unit.to_ascii_lowercase();
+    match u.as_str() {
+        "m" | "km" | "mi" | "ft" => Ok(u),
+        _ => Err(invalid_argument(format!("Invalid geo unit: {}", unit))),
+    }
+}
+
+/// 构造 `SCAN cursor [MATCH pattern] [COUNT count] [TYPE type]` 命令，空的模式与类型被忽略
+fn scan_cmd(cursor: u64, pattern: Option<String>, count: Option<usize>, type_filter: Option<String>) -> Result<Cmd> {
+    let mut cmd = redis::cmd("SCAN");
+    cmd.arg(cursor);
+    if let Some(p) = &pattern {
+        if !p.is_empty() {
+            cmd.arg("MATCH").arg(p);
+        }
+    }
+    if let Some(c) = count {
+        if c > 0 {
+            cmd.arg("COUNT").arg(c);
+        }
+    }
+    if let Some(t) = &type_filter {
+        if !t.is_empty() {
+            cmd.arg("TYPE").arg(scan_type(t)?);
+        }
+    }
+    Ok(cmd)
+}
+
+/// 校验并规范化 `SCAN ... TYPE` 的类型名
+/// 
+/// 服务端按名称忽略大小写比较，未知类型不会报错而是返回空结果，因此在客户端提前校验。
+fn scan_type(t: &str) -> Result<String> {
+    const TYPES: &[&str] = &["string", "list", "set", "zset", "hash", "stream", "rejson-rl"];
+    let lower = t.to_ascii_lowercase();
+    if TYPES.contains(&lower.as_str()) {
+        Ok(lower)
+    } else {
+        Err(invalid_argument(format!("Invalid key type: {}", t)))
+    }
+}
+
+/// 将单个坐标分量（RESP2 为字符串，RESP3 可能为浮点）解析为 `f64`
+fn geo_coord(v: &redis::Value) -> Result<f64> {
+    match v {
+        redis::Value::BulkString(b) => std::str::from_utf8(b)?.parse::<f64>().context("parse geo coordinate"),
+        redis::Value::SimpleString(s) => s.parse::<f64>().context("parse geo coordinate"),
+        redis::Value::Double(d) => Ok(*d),
+        other => Err(anyhow!("Unexpected geo coordinate: {:?}", other)),
+    }
+}
+
+/// 解析 GEOPOS 的嵌套数组返回值
+/// 
+/// 每个元素为 `nil` 或 `[经度, 纬度]`。
+fn parse_geopos(v: redis::Value) -> Result<Vec<Option<(f64, f64)>>> {
+    let items = match v {
+        redis::Value::Array(items) => items,
+        other => return Err(anyhow!("Unexpected GEOPOS reply: {:?}", other)),
+    };
+    items.iter().map(|item| match item {
+        redis::Value::Nil => Ok(None),
+        redis::Value::Array(pair) if pair.len() == 2 => Ok(Some((geo_coord(&pair[0])?, geo_coord(&pair[1])?))),
+        other => Err(anyhow!("Unexpected GEOPOS entry: {:?}", other)),
+    }).collect()
+}
+
+/// 解析流消息列表 `[[id, [field, value, ...]], ...]`
+/// 
+/// 已删除但仍待确认的消息，其字段部分为 `nil`，解析为空的 `fields`。
+fn parse_stream_entries(v: &redis::Value) -> Result<Vec<StreamEntry>> {
+    let redis::Value::Array(items) = v else {
+        return Err(anyhow!("Unexpected stream entries: {:?}", v));
+    };
+    items.iter().map(|item| {
+        let redis::Value::Array(parts) = item else {
+            return Err(anyhow!("Unexpected stream entry: {:?}", item));
+        };
+        let id = parts.first().and_then(value_string).ok_or_else(|| anyhow!("Missing stream entry id: {:?}", item))?;
+        let fields = match parts.get(1) {
+            Some(redis::Value::Array(kv)) => kv
+                .chunks_exact(2)
+                .map(|pair| Ok((
+                    value_string(&pair[0]).ok_or_else(|| anyhow!("Unexpected stream field: {:?}", pair[0]))?,
+                    value_string(&pair[1]).ok_or_else(|| anyhow!("Unexpected stream value: {:?}", pair[1]))?,
+                )))
+                .collect::<Result<Vec<_>>>()?,
+            Some(redis::Value::Nil) | None => Vec::new(),
+            Some(other) => return Err(anyhow!("Unexpected stream entry fields: {:?}", other)),
+        };
+        Ok(StreamEntry { id, fields })
+    }).collect()
+}
+
+/// 解析 XREAD / XREADGROUP 的回复
+/// 
+/// RESP2 为 `[[key, entries], ...]`，RESP3 为 `{key: entries}`；阻塞超时返回 `nil`，解析为空列表。
+fn parse_xread_reply(v: redis::Value) -> Result<Vec<(String, Vec<StreamEntry>)>> {
+    let pairs: Vec<(redis::Value, redis::Value)> = match v {
+        redis::Value::Nil => return Ok(Vec::new()),
+        redis::Value::Map(pairs) => pairs,
+        redis::Value::Array(items) => items
+            .into_iter()
+            .map(|item| match item {
+                redis::Value::Array(mut kv) if kv.len() == 2 => {
+                    let entries = kv.pop().unwrap();
+                    Ok((kv.pop().unwrap(), entries))
+                }
+                other => Err(anyhow!("Unexpected XREADGROUP stream: {:?}", other)),
+            })
+            .collect::<Result<_>>()?,
+        other => return Err(anyhow!("Unexpected XREADGROUP reply: {:?}", other)),
+    };
+    pairs.iter().map(|(key, entries)| {
+        let key = value_string(key).ok_or_else(|| anyhow!("Unexpected stream key: {:?}", key))?;
+        Ok((key, parse_stream_entries(entries)?))
+    }).collect()
+}
+
+/// 构造 `count_keys` 使用的 SCAN 命令
+fn scan_count_cmd(cursor: u64, pattern: &str) -> Cmd {
+    let mut cmd = redis::cmd("SCAN");
+    cmd.arg(cursor).arg("MATCH").arg(pattern).arg("COUNT").arg(COUNT_KEYS_BATCH);
+    cmd
+}
+
+/// 解析 SCAN 的回复，返回 `(下一个游标, 本页键数量)`
+/// 
+/// 只统计数量，不解码键名，因此二进制键也能正确计数。
+fn parse_scan_reply(v: redis::Value) -> Result<(u64, usize)> {
+    let redis::Value::Array(items) = v else {
+        return Err(anyhow!("Unexpected SCAN reply: {:?}", v));
+    };
+    let cursor = parse_scan_cursor(items.first())?;
+    match items.get(1) {
+        Some(redis::Value::Array(keys)) => Ok((cursor, keys.len())),
+        other => Err(anyhow!("Unexpected SCAN keys: {:?}", other)),
+    }
+}
+
+/// 解析 SCAN 回复中的游标
+fn parse_scan_cursor(v: Option<&redis::Value>) -> Result<u64> {
+    match v {
+        Some(redis::Value::BulkString(b)) => std::str::from_utf8(b)?.parse::<u64>().context("parse SCAN cursor"),
+        Some(redis::Value::SimpleString(s)) => s.parse::<u64>().context("parse SCAN cursor"),
+        Some(redis::Value::Int(n)) => Ok(*n as u64),
+        other => Err(anyhow!("Unexpected SCAN cursor: {:?}", other)),
+    }
+}
+
+/// 解析 SCAN 的回复，返回 `(下一个游标, 本页键名)`
+/// 
+/// 键名保留原始字节，非 UTF-8 的键名也能原样用于后续命令。
+fn parse_scan_keys(v: redis::Value) -> Result<(u64, Vec<Vec<u8>>)> {
+    let redis::Value::Array(items) = v else {
+        return Err(anyhow!("Unexpected SCAN reply: {:?}", v));
+    };
+    let cursor = parse_scan_cursor(items.first())?;
+    let keys = match items.get(1) {
+        Some(redis::Value::Array(keys)) => keys,
+        other => return Err(anyhow!("Unexpected SCAN keys: {:?}", other)),
+    };
+    let keys = keys.iter()
+        .map(|k| match k {
+            redis::Value::BulkString(b) => Ok(b.clone()),
+            redis::Value::SimpleString(s) => Ok(s.clone().into_bytes()),
+            other => Err(anyhow!("Unexpected SCAN key: {:?}", other)),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok((cursor, keys))
+}
+
+/// 返回获取指定类型元素数量的命令，字符串返回字节长度，未知类型返回 `None`
+fn size_command(key_type: &str) -> Option<&'static str> {
+    match key_type {
+        "string" => Some("STRLEN"),
+        "list" => Some("LLEN"),
+        "set" => Some("SCARD"),
+        "zset" => Some("ZCARD"),
+        "hash" => Some("HLEN"),
+        "stream" => Some("XLEN"),
+        _ => None,
+    }
+}
+
+/// 将 `sample_key` 的样本回复转换为字符串列表
+/// 
+/// 哈希的 `HSCAN` 回复为 `[游标, [field, value, ...]]`，转换为 `field: value` 并截取前若干个字段；
+/// 其他类型为字符串或成员数组。
+fn parse_sample(key_type: &str, v: redis::Value) -> Vec<String> {
+    let strings = |items: &[redis::Value]| items.iter().filter_map(value_string).collect::<Vec<_>>();
+    match (key_type, v) {
+        ("hash", redis::Value::Array(reply)) => match reply.get(1) {
+            Some(redis::Value::Array(pairs)) => strings(pairs)
+                .chunks_exact(2)
+                .take(PREVIEW_SAMPLE_SIZE)
+                .map(|pair| format!("{}: {}", pair[0], pair[1]))
+                .collect(),
+            _ => Vec::new(),
+        },
+        (_, redis::Value::Array(items) | redis::Value::Set(items)) => strings(&items),
+        (_, other) => value_string(&other).into_iter().collect(),
+    }
+}
+
+/// 从 `COMMAND LIST`、`COMMAND` 或 `COMMAND DOCS` 的回复中提取命令名称
+/// 
+/// - `COMMAND LIST`：名称数组
+/// - `COMMAND`：每条命令信息为数组，第一个元素是名称
+/// - `COMMAND DOCS`：RESP2 下为名称与文档交替的数组，RESP3 下为以名称为键的 Map
+fn parse_command_names(v: redis::Value) -> Result<Vec<String>> {
+    let name = |v: &redis::Value| value_string(v).map(|s| s.to_ascii_lowercase());
+    match v {
+        redis::Value::Map(entries) => Ok(entries.iter().filter_map(|(k, _)| name(k)).collect()),
+        redis::Value::Array(items) => {
+            let mut names = Vec::with_capacity(items.len());
+            for item in &items {
+                match item {
+                    redis::Value::Array(info) => names.extend(info.first().and_then(name)),
+                    // COMMAND DOCS (RESP2) 中的文档部分
+                    redis::Value::Map(_) => {}
+                    other => names.push(name(other).ok_or_else(|| anyhow!("Unexpected command name: {:?}", other))?),
+                }
+            }
+            Ok(names)
+        }
+        other => Err(anyhow!("Unexpected COMMAND reply: {:?}", other)),
+    }
+}
+
+/// 解析 `LATENCY HISTORY` 的回复
+/// 
+/// 回复为数组，每个元素是 `[时间戳, 延迟毫秒]` 二元数组。
+fn parse_latency_history(v: redis::Value) -> Result<Vec<(i64, i64)>> {
+    let int = |v: &redis::Value| match v {
+        redis::Value::Int(n) => Some(*n),
+        other => value_string(other).and_then(|s| s.parse().ok()),
+    };
+    let redis::Value::Array(items) = v else {
+        return Err(anyhow!("Unexpected LATENCY HISTORY reply: {:?}", v));
+    };
+    items
+        .iter()
+        .map(|item| match item {
+            redis::Value::Array(pair) if pair.len() == 2 => match (int(&pair[0]), int(&pair[1])) {
+                (Some(ts), Some(latency)) => Ok((ts, latency)),
+                _ => Err(anyhow!("Unexpected LATENCY HISTORY sample: {:?}", item)),
+            },
+            other => Err(anyhow!("Unexpected LATENCY HISTORY sample: {:?}", other)),
+        })
+        .collect()
+}
+
+/// 当前本地时间（Unix 毫秒，含小数部分）
+fn unix_millis_f64() -> f64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs_f64() * 1000.0)
+        .unwrap_or(0.0)
+}
+
+/// 计算已排序样本的分位数，样本为空时返回 0
+fn percentile(sorted: &[u64], p: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let idx = ((sorted.len() as f64 * p).ceil() as usize).saturating_sub(1);
+    sorted[idx.min(sorted.len() - 1)]
+}
+
+/// 判断错误是否为服务端返回的 WRONGTYPE
+pub(crate) fn is_wrong_type(e: &anyhow::Error) -> bool {
+    e.chain().any(|cause| {
+        cause.downcast_ref::<redis::RedisError>()
+            .is_some_and(|re| re.code() == Some("WRONGTYPE"))
+    })
+}
+
+/// 解析 `CLUSTER NODES` 的完整回复，跳过格式不正确的行
+fn parse_cluster_nodes(info: &str) -> Vec<ClusterNodeInfo> {
+    info.lines().filter_map(ClusterNodeInfo::parse_line).collect()
+}
+
+/// 解析节点地址 `ip:port@cport[,hostname]`
+/// 
+/// IP 可能是 IPv6（包含冒号），因此从右侧拆分端口；Redis 4.0 之前的格式没有 `@cport`。
+fn parse_node_addr(addr: &str) -> (String, u16, u16) {
+    let addr = addr.split(',').next().unwrap_or_default();
+    let (host_port, cport) = match addr.split_once('@') {
+        Some((host_port, cport)) => (host_port, cport.parse().unwrap_or(0)),
+        None => (addr, 0),
+    };
+    match host_port.rsplit_once(':') {
+        Some((ip, port)) => (ip.to_string(), port.parse().unwrap_or(0), cport),
+        None => (host_port.to_string(), 0, cport),
+    }
+}
+
+/// 解析槽位标记：`5461` 或 `0-5460`，迁移标记（以 `[` 开头）返回 `None`
+fn parse_slot_range(token: &str) -> Option<(u16, u16)> {
+    if token.starts_with('[') {
+        return None;
+    }
+    match token.split_once('-') {
+        Some((start, end)) => Some((start.parse().ok()?, end.parse().ok()?)),
+        None => {
+            let slot = token.parse().ok()?;
+            Some((slot, slot))
+        }
+    }
+}
+
+/// 判断错误是否为 RESTORE 目标键已存在（BUSYKEY）
+pub(crate) fn is_busy_key(e: &anyhow::Error) -> bool {
+    e.chain().any(|cause| {
+        cause.downcast_ref::<redis::RedisError>()
+            .is_some_and(|re| re.code() == Some("BUSYKEY"))
+    })
+}
+
+/// 判断匹配模式是否会匹配全部键（如 `*`、`**`）
+pub(crate) fn matches_all_keys(pattern: &str) -> bool {
+    !pattern.is_empty() && pattern.chars().all(|c| c == '*')
+}
+
+/// 将 Redis 回复转换为 JSON 值
+/// 
+/// 字符串类回复转为字符串，数组/集合转为数组，Map 转为对象（键按字符串处理）。
+fn value_to_json(v: &redis::Value) -> serde_json::Value {
+    use serde_json::Value as Json;
+    match v {
+        redis::Value::Nil => Json::Null,
+        redis::Value::Int(n) => Json::from(*n),
+        redis::Value::Double(d) => Json::from(*d),
+        redis::Value::Boolean(b) => Json::Bool(*b),
+        redis::Value::Okay => Json::String("OK".into()),
+        redis::Value::SimpleString(s) => Json::String(s.clone()),
+        redis::Value::BulkString(b) => Json::String(String::from_utf8_lossy(b).into_owned()),
+        redis::Value::VerbatimString { text, .. } => Json::String(text.clone()),
+        redis::Value::Array(items) | redis::Value::Set(items) => Json::Array(items.iter().map(value_to_json).collect()),
+        redis::Value::Map(pairs) => Json::Object(pairs.iter().map(|(k, v)| (json_key(k), value_to_json(v))).collect()),
+        other => Json::String(format!("{:?}", other)),
+    }
+}
+
+/// 将 Redis 回复渲染为保留类型与嵌套的调试文本
+/// 
+/// 数组与集合渲染为 `Array[...]` / `Set[...]`，Map 渲染为 `Map{k => v, ...}`；
+/// 无法按 UTF-8 解码的字符串以十六进制显示。
+fn render_value(v: &redis::Value) -> String {
+    let join = |items: &[redis::Value]| items.iter().map(render_value).collect::<Vec<_>>().join(", ");
+    match v {
+        redis::Value::Nil => "Nil".to_string(),
+        redis::Value::Int(n) => format!("Int({})", n),
+        redis::Value::Double(d) => format!("Double({})", d),
+        redis::Value::Boolean(b) => format!("Boolean({})", b),
+        redis::Value::Okay => "Okay".to_string(),
+        redis::Value::SimpleString(s) => format!("SimpleString({:?})", s),
+        redis::Value::BulkString(b) => match std::str::from_utf8(b) {
+            Ok(s) => format!("BulkString({:?})", s),
+            Err(_) => format!("BulkString(0x{})", b.iter().map(|byte| format!("{:02x}", byte)).collect::<String>()),
+        },
+        redis::Value::VerbatimString { text, .. } => format!("VerbatimString({:?})", text),
+        redis::Value::Array(items) => format!("Array[{}]", join(items)),
+        redis::Value::Set(items) => format!("Set[{}]", join(items)),
+        redis::Value::Map(pairs) => {
+            let entries: Vec<String> = pairs.iter().map(|(k, v)| format!("{} => {}", render_value(k), render_value(v))).collect();
+            format!("Map{{{}}}", entries.join(", "))
+        }
+        other => format!("{:?}", other),
+    }
+}
+
+/// 将 Map 的键转换为字符串
+fn json_key(v: &redis::Value) -> String {
+    match value_to_json(v) {
+        serde_json::Value::String(s) => s,
+        other => other.to_string(),
+    }
+}
+
+/// 解析 ACL GETUSER 的回复
+/// 
+/// RESP2 下为字段名与值交替排列的数组，RESP3 下为 Map；用户不存在时为 `nil`。
+fn parse_acl_getuser(v: redis::Value) -> Result<HashMap<String, serde_json::Value>> {
+    match v {
+        redis::Value::Nil => Ok(HashMap::new()),
+        redis::Value::Array(items) => {
+            if items.len() % 2 != 0 {
+                return Err(anyhow!("Unexpected ACL GETUSER reply length: {}", items.len()));
+            }
+            Ok(items.chunks(2).map(|pair| (json_key(&pair[0]), value_to_json(&pair[1]))).collect())
+        }
+        redis::Value::Map(pairs) => Ok(pairs.iter().map(|(k, v)| (json_key(k), value_to_json(v))).collect()),
+        other => Err(anyhow!("Unexpected ACL GETUSER reply: {:?}", other)),
+    }
+}
+
+/// 解析 RedisJSON 返回的整数回复
+/// 
+/// JSONPath 查询返回每个匹配一项的数组（非适用类型的匹配为 `nil`），旧式路径直接返回整数；
+/// 取第一个匹配的结果。
+fn first_json_int(v: redis::Value) -> Result<Option<i64>> {
+    match v {
+        redis::Value::Nil => Ok(None),
+        redis::Value::Int(n) => Ok(Some(n)),
+        redis::Value::Array(items) => match items.into_iter().next() {
+            None | Some(redis::Value::Nil) => Ok(None),
+            Some(redis::Value::Int(n)) => Ok(Some(n)),
+            Some(other) => Err(anyhow!("Unexpected RedisJSON reply item: {:?}", other)),
+        },
+        other => Err(anyhow!("Unexpected RedisJSON reply: {:?}", other)),
+    }
+}
+
+/// 将字符串类回复转换为 `String`
+fn value_string(v: &redis::Value) -> Option<String> {
+    match v {
+        redis::Value::BulkString(b) => Some(String::from_utf8_lossy(b).into_owned()),
+        redis::Value::SimpleString(s) => Some(s.clone()),
+        redis::Value::VerbatimString { text, .. } => Some(text.clone()),
+        _ => None,
+    }
+}
+
+/// 将 `7.2.4` 形式的版本号解析为 `(主, 次, 修订)`，无法解析的部分按 0 处理
+fn parse_version(version: &str) -> (u32, u32, u32) {
+    let mut parts = version.trim().split('.').map(|p| p.parse::<u32>().unwrap_or(0));
+    (parts.next().unwrap_or(0), parts.next().unwrap_or(0), parts.next().unwrap_or(0))
+}
+
+/// 从 `MODULE LIST` 的回复中提取模块名称
+/// 
+/// 每个模块在 RESP2 下为 `[name, ReJSON, ver, 20609, ...]` 形式的键值数组，RESP3 下为映射。
+fn parse_module_names(v: redis::Value) -> Result<Vec<String>> {
+    let modules = match v {
+        redis::Value::Array(modules) | redis::Value::Set(modules) => modules,
+        other => return Err(anyhow!("Unexpected MODULE LIST reply: {:?}", other)),
+    };
+    let mut names = Vec::with_capacity(modules.len());
+    for module in &modules {
+        let pairs: Vec<(&redis::Value, &redis::Value)> = match module {
+            redis::Value::Array(items) => items.chunks_exact(2).map(|c| (&c[0], &c[1])).collect(),
+            redis::Value::Map(entries) => entries.iter().map(|(k, v)| (k, v)).collect(),
+            other => return Err(anyhow!("Unexpected MODULE LIST entry: {:?}", other)),
+        };
+        let name = pairs
+            .into_iter()
+            .find(|(k, _)| value_string(k).as_deref() == Some("name"))
+            .and_then(|(_, v)| value_string(v));
+        if let Some(name) = name {
+            names.push(name);
+        }
+    }
+    Ok(names)
+}
+
+/// 解析 JSON.TYPE 的回复
+/// 
+/// JSONPath 查询返回 `["object"]` 形式的数组（RESP3 下可能再嵌套一层），旧式路径直接返回字符串。
+fn parse_json_type(v: redis::Value) -> Result<Option<String>> {
+    match v {
+        redis::Value::Nil => Ok(None),
+        redis::Value::Array(items) => match items.into_iter().next() {
+            None => Ok(None),
+            Some(first) => parse_json_type(first),
+        },
+        other => value_string(&other).map(Some).ok_or_else(|| anyhow!("Unexpected JSON.TYPE reply: {:?}", other)),
+    }
+}
+
+/// 解析 JSON.OBJKEYS 的回复
+/// 
+/// JSONPath 查询返回每个匹配一项的数组（非对象为 `nil`），旧式路径直接返回键名数组。
+fn parse_json_objkeys(v: redis::Value) -> Result<Vec<String>> {
+    let items = match v {
+        redis::Value::Nil => return Ok(Vec::new()),
+        redis::Value::Array(items) => items,
+        other => return Err(anyhow!("Unexpected JSON.OBJKEYS reply: {:?}", other)),
+    };
+    let keys = match items.first() {
+        Some(redis::Value::Array(_)) | Some(redis::Value::Nil) => match items.into_iter().next() {
+            Some(redis::Value::Array(keys)) => keys,
+            _ => return Ok(Vec::new()),
+        },
+        _ => items,
+    };
+    keys.iter()
+        .map(|k| value_string(k).ok_or_else(|| anyhow!("Unexpected JSON.OBJKEYS key: {:?}", k)))
+        .collect()
+}
+
+/// 解析 DEBUG OBJECT 的回复
+///
+/// 回复由空格分隔的 `field:value` 组成，不含冒号的片段（如开头的 `Value`）会被忽略。
+fn parse_debug_object(reply: &str) -> HashMap<String, String> {
+    reply.split_whitespace()
+        .filter_map(|part| part.split_once(':'))
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+/// 构建 Sentinel 连接 URL
+/// 
+/// 格式: redis+sentinel://host1:port1,host2:port2/master_name
+fn build_sentinel_url(master: &str, urls: &[String]) -> Result<String> {
+    let hosts: Vec<String> = urls.iter().map(|u| {
+        u.trim_start_matches("redis://")
+         .trim_start_matches("http://")
+         .trim_end_matches('/')
+         .to_string()
+    }).collect();
+    
+    if hosts.is_empty() {
+         return Err(invalid_argument("No sentinel URLs provided"));
+    }
+    
+    Ok(format!("redis+sentinel://{}/{}", hosts.join(","), master))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    /// 初始化测试日志记录器
+    fn init_test_logger() {
+        crate::logging::tests::init_test_logger();
+    }
+
+    /// 生成唯一的测试键名
+    fn gen_key(prefix: &str) -> String {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        format!("{}-{}", prefix, timestamp)
+    }
+
+    /// 测试修改值时保留或清除过期时间（KEEPTTL）
+    #[tokio::test]
+    #[ignore]
+    async fn test_set_keep_ttl() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let key = gen_key("keep_ttl");
+
+        for db in [0, 1] {
+            svc.set(db, &key, "v1", Some(100), false).await.unwrap();
+            svc.set(db, &key, "v2", None, true).await.unwrap();
+            let ttl = svc.ttl(db, &key).await.unwrap();
+            assert!(ttl > 0 && ttl <= 100);
+            let v: Option<String> = svc.get(db, &key).await.unwrap();
+            assert_eq!(v.as_deref(), Some("v2"));
+
+            svc.set(db, &key, "v3", None, false).await.unwrap();
+            assert_eq!(svc.ttl(db, &key).await.unwrap(), -1);
+
+            svc.del(db, &key).await.unwrap();
+        }
+    }
+
+    /// 测试基础键值操作
+    #[tokio::test]
+    #[ignore]
+    async fn test_kv_ops() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        
+        let key = gen_key("kv_test");
+        
+        // 基础设置和获取
+        svc.set(0, &key, "val-1", Some(60), false).await.unwrap();
+        let v: Option<String> = svc.get(0, &key).await.unwrap();
+        assert_eq!(v, Some("val-1".into()));
+        
+        // 更新值
+        svc.set(0, &key, "val-2", None, false).await.unwrap();
+        let v: Option<String> = svc.get(0, &key).await.unwrap();
+        assert_eq!(v, Some("val-2".into()));
+
+        // TTL 操作
+        svc.expire(0, &key, 100).await.unwrap();
+        let ttl = svc.ttl(0, &key).await.unwrap();
+        assert!(ttl > 0 && ttl <= 100);
+        
+        // 存在性检查
+        assert!(svc.exists(0, &key).await.unwrap());
+        
+        // 删除操作
+        let ok = svc.del(0, &key).await.unwrap();
+        assert!(ok);
+        assert!(!svc.exists(0, &key).await.unwrap());
+    }
+
+    /// 测试非 0 号数据库操作不再占用阻塞线程池
+    /// 
+    /// 阻塞线程池只保留 1 个线程并被长时间占用，如果 db 3 的操作仍然经过
+    /// `spawn_blocking`，就会一直排队直到超时。
+    #[test]
+    #[ignore]
+    fn test_db_ops_without_blocking_pool() {
+        init_test_logger();
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .max_blocking_threads(1)
+            .enable_all()
+            .build()
+            .unwrap();
+
+        rt.block_on(async {
+            let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+            let key = gen_key("db3_test");
+
+            // 占满阻塞线程池
+            let _busy = tokio::task::spawn_blocking(|| std::thread::sleep(Duration::from_secs(5)));
+
+            let ops = async {
+                svc.set(3, &key, "v3", None, false).await.unwrap();
+                let v: Option<String> = svc.get(3, &key).await.unwrap();
+                assert_eq!(v, Some("v3".into()));
+
+                // 确认写入的是 db 3 而不是 db 0
+                assert!(svc.exists(3, &key).await.unwrap());
+                assert!(!svc.exists(0, &key).await.unwrap());
+
+                assert!(svc.del(3, &key).await.unwrap());
+            };
+
+            tokio::time::timeout(Duration::from_secs(2), ops).await
+                .expect("db 3 operations should not wait on the blocking pool");
+        });
+    }
+
+    /// 测试连接池按轮询方式复用多条连接
+    #[tokio::test]
+    #[ignore]
+    async fn test_connection_pool_round_robin() {
+        init_test_logger();
+        let cfg = RedisConfig { pool_size: 4, ..Default::default() };
+        let svc = RedisService::new(cfg).await.unwrap();
+        let pool = match &svc.kind {
+            ConnectionKind::Standalone(pool, _) => pool.clone(),
+            ConnectionKind::Cluster(_) => unreachable!(),
+        };
+        assert_eq!(pool.all().len(), 4);
+
+        // 连续取 8 次，前 4 次对应 4 条不同的连接，之后按相同顺序复用
+        let mut ids = Vec::new();
+        for _ in 0..8 {
+            let mut conn = pool.get();
+            let id: i64 = redis::cmd("CLIENT").arg("ID").query_async(&mut conn).await.unwrap();
+            ids.push(id);
+        }
+        let distinct: std::collections::HashSet<i64> = ids[..4].iter().copied().collect();
+        assert_eq!(distinct.len(), 4);
+        assert_eq!(ids[..4], ids[4..]);
+    }
+
+    /// 测试哈希操作
+    #[tokio::test]
+    #[ignore]
+    async fn test_hash_ops() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        
+        let key = gen_key("hash_test");
+        
+        // 单字段设置和获取
+        svc.hset(0, &key, "f1", "v1").await.unwrap();
+        svc.hset(0, &key, "f2", "v2").await.unwrap();
+        
+        let v1: Option<String> = svc.hget(0, &key, "f1").await.unwrap();
+        assert_eq!(v1, Some("v1".into()));
+        
+        let v2: Option<String> = svc.hget(0, &key, "f2").await.unwrap();
+        assert_eq!(v2, Some("v2".into()));
+        
+        let v3: Option<String> = svc.hget(0, &key, "f3").await.unwrap();
+        assert_eq!(v3, None);
+        
+        // 清理
+        svc.del(0, &key).await.unwrap();
+    }
+
+    /// 测试 RESP3 协议下的哈希读写
+    /// 
+    /// RESP3 中 HGETALL 返回 Map 类型而不是扁平数组，确认解析结果一致。
+    #[tokio::test]
+    #[ignore]
+    async fn test_resp3_hash_ops() {
+        init_test_logger();
+        let cfg = RedisConfig { resp3: true, ..Default::default() };
+        let svc = RedisService::new(cfg).await.unwrap();
+        let key = gen_key("resp3_hash_test");
+
+        let items = vec![("f1", "v1"), ("f2", "v2")];
+        svc.hmset(0, &key, &items).await.unwrap();
+
+        let all: HashMap<String, String> = svc.hgetall(0, &key).await.unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all.get("f1"), Some(&"v1".to_string()));
+        assert_eq!(all.get("f2"), Some(&"v2".to_string()));
+
+        svc.del(0, &key).await.unwrap();
+    }
+
+    /// 测试哈希批量操作
+    #[tokio::test]
+    #[ignore]
+    async fn test_hash_batch_ops() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let key = gen_key("hash_batch_test");
+
+        // 批量设置
+        let items = vec![("f1", "v1"), ("f2", "v2")];
+        svc.hmset(0, &key, &items).await.unwrap();
+
+        // 获取所有字段
+        let all: HashMap<String, String> = svc.hgetall(0, &key).await.unwrap();
+        assert_eq!(all.get("f1"), Some(&"v1".to_string()));
+        assert_eq!(all.get("f2"), Some(&"v2".to_string()));
+
+        // 清理
+        svc.del(0, &key).await.unwrap();
+    }
+
+    /// 测试列表操作
+    #[tokio::test]
+    #[ignore]
+    async fn test_list_ops() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        
+        let key = gen_key("list_test");
+        
+        // 推入操作
+        svc.lpush(0, &key, "v1").await.unwrap();
+        svc.lpush(0, &key, "v2").await.unwrap();
+        
+        // 弹出操作（LIFO）
+        let v: Option<String> = svc.rpop(0, &key).await.unwrap();
+        assert_eq!(v, Some("v1".into())); 
+        
+        let v: Option<String> = svc.rpop(0, &key).await.unwrap();
+        assert_eq!(v, Some("v2".into()));
+        
+        let v: Option<String> = svc.rpop(0, &key).await.unwrap();
+        assert_eq!(v, None);
+    }
+
+    /// 测试多键弹出与阻塞弹出
+    #[tokio::test]
+    #[ignore]
+    async fn test_list_mpop_and_blpop() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let empty = gen_key("mpop_empty");
+        let key = gen_key("mpop_list");
+
+        // 列表为: a b c
+        for v in ["c", "b", "a"] {
+            svc.lpush(0, &key, v).await.unwrap();
+        }
+        let keys = vec![empty.clone(), key.clone()];
+        let popped = svc.lmpop(0, &keys, true, 2).await.unwrap();
+        assert_eq!(popped, Some((key.clone(), vec!["a".to_string(), "b".to_string()])));
+
+        let popped = svc.blpop(0, &keys, 1.0).await.unwrap();
+        assert_eq!(popped, Some((key.clone(), "c".to_string())));
+
+        // 所有列表为空时，超时后返回 None
+        let started = Instant::now();
+        assert_eq!(svc.blpop(0, &[empty.clone()], 0.2).await.unwrap(), None);
+        assert!(started.elapsed() >= Duration::from_millis(150));
+        assert_eq!(svc.lmpop(0, &keys, false, 1).await.unwrap(), None);
+        assert!(svc.blpop(0, &keys, 0.0).await.is_err());
+    }
+
+    /// 测试列表查找与移除
+    #[tokio::test]
+    #[ignore]
+    async fn test_list_lpos_lrem() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let key = gen_key("list_lpos_test");
+
+        // LPUSH 后列表为: a x b x c x
+        for v in ["x", "c", "x", "b", "x", "a"] {
+            svc.lpush(0, &key, v).await.unwrap();
+        }
+
+        assert_eq!(svc.lpos(0, &key, "x", None, None).await.unwrap(), vec![1]);
+        assert_eq!(svc.lpos(0, &key, "x", None, Some(0)).await.unwrap(), vec![1, 3, 5]);
+        assert_eq!(svc.lpos(0, &key, "x", Some(-1), None).await.unwrap(), vec![5]);
+        assert!(svc.lpos(0, &key, "missing", None, None).await.unwrap().is_empty());
+
+        // 从尾部移除一个 x，列表变为: a x b x c
+        assert_eq!(svc.lrem(0, &key, -1, "x").await.unwrap(), 1);
+        assert_eq!(svc.lpos(0, &key, "x", None, Some(0)).await.unwrap(), vec![1, 3]);
+
+        // 从头部移除一个 x，列表变为: a b x c
+        assert_eq!(svc.lrem(0, &key, 1, "x").await.unwrap(), 1);
+        assert_eq!(svc.lpos(0, &key, "x", None, Some(0)).await.unwrap(), vec![2]);
+
+        // 清理
+        svc.del(0, &key).await.unwrap();
+    }
+
+    /// 测试集合操作
+    #[tokio::test]
+    #[ignore]
+    async fn test_set_ops() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        
+        let key = gen_key("set_test");
+        
+        // 添加成员
+        svc.sadd(0, &key, "m1").await.unwrap();
+        svc.sadd(0, &key, "m2").await.unwrap();
+        svc.sadd(0, &key, "m1").await.unwrap(); // 重复添加
+        
+        // 获取所有成员
+        let members: Vec<String> = svc.smembers(0, &key).await.unwrap();
+        assert_eq!(members.len(), 2);
+        assert!(members.contains(&"m1".to_string()));
+        assert!(members.contains(&"m2".to_string()));
+        
+        // 清理
+        svc.del(0, &key).await.unwrap();
+    }
+
+    /// 测试地理位置操作
+    #[tokio::test]
+    #[ignore]
+    async fn test_geo_ops() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+
+        let key = gen_key("geo_test");
+
+        // 北京、上海
+        let members = vec![
+            (116.4074, 39.9042, "beijing".to_string()),
+            (121.4737, 31.2304, "shanghai".to_string()),
+        ];
+        let added = svc.geoadd(0, &key, members).await.unwrap();
+        assert_eq!(added, 2);
+
+        // 两地直线距离约 1067 km
+        let dist = svc.geodist(0, &key, "beijing", "shanghai", "km").await.unwrap().unwrap();
+        assert!((dist - 1067.0).abs() < 10.0, "unexpected distance: {}", dist);
+
+        let missing = svc.geodist(0, &key, "beijing", "nowhere", "km").await.unwrap();
+        assert_eq!(missing, None);
+
+        let pos = svc.geopos(0, &key, &["beijing".to_string(), "nowhere".to_string()]).await.unwrap();
+        let (lon, lat) = pos[0].unwrap();
+        assert!((lon - 116.4074).abs() < 0.001);
+        assert!((lat - 39.9042).abs() < 0.001);
+        assert_eq!(pos[1], None);
+
+        let near = svc.geosearch(0, &key, (116.4, 39.9), 100.0, "km").await.unwrap();
+        assert_eq!(near, vec!["beijing".to_string()]);
+
+        // 清理
+        svc.del(0, &key).await.unwrap();
+    }
+
+    /// 测试命令策略的匹配规则
+    #[test]
+    fn test_command_policy_permits() {
+        let deny = CommandPolicy { mode: PolicyMode::Deny, commands: vec!["flushall".into(), "CONFIG SET".into()] };
+        assert!(!deny.permits("FLUSHALL"));
+        assert!(!deny.permits("config set"));
+        assert!(deny.permits("CONFIG GET"));
+        assert!(deny.permits("GET"));
+        assert!(deny.permits("FLUSHDB"));
+
+        let allow = CommandPolicy { mode: PolicyMode::Allow, commands: vec!["GET".into(), "CONFIG".into()] };
+        assert!(allow.permits("get"));
+        assert!(allow.permits("CONFIG SET"));
+        assert!(!allow.permits("DEL"));
+        assert!(!allow.permits("GETDEL"));
+    }
+
+    /// 测试命令策略在发送命令前拒绝被禁止的命令
+    #[tokio::test]
+    #[ignore]
+    async fn test_command_policy() {
+        init_test_logger();
+        let key = gen_key("policy");
+        let plain = RedisService::new(RedisConfig::default()).await.unwrap();
+        plain.set(0, &key, "v", None, false).await.unwrap();
+
+        let cfg = RedisConfig {
+            command_policy: Some(CommandPolicy { mode: PolicyMode::Deny, commands: vec!["FLUSHALL".into(), "DEL".into()] }),
+            ..RedisConfig::default()
+        };
+        let svc = RedisService::new(cfg).await.unwrap();
+
+        let err = svc.run_raw_command_debug(0, &["FLUSHALL".to_string()]).await.unwrap_err();
+        assert!(err.downcast_ref::<PolicyBlocked>().is_some());
+        assert!(svc.del(0, &key).await.unwrap_err().downcast_ref::<PolicyBlocked>().is_some());
+
+        // 未被禁止的命令正常执行，数据没有被清空
+        let raw = svc.run_raw_command_debug(0, &["GET".to_string(), key.clone()]).await.unwrap();
+        assert_eq!(raw, "BulkString(\"v\")");
+        assert_eq!(svc.get::<String>(0, &key).await.unwrap().as_deref(), Some("v"));
+
+        // 清理
+        plain.del(0, &key).await.unwrap();
+    }
+
+    /// 测试原始命令的写命令识别
+    #[test]
+    fn test_is_write_command() {
+        let args = |list: &[&str]| list.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        assert!(is_write_command("set", &args(&["k", "v"])));
+        assert!(is_write_command("FLUSHALL", &[]));
+        assert!(is_write_command("json.set", &args(&["k", "$", "1"])));
+        assert!(is_write_command("config", &args(&["set", "maxmemory", "0"])));
+        assert!(!is_write_command("CONFIG", &args(&["GET", "maxmemory"])));
+        assert!(!is_write_command("GET", &args(&["k"])));
+        assert!(!is_write_command("EVAL_RO", &args(&["return 1", "0"])));
+
+        // 读写取决于参数的命令
+        assert!(!is_write_command("GETEX", &args(&["k"])));
+        assert!(is_write_command("getex", &args(&["k", "PERSIST"])));
+        assert!(is_write_command("GETEX", &args(&["k", "EX", "10"])));
+        assert!(!is_write_command("SORT", &args(&["list", "ALPHA"])));
+        assert!(is_write_command("sort", &args(&["list", "LIMIT", "0", "10", "store", "dest"])));
+        assert!(is_write_command("GEORADIUS", &args(&["geo", "0", "0", "1", "km", "STOREDIST", "dest"])));
+
+        // 未列出的命令与子命令按写命令处理
+        for cmd in ["HPEXPIRE", "HEXPIREAT", "HPEXPIREAT", "REPLICAOF", "SLAVEOF", "SHUTDOWN", "SOME.MODULECMD"] {
+            assert!(is_write_command(cmd, &args(&["x"])), "{}", cmd);
+        }
+        assert!(is_write_command("CLUSTER", &args(&["RESET"])));
+        assert!(!is_write_command("cluster", &args(&["nodes"])));
+    }
+
+    /// 测试只读连接拒绝写入、允许读取
+    #[tokio::test]
+    #[ignore]
+    async fn test_readonly_connection() {
+        init_test_logger();
+        let key = gen_key("readonly");
+        let plain = RedisService::new(RedisConfig::default()).await.unwrap();
+        plain.set(0, &key, "v", None, false).await.unwrap();
+
+        let svc = RedisService::new(RedisConfig { readonly: true, ..RedisConfig::default() }).await.unwrap();
+        let err = svc.set(0, &key, "changed", None, false).await.unwrap_err();
+        assert!(err.downcast_ref::<ReadOnlyConnection>().is_some());
+        assert!(svc.del(0, &key).await.is_err());
+        assert!(svc.run_raw_command_debug(0, &["FLUSHDB".to_string()]).await.is_err());
+        let raw = |args: &[&str]| args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        let err = svc.run_raw_command_debug(0, &raw(&["GETEX", &key, "PERSIST"])).await.unwrap_err();
+        assert!(err.downcast_ref::<ReadOnlyConnection>().is_some());
+        let dest = gen_key("readonly_sorted");
+        let err = svc.run_raw_command_debug(0, &raw(&["SORT", &key, "ALPHA", "STORE", &dest])).await.unwrap_err();
+        assert!(err.downcast_ref::<ReadOnlyConnection>().is_some());
+        assert_eq!(svc.key_type(0, &dest).await.unwrap(), "none");
+
+        // 发布消息、保存快照、清空延迟记录与 DEBUG SLEEP 同样会改变服务端状态，不会发送
+        assert!(svc.publish("readonly:channel", "m").await.unwrap_err().downcast_ref::<ReadOnlyConnection>().is_some());
+        assert!(svc.save().await.unwrap_err().downcast_ref::<ReadOnlyConnection>().is_some());
+        assert!(svc.bgsave().await.unwrap_err().downcast_ref::<ReadOnlyConnection>().is_some());
+        assert!(svc.latency_reset().await.unwrap_err().downcast_ref::<ReadOnlyConnection>().is_some());
+        assert!(svc.debug_sleep(0.0).await.unwrap_err().downcast_ref::<ReadOnlyConnection>().is_some());
+
+        assert_eq!(svc.get::<String>(0, &key).await.unwrap().as_deref(), Some("v"));
+        assert_eq!(svc.run_raw_command_debug(0, &raw(&["GETEX", &key])).await.unwrap(), "BulkString(\"v\")");
+        assert_eq!(svc.run_raw_command_debug(0, &["GET".to_string(), key.clone()]).await.unwrap(), "BulkString(\"v\")");
+
+        // 清理
+        plain.del(0, &key).await.unwrap();
+    }
+
+    /// 测试服务端排序
+    #[tokio::test]
+    #[ignore]
+    async fn test_sort() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let key = gen_key("sort_list");
+        let words = gen_key("sort_words");
+        for v in [3, 10, 1, 7, 5] {
+            svc.lpush(0, &key, v).await.unwrap();
+        }
+        for v in ["pear", "apple", "fig"] {
+            svc.lpush(0, &words, v).await.unwrap();
+        }
+
+        let sorted = svc.sort(0, &key, None, Some((0, 3)), Vec::new(), false, true).await.unwrap();
+        assert_eq!(sorted, vec!["10", "7", "5"]);
+
+        let all = svc.sort(0, &key, None, None, Vec::new(), false, false).await.unwrap();
+        assert_eq!(all, vec!["1", "3", "5", "7", "10"]);
+
+        // 非数字元素必须使用 alpha
+        let err = svc.sort(0, &words, None, None, Vec::new(), false, false).await.unwrap_err();
+        assert!(format!("{:#}", err).contains("set alpha"));
+        let sorted = svc.sort(0, &words, None, None, Vec::new(), true, false).await.unwrap();
+        assert_eq!(sorted, vec!["apple", "fig", "pear"]);
+
+        // 清理
+        svc.del(0, &key).await.unwrap();
+        svc.del(0, &words).await.unwrap();
+    }
+
+    /// 测试原子地取出整个列表
+    #[tokio::test]
+    #[ignore]
+    async fn test_drain_list() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let key = gen_key("drain_list");
+        for v in ["a", "b", "c"] {
+            svc.lpush(0, &key, v).await.unwrap();
+        }
+
+        let items = svc.drain_list(0, &key).await.unwrap();
+        assert_eq!(items, vec!["c", "b", "a"]);
+        assert_eq!(svc.key_type(0, &key).await.unwrap(), "none");
+
+        // 键不存在时返回空列表
+        assert!(svc.drain_list(0, &key).await.unwrap().is_empty());
+    }
+
+    /// 测试字符串写回：保留过期时间并可替换其他类型的键
+    #[tokio::test]
+    #[ignore]
+    async fn test_write_key_string() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let key = gen_key("write_string");
+        svc.set(0, &key, "old", Some(100), false).await.unwrap();
+
+        let payload = KeyPayload::String { value: "new value".into() };
+        svc.write_key(0, &key, &payload).await.unwrap();
+        assert_eq!(svc.get::<String>(0, &key).await.unwrap().as_deref(), Some("new value"));
+        let ttl = svc.ttl(0, &key).await.unwrap();
+        assert!(ttl > 0 && ttl <= 100);
+
+        // 原来是哈希的键被替换为字符串
+        let other = gen_key("write_string_from_hash");
+        svc.hset(0, &other, "f", "v").await.unwrap();
+        svc.write_key(0, &other, &payload).await.unwrap();
+        assert_eq!(svc.key_type(0, &other).await.unwrap(), "string");
+        assert_eq!(svc.ttl(0, &other).await.unwrap(), -1);
+
+        // 清理
+        svc.del(0, &key).await.unwrap();
+        svc.del(0, &other).await.unwrap();
+    }
+
+    /// 测试哈希写回：旧字段被删除，空哈希删除键
+    #[tokio::test]
+    #[ignore]
+    async fn test_write_key_hash() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let key = gen_key("write_hash");
+        svc.hset(0, &key, "stale", "x").await.unwrap();
+        svc.hset(0, &key, "name", "old").await.unwrap();
+
+        let fields: BTreeMap<String, String> = [("name", "alice"), ("age", "30")]
+            .into_iter()
+            .map(|(f, v)| (f.to_string(), v.to_string()))
+            .collect();
+        svc.write_key(0, &key, &KeyPayload::Hash { fields: fields.clone() }).await.unwrap();
+        let stored: HashMap<String, String> = svc.hgetall(0, &key).await.unwrap();
+        assert_eq!(stored.into_iter().collect::<BTreeMap<_, _>>(), fields);
+
+        svc.write_key(0, &key, &KeyPayload::Hash { fields: BTreeMap::new() }).await.unwrap();
+        assert_eq!(svc.key_type(0, &key).await.unwrap(), "none");
+    }
+
+    /// 测试消费者组的创建、读取与确认
+    #[tokio::test]
+    #[ignore]
+    async fn test_stream_consumer_group() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let key = gen_key("stream_group");
+
+        assert!(svc.xgroup_create(0, &key, "workers", "$", true).await.unwrap());
+        // 重复创建不报错
+        assert!(!svc.xgroup_create(0, &key, "workers", "$", true).await.unwrap());
+
+        for job in ["a", "b"] {
+            let mut cmd = redis::cmd("XADD");
+            cmd.arg(&key).arg("*").arg("job").arg(job);
+            let _: String = svc.query_cmd(0, cmd, "XADD").await.unwrap();
+        }
+
+        let streams = vec![key.clone()];
+        let read = svc.xreadgroup(0, "workers", "w1", &streams, &[">".to_string()], Some(10), None).await.unwrap();
+        assert_eq!(read.len(), 1);
+        assert_eq!(read[0].0, key);
+        let entries = &read[0].1;
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].fields, vec![("job".to_string(), "a".to_string())]);
+
+        // 已全部投递，阻塞读取超时后返回空列表
+        let empty = svc.xreadgroup(0, "workers", "w1", &streams, &[">".to_string()], None, Some(50)).await.unwrap();
+        assert!(empty.is_empty());
+
+        let ids: Vec<String> = entries.iter().map(|e| e.id.clone()).collect();
+        assert_eq!(svc.xack(0, &key, "workers", &ids).await.unwrap(), 2);
+        assert_eq!(svc.xack(0, &key, "workers", &ids).await.unwrap(), 0);
+
+        // 已确认的消息不再出现在待确认列表中
+        let pending = svc.xreadgroup(0, "workers", "w1", &streams, &["0".to_string()], None, None).await.unwrap();
+        assert!(pending.iter().all(|(_, entries)| entries.is_empty()));
+
+        // 清理
+        svc.del(0, &key).await.unwrap();
+    }
+
+    /// 测试流的删除与裁剪
+    #[tokio::test]
+    #[ignore]
+    async fn test_stream_trim() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let key = gen_key("stream_trim");
+
+        let mut ids = Vec::new();
+        for i in 0..10 {
+            let mut cmd = redis::cmd("XADD");
+            cmd.arg(&key).arg("*").arg("n").arg(i);
+            let id: String = svc.query_cmd(0, cmd, "XADD").await.unwrap();
+            ids.push(id);
+        }
+        async fn xlen(svc: &RedisService, key: &str) -> i64 {
+            let mut cmd = redis::cmd("XLEN");
+            cmd.arg(key);
+            svc.query_cmd(0, cmd, "XLEN").await.unwrap()
+        }
+
+        // 小流的近似裁剪可能不删除任何消息，但不会裁剪到目标数量以下
+        svc.xtrim(0, &key, Some(5), None, true).await.unwrap();
+        assert!(xlen(&svc, &key).await >= 5);
+
+        svc.xtrim(0, &key, Some(5), None, false).await.unwrap();
+        assert_eq!(xlen(&svc, &key).await, 5);
+
+        // 最早的 5 条已被裁剪，只能删除剩余的消息
+        assert_eq!(svc.xdel(0, &key, vec![ids[0].clone(), ids[9].clone()]).await.unwrap(), 1);
+        assert_eq!(svc.xtrim(0, &key, None, Some(ids[7].clone()), false).await.unwrap(), 2);
+        assert_eq!(xlen(&svc, &key).await, 2);
+
+        assert!(svc.xtrim(0, &key, None, None, false).await.is_err());
+        assert!(svc.xtrim(0, &key, Some(1), Some("0".into()), false).await.is_err());
+
+        // 清理
+        svc.del(0, &key).await.unwrap();
+    }
+
+    /// 测试 XREADGROUP 回复解析
+    #[test]
+    fn test_parse_xread_reply() {
+        let bulk = |s: &str| redis::Value::BulkString(s.as_bytes().to_vec());
+        let entries = redis::Value::Array(vec![
+            redis::Value::Array(vec![bulk("1-0"), redis::Value::Array(vec![bulk("f"), bulk("v")])]),
+            redis::Value::Array(vec![bulk("2-0"), redis::Value::Nil]),
+        ]);
+        let expected = vec![("s".to_string(), vec![
+            StreamEntry { id: "1-0".into(), fields: vec![("f".into(), "v".into())] },
+            StreamEntry { id: "2-0".into(), fields: Vec::new() },
+        ])];
+
+        let resp2 = redis::Value::Array(vec![redis::Value::Array(vec![bulk("s"), entries.clone()])]);
+        assert_eq!(parse_xread_reply(resp2).unwrap(), expected);
+        let resp3 = redis::Value::Map(vec![(bulk("s"), entries)]);
+        assert_eq!(parse_xread_reply(resp3).unwrap(), expected);
+        assert!(parse_xread_reply(redis::Value::Nil).unwrap().is_empty());
+    }
+
+    /// 测试 GEOPOS 返回值解析
+    #[test]
+    fn test_parse_geopos() {
+        let v = redis::Value::Array(vec![
+            redis::Value::Array(vec![
+                redis::Value::BulkString(b"116.40740".to_vec()),
+                redis::Value::BulkString(b"39.90420".to_vec()),
+            ]),
+            redis::Value::Nil,
+            redis::Value::Array(vec![redis::Value::Double(1.5), redis::Value::Double(-2.5)]),
+        ]);
+        let parsed = parse_geopos(v).unwrap();
+        assert_eq!(parsed, vec![Some((116.4074, 39.9042)), None, Some((1.5, -2.5))]);
+
+        assert!(geo_unit("KM").is_ok());
+        assert!(geo_unit("lightyear").is_err());
+
+        assert_eq!(scan_type("Hash").unwrap(), "hash");
+        assert!(scan_type("document").is_err());
+    }
+
+    /// 测试 HyperLogLog 操作
+    #[tokio::test]
+    #[ignore]
+    async fn test_hll_ops() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+
+        let key = gen_key("hll_test");
+        let other = gen_key("hll_other");
+        let dest = gen_key("hll_dest");
+
+        let elements: Vec<String> = (0..1000).map(|i| format!("e{}", i)).collect();
+        assert!(svc.pfadd(0, &key, elements).await.unwrap());
+
+        // HLL 标准误差约 0.81%，这里放宽到 2%
+        let count = svc.pfcount(0, std::slice::from_ref(&key)).await.unwrap();
+        assert!((count - 1000).abs() <= 20, "unexpected estimate: {}", count);
+
+        // 与部分重叠的集合合并
+        let more: Vec<String> = (500..1500).map(|i| format!("e{}", i)).collect();
+        svc.pfadd(0, &other, more).await.unwrap();
+        svc.pfmerge(0, &dest, &[key.clone(), other.clone()]).await.unwrap();
+        let merged = svc.pfcount(0, std::slice::from_ref(&dest)).await.unwrap();
+        assert!((merged - 1500).abs() <= 30, "unexpected merged estimate: {}", merged);
+
+        // 清理
+        svc.del(0, &key).await.unwrap();
+        svc.del(0, &other).await.unwrap();
+        svc.del(0, &dest).await.unwrap();
+    }
+
+    /// 测试集群槽位计算
+    #[test]
+    fn test_key_slot() {
+        // 官方文档中的参考值
+        assert_eq!(key_slot("foo"), 12182);
+        assert_eq!(key_slot("123456789"), 12739);
+        // hash tag 只对花括号内的内容计算
+        assert_eq!(key_slot("{user1000}.following"), key_slot("{user1000}.followers"));
+        assert_eq!(key_slot("{user1000}.following"), key_slot("user1000"));
+        // 空的 hash tag 对整个键计算
+        assert_ne!(key_slot("{}a"), key_slot("{}b"));
+        // 与服务端 CLUSTER KEYSLOT 的返回值一致
+        assert_eq!(key_slot("somekey"), 11058);
+        assert_eq!(key_slot("foo{hash_tag}"), 2515);
+        assert_eq!(key_slot("{user1000}.following"), 3443);
+        assert_eq!(key_slot("{user1000}.followers"), 3443);
+        // 只使用第一对花括号
+        assert_eq!(key_slot("{hash_tag}{other}"), 2515);
+    }
+
+    /// 测试从地址路径中解析数据库索引
+    #[test]
+    fn test_split_url_db() {
+        assert_eq!(split_url_db("redis://host:6379/3").unwrap(), ("redis://host:6379".to_string(), Some(3)));
+        assert_eq!(split_url_db("redis://host:6379").unwrap(), ("redis://host:6379".to_string(), None));
+        assert_eq!(split_url_db("redis://host:6379/").unwrap(), ("redis://host:6379".to_string(), None));
+        // 密码中的斜杠不会被当作路径
+        assert_eq!(
+            split_url_db("rediss://user:p/ss@host:6380/2?protocol=resp3").unwrap(),
+            ("rediss://user:p/ss@host:6380?protocol=resp3".to_string(), Some(2))
+        );
+        assert_eq!(split_url_db("unix:///tmp/redis.sock").unwrap(), ("unix:///tmp/redis.sock".to_string(), None));
+        assert!(split_url_db("redis://host:6379/abc").is_err());
+    }
+
+    /// 测试槽位范围校验
+    #[test]
+    fn test_check_slot() {
+        assert!(check_slot(0).is_ok());
+        assert!(check_slot(16383).is_ok());
+        assert!(check_slot(16384).is_err());
+    }
+
+    /// 测试位图操作
+    #[tokio::test]
+    #[ignore]
+    async fn test_bitmap_ops() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+
+        let key = gen_key("bitmap_test");
+        let other = gen_key("bitmap_other");
+        let dest = gen_key("bitmap_dest");
+
+        let offsets = [0u64, 3, 7, 100, 1000];
+        for &o in &offsets {
+            assert!(!svc.setbit(0, &key, o, true).await.unwrap());
+        }
+        // 重复设置返回旧值
+        assert!(svc.setbit(0, &key, 3, true).await.unwrap());
+
+        assert!(svc.getbit(0, &key, 7).await.unwrap());
+        assert!(!svc.getbit(0, &key, 8).await.unwrap());
+        assert_eq!(svc.bitcount(0, &key, None).await.unwrap(), offsets.len() as i64);
+        // 第 0 个字节包含偏移 0、3、7
+        assert_eq!(svc.bitcount(0, &key, Some((0, 0))).await.unwrap(), 3);
+
+        svc.setbit(0, &other, 3, true).await.unwrap();
+        svc.setbit(0, &other, 5, true).await.unwrap();
+        svc.bitop(0, "and", &dest, &[key.clone(), other.clone()]).await.unwrap();
+        assert_eq!(svc.bitcount(0, &dest, None).await.unwrap(), 1);
+
+        assert!(svc.bitop(0, "NAND", &dest, std::slice::from_ref(&key)).await.is_err());
+        assert!(svc.bitop(0, "NOT", &dest, &[key.clone(), other.clone()]).await.is_err());
+
+        // 清理
+        svc.del(0, &key).await.unwrap();
+        svc.del(0, &other).await.unwrap();
+        svc.del(0, &dest).await.unwrap();
+    }
+
+    /// 测试键过期信息
+    #[tokio::test]
+    #[ignore]
+    async fn test_ttl_info() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let expiring = gen_key("ttl_info_expiring");
+        let persistent = gen_key("ttl_info_persistent");
+        let missing = gen_key("ttl_info_missing");
+
+        svc.set(0, &expiring, "v", Some(60), false).await.unwrap();
+        svc.set(0, &persistent, "v", None, false).await.unwrap();
+
+        let info = svc.ttl_info(0, &expiring).await.unwrap();
+        assert!(info.exists && info.has_expiry);
+        let ms = info.ttl_ms.unwrap();
+        assert!(ms > 0 && ms <= 60_000);
+
+        let info = svc.ttl_info(0, &persistent).await.unwrap();
+        assert_eq!(info, KeyTtlInfo { exists: true, has_expiry: false, ttl_ms: None });
+
+        let info = svc.ttl_info(0, &missing).await.unwrap();
+        assert_eq!(info, KeyTtlInfo { exists: false, has_expiry: false, ttl_ms: None });
+
+        // 清理
+        svc.del(0, &expiring).await.unwrap();
+        svc.del(0, &persistent).await.unwrap();
+    }
+
+    /// 测试仅删除设置了过期时间的键
+    #[tokio::test]
+    #[ignore]
+    async fn test_del_if_volatile() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let volatile = gen_key("del_volatile");
+        let persistent = gen_key("del_persistent");
+        let missing = gen_key("del_missing");
+
+        svc.set(0, &volatile, "v", Some(60), false).await.unwrap();
+        svc.set(0, &persistent, "v", None, false).await.unwrap();
+
+        assert_eq!(svc.del_if_volatile(0, &volatile).await.unwrap(), VolatileDeleteStatus::Deleted);
+        assert!(!svc.exists(0, &volatile).await.unwrap());
+
+        assert_eq!(svc.del_if_volatile(0, &persistent).await.unwrap(), VolatileDeleteStatus::SkippedPersistent);
+        assert!(svc.exists(0, &persistent).await.unwrap());
+
+        assert_eq!(svc.del_if_volatile(0, &missing).await.unwrap(), VolatileDeleteStatus::NotFound);
+
+        // 清理
+        svc.del(0, &persistent).await.unwrap();
+    }
+
+    /// 测试 PTTL 返回值转换
+    #[test]
+    fn test_ttl_info_from_pttl() {
+        assert_eq!(KeyTtlInfo::from_pttl(-2), KeyTtlInfo { exists: false, has_expiry: false, ttl_ms: None });
+        assert_eq!(KeyTtlInfo::from_pttl(-1), KeyTtlInfo { exists: true, has_expiry: false, ttl_ms: None });
+        assert_eq!(KeyTtlInfo::from_pttl(1500), KeyTtlInfo { exists: true, has_expiry: true, ttl_ms: Some(1500) });
+    }
+
+    /// 测试字符串分页读取
+    #[tokio::test]
+    #[ignore]
+    async fn test_get_value_page() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let key = gen_key("value_page_test");
+        let value: String = (0..1000).map(|i| char::from(b'a' + (i % 26) as u8)).collect();
+        svc.set(0, &key, &value, None, false).await.unwrap();
+
+        // 分两页读取并拼接，应还原出原始值
+        let first = svc.get_value_page(0, &key, 0, 600).await.unwrap();
+        let second = svc.get_value_page(0, &key, 600, 600).await.unwrap();
+        assert_eq!(first.total_len, 1000);
+        assert_eq!(second.total_len, 1000);
+        assert!(!first.base64 && !second.base64);
+        assert_eq!(first.chunk.len(), 600);
+        assert_eq!(second.chunk.len(), 400);
+        assert_eq!(format!("{}{}", first.chunk, second.chunk), value);
+
+        // 超出范围时返回空片段
+        let empty = svc.get_value_page(0, &key, 1000, 10).await.unwrap();
+        assert_eq!(empty.chunk, "");
+
+        // 中文字符跨越页边界时，按 next_offset 读取的各页都是文本且能拼接还原
+        let text: String = "你好，世界".repeat(50);
+        svc.set(0, &key, &text, None, false).await.unwrap();
+        let mut offset = 0;
+        let mut joined = String::new();
+        while offset < text.len() as u64 {
+            let page = svc.get_value_page(0, &key, offset, 100).await.unwrap();
+            assert!(!page.base64);
+            joined.push_str(&page.chunk);
+            offset = page.next_offset;
+        }
+        assert_eq!(joined, text);
+
+        // 清理
+        svc.del(0, &key).await.unwrap();
+    }
+
+    /// 测试分页范围计算，包括空页和偏移量溢出
+    #[test]
+    fn test_page_end() {
+        assert_eq!(page_end(0, 600, 1000), Some(599));
+        assert_eq!(page_end(600, 600, 1000), Some(999));
+        assert_eq!(page_end(0, 0, 1000), None);
+        assert_eq!(page_end(1000, 10, 1000), None);
+        assert_eq!(page_end(5, u64::MAX, 1000), Some(999));
+        assert_eq!(page_end(u64::MAX, u64::MAX, 1000), None);
+        assert_eq!(page_end(0, 1, 0), None);
+    }
+
+    /// 测试二进制分页内容的 Base64 编码
+    #[test]
+    fn test_value_page_from_bytes() {
+        let page = ValuePage::from_bytes(5, 0, b"hello".to_vec());
+        assert_eq!(page, ValuePage { total_len: 5, chunk: "hello".into(), base64: false, next_offset: 5 });
+        let page = ValuePage::from_bytes(3, 0, vec![0xff, 0x00, 0xfe]);
+        assert_eq!(page, ValuePage { total_len: 3, chunk: "/wD+".into(), base64: true, next_offset: 3 });
+        let page = ValuePage::from_bytes(10, 10, Vec::new());
+        assert_eq!(page, ValuePage { total_len: 10, chunk: String::new(), base64: false, next_offset: 10 });
+    }
+
+    /// 测试中文字符跨越页边界时回退到字符边界
+    #[test]
+    fn test_value_page_char_boundary() {
+        let text = "ab你好";
+        let bytes = text.as_bytes();
+        // page_size 为 4 时第一页截断了“你”（3 字节）
+        let first = ValuePage::from_bytes(bytes.len() as u64, 0, bytes[..4].to_vec());
+        assert_eq!(first, ValuePage { total_len: 8, chunk: "ab".into(), base64: false, next_offset: 2 });
+        let second = ValuePage::from_bytes(8, 2, bytes[2..6].to_vec());
+        assert_eq!(second, ValuePage { total_len: 8, chunk: "你".into(), base64: false, next_offset: 5 });
+        let third = ValuePage::from_bytes(8, 5, bytes[5..].to_vec());
+        assert_eq!(third, ValuePage { total_len: 8, chunk: "好".into(), base64: false, next_offset: 8 });
+        // 最后一页末尾不完整的字符按二进制处理
+        let last = ValuePage::from_bytes(4, 0, bytes[..4].to_vec());
+        assert!(last.base64);
+        assert_eq!(last.next_offset, 4);
+    }
+
+    /// 测试 DEBUG OBJECT 与 OBJECT FREQ
+    #[tokio::test]
+    #[ignore]
+    async fn test_debug_object() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let key = gen_key("debug_object_test");
+        svc.set(0, &key, "value", None, false).await.unwrap();
+
+        let info = svc.debug_object(0, &key).await.unwrap();
+        assert!(info.contains_key("encoding"));
+
+        // 仅 LFU 策略下可用，其他策略应返回带说明的错误
+        if let Err(e) = svc.object_freq(0, &key).await {
+            assert!(format!("{:#}", e).contains("LFU"));
+        }
+
+        // 清理
+        svc.del(0, &key).await.unwrap();
+    }
+
+    /// 测试 DEBUG OBJECT 回复解析
+    #[test]
+    fn test_parse_debug_object() {
+        let info = parse_debug_object("Value at:0x7f1c refcount:1 encoding:embstr serializedlength:6 lru:123 lru_seconds_idle:4");
+        assert_eq!(info.get("at").map(String::as_str), Some("0x7f1c"));
+        assert_eq!(info.get("encoding").map(String::as_str), Some("embstr"));
+        assert_eq!(info.get("serializedlength").map(String::as_str), Some("6"));
+        assert!(!info.contains_key("Value"));
+    }
+
+    /// 测试默认数据库
+    #[tokio::test]
+    #[ignore]
+    async fn test_default_db() {
+        init_test_logger();
+        let cfg = RedisConfig { default_db: 2, ..Default::default() };
+        let svc = RedisService::new(cfg).await.unwrap();
+        let key = gen_key("default_db_test");
+
+        // 未指定 db 时写入默认数据库
+        svc.set(svc.resolve_db(None), &key, "v2", None, false).await.unwrap();
+
+        let v: Option<String> = svc.get(2, &key).await.unwrap();
+        assert_eq!(v, Some("v2".to_string()));
+        let v: Option<String> = svc.get(0, &key).await.unwrap();
+        assert_eq!(v, None);
+
+        // 显式指定的 db 优先
+        assert_eq!(svc.resolve_db(Some(5)), 5);
+
+        // 清理
+        svc.del(2, &key).await.unwrap();
+    }
+
+    /// 测试地址中的数据库索引作为默认数据库，且不修改保存的配置
+    #[tokio::test]
+    #[ignore]
+    async fn test_url_db_keeps_config() {
+        init_test_logger();
+        let cfg = RedisConfig { urls: vec!["redis://127.0.0.1:6379/3".into()], ..Default::default() };
+        let svc = RedisService::new(cfg.clone()).await.unwrap();
+        assert_eq!(svc.default_db(), 3);
+        assert_eq!(svc.resolve_db(None), 3);
+        assert_eq!(svc.config().default_db, 0);
+        assert_eq!(serde_json::to_value(svc.config()).unwrap(), serde_json::to_value(&cfg).unwrap());
+    }
+
+    /// 测试获取所有数据库键数量
+    #[tokio::test]
+    #[ignore]
+    async fn test_all_dbsizes() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let k0 = gen_key("all_dbsizes_0");
+        let k1 = gen_key("all_dbsizes_1");
+        svc.set(0, &k0, "v", None, false).await.unwrap();
+        svc.set(1, &k1, "v", None, false).await.unwrap();
+
+        let sizes = svc.all_dbsizes().await.unwrap();
+        let size_of = |db: u32| sizes.iter().find(|(d, _)| *d == db).map(|(_, n)| *n);
+        assert!(size_of(0).unwrap() >= 1);
+        assert!(size_of(1).unwrap() >= 1);
+
+        // 清理
+        svc.del(0, &k0).await.unwrap();
+        svc.del(1, &k1).await.unwrap();
+    }
+
+    /// 测试获取数据库数量
+    #[tokio::test]
+    #[ignore]
+    async fn test_database_count() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let count = svc.database_count().await.unwrap();
+        assert!(count >= 1);
+        // 查询成功后才缓存，第二次读取缓存值
+        assert!(svc.database_count.initialized());
+        assert_eq!(svc.database_count().await.unwrap(), count);
+    }
+
+    /// 测试连接延迟指标
+    #[tokio::test]
+    #[ignore]
+    async fn test_connection_metrics() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let key = gen_key("metrics_test");
+
+        let before = svc.metrics_snapshot().count;
+        for i in 0..20 {
+            svc.set(0, &key, i, None, false).await.unwrap();
+        }
+        let m = svc.metrics_snapshot();
+        assert_eq!(m.count - before, 20);
+        assert_eq!(m.errors, 0);
+        assert!(m.avg_ms > 0.0 && m.avg_ms < 1000.0, "unexpected avg: {}", m.avg_ms);
+        assert!(m.p95_ms >= 0.0 && m.p95_ms < 1000.0, "unexpected p95: {}", m.p95_ms);
+
+        // 克隆的实例共享统计
+        svc.clone().del(0, &key).await.unwrap();
+        assert_eq!(svc.metrics_snapshot().count, m.count + 1);
+    }
+
+    /// 测试不可恢复的错误不会重试
+    #[tokio::test]
+    async fn test_retry_fails_fast_on_non_retryable() {
+        let metrics = MetricsRecorder::default();
+        let counter = AtomicUsize::new(0);
+        let attempts = &counter;
+        let res: Result<()> = run_with_retry(3, Duration::from_millis(1), &metrics, move || async move {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err(anyhow::Error::new(WrongType { key: "k".into(), actual: "list".into(), operation: None }).context("GET"))
+        }).await;
+        assert!(res.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+        attempts.store(0, Ordering::SeqCst);
+        let res: Result<()> = run_with_retry(3, Duration::from_millis(1), &metrics, move || async move {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            let re = redis::RedisError::from((redis::ErrorKind::AuthenticationFailed, "Password authentication failed"));
+            Err(anyhow::Error::new(re))
+        }).await;
+        assert!(res.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+        assert_eq!(metrics.snapshot().count, 2);
+    }
+
+    /// 测试 IO 错误会按配置重试
+    #[tokio::test]
+    async fn test_retry_retries_io_errors() {
+        let metrics = MetricsRecorder::default();
+        let counter = AtomicUsize::new(0);
+        let attempts = &counter;
+        let res: Result<()> = run_with_retry(2, Duration::from_millis(1), &metrics, move || async move {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            let io = std::io::Error::from(std::io::ErrorKind::ConnectionReset);
+            Err(anyhow::Error::new(redis::RedisError::from(io)).context("GET"))
+        }).await;
+        assert!(res.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+
+        // 重试期间恢复则返回成功
+        attempts.store(0, Ordering::SeqCst);
+        let res = run_with_retry(2, Duration::from_millis(1), &metrics, move || async move {
+            if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                let io = std::io::Error::from(std::io::ErrorKind::BrokenPipe);
+                return Err(anyhow::Error::new(redis::RedisError::from(io)));
+            }
+            Ok(7)
+        }).await;
+        assert_eq!(res.unwrap(), 7);
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    /// 测试命令名称解析
+    #[test]
+    fn test_parse_command_names() {
+        use redis::Value;
+        let bulk = |s: &str| Value::BulkString(s.as_bytes().to_vec());
+
+        let list = Value::Array(vec![bulk("get"), bulk("SET")]);
+        assert_eq!(parse_command_names(list).unwrap(), vec!["get", "set"]);
+
+        let info = Value::Array(vec![
+            Value::Array(vec![bulk("get"), Value::Int(2), Value::Array(vec![])]),
+            Value::Array(vec![bulk("hset"), Value::Int(-4), Value::Array(vec![])]),
+        ]);
+        assert_eq!(parse_command_names(info).unwrap(), vec!["get", "hset"]);
+
+        let docs = Value::Map(vec![(bulk("get"), Value::Map(vec![])), (bulk("del"), Value::Map(vec![]))]);
+        assert_eq!(parse_command_names(docs).unwrap(), vec!["get", "del"]);
+
+        assert!(parse_command_names(Value::Int(1)).is_err());
+    }
+
+    /// 测试 CLUSTER NODES 解析
+    #[test]
+    fn test_parse_cluster_nodes() {
+        let info = "\
+07c37dfeb235213a872192d90877d0cd55635b91 127.0.0.1:30004@31004,node-4 slave e7d1eecce10fd6bb5eb35b9f99a514335d9ba9ca 0 1426238317239 4 connected
+67ed2db8d677e59ec4a4cefb06858cf2a1a89fa1 127.0.0.1:30002@31002 master - 0 1426238316232 2 connected 5461-10922 [5461->-292f8b365bb7edb5e285caf0b7e6ddc7265d2f4f]
+e7d1eecce10fd6bb5eb35b9f99a514335d9ba9ca 127.0.0.1:30001@31001 myself,master - 0 0 1 connected 0-5460 12000 [12001-<-67ed2db8d677e59ec4a4cefb06858cf2a1a89fa1]
+6ec23923021cf3ffec47632106199cb7f496ce01 :0@0 handshake,noaddr - 0 0 0 disconnected
+broken line
+";
+        let nodes = parse_cluster_nodes(info);
+        assert_eq!(nodes.len(), 4);
+
+        let replica = &nodes[0];
+        assert_eq!(replica.addr, "127.0.0.1:30004@31004,node-4");
+        assert_eq!((replica.ip.as_str(), replica.port, replica.cport), ("127.0.0.1", 30004, 31004));
+        assert_eq!(replica.role, Some(NodeRole::Slave));
+        assert_eq!(replica.master_id, "e7d1eecce10fd6bb5eb35b9f99a514335d9ba9ca");
+        assert!(replica.slot_ranges.is_empty());
+
+        let migrating = &nodes[1];
+        assert_eq!(migrating.role, Some(NodeRole::Master));
+        assert_eq!(migrating.slots.len(), 2);
+        assert_eq!(migrating.slot_ranges, vec![(5461, 10922)]);
+
+        let myself = &nodes[2];
+        assert_eq!(myself.role, Some(NodeRole::Master));
+        assert_eq!(myself.slot_ranges, vec![(0, 5460), (12000, 12000)]);
+
+        let handshake = &nodes[3];
+        assert_eq!((handshake.ip.as_str(), handshake.port, handshake.cport), ("", 0, 0));
+        assert_eq!(handshake.role, None);
+
+        assert_eq!(parse_node_addr("::1:6379@16379"), ("::1".to_string(), 6379, 16379));
+        assert_eq!(parse_node_addr("10.0.0.1:6379"), ("10.0.0.1".to_string(), 6379, 0));
+    }
+
+    /// 测试延迟历史解析
+    #[test]
+    fn test_parse_latency_history() {
+        use redis::Value;
+        let history = Value::Array(vec![
+            Value::Array(vec![Value::Int(1_700_000_000), Value::Int(12)]),
+            Value::Array(vec![Value::Int(1_700_000_060), Value::BulkString(b"250".to_vec())]),
+        ]);
+        assert_eq!(parse_latency_history(history).unwrap(), vec![(1_700_000_000, 12), (1_700_000_060, 250)]);
+        assert!(parse_latency_history(Value::Array(vec![])).unwrap().is_empty());
+        assert!(parse_latency_history(Value::Array(vec![Value::Array(vec![Value::Int(1)])])).is_err());
+        assert!(parse_latency_history(Value::Nil).is_err());
+    }
+
+    /// 测试延迟监控
+    #[tokio::test]
+    #[ignore]
+    async fn test_latency_history() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        assert!(svc.latency_reset().await.unwrap() >= 0);
+        // 重置后 command 事件的历史为空
+        let history = svc.latency_history("command").await.unwrap();
+        assert!(history.is_empty(), "{:?}", history);
+    }
+
+    /// 测试按槽位查找缓存的主节点地址
+    #[test]
+    fn test_slot_owners_lookup() {
+        let owners = SlotOwners {
+            fetched_at: Instant::now(),
+            ranges: vec![(0, 5460, "10.0.0.1:7000".into()), (5461, 10922, "10.0.0.2:7001".into())],
+        };
+        assert_eq!(owners.lookup(0).as_deref(), Some("10.0.0.1:7000"));
+        assert_eq!(owners.lookup(5461).as_deref(), Some("10.0.0.2:7001"));
+        assert_eq!(owners.lookup(16000), None);
+    }
+
+    /// 测试回复结构的调试渲染
+    #[test]
+    fn test_render_value() {
+        use redis::Value;
+        let bulk = |s: &str| Value::BulkString(s.as_bytes().to_vec());
+
+        let array = Value::Array(vec![bulk("a"), Value::Int(5), Value::Nil]);
+        assert_eq!(render_value(&array), r#"Array[BulkString("a"), Int(5), Nil]"#);
+
+        let nested = Value::Map(vec![(bulk("k"), Value::Set(vec![Value::Okay, Value::Boolean(true)]))]);
+        assert_eq!(render_value(&nested), r#"Map{BulkString("k") => Set[Okay, Boolean(true)]}"#);
+
+        assert_eq!(render_value(&Value::BulkString(vec![0xff, 0x00])), "BulkString(0xff00)");
+        assert_eq!(render_value(&Value::SimpleString("PONG".into())), r#"SimpleString("PONG")"#);
+        assert_eq!(render_value(&Value::Array(vec![])), "Array[]");
+    }
+
+    /// 测试执行任意命令的调试输出
+    #[tokio::test]
+    #[ignore]
+    async fn test_run_raw_command_debug() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let key = gen_key("raw_debug");
+        svc.hset(0, &key, "f", "v").await.unwrap();
+
+        let args = vec!["HGETALL".to_string(), key.clone()];
+        let out = svc.run_raw_command_debug(0, &args).await.unwrap();
+        // RESP2 返回字段与值交替的数组，RESP3 返回 Map
+        assert!(
+            out == r#"Array[BulkString("f"), BulkString("v")]"# || out == r#"Map{BulkString("f") => BulkString("v")}"#,
+            "{}", out
+        );
+        assert!(svc.run_raw_command_debug(0, &[]).await.is_err());
+
+        // 附带耗时，单机模式下没有节点信息
+        let (timed, meta) = svc.run_raw_command_timed(0, &args).await.unwrap();
+        assert_eq!(timed, out);
+        assert!(meta.duration_ms.is_finite() && meta.duration_ms >= 0.0);
+        assert_eq!(meta.node, None);
+        assert!(svc.run_raw_command_timed(0, &[]).await.is_err());
+
+        // 清理
+        svc.del(0, &key).await.unwrap();
+    }
+
+    /// 测试命令数量与名称列表
+    #[tokio::test]
+    #[ignore]
+    async fn test_command_list() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let count = svc.command_count().await.unwrap();
+        let names = svc.command_list().await.unwrap();
+        assert!(count > 100);
+        assert!(names.iter().any(|n| n == "get"));
+        assert!(names.iter().any(|n| n == "set"));
+        assert!(names.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    /// 测试时钟偏差计算
+    #[test]
+    fn test_server_time_skew() {
+        let t = ServerTime::new(1_700_000_000, 500_000, 1_700_000_000_000.0);
+        assert!((t.skew_ms - 500.0).abs() < 1e-6);
+        let t = ServerTime::new(1_700_000_000, 0, 1_700_000_002_000.0);
+        assert!((t.skew_ms + 2000.0).abs() < 1e-6);
+    }
+
+    /// 测试服务端时间与本地时间接近
+    #[tokio::test]
+    #[ignore]
+    async fn test_server_time() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let (secs, micros) = svc.server_time().await.unwrap();
+        let local = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64;
+        assert!((secs - local).abs() < 60, "server {} vs local {}", secs, local);
+        assert!((0..1_000_000).contains(&micros));
+
+        let t = svc.server_time_skew().await.unwrap();
+        assert!(t.skew_ms.abs() < 60_000.0);
+    }
+
+    /// 测试 DEBUG SLEEP 的阻塞时长
+    #[tokio::test]
+    #[ignore]
+    async fn test_debug_sleep() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let start = Instant::now();
+        svc.debug_sleep(0.1).await.unwrap();
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(100), "{:?}", elapsed);
+        assert!(elapsed < Duration::from_secs(2), "{:?}", elapsed);
+
+        assert!(svc.debug_sleep(-1.0).await.is_err());
+        assert!(svc.debug_sleep(f64::NAN).await.is_err());
+        assert!(svc.debug_sleep(DEBUG_SLEEP_MAX_SECS + 1.0).await.is_err());
+    }
+
+    /// 测试分位数计算
+    #[test]
+    fn test_percentile() {
+        let samples: Vec<u64> = (1..=100).collect();
+        assert_eq!(percentile(&samples, 0.50), 50);
+        assert_eq!(percentile(&samples, 0.99), 99);
+        assert_eq!(percentile(&samples, 1.0), 100);
+        assert_eq!(percentile(&[7], 0.99), 7);
+        assert_eq!(percentile(&[], 0.5), 0);
+    }
+
+    /// 测试小规模性能测试
+    #[tokio::test]
+    #[ignore]
+    async fn test_benchmark() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let result = svc.benchmark(0, 200, 4, 64).await.unwrap();
+        assert_eq!(result.total_ops, 200);
+        assert!(result.ops_per_sec > 0.0);
+        assert!(result.p50_ms <= result.p99_ms);
+        assert!(!result.cancelled);
+
+        assert!(svc.benchmark(0, 0, 4, 64).await.is_err());
+        assert!(svc.benchmark(0, 10, BENCHMARK_MAX_CONCURRENCY + 1, 64).await.is_err());
+
+        // 没有测试运行时的取消请求不会影响下一次测试
+        assert!(!svc.cancel_benchmark());
+        assert!(!svc.benchmark(0, 20, 2, 64).await.unwrap().cancelled);
+
+        // 取消正在运行的测试
+        let runner = svc.clone();
+        let running = tokio::spawn(async move { runner.benchmark(0, BENCHMARK_MAX_OPS, 4, 64).await });
+        while !svc.cancel_benchmark() {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        let result = running.await.unwrap().unwrap();
+        assert!(result.cancelled);
+        assert!(result.total_ops < BENCHMARK_MAX_OPS);
+        assert!(!svc.cancel_benchmark());
+
+        // 临时键已清理
+        assert_eq!(svc.count_keys(0, "__redis_mate_bench__:*".into()).await.unwrap().count, 0);
+    }
+
+    /// 测试延迟指标计算
+    #[test]
+    fn test_metrics_recorder() {
+        let recorder = MetricsRecorder::default();
+        assert_eq!(recorder.snapshot(), ConnMetrics::default());
+
+        for ms in 1..=100 {
+            recorder.record(Duration::from_millis(ms), ms % 10 != 0);
+        }
+        let m = recorder.snapshot();
+        assert_eq!(m.count, 100);
+        assert_eq!(m.errors, 10);
+        assert!((m.avg_ms - 50.5).abs() < 1e-9);
+        assert!((m.p95_ms - 95.0).abs() < 1e-9);
+
+        // 采样窗口有界
+        for _ in 0..METRICS_SAMPLE_CAPACITY {
+            recorder.record(Duration::from_millis(1), true);
+        }
+        assert_eq!(recorder.samples.lock().unwrap().len(), METRICS_SAMPLE_CAPACITY);
+        assert!((recorder.snapshot().p95_ms - 1.0).abs() < 1e-9);
+    }
+
+    /// 测试读缓存命中与写入失效
+    #[tokio::test]
+    #[ignore]
+    async fn test_read_cache() {
+        init_test_logger();
+        let cfg = RedisConfig { read_cache_ttl_ms: Some(60_000), ..RedisConfig::default() };
+        let svc = RedisService::new(cfg).await.unwrap();
+        let key = gen_key("read_cache");
+        svc.set(0, &key, "v1", None, false).await.unwrap();
+
+        let v: Option<String> = svc.get(0, &key).await.unwrap();
+        assert_eq!(v.as_deref(), Some("v1"));
+        let before = svc.metrics_snapshot();
+        let v: Option<String> = svc.get(0, &key).await.unwrap();
+        assert_eq!(v.as_deref(), Some("v1"));
+        let after = svc.metrics_snapshot();
+        assert_eq!(after.cache_hits, before.cache_hits + 1);
+        // 命中缓存时不发送命令
+        assert_eq!(after.count, before.count);
+
+        // 写入后缓存失效
+        svc.set(0, &key, "v2", None, false).await.unwrap();
+        let v: Option<String> = svc.get(0, &key).await.unwrap();
+        assert_eq!(v.as_deref(), Some("v2"));
+        assert_eq!(svc.metrics_snapshot().cache_misses, after.cache_misses + 1);
+
+        // 未启用缓存时不统计
+        let plain = RedisService::new(RedisConfig::default()).await.unwrap();
+        let _: Option<String> = plain.get(0, &key).await.unwrap();
+        assert_eq!(plain.metrics_snapshot().cache_hits + plain.metrics_snapshot().cache_misses, 0);
+
+        // 清理
+        svc.del(0, &key).await.unwrap();
+    }
+
+    /// 测试延迟指标重置
+    #[test]
+    fn test_metrics_recorder_reset() {
+        let recorder = MetricsRecorder::default();
+        for ms in 1..=10 {
+            recorder.record(Duration::from_millis(ms), ms % 2 == 0);
+        }
+        recorder.reset();
+        assert_eq!(recorder.snapshot(), ConnMetrics::default());
+        assert!(recorder.samples.lock().unwrap().is_empty());
+    }
+
+    /// 测试连接运行时长与指标重置
+    #[tokio::test]
+    #[ignore]
+    async fn test_uptime_and_reset_metrics() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let first = svc.uptime();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(svc.uptime() >= first + Duration::from_millis(50));
+        assert_eq!(svc.clone().connected_since(), svc.connected_since());
+
+        svc.check_health().await.unwrap();
+        assert!(svc.metrics_snapshot().count > 0);
+        svc.reset_metrics();
+        assert_eq!(svc.metrics_snapshot().count, 0);
+    }
+
+    /// 测试集合与有序集合的 *STORE 操作
+    #[tokio::test]
+    #[ignore]
+    async fn test_store_ops() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let a = gen_key("store_a");
+        let b = gen_key("store_b");
+        let dest = gen_key("store_dest");
+        let z = gen_key("store_z");
+
+        for m in ["1", "2", "3", "4"] {
+            svc.sadd(0, &a, m).await.unwrap();
+        }
+        for m in ["3", "4", "5"] {
+            svc.sadd(0, &b, m).await.unwrap();
+        }
+        let keys = [a.clone(), b.clone()];
+
+        assert_eq!(svc.sinterstore(0, &dest, &keys).await.unwrap(), 2);
+        let members: Vec<String> = svc.smembers(0, &dest).await.unwrap();
+        assert_eq!(members.len(), 2);
+        assert_eq!(svc.sunionstore(0, &dest, &keys).await.unwrap(), 5);
+        assert_eq!(svc.sdiffstore(0, &dest, &keys).await.unwrap(), 2);
+
+        svc.del(0, &dest).await.unwrap();
+        for (i, m) in ["a", "b", "c", "d"].iter().enumerate() {
+            svc.zadd(0, &z, *m, i as f64).await.unwrap();
+        }
+        assert_eq!(svc.zrangestore(0, &dest, &z, 0, 1).await.unwrap(), 2);
+        let v = svc.zrange_withscores(0, &dest, 0, -1).await.unwrap();
+        assert_eq!(v, vec![("a".to_string(), 0.0), ("b".to_string(), 1.0)]);
+
+        // 清理
+        for k in [&a, &b, &dest, &z] {
+            svc.del(0, k).await.unwrap();
+        }
+    }
+
+    /// 测试 SINTERCARD 与 ZINTERCARD
+    #[tokio::test]
+    #[ignore]
+    async fn test_intercard() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let a = gen_key("intercard_a");
+        let b = gen_key("intercard_b");
+        let za = gen_key("intercard_za");
+        let zb = gen_key("intercard_zb");
+
+        for m in ["1", "2", "3", "4"] {
+            svc.sadd(0, &a, m).await.unwrap();
+            svc.zadd(0, &za, m, 1.0).await.unwrap();
+        }
+        for m in ["3", "4", "5"] {
+            svc.sadd(0, &b, m).await.unwrap();
+            svc.zadd(0, &zb, m, 2.0).await.unwrap();
+        }
+
+        let sets = [a.clone(), b.clone()];
+        assert_eq!(svc.sintercard(0, &sets, None).await.unwrap(), 2);
+        assert_eq!(svc.sintercard(0, &sets, Some(1)).await.unwrap(), 1);
+
+        let zsets = [za.clone(), zb.clone()];
+        assert_eq!(svc.zintercard(0, &zsets, None).await.unwrap(), 2);
+        assert_eq!(svc.zintercard(0, &zsets, Some(1)).await.unwrap(), 1);
+        assert!(svc.sintercard(0, &[], None).await.is_err());
+
+        // 清理
+        for k in [&a, &b, &za, &zb] {
+            svc.del(0, k).await.unwrap();
+        }
+    }
+
+    /// 测试 RANDOMKEY 与 TOUCH
+    #[tokio::test]
+    #[ignore]
+    async fn test_randomkey_touch() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        // 使用独立的数据库，尽量避免其他键的干扰
+        let db = 4;
+        let keys: Vec<String> = (0..3).map(|i| gen_key(&format!("randomkey_{}", i))).collect();
+        for k in &keys {
+            svc.set(db, k, "v", None, false).await.unwrap();
+        }
+
+        // 数据库中可能还有其他键，多抽几次
+        let mut found = false;
+        for _ in 0..50 {
+            let k = svc.randomkey(db).await.unwrap().expect("db should not be empty");
+            if keys.contains(&k) {
+                found = true;
+                break;
+            }
+        }
+        assert!(found);
+
+        let mut touch_keys = keys.clone();
+        touch_keys.push(gen_key("randomkey_missing"));
+        assert_eq!(svc.touch(db, &touch_keys).await.unwrap(), 3);
+
+        // 清理
+        for k in &keys {
+            svc.del(db, k).await.unwrap();
+        }
+    }
+
+    /// 测试管理命令
+    #[tokio::test]
+    #[ignore]
+    async fn test_admin_ops() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        
+        // Ping 测试
+        let pong = svc.ping().await.unwrap();
+        assert_eq!(pong, "PONG");
+        let latency = svc.ping_latency().await.unwrap();
+        assert!(latency.is_finite() && latency > 0.0, "unexpected latency: {}", latency);
+        // PING 不应写入测试键
+        assert!(!svc.exists(0, "__ping__").await.unwrap());
+        
+        // Config Set 测试（某些环境可能受限）
+        if let Err(e) = svc.config_set("timeout", "300").await {
+            println!("config set warning: {}", e);
+        } else {
+            println!("config set success");
+        }
+
+        // BGSAVE 测试
+        match svc.bgsave().await {
+            Ok(_) => println!("bgsave started"),
+            Err(e) => println!("bgsave failed (expected if busy): {}", e),
+        }
+    }
+
+    /// 测试 LASTSAVE 与 BGSAVE
+    #[tokio::test]
+    #[ignore]
+    async fn test_lastsave() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+
+        let before = svc.lastsave().await.unwrap();
+        assert!(before > 0);
+
+        // 后台保存可能因已有保存在进行而失败，只要时间戳保持为正即可
+        if let Err(e) = svc.bgsave().await {
+            println!("bgsave failed (expected if busy): {}", e);
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        let after = svc.lastsave().await.unwrap();
+        assert!(after > 0);
+        assert!(after >= before);
+    }
+
+    /// 测试运行模式不一致时产生警告
+    #[test]
+    fn test_server_info_mode_mismatch() {
+        let info = "# Server\r\nredis_version:7.2.4\r\nredis_mode:standalone\r\nos:Linux\r\n";
+        let cluster_cfg = RedisConfig { cluster: true, ..Default::default() };
+        let result = TestResult::from_server_info(info, &cluster_cfg, 1.5);
+        assert_eq!(result.server_version, "7.2.4");
+        assert_eq!(result.redis_mode, "standalone");
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].contains("Mode mismatch"));
+
+        let result = TestResult::from_server_info(info, &RedisConfig::default(), 1.5);
+        assert!(result.warnings.is_empty());
+
+        let sentinel_node = "redis_version:7.2.4\nredis_mode:sentinel\n";
+        let result = TestResult::from_server_info(sentinel_node, &RedisConfig::default(), 1.5);
+        assert_eq!(result.warnings.len(), 1);
+    }
+
+    /// 测试根据 INFO 与 MODULE LIST 推断服务端能力
+    #[test]
+    fn test_capabilities() {
+        use redis::Value;
+        let bulk = |s: &str| Value::BulkString(s.as_bytes().to_vec());
+        let modules = Value::Array(vec![
+            Value::Array(vec![bulk("name"), bulk("ReJSON"), bulk("ver"), Value::Int(20609)]),
+            Value::Map(vec![(bulk("name"), bulk("search")), (bulk("ver"), Value::Int(21005))]),
+        ]);
+        let names = parse_module_names(modules).unwrap();
+        assert_eq!(names, vec!["ReJSON".to_string(), "search".to_string()]);
+
+        let info = "# Server\r\nredis_version:6.2.14\r\nredis_mode:standalone\r\n";
+        let caps = Capabilities::from_info(info, names);
+        assert_eq!(caps.server_version, "6.2.14");
+        assert!(caps.has_json && caps.has_search);
+        assert!(caps.supports_resp3 && caps.supports_getdel);
+        assert!(!caps.supports_expire_flags);
+
+        let caps = Capabilities::from_info("redis_version:5.0.7\n", Vec::new());
+        assert!(!caps.has_json && !caps.supports_resp3 && !caps.supports_getdel);
+        assert!(Capabilities::from_info("redis_version:7.0.0\n", Vec::new()).supports_expire_flags);
+
+        assert_eq!(parse_module_names(Value::Array(vec![])).unwrap(), Vec::<String>::new());
+        assert!(parse_module_names(Value::Int(1)).is_err());
+    }
+
+    /// 测试用集群配置连接单机服务器时返回模式不一致警告
+    #[tokio::test]
+    #[ignore]
+    async fn test_probe_cluster_config_on_standalone() {
+        init_test_logger();
+        let cfg = RedisConfig { cluster: true, ..Default::default() };
+        let result = RedisService::probe(cfg).await.unwrap();
+        assert!(!result.ok);
+        assert_eq!(result.redis_mode, "standalone");
+        assert!(result.warnings.iter().any(|w| w.contains("Mode mismatch")));
+
+        let result = RedisService::probe(RedisConfig::default()).await.unwrap();
+        assert!(result.ok);
+        assert!(!result.server_version.is_empty());
+        assert!(result.warnings.is_empty());
+    }
+
+    /// 测试 INFO persistence 解析
+    #[test]
+    fn test_persistence_status_from_info() {
+        let info = "# Persistence\r\n\
+            loading:0\r\n\
+            rdb_changes_since_last_save:42\r\n\
+            rdb_bgsave_in_progress:1\r\n\
+            rdb_last_save_time:1700000000\r\n\
+            rdb_last_bgsave_status:ok\r\n\
+            aof_enabled:1\r\n\
+            aof_rewrite_in_progress:0\r\n\
+            aof_rewrite_scheduled:1\r\n\
+            aof_last_bgrewrite_status:err\r\n\
+            aof_last_write_status:ok\r\n";
+        let status = PersistenceStatus::from_info(info);
+        assert_eq!(status, PersistenceStatus {
+            loading: false,
+            rdb_changes_since_last_save: 42,
+            rdb_bgsave_in_progress: true,
+            rdb_last_save_time: 1_700_000_000,
+            rdb_last_bgsave_status: "ok".into(),
+            aof_enabled: true,
+            aof_rewrite_in_progress: false,
+            aof_rewrite_scheduled: true,
+            aof_last_bgrewrite_status: "err".into(),
+        });
+    }
+
+    /// 测试读取持久化状态
+    #[tokio::test]
+    #[ignore]
+    async fn test_persistence_status() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let status = svc.persistence_status().await.unwrap();
+        assert!(status.rdb_last_save_time > 0);
+        assert_eq!(status.rdb_last_save_time, svc.lastsave().await.unwrap());
+    }
+
+    /// 测试 WAIT 副本确认（需要配置副本）
+    #[tokio::test]
+    #[ignore]
+    async fn test_wait_replicas() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let key = gen_key("wait_test");
+
+        svc.set(0, &key, "v", None, false).await.unwrap();
+        let acked = svc.wait(1, 500).await.unwrap();
+        assert!(acked >= 0);
+
+        // 清理
+        svc.del(0, &key).await.unwrap();
+    }
+
+    /// 测试 CLUSTER INFO 解析
+    #[test]
+    fn test_cluster_status_from_info() {
+        let info = "cluster_enabled:1\r\n\
+            cluster_state:ok\r\n\
+            cluster_slots_assigned:16384\r\n\
+            cluster_slots_ok:16380\r\n\
+            cluster_slots_pfail:4\r\n\
+            cluster_slots_fail:0\r\n\
+            cluster_known_nodes:6\r\n\
+            cluster_size:3\r\n\
+            cluster_current_epoch:7\r\n\
+            cluster_my_epoch:2\r\n\
+            cluster_stats_messages_sent:1483972\r\n";
+        assert_eq!(ClusterStatus::from_info(info), ClusterStatus {
+            cluster_enabled: true,
+            cluster_state: "ok".into(),
+            cluster_slots_assigned: 16384,
+            cluster_slots_ok: 16380,
+            cluster_slots_pfail: 4,
+            cluster_slots_fail: 0,
+            cluster_known_nodes: 6,
+            cluster_size: 3,
+            cluster_current_epoch: 7,
+        });
+        assert_eq!(ClusterStatus::from_info("").cluster_state, "");
+    }
+
+    /// 测试集群整体状态（需要集群环境）
+    #[tokio::test]
+    #[ignore]
+    async fn test_cluster_info() {
+        init_test_logger();
+        let cfg = RedisConfig {
+            cluster: true,
+            urls: vec!["redis://127.0.0.1:7010".to_string()],
+            ..Default::default()
+        };
+        let svc = RedisService::new(cfg).await.expect("Cluster service init failed");
+        let status = svc.cluster_info().await.unwrap();
+        assert!(status.cluster_enabled);
+        assert_eq!(status.cluster_state, "ok");
+        assert!(status.cluster_known_nodes > 0);
+    }
+
+    /// 测试集群操作
+    #[tokio::test]
+    #[ignore]
+    async fn test_cluster_ops() {
+        init_test_logger();
+        let cfg = RedisConfig {
+            cluster: true,
+            urls: vec!["redis://127.0.0.1:7010".to_string()],
+            ..Default::default()
+        };
+        
+        // 需要集群环境运行
+        let svc = RedisService::new(cfg).await.expect("Cluster service init failed");
+        
+        // 验证基本功能
+        let ping = svc.ping().await.unwrap();
+        assert_eq!(ping, "PONG");
+        
+        // 验证键值操作
+        let key = gen_key("cluster_kv");
+        svc.set(0, &key, "c-val", None, false).await.unwrap();
+        let v: Option<String> = svc.get(0, &key).await.unwrap();
+        assert_eq!(v, Some("c-val".into()));
+
+        // 槽位统计路由到负责该槽位的节点
+        let slot = svc.cluster_keyslot(&key).await.unwrap();
+        assert!(svc.cluster_countkeysinslot(slot).await.unwrap() >= 1);
+        assert!(svc.cluster_countkeysinslot(16384).await.is_err());
+        svc.del(0, &key).await.unwrap();
+
+        // 集群信息
+        let nodes = svc.cluster_nodes().await.unwrap();
+        assert!(nodes.contains("myself"));
+        
+        let slots = svc.cluster_slots().await.unwrap();
+        if let redis::Value::Array(arr) = slots {
+            assert!(!arr.is_empty());
+        } else {
+            panic!("Expected Array for cluster slots, got {:?}", slots);
+        }
+    }
+
+    /// 测试跨多个槽位的单键操作不会泄露 MOVED/ASK 错误（需要集群环境）
+    #[tokio::test]
+    #[ignore]
+    async fn test_cluster_redirects() {
+        init_test_logger();
+        let cfg = RedisConfig {
+            cluster: true,
+            urls: vec!["redis://127.0.0.1:7010".to_string()],
+            ..Default::default()
+        };
+        let svc = RedisService::new(cfg).await.expect("Cluster service init failed");
+
+        // 不带哈希标签的键分布在不同槽位，通常落在不同节点
+        let keys: Vec<String> = (0..64).map(|i| gen_key(&format!("cluster_redirect_{}", i))).collect();
+        let slots: std::collections::HashSet<u16> = keys.iter().map(|k| key_slot(k)).collect();
+        assert!(slots.len() > 1);
+
+        for key in &keys {
+            svc.set(0, key, "v", None, false).await.unwrap();
+            svc.hset(0, &format!("{}:h", key), "f", "v").await.unwrap();
+        }
+        for key in &keys {
+            let v: Option<String> = svc.get(0, key).await.unwrap();
+            assert_eq!(v.as_deref(), Some("v"));
+            assert_eq!(svc.get_type(0, &format!("{}:h", key)).await.unwrap(), "hash");
+        }
+
+        // 清理
+        for key in &keys {
+            svc.del(0, key).await.unwrap();
+            svc.del(0, &format!("{}:h", key)).await.unwrap();
+        }
+    }
+
+    /// 测试槽位分配（需要集群环境，直接连接单个节点）
+    #[tokio::test]
+    #[ignore]
+    async fn test_cluster_slot_management() {
+        init_test_logger();
+        let cfg = RedisConfig { urls: vec!["redis://127.0.0.1:7010".to_string()], ..Default::default() };
+        let svc = RedisService::new(cfg).await.expect("Cluster node connection failed");
+
+        // 取当前节点负责的第一个槽位
+        let nodes = svc.cluster_nodes().await.unwrap();
+        let myself = nodes.lines().find(|l| l.contains("myself")).expect("myself line");
+        let range = myself.split_whitespace().nth(8).expect("node owns no slots");
+        let slot: u16 = range.split('-').next().unwrap().parse().unwrap();
+
+        // 先释放槽位，再重新分配给当前节点，恢复原状
+        svc.cluster_delslots(vec![slot]).await.unwrap();
+        svc.cluster_addslots(vec![slot]).await.unwrap();
+        assert!(svc.cluster_nodes().await.unwrap().lines().any(|l| l.contains("myself") && l.contains(range)));
+
+        // 参数校验
+        assert!(svc.cluster_addslots(vec![16384]).await.is_err());
+        assert!(svc.cluster_setslot(slot, "importing", None).await.is_err());
+        assert!(svc.cluster_setslot(slot, "bogus", Some("id")).await.is_err());
+        svc.cluster_setslot(slot, "stable", None).await.unwrap();
+    }
+
+    /// 测试集群副本读取（需要带副本的集群环境）
+    #[tokio::test]
+    #[ignore]
+    async fn test_cluster_read_from_replicas() {
+        init_test_logger();
+        let cfg = RedisConfig {
+            cluster: true,
+            urls: vec!["redis://127.0.0.1:7010".to_string()],
+            read_from_replicas: true,
+            ..Default::default()
+        };
+        let svc = RedisService::new(cfg).await.expect("Cluster service init failed");
+
+        let key = gen_key("cluster_replica_read");
+        svc.set(0, &key, "r-val", None, false).await.unwrap();
+        // 复制是异步的，稍等片刻再从副本读取
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let v: Option<String> = svc.get(0, &key).await.unwrap();
+        assert_eq!(v, Some("r-val".into()));
+        svc.del(0, &key).await.unwrap();
+    }
+
+    /// 测试哨兵操作
+    #[tokio::test]
+    #[ignore]
+    async fn test_sentinel_ops() {
+        init_test_logger();
+        // Docker 哨兵测试环境特殊处理
+        unsafe { std::env::set_var("FORCE_SENTINEL_LOCAL_IP", "1"); }
+        
+        let cfg = RedisConfig {
+            sentinel: true,
+            sentinel_master_name: Some("mymaster".into()),
+            sentinel_urls: vec!["redis://127.0.0.1:26379".to_string()],
+            ..Default::default()
+        };
+        
+        let svc = RedisService::new(cfg).await.expect("Sentinel service init failed");
+        
+        let ping = svc.ping().await.unwrap();
+        assert_eq!(ping, "PONG");
+        
+        let key = gen_key("sentinel_kv");
+        svc.set(0, &key, "s-val", None, false).await.unwrap();
+        let v: Option<String> = svc.get(0, &key).await.unwrap();
+        assert_eq!(v, Some("s-val".into()));
+        svc.del(0, &key).await.unwrap();
+    }
+
+    /// 测试哨兵主节点发现
+    #[tokio::test]
+    #[ignore]
+    async fn test_sentinel_master_discovery() {
+        init_test_logger();
+        let cfg = RedisConfig {
+            sentinel: true,
+            sentinel_master_name: Some("mymaster".into()),
+            sentinel_urls: vec!["redis://127.0.0.1:26379".to_string()],
+            ..Default::default()
+        };
+        let svc = RedisService::new(cfg).await.expect("Sentinel service init failed");
+
+        let (ip, port) = svc.sentinel_get_master_addr("mymaster").await.unwrap().expect("master addr");
+        assert!(!ip.is_empty());
+        assert!(port > 0);
+
+        let masters = svc.sentinel_masters().await.unwrap();
+        assert!(masters.iter().any(|m| m.get("name").map(String::as_str) == Some("mymaster")));
+
+        assert_eq!(svc.sentinel_get_master_addr("no-such-master").await.unwrap(), None);
+    }
+
+    /// 测试 JSON 操作
+    #[tokio::test]
+    #[ignore]
+    async fn test_json_ops() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let key = gen_key("json_test");
+        
+        #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Clone)]
+        struct User {
+            name: String,
+            age: u32,
+        }
+        
+        let u = User { name: "Alice".into(), age: 30 };
+        svc.set_json(0, &key, &u, None).await.unwrap();
+        
+        let u2: Option<User> = svc.get_json(0, &key).await.unwrap();
+        assert_eq!(Some(u), u2);
+        
+        svc.del(0, &key).await.unwrap();
+    }
+
+    /// 测试批量操作
+    #[tokio::test]
+    #[ignore]
+    async fn test_batch_ops() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let k1 = gen_key("batch_1");
+        let k2 = gen_key("batch_2");
+        
+        // 批量设置
+        let items = vec![(k1.clone(), "v1".to_string()), (k2.clone(), "v2".to_string())];
+        svc.mset(0, &items).await.unwrap();
+        
+        // 批量获取
+        let keys = vec![k1.clone(), k2.clone(), "non_existent".to_string()];
+        let vals: Vec<Option<String>> = svc.mget(0, &keys).await.unwrap();
+        assert_eq!(vals.len(), 3);
+        assert_eq!(vals[0], Some("v1".into()));
+        assert_eq!(vals[1], Some("v2".into()));
+        assert_eq!(vals[2], None);
+        
+        // 清理
+        svc.del(0, &k1).await.unwrap();
+        svc.del(0, &k2).await.unwrap();
     }
-    
-    Ok(format!("redis+sentinel://{}/{}", hosts.join(","), master))
-}
 
-#[cfg(test)]
-pub mod tests {
-    use super::*;
-    use std::time::{SystemTime, UNIX_EPOCH};
+    /// 测试指定数据库的批量操作
+    #[tokio::test]
+    #[ignore]
+    async fn test_batch_ops_with_db() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let k1 = gen_key("batch_db_1");
+        let k2 = gen_key("batch_db_2");
+        let missing = gen_key("batch_db_missing");
 
-    /// 初始化测试日志记录器
-    fn init_test_logger() {
-        let _ = env_logger::builder().is_test(true).try_init();
+        svc.mset(1, &[(k1.clone(), "v1"), (k2.clone(), "v2")]).await.unwrap();
+
+        // 保持键的顺序
+        let vals: Vec<Option<String>> = svc.mget(1, &[k2.clone(), missing.clone(), k1.clone()]).await.unwrap();
+        assert_eq!(vals, vec![Some("v2".to_string()), None, Some("v1".to_string())]);
+
+        // 0 号库中不存在
+        let vals: Vec<Option<String>> = svc.mget(0, &[k1.clone(), k2.clone()]).await.unwrap();
+        assert_eq!(vals, vec![None, None]);
+
+        // 清理
+        svc.del(1, &k1).await.unwrap();
+        svc.del(1, &k2).await.unwrap();
     }
 
-    /// 生成唯一的测试键名
-    fn gen_key(prefix: &str) -> String {
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_nanos();
-        format!("{}-{}", prefix, timestamp)
+    /// 测试按时刻设置过期时间
+    #[tokio::test]
+    #[ignore]
+    async fn test_pexpire_at() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let key = gen_key("pexpireat");
+        svc.set(0, &key, "v", None, false).await.unwrap();
+
+        let now_ms = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as i64;
+        assert!(svc.pexpire_at(0, &key, now_ms + 5000).await.unwrap());
+        let pttl = svc.pttl(0, &key).await.unwrap();
+        assert!(pttl > 3000 && pttl <= 5000, "pttl {}", pttl);
+
+        // 过去的时刻会立即删除键
+        assert!(svc.pexpire_at(0, &key, now_ms - 1000).await.unwrap());
+        assert!(!svc.exists(0, &key).await.unwrap());
+        assert!(!svc.pexpire_at(0, &key, now_ms + 5000).await.unwrap());
     }
 
-    /// 测试基础键值操作
+    /// 测试 EXPIRE 条件标志
     #[tokio::test]
     #[ignore]
-    async fn test_kv_ops() {
+    async fn test_expire_with_flag() {
         init_test_logger();
         let svc = RedisService::new(RedisConfig::default()).await.unwrap();
-        
-        let key = gen_key("kv_test");
-        
-        // 基础设置和获取
-        svc.set(0, &key, "val-1", Some(60)).await.unwrap();
-        let v: Option<String> = svc.get(0, &key).await.unwrap();
-        assert_eq!(v, Some("val-1".into()));
-        
-        // 更新值
-        svc.set(0, &key, "val-2", None).await.unwrap();
-        let v: Option<String> = svc.get(0, &key).await.unwrap();
-        assert_eq!(v, Some("val-2".into()));
+        let key = gen_key("expire_flag");
+        svc.set(0, &key, "v", None, false).await.unwrap();
 
-        // TTL 操作
-        svc.expire(0, &key, 100).await.unwrap();
+        // NX 仅在没有过期时间时生效
+        assert!(svc.expire_with_flag(0, &key, 100, Some(ExpireFlag::Nx)).await.unwrap());
+        assert!(!svc.expire_with_flag(0, &key, 200, Some(ExpireFlag::Nx)).await.unwrap());
         let ttl = svc.ttl(0, &key).await.unwrap();
         assert!(ttl > 0 && ttl <= 100);
-        
-        // 存在性检查
-        assert!(svc.exists(0, &key).await.unwrap());
-        
-        // 删除操作
-        let ok = svc.del(0, &key).await.unwrap();
-        assert!(ok);
-        assert!(!svc.exists(0, &key).await.unwrap());
+
+        // GT 只能延长
+        assert!(!svc.expire_with_flag(0, &key, 50, Some(ExpireFlag::Gt)).await.unwrap());
+        assert!(svc.expire_with_flag(0, &key, 300, Some(ExpireFlag::Gt)).await.unwrap());
+        let ttl = svc.ttl(0, &key).await.unwrap();
+        assert!(ttl > 200);
+
+        // 清理
+        svc.del(0, &key).await.unwrap();
     }
 
-    /// 测试哈希操作
+    /// 测试解析 SCAN 回复
+    #[test]
+    fn test_parse_scan_reply() {
+        use redis::Value;
+        let reply = Value::Array(vec![
+            Value::BulkString(b"17".to_vec()),
+            Value::Array(vec![Value::BulkString(b"a".to_vec()), Value::BulkString(vec![0xff, 0xfe])]),
+        ]);
+        assert_eq!(parse_scan_reply(reply).unwrap(), (17, 2));
+
+        let done = Value::Array(vec![Value::BulkString(b"0".to_vec()), Value::Array(vec![])]);
+        assert_eq!(parse_scan_reply(done).unwrap(), (0, 0));
+
+        assert!(parse_scan_reply(Value::Nil).is_err());
+    }
+
+    /// 测试按模式统计键数量
     #[tokio::test]
     #[ignore]
-    async fn test_hash_ops() {
+    async fn test_count_keys() {
         init_test_logger();
         let svc = RedisService::new(RedisConfig::default()).await.unwrap();
-        
-        let key = gen_key("hash_test");
-        
-        // 单字段设置和获取
-        svc.hset(0, &key, "f1", "v1").await.unwrap();
-        svc.hset(0, &key, "f2", "v2").await.unwrap();
-        
-        let v1: Option<String> = svc.hget(0, &key, "f1").await.unwrap();
-        assert_eq!(v1, Some("v1".into()));
-        
-        let v2: Option<String> = svc.hget(0, &key, "f2").await.unwrap();
-        assert_eq!(v2, Some("v2".into()));
-        
-        let v3: Option<String> = svc.hget(0, &key, "f3").await.unwrap();
-        assert_eq!(v3, None);
-        
+        let prefix = gen_key("session");
+        let keys: Vec<String> = (0..50).map(|i| format!("{}:{}", prefix, i)).collect();
+        for key in &keys {
+            svc.set(0, key, "1", None, false).await.unwrap();
+        }
+
+        let result = svc.count_keys(0, format!("{}:*", prefix)).await.unwrap();
+        assert_eq!(result.count, 50);
+        assert!(result.complete);
+        assert!(result.iterations >= 1);
+
+        // 清理
+        for key in &keys {
+            svc.del(0, key).await.unwrap();
+        }
+    }
+
+    /// 测试 SCAN 键名解析与全量匹配模式识别
+    #[test]
+    fn test_parse_scan_keys() {
+        use redis::Value;
+        let bulk = |s: &str| Value::BulkString(s.as_bytes().to_vec());
+        let reply = Value::Array(vec![bulk("17"), Value::Array(vec![bulk("cache:a"), bulk("cache:b")])]);
+        assert_eq!(parse_scan_keys(reply).unwrap(), (17, vec![b"cache:a".to_vec(), b"cache:b".to_vec()]));
+        assert!(parse_scan_keys(Value::Array(vec![bulk("0")])).is_err());
+        // 非 UTF-8 键名原样保留
+        let reply = Value::Array(vec![bulk("0"), Value::Array(vec![Value::BulkString(vec![b'k', 0xff])])]);
+        assert_eq!(parse_scan_keys(reply).unwrap(), (0, vec![vec![b'k', 0xff]]));
+
+        assert!(matches_all_keys("*"));
+        assert!(matches_all_keys("**"));
+        assert!(!matches_all_keys("cache:*"));
+        assert!(!matches_all_keys(""));
+    }
+
+    /// 测试按模式批量删除
+    #[tokio::test]
+    #[ignore]
+    async fn test_delete_by_pattern() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let prefix = gen_key("cache");
+        let keys: Vec<String> = (0..120).map(|i| format!("{}:{}", prefix, i)).collect();
+        for key in &keys {
+            svc.set(0, key, "1", None, false).await.unwrap();
+        }
+        let other = gen_key("keep");
+        svc.set(0, &other, "1", None, false).await.unwrap();
+        // 非 UTF-8 键名也应被删除
+        let mut binary_key = format!("{}:", prefix).into_bytes();
+        binary_key.push(0xff);
+        let mut raw = redis::Client::open("redis://127.0.0.1:6379").unwrap().get_connection().unwrap();
+        redis::cmd("SET").arg(&binary_key).arg("1").query::<()>(&mut raw).unwrap();
+
+        let deleted = svc.delete_by_pattern(0, format!("{}:*", prefix), 50).await.unwrap();
+        assert_eq!(deleted, 121);
+        assert!(!redis::cmd("EXISTS").arg(&binary_key).query::<bool>(&mut raw).unwrap());
+        for key in &keys {
+            assert!(!svc.exists(0, key).await.unwrap());
+        }
+        assert!(svc.exists(0, &other).await.unwrap());
+        assert!(svc.delete_by_pattern(0, String::new(), 50).await.is_err());
+
+        // 清理
+        svc.del(0, &other).await.unwrap();
+    }
+
+    /// 测试按模式批量设置过期时间
+    #[tokio::test]
+    #[ignore]
+    async fn test_expire_by_pattern() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let prefix = gen_key("temp");
+        let keys: Vec<String> = (0..30).map(|i| format!("{}:{}", prefix, i)).collect();
+        for key in &keys {
+            svc.set(0, key, "1", None, false).await.unwrap();
+        }
+        let other = gen_key("keep");
+        svc.set(0, &other, "1", None, false).await.unwrap();
+
+        let applied = svc.expire_by_pattern(0, format!("{}:*", prefix), 600, 10).await.unwrap();
+        assert_eq!(applied, 30);
+        for key in &keys {
+            assert!(svc.ttl(0, key).await.unwrap() > 0);
+        }
+        assert_eq!(svc.ttl(0, &other).await.unwrap(), -1);
+        assert!(svc.expire_by_pattern(0, String::new(), 600, 10).await.is_err());
+
+        // 清理
+        svc.delete_by_pattern(0, format!("{}:*", prefix), 50).await.unwrap();
+        svc.del(0, &other).await.unwrap();
+    }
+
+    /// 测试对非字符串键执行 GET 时返回实际类型
+    #[tokio::test]
+    #[ignore]
+    async fn test_get_wrong_type() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let key = gen_key("wrongtype");
+        svc.lpush(0, &key, "a").await.unwrap();
+
+        let err = svc.get::<String>(0, &key).await.unwrap_err();
+        let wrong = err.downcast_ref::<WrongType>().expect("WrongType error");
+        assert_eq!(wrong.actual, "list");
+
+        let resp: crate::command::CommandResponse<String> = crate::command::AppError::from(err).into_response();
+        assert_eq!(resp.code, "WRONGTYPE");
+        assert_eq!(resp.message, format!("key '{}' is a list, use the list viewer", key));
+
         // 清理
         svc.del(0, &key).await.unwrap();
     }
 
-    /// 测试哈希批量操作
+    /// 测试向非哈希键写入字段时返回实际类型和命令
     #[tokio::test]
     #[ignore]
-    async fn test_hash_batch_ops() {
+    async fn test_write_wrong_type() {
         init_test_logger();
         let svc = RedisService::new(RedisConfig::default()).await.unwrap();
-        let key = gen_key("hash_batch_test");
+        let key = gen_key("write_wrongtype");
+        svc.set(0, &key, "v", None, false).await.unwrap();
 
-        // 批量设置
-        let items = vec![("f1", "v1"), ("f2", "v2")];
-        svc.hmset(0, &key, &items).await.unwrap();
+        let err = svc.hset(0, &key, "field", "value").await.unwrap_err();
+        let wrong = err.downcast_ref::<WrongType>().expect("WrongType error");
+        assert_eq!(wrong.actual, "string");
+        assert_eq!(wrong.operation.as_deref(), Some("HSET"));
+
+        let resp: crate::command::CommandResponse<bool> = crate::command::AppError::from(err).into_response();
+        assert_eq!(resp.code, "WRONGTYPE");
+        assert_eq!(resp.message, format!("cannot HSET key '{}', it already holds a string", key));
+
+        let err = svc.lpush(0, &key, "a").await.unwrap_err();
+        assert_eq!(err.downcast_ref::<WrongType>().unwrap().operation.as_deref(), Some("LPUSH"));
+
+        // 清理
+        svc.del(0, &key).await.unwrap();
+    }
+
+    /// 测试按类型选择元素数量命令
+    #[test]
+    fn test_size_command() {
+        assert_eq!(size_command("string"), Some("STRLEN"));
+        assert_eq!(size_command("hash"), Some("HLEN"));
+        assert_eq!(size_command("stream"), Some("XLEN"));
+        assert_eq!(size_command("ReJSON-RL"), None);
+    }
+
+    /// 测试删除前的键预览
+    #[tokio::test]
+    #[ignore]
+    async fn test_preview_key() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let hash = gen_key("preview_hash");
+        let string = gen_key("preview_str");
+        svc.hset(0, &hash, "a", "1").await.unwrap();
+        svc.hset(0, &hash, "b", "2").await.unwrap();
+        svc.set(0, &string, "hello", Some(100), false).await.unwrap();
+
+        let p = svc.preview_key(0, &hash).await.unwrap();
+        assert_eq!(p, KeyPreview { key_type: "hash".into(), ttl: -1, element_count: Some(2), sample: Vec::new(), error: None });
+
+        let p = svc.preview_key(0, &string).await.unwrap();
+        assert_eq!(p.key_type, "string");
+        assert_eq!(p.element_count, Some(5));
+        assert!(p.ttl > 0 && p.ttl <= 100);
+
+        let p = svc.preview_key(0, "no_such_key_for_preview").await.unwrap();
+        assert_eq!(p, KeyPreview { key_type: "none".into(), ttl: -2, element_count: None, sample: Vec::new(), error: None });
+
+        // 清理
+        svc.del(0, &hash).await.unwrap();
+        svc.del(0, &string).await.unwrap();
+    }
+
+    /// 测试批量预览不同类型的键
+    #[tokio::test]
+    #[ignore]
+    async fn test_preview_keys() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let string = gen_key("previews_str");
+        let list = gen_key("previews_list");
+        let set = gen_key("previews_set");
+        svc.set(0, &string, "x".repeat(1000), None, false).await.unwrap();
+        for v in 0..20 {
+            svc.lpush(0, &list, v).await.unwrap();
+        }
+        svc.sadd(0, &set, "m").await.unwrap();
+
+        let keys = vec![string.clone(), list.clone(), "no_such_key_for_previews".to_string(), set.clone()];
+        let previews = svc.preview_keys(0, keys).await.unwrap();
+        assert_eq!(previews.len(), 4);
+        assert_eq!(previews[0].key_type, "string");
+        assert_eq!(previews[0].element_count, Some(1000));
+        assert_eq!(previews[0].sample[0].len(), PREVIEW_STRING_BYTES);
+        assert_eq!(previews[1].key_type, "list");
+        assert_eq!(previews[1].element_count, Some(20));
+        assert_eq!(previews[1].sample.len(), PREVIEW_SAMPLE_SIZE);
+        assert_eq!(previews[1].sample[0], "19");
+        assert_eq!(previews[2].key_type, "none");
+        assert_eq!(previews[2].ttl, -2);
+        assert_eq!(previews[3].sample, vec!["m".to_string()]);
+        assert!(previews.iter().all(|p| p.error.is_none()));
+
+        // 清理
+        svc.del(0, &string).await.unwrap();
+        svc.del(0, &list).await.unwrap();
+        svc.del(0, &set).await.unwrap();
+    }
+
+    /// 测试 `KeyPayload` 的标签序列化格式
+    #[test]
+    fn test_key_payload_serde() {
+        let payload: KeyPayload = serde_json::from_str(r#"{"type":"hash","fields":{"a":"1"}}"#).unwrap();
+        assert_eq!(payload, KeyPayload::Hash { fields: BTreeMap::from([("a".to_string(), "1".to_string())]) });
+        assert_eq!(payload.type_name(), "hash");
+
+        let payload: KeyPayload = serde_json::from_str(r#"{"type":"zset","members":[["m",1.5]]}"#).unwrap();
+        assert_eq!(payload, KeyPayload::Zset { members: vec![("m".to_string(), 1.5)] });
+
+        let json = serde_json::to_value(KeyPayload::List { items: vec!["x".into()] }).unwrap();
+        assert_eq!(json, serde_json::json!({ "type": "list", "items": ["x"] }));
+        assert!(KeyPayload::Set { members: Vec::new() }.is_empty());
+        assert!(!KeyPayload::String { value: String::new() }.is_empty());
+        assert!(serde_json::from_str::<KeyPayload>(r#"{"type":"stream","entries":[]}"#).is_err());
+    }
+
+    /// 测试样本回复的解析
+    #[test]
+    fn test_parse_sample() {
+        let bulk = |s: &str| redis::Value::BulkString(s.as_bytes().to_vec());
+        let hscan = redis::Value::Array(vec![bulk("0"), redis::Value::Array(vec![bulk("f1"), bulk("v1"), bulk("f2"), bulk("v2")])]);
+        assert_eq!(parse_sample("hash", hscan), vec!["f1: v1".to_string(), "f2: v2".to_string()]);
+        assert_eq!(parse_sample("list", redis::Value::Array(vec![bulk("a"), bulk("b")])), vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(parse_sample("string", bulk("hello")), vec!["hello".to_string()]);
+        assert!(parse_sample("string", redis::Value::Nil).is_empty());
+    }
+
+    /// 测试哈希字段值的 JSON 识别
+    #[test]
+    fn test_parsed_hash_field() {
+        let f = ParsedHashField::parse("profile".into(), r#"{"age": 30, "tags": ["a"]}"#.into());
+        assert!(f.is_json);
+        assert_eq!(f.json, Some(serde_json::json!({ "age": 30, "tags": ["a"] })));
+
+        let f = ParsedHashField::parse("list".into(), " [1, 2] ".into());
+        assert!(f.is_json);
+
+        for raw in ["hello", "42", "true", "{broken", ""] {
+            let f = ParsedHashField::parse("plain".into(), raw.into());
+            assert!(!f.is_json);
+            assert_eq!(f.json, None);
+            assert_eq!(f.raw, raw);
+        }
+    }
+
+    /// 测试读取哈希时识别 JSON 字段
+    #[tokio::test]
+    #[ignore]
+    async fn test_hgetall_parsed() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let key = gen_key("hgetall_parsed");
+        svc.hset(0, &key, "meta", r#"{"v":1}"#).await.unwrap();
+        svc.hset(0, &key, "name", "alice").await.unwrap();
+
+        let fields = svc.hgetall_parsed(0, &key).await.unwrap();
+        assert_eq!(fields.len(), 2);
+        assert_eq!(fields[0].field, "meta");
+        assert!(fields[0].is_json);
+        assert_eq!(fields[0].json, Some(serde_json::json!({ "v": 1 })));
+        assert_eq!(fields[1].field, "name");
+        assert!(!fields[1].is_json);
+        assert_eq!(fields[1].raw, "alice");
+
+        // 清理
+        svc.del(0, &key).await.unwrap();
+    }
+
+    /// 测试键检查元数据
+    #[tokio::test]
+    #[ignore]
+    async fn test_inspect_key() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let key = gen_key("inspect_zset");
+        svc.zadd(0, &key, "a", 1.0).await.unwrap();
+        svc.zadd(0, &key, "b", 2.0).await.unwrap();
+        svc.zadd(0, &key, "c", 3.0).await.unwrap();
+
+        let info = svc.inspect_key(0, &key).await.unwrap();
+        assert_eq!(info.key_type, "zset");
+        assert_eq!(info.ttl_ms, -1);
+        assert!(matches!(info.encoding.as_deref(), Some("listpack" | "ziplist")));
+        assert_eq!(info.element_count, Some(3));
+        assert!(info.memory_usage.unwrap() > 0);
+
+        let info = svc.inspect_key(0, "no_such_key_for_inspect").await.unwrap();
+        assert_eq!(info, KeyInspection { key_type: "none".into(), ttl_ms: -2, encoding: None, memory_usage: None, element_count: None });
+
+        // 清理
+        svc.del(0, &key).await.unwrap();
+    }
+
+    /// 测试 ACL GETUSER 回复解析
+    #[test]
+    fn test_parse_acl_getuser() {
+        use redis::Value;
+        let bulk = |s: &str| Value::BulkString(s.as_bytes().to_vec());
+        let reply = Value::Array(vec![
+            bulk("flags"), Value::Array(vec![bulk("on"), bulk("nopass")]),
+            bulk("passwords"), Value::Array(vec![]),
+            bulk("commands"), bulk("+@all"),
+            bulk("selectors"), Value::Array(vec![Value::Array(vec![bulk("commands"), bulk("-@all +get")])]),
+        ]);
+        let user = parse_acl_getuser(reply).unwrap();
+        assert_eq!(user["flags"], serde_json::json!(["on", "nopass"]));
+        assert_eq!(user["passwords"], serde_json::json!([]));
+        assert_eq!(user["commands"], serde_json::json!("+@all"));
+        assert_eq!(user["selectors"], serde_json::json!([["commands", "-@all +get"]]));
+
+        assert!(parse_acl_getuser(Value::Nil).unwrap().is_empty());
+        assert!(parse_acl_getuser(Value::Array(vec![bulk("flags")])).is_err());
+    }
+
+    /// 测试 ACL 用户管理
+    #[tokio::test]
+    #[ignore]
+    async fn test_acl_users() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let users = svc.acl_list().await.unwrap();
+        assert!(users.iter().any(|u| u.starts_with("user default ")));
+
+        let name = gen_key("acl_user");
+        svc.acl_setuser(&name, &["on".to_string(), "nopass".to_string(), "+get".to_string()]).await.unwrap();
+        let user = svc.acl_getuser(&name).await.unwrap();
+        assert!(user["flags"].as_array().unwrap().contains(&serde_json::json!("on")));
+
+        assert_eq!(svc.acl_deluser(&name).await.unwrap(), 1);
+        assert!(svc.acl_getuser(&name).await.unwrap().is_empty());
+    }
+
+    /// 测试扫描键并附带类型
+    #[tokio::test]
+    #[ignore]
+    async fn test_scan_with_types() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let prefix = gen_key("scan_types");
+        let str_key = format!("{}:str", prefix);
+        let list_key = format!("{}:list", prefix);
+        let hash_key = format!("{}:hash", prefix);
+        svc.set(0, &str_key, "v", None, false).await.unwrap();
+        svc.lpush(0, &list_key, "v").await.unwrap();
+        svc.hset(0, &hash_key, "f", "v").await.unwrap();
+
+        let mut found = HashMap::new();
+        let mut cursor = 0;
+        loop {
+            let (next, page) = svc.scan_with_types(0, cursor, Some(format!("{}:*", prefix)), Some(100)).await.unwrap();
+            found.extend(page);
+            cursor = next;
+            if cursor == 0 {
+                break;
+            }
+        }
+        assert_eq!(found.get(&str_key).map(String::as_str), Some("string"));
+        assert_eq!(found.get(&list_key).map(String::as_str), Some("list"));
+        assert_eq!(found.get(&hash_key).map(String::as_str), Some("hash"));
+
+        // 清理
+        for key in [&str_key, &list_key, &hash_key] {
+            svc.del(0, key).await.unwrap();
+        }
+    }
+
+    /// 测试连接不可达地址时在超时时间内返回错误
+    #[tokio::test]
+    async fn test_connect_timeout() {
+        let cfg = RedisConfig {
+            urls: vec!["redis://10.255.255.1:6379".into()],
+            connect_timeout_ms: 500,
+            ..Default::default()
+        };
+        let started = Instant::now();
+        let result = RedisService::new(cfg).await;
+        assert!(result.is_err());
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+
+    /// 测试查找空闲键
+    #[tokio::test]
+    #[ignore]
+    async fn test_find_idle_keys() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let prefix = gen_key("idle");
+        let keys: Vec<String> = (0..5).map(|i| format!("{}:{}", prefix, i)).collect();
+        for key in &keys {
+            svc.set(0, key, "v", None, false).await.unwrap();
+        }
+        let pattern = Some(format!("{}:*", prefix));
+
+        // 刚写入的键空闲时间接近 0，不应超过较高的阈值
+        let idle = svc.find_idle_keys(0, 3600, pattern.clone(), 100).await.unwrap();
+        assert!(idle.is_empty());
+
+        let idle = svc.find_idle_keys(0, 0, pattern, 3).await.unwrap();
+        assert_eq!(idle.len(), 3);
+        assert!(idle.iter().all(|(k, _)| k.starts_with(&prefix)));
+
+        // 清理
+        for key in &keys {
+            svc.del(0, key).await.unwrap();
+        }
+    }
+
+    /// 测试 RedisJSON 整数回复解析
+    #[test]
+    fn test_first_json_int() {
+        use redis::Value;
+        assert_eq!(first_json_int(Value::Int(3)).unwrap(), Some(3));
+        assert_eq!(first_json_int(Value::Array(vec![Value::Int(2), Value::Nil])).unwrap(), Some(2));
+        assert_eq!(first_json_int(Value::Array(vec![Value::Nil])).unwrap(), None);
+        assert_eq!(first_json_int(Value::Array(vec![])).unwrap(), None);
+        assert!(first_json_int(Value::Okay).is_err());
+    }
+
+    /// 测试批量写入 JSON 文档（需要 RedisJSON 模块）
+    #[tokio::test]
+    #[ignore]
+    async fn test_json_mset() {
+        init_test_logger();
+        let cfg = RedisConfig::default();
+        let svc = RedisService::new(cfg).await.expect("Failed to create service");
+
+        let keys: Vec<String> = (0..3).map(|i| gen_key(&format!("json_mset_{}", i))).collect();
+        let items = keys
+            .iter()
+            .enumerate()
+            .map(|(i, k)| (k.clone(), "$".to_string(), serde_json::json!({ "id": i, "name": format!("doc{}", i) })))
+            .collect();
+        svc.json_mset(0, items).await.unwrap();
+
+        for (i, key) in keys.iter().enumerate() {
+            let v = svc.json_get(0, key, "$").await.unwrap();
+            assert_eq!(v, Some(serde_json::json!([{ "id": i, "name": format!("doc{}", i) }])));
+        }
+        assert!(svc.json_mset(0, Vec::new()).await.is_err());
+
+        for key in &keys {
+            svc.del(0, key).await.unwrap();
+        }
+    }
+
+    /// 测试 JSON.TYPE / JSON.OBJKEYS 回复解析
+    #[test]
+    fn test_parse_json_type_and_objkeys() {
+        use redis::Value;
+        let bulk = |s: &str| Value::BulkString(s.as_bytes().to_vec());
 
-        // 获取所有字段
-        let all: HashMap<String, String> = svc.hgetall(0, &key).await.unwrap();
-        assert_eq!(all.get("f1"), Some(&"v1".to_string()));
-        assert_eq!(all.get("f2"), Some(&"v2".to_string()));
+        assert_eq!(parse_json_type(Value::Array(vec![bulk("object")])).unwrap(), Some("object".into()));
+        assert_eq!(parse_json_type(Value::Array(vec![Value::Array(vec![bulk("array")])])).unwrap(), Some("array".into()));
+        assert_eq!(parse_json_type(Value::SimpleString("integer".into())).unwrap(), Some("integer".into()));
+        assert_eq!(parse_json_type(Value::Array(vec![])).unwrap(), None);
+        assert_eq!(parse_json_type(Value::Nil).unwrap(), None);
 
-        // 清理
-        svc.del(0, &key).await.unwrap();
+        let jsonpath = Value::Array(vec![Value::Array(vec![bulk("a"), bulk("b")])]);
+        assert_eq!(parse_json_objkeys(jsonpath).unwrap(), vec!["a", "b"]);
+        let legacy = Value::Array(vec![bulk("x")]);
+        assert_eq!(parse_json_objkeys(legacy).unwrap(), vec!["x"]);
+        assert!(parse_json_objkeys(Value::Array(vec![Value::Nil])).unwrap().is_empty());
+        assert!(parse_json_objkeys(Value::Nil).unwrap().is_empty());
     }
 
-    /// 测试列表操作
+    /// 测试 JSON 文档的类型与对象键查询（需要 RedisJSON 模块）
     #[tokio::test]
     #[ignore]
-    async fn test_list_ops() {
+    async fn test_json_type_objkeys() {
         init_test_logger();
         let svc = RedisService::new(RedisConfig::default()).await.unwrap();
-        
-        let key = gen_key("list_test");
-        
-        // 推入操作
-        svc.lpush(0, &key, "v1").await.unwrap();
-        svc.lpush(0, &key, "v2").await.unwrap();
-        
-        // 弹出操作（LIFO）
-        let v: Option<String> = svc.rpop(0, &key).await.unwrap();
-        assert_eq!(v, Some("v1".into())); 
-        
-        let v: Option<String> = svc.rpop(0, &key).await.unwrap();
-        assert_eq!(v, Some("v2".into()));
-        
-        let v: Option<String> = svc.rpop(0, &key).await.unwrap();
-        assert_eq!(v, None);
+        let key = gen_key("json_introspect");
+        let doc = serde_json::json!({"user": {"name": "a", "age": 3}, "tags": ["x"]});
+        svc.json_set(0, &key, "$", &doc).await.unwrap();
+
+        assert_eq!(svc.json_type(0, &key, "$").await.unwrap(), Some("object".into()));
+        assert_eq!(svc.json_type(0, &key, "$.tags").await.unwrap(), Some("array".into()));
+        assert_eq!(svc.json_type(0, &key, "$.missing").await.unwrap(), None);
+
+        let mut keys = svc.json_objkeys(0, &key, "$").await.unwrap();
+        keys.sort();
+        assert_eq!(keys, vec!["tags", "user"]);
+        assert!(svc.json_objkeys(0, &key, "$.tags").await.unwrap().is_empty());
+
+        // 清理
+        svc.del(0, &key).await.unwrap();
     }
 
-    /// 测试集合操作
+    /// 测试 RedisJSON 数组追加、数值增量与删除（需要 RedisJSON 模块）
     #[tokio::test]
     #[ignore]
-    async fn test_set_ops() {
+    async fn test_json_arrappend_numincrby_del() {
         init_test_logger();
         let svc = RedisService::new(RedisConfig::default()).await.unwrap();
-        
-        let key = gen_key("set_test");
-        
-        // 添加成员
-        svc.sadd(0, &key, "m1").await.unwrap();
-        svc.sadd(0, &key, "m2").await.unwrap();
-        svc.sadd(0, &key, "m1").await.unwrap(); // 重复添加
-        
-        // 获取所有成员
-        let members: Vec<String> = svc.smembers(0, &key).await.unwrap();
-        assert_eq!(members.len(), 2);
-        assert!(members.contains(&"m1".to_string()));
-        assert!(members.contains(&"m2".to_string()));
-        
+        let key = gen_key("json_edit");
+        let doc = serde_json::json!({"tags": ["a"], "count": 1, "tmp": true});
+        svc.json_set(0, &key, "$", &doc).await.unwrap();
+
+        let len = svc.json_arrappend(0, &key, "$.tags", &[serde_json::json!("b"), serde_json::json!("c")]).await.unwrap();
+        assert_eq!(len, Some(3));
+        assert_eq!(svc.json_arrappend(0, &key, "$.count", &[serde_json::json!(1)]).await.unwrap(), None);
+
+        assert_eq!(svc.json_numincrby(0, &key, "$.count", 2.5).await.unwrap(), Some(3.5));
+
+        assert_eq!(svc.json_del(0, &key, "$.tmp").await.unwrap(), 1);
+        let v = svc.json_get(0, &key, "$.tmp").await.unwrap();
+        assert_eq!(v, Some(serde_json::json!([])));
+
         // 清理
         svc.del(0, &key).await.unwrap();
     }
 
-    /// 测试管理命令
+    /// 测试跨库移动键
     #[tokio::test]
     #[ignore]
-    async fn test_admin_ops() {
+    async fn test_move_key() {
         init_test_logger();
         let svc = RedisService::new(RedisConfig::default()).await.unwrap();
-        
-        // Ping 测试
-        let pong = svc.ping().await.unwrap();
-        assert_eq!(pong, "PONG");
-        
-        // Config Set 测试（某些环境可能受限）
-        if let Err(e) = svc.config_set("timeout", "300").await {
-            println!("config set warning: {}", e);
-        } else {
-            println!("config set success");
-        }
+        let key = gen_key("move_key");
+        svc.set(0, &key, "v", None, false).await.unwrap();
 
-        // BGSAVE 测试
-        match svc.bgsave().await {
-            Ok(_) => println!("bgsave started"),
-            Err(e) => println!("bgsave failed (expected if busy): {}", e),
-        }
+        assert!(svc.move_key(0, &key, 1).await.unwrap());
+        assert!(!svc.exists(0, &key).await.unwrap());
+        let v: Option<String> = svc.get(1, &key).await.unwrap();
+        assert_eq!(v, Some("v".into()));
+
+        // 源库中已不存在
+        assert!(!svc.move_key(0, &key, 1).await.unwrap());
+
+        // 目标库已存在同名键
+        svc.set(0, &key, "v0", None, false).await.unwrap();
+        assert!(!svc.move_key(0, &key, 1).await.unwrap());
+        assert!(svc.move_key(0, &key, 0).await.is_err());
+
+        // 清理
+        svc.del(0, &key).await.unwrap();
+        svc.del(1, &key).await.unwrap();
     }
 
-    /// 测试集群操作
+    /// 测试交换数据库
     #[tokio::test]
     #[ignore]
-    async fn test_cluster_ops() {
+    async fn test_swapdb() {
         init_test_logger();
-        let cfg = RedisConfig {
-            cluster: true,
-            urls: vec!["redis://127.0.0.1:7010".to_string()],
-            ..Default::default()
-        };
-        
-        // 需要集群环境运行
-        let svc = RedisService::new(cfg).await.expect("Cluster service init failed");
-        
-        // 验证基本功能
-        let ping = svc.ping().await.unwrap();
-        assert_eq!(ping, "PONG");
-        
-        // 验证键值操作
-        let key = gen_key("cluster_kv");
-        svc.set(0, &key, "c-val", None).await.unwrap();
-        let v: Option<String> = svc.get(0, &key).await.unwrap();
-        assert_eq!(v, Some("c-val".into()));
-        svc.del(0, &key).await.unwrap();
+        let svc = RedisService::new(RedisConfig { retries: 3, ..RedisConfig::default() }).await.unwrap();
+        let key0 = gen_key("swapdb_0");
+        let key1 = gen_key("swapdb_1");
+        svc.set(0, &key0, "zero", None, false).await.unwrap();
+        svc.set(1, &key1, "one", None, false).await.unwrap();
 
-        // 集群信息
-        let nodes = svc.cluster_nodes().await.unwrap();
-        assert!(nodes.contains("myself"));
-        
-        let slots = svc.cluster_slots().await.unwrap();
-        if let redis::Value::Array(arr) = slots {
-            assert!(!arr.is_empty());
-        } else {
-            panic!("Expected Array for cluster slots, got {:?}", slots);
-        }
+        // 开启重试时服务端也只执行一次 SWAPDB
+        let before = command_calls("swapdb").await;
+        svc.swapdb(0, 1).await.unwrap();
+        assert_eq!(command_calls("swapdb").await, before + 1);
+        assert!(!svc.exists(0, &key0).await.unwrap());
+        assert!(!svc.exists(1, &key1).await.unwrap());
+        let v: Option<String> = svc.get(1, &key0).await.unwrap();
+        assert_eq!(v, Some("zero".into()));
+        let v: Option<String> = svc.get(0, &key1).await.unwrap();
+        assert_eq!(v, Some("one".into()));
+
+        // 相同数据库应直接报错
+        assert!(svc.swapdb(1, 1).await.is_err());
+
+        // 换回并清理
+        svc.swapdb(0, 1).await.unwrap();
+        svc.del(0, &key0).await.unwrap();
+        svc.del(1, &key1).await.unwrap();
     }
 
-    /// 测试哨兵操作
-    #[tokio::test]
-    #[ignore]
-    async fn test_sentinel_ops() {
-        init_test_logger();
-        // Docker 哨兵测试环境特殊处理
-        unsafe { std::env::set_var("FORCE_SENTINEL_LOCAL_IP", "1"); }
-        
-        let cfg = RedisConfig {
-            sentinel: true,
-            sentinel_master_name: Some("mymaster".into()),
-            sentinel_urls: vec!["redis://127.0.0.1:26379".to_string()],
-            ..Default::default()
-        };
-        
-        let svc = RedisService::new(cfg).await.expect("Sentinel service init failed");
-        
-        let ping = svc.ping().await.unwrap();
-        assert_eq!(ping, "PONG");
-        
-        let key = gen_key("sentinel_kv");
-        svc.set(0, &key, "s-val", None).await.unwrap();
-        let v: Option<String> = svc.get(0, &key).await.unwrap();
-        assert_eq!(v, Some("s-val".into()));
-        svc.del(0, &key).await.unwrap();
+    /// 读取服务端记录的命令调用次数（INFO commandstats）
+    async fn command_calls(command: &str) -> u64 {
+        let client = redis::Client::open("redis://127.0.0.1:6379").unwrap();
+        let mut conn = client.get_multiplexed_async_connection().await.unwrap();
+        let info: String = redis::cmd("INFO").arg("commandstats").query_async(&mut conn).await.unwrap();
+        let prefix = format!("cmdstat_{}:calls=", command);
+        info.lines()
+            .find_map(|line| line.strip_prefix(&prefix))
+            .and_then(|rest| rest.split(',').next())
+            .and_then(|calls| calls.parse().ok())
+            .unwrap_or(0)
     }
 
-    /// 测试 JSON 操作
+    /// 测试原子获取并删除
     #[tokio::test]
     #[ignore]
-    async fn test_json_ops() {
+    async fn test_getdel() {
         init_test_logger();
         let svc = RedisService::new(RedisConfig::default()).await.unwrap();
-        let key = gen_key("json_test");
-        
-        #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Clone)]
-        struct User {
-            name: String,
-            age: u32,
-        }
-        
-        let u = User { name: "Alice".into(), age: 30 };
-        svc.set_json(0, &key, &u, None).await.unwrap();
-        
-        let u2: Option<User> = svc.get_json(0, &key).await.unwrap();
-        assert_eq!(Some(u), u2);
-        
-        svc.del(0, &key).await.unwrap();
+        let key = gen_key("getdel");
+        svc.set(0, &key, "token", None, false).await.unwrap();
+
+        assert_eq!(svc.getdel(0, &key).await.unwrap(), Some("token".into()));
+        assert!(!svc.exists(0, &key).await.unwrap());
+        assert_eq!(svc.getdel(0, &key).await.unwrap(), None);
     }
 
-    /// 测试批量操作
+    /// 测试 GETEX 过期选项的序列化格式
+    #[test]
+    fn test_set_expiry_serde() {
+        let ex: SetExpiry = serde_json::from_str(r#"{"EX":60}"#).unwrap();
+        assert_eq!(ex, SetExpiry::Ex(60));
+        let at: SetExpiry = serde_json::from_str(r#"{"EXAT":1700000000}"#).unwrap();
+        assert_eq!(at, SetExpiry::ExAt(1_700_000_000));
+        let persist: SetExpiry = serde_json::from_str(r#""PERSIST""#).unwrap();
+        assert_eq!(persist, SetExpiry::Persist);
+    }
+
+    /// 测试读取值并延长过期时间
     #[tokio::test]
     #[ignore]
-    async fn test_batch_ops() {
+    async fn test_getex() {
         init_test_logger();
         let svc = RedisService::new(RedisConfig::default()).await.unwrap();
-        let k1 = gen_key("batch_1");
-        let k2 = gen_key("batch_2");
-        
-        // 批量设置
-        let items = vec![(k1.clone(), "v1".to_string()), (k2.clone(), "v2".to_string())];
-        svc.mset(&items).await.unwrap();
-        
-        // 批量获取
-        let keys = vec![k1.clone(), k2.clone(), "non_existent".to_string()];
-        let vals: Vec<Option<String>> = svc.mget(&keys).await.unwrap();
-        assert_eq!(vals.len(), 3);
-        assert_eq!(vals[0], Some("v1".into()));
-        assert_eq!(vals[1], Some("v2".into()));
-        assert_eq!(vals[2], None);
-        
+        let key = gen_key("getex");
+        svc.set(0, &key, "session", Some(10), false).await.unwrap();
+
+        let v = svc.getex(0, &key, Some(SetExpiry::Ex(600))).await.unwrap();
+        assert_eq!(v, Some("session".into()));
+        let ttl = svc.ttl(0, &key).await.unwrap();
+        assert!(ttl > 10 && ttl <= 600, "ttl = {}", ttl);
+
+        svc.getex(0, &key, Some(SetExpiry::Persist)).await.unwrap();
+        assert_eq!(svc.ttl(0, &key).await.unwrap(), -1);
+
+        assert_eq!(svc.getex(0, "no_such_key_for_getex", Some(SetExpiry::Ex(5))).await.unwrap(), None);
+
         // 清理
-        svc.del(0, &k1).await.unwrap();
-        svc.del(0, &k2).await.unwrap();
+        svc.del(0, &key).await.unwrap();
     }
 
     /// 测试事务操作
@@ -3375,7 +10694,7 @@ pub mod tests {
         let key = gen_key("tx_test");
         
         // 初始化
-        svc.set(0, &key, "0", None).await.unwrap();
+        svc.set(0, &key, "0", None, false).await.unwrap();
         
         // 执行事务
         let key_tx = key.clone();
@@ -3392,6 +10711,54 @@ pub mod tests {
         svc.del(0, &key).await.unwrap();
     }
 
+    /// 测试 WATCH 乐观事务
+    #[tokio::test]
+    #[ignore]
+    async fn test_transaction_watched_ops() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let key = gen_key("tx_watch_test");
+        let hash_key = gen_key("tx_watch_hash");
+        svc.set(0, &key, "10", None, false).await.unwrap();
+        // 同时监视一个非字符串键，其值为 None
+        svc.hset(0, &hash_key, "f", "v").await.unwrap();
+
+        // 另一个客户端，用于在 WATCH 之后修改键
+        let other = redis::Client::open("redis://127.0.0.1:6379").unwrap();
+        let mut attempts = 0;
+        let mut aborted = 0;
+        loop {
+            attempts += 1;
+            let first = attempts == 1;
+            let key_tx = key.clone();
+            let other = other.clone();
+            let committed = svc.transaction_watched(0, vec![key.clone(), hash_key.clone()], move |values, pipe| {
+                assert_eq!(values[1], None);
+                let current: i64 = values[0].as_deref().unwrap().parse().unwrap();
+                if first {
+                    // 模拟并发修改
+                    let mut conn = other.get_connection().unwrap();
+                    redis::cmd("INCRBY").arg(&key_tx).arg(100).query::<i64>(&mut conn).unwrap();
+                }
+                pipe.set(&key_tx, current * 2).ignore();
+            }).await.unwrap();
+            if committed {
+                break;
+            }
+            aborted += 1;
+            assert!(attempts < 5, "transaction never committed");
+        }
+
+        assert_eq!(aborted, 1);
+        // 第二次基于并发修改后的值 110 计算
+        let v: Option<i64> = svc.get(0, &key).await.unwrap();
+        assert_eq!(v, Some(220));
+
+        // 清理
+        svc.del(0, &key).await.unwrap();
+        svc.del(0, &hash_key).await.unwrap();
+    }
+
     /// 测试分布式锁操作
     #[tokio::test]
     #[ignore]
@@ -3418,6 +10785,30 @@ pub mod tests {
         svc.del(0, &resource).await.unwrap();
     }
 
+    /// 测试带上限的原子自增
+    #[tokio::test]
+    #[ignore]
+    async fn test_incr_capped() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let key = gen_key("incr_capped");
+
+        for expected in 1..=3 {
+            assert_eq!(svc.incr_capped(0, &key, 3, Some(60)).await.unwrap(), Some(expected));
+        }
+        // 首次自增时设置了过期时间
+        let ttl = svc.ttl(0, &key).await.unwrap();
+        assert!(ttl > 0 && ttl <= 60);
+
+        // 达到上限后不再自增
+        assert_eq!(svc.incr_capped(0, &key, 3, Some(60)).await.unwrap(), None);
+        let v: Option<i64> = svc.get(0, &key).await.unwrap();
+        assert_eq!(v, Some(3));
+
+        // 清理
+        svc.del(0, &key).await.unwrap();
+    }
+
     /// 测试发布订阅操作
     #[tokio::test]
     #[ignore]
@@ -3436,7 +10827,7 @@ pub mod tests {
             let _ = svc_clone.subscribe(ch_clone, move |msg| {
                 let _ = tx.try_send(msg);
                 false // 收到第一条消息后停止
-            }).await;
+            }, || {}).await;
         });
         
         tokio::time::sleep(Duration::from_millis(500)).await; // 等待订阅建立
@@ -3449,6 +10840,117 @@ pub mod tests {
         assert_eq!(msg, Some("hello".to_string()));
     }
 
+    /// 测试订阅流结束后会重建连接并继续投递消息
+    #[tokio::test]
+    async fn test_subscription_reconnects() {
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let resubscribed = Arc::new(AtomicUsize::new(0));
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let initial = futures::stream::iter(vec!["a".to_string()]).boxed();
+        let reconnect = {
+            let attempts = attempts.clone();
+            move || {
+                let n = attempts.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    match n {
+                        0 => Ok(futures::stream::iter(vec!["b".to_string()]).boxed()),
+                        1 => Err(anyhow!("connection refused")),
+                        _ => Ok(futures::stream::iter(vec!["c".to_string()]).boxed()),
+                    }
+                }
+            }
+        };
+        let callback = {
+            let received = received.clone();
+            move |payload: String| {
+                let stop = payload == "c";
+                received.lock().unwrap().push(payload);
+                !stop
+            }
+        };
+        let on_resubscribed = {
+            let resubscribed = resubscribed.clone();
+            move || { resubscribed.fetch_add(1, Ordering::SeqCst); }
+        };
+
+        let run = run_subscription(initial, reconnect, Duration::from_millis(1), callback, on_resubscribed);
+        tokio::time::timeout(Duration::from_secs(2), run).await.unwrap();
+
+        assert_eq!(*received.lock().unwrap(), vec!["a", "b", "c"]);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        assert_eq!(resubscribed.load(Ordering::SeqCst), 2);
+    }
+
+    /// 测试单连接多频道订阅
+    #[tokio::test]
+    #[ignore]
+    async fn test_subscribe_many() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let ch1 = gen_key("multi_ch1");
+        let ch2 = gen_key("multi_ch2");
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel(2);
+        let handle = svc.subscribe_many(vec![ch1.clone(), ch2.clone(), ch1.clone()], move |msg| {
+            let _ = tx.try_send(msg);
+            true
+        }, |_| {}).await.unwrap();
+        // 与单频道订阅共享同一条连接
+        let single = svc.subscribe(ch1.clone(), |_| true, || {}).await.unwrap();
+
+        tokio::time::sleep(Duration::from_millis(200)).await; // 等待订阅建立
+
+        // 两个频道各自只有这一个订阅连接，重复的频道只投递一次
+        assert_eq!(svc.publish(&ch1, "one").await.unwrap(), 1);
+        assert_eq!(svc.publish(&ch2, "two").await.unwrap(), 1);
+
+        let first = tokio::time::timeout(Duration::from_secs(2), rx.recv()).await.unwrap().unwrap();
+        let second = tokio::time::timeout(Duration::from_secs(2), rx.recv()).await.unwrap().unwrap();
+        assert_eq!(first, PubSubMessage { channel: ch1, payload: "one".into() });
+        assert_eq!(second, PubSubMessage { channel: ch2, payload: "two".into() });
+        assert!(rx.try_recv().is_err());
+
+        handle.abort();
+        single.abort();
+    }
+
+    /// 测试批量订阅：快速发布的消息按批次送达
+    #[tokio::test]
+    #[ignore]
+    async fn test_subscribe_batched() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let channel = gen_key("batched_ch");
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let handle = svc.subscribe_batched(channel.clone(), 200, 10, move |batch| {
+            let _ = tx.send(batch);
+            true
+        }, || {}).await.unwrap();
+        assert!(svc.subscribe_batched(channel.clone(), 0, 10, |_| true, || {}).await.is_err());
+
+        tokio::time::sleep(Duration::from_millis(200)).await; // 等待订阅建立
+
+        for i in 0..45 {
+            svc.publish(&channel, &i.to_string()).await.unwrap();
+        }
+
+        let mut received = Vec::new();
+        let mut batches = 0;
+        while received.len() < 45 {
+            let batch = tokio::time::timeout(Duration::from_secs(2), rx.recv()).await.unwrap().unwrap();
+            assert!(!batch.is_empty() && batch.len() <= 10, "unexpected batch size: {}", batch.len());
+            received.extend(batch);
+            batches += 1;
+        }
+        assert!(batches < 45, "messages were not batched");
+        let expected: Vec<String> = (0..45).map(|i| i.to_string()).collect();
+        assert_eq!(received, expected);
+
+        handle.abort();
+    }
+
     /// 测试分片发布订阅操作
     #[tokio::test]
     #[ignore]
@@ -3482,8 +10984,8 @@ pub mod tests {
         // Prepare some data
         let k1 = gen_key("scan_1");
         let k2 = gen_key("scan_2");
-        svc.set(0, &k1, "1", None).await.unwrap();
-        svc.set(0, &k2, "2", None).await.unwrap();
+        svc.set(0, &k1, "1", None, false).await.unwrap();
+        svc.set(0, &k2, "2", None, false).await.unwrap();
         
         // Test scan
         // Use a pattern that matches our generated keys
@@ -3492,7 +10994,7 @@ pub mod tests {
         let mut acc: Vec<String> = Vec::new();
         let mut rounds = 0;
         loop {
-            let (next, keys) = svc.scan(0, cursor, Some(pattern.clone()), Some(100)).await.unwrap();
+            let (next, keys) = svc.scan(0, cursor, Some(pattern.clone()), Some(100), None).await.unwrap();
             acc.extend(keys);
             cursor = next;
             rounds += 1;
@@ -3509,4 +11011,34 @@ pub mod tests {
         svc.del(0, &k1).await.unwrap();
         svc.del(0, &k2).await.unwrap();
     }
+
+    /// 测试 SCAN 的 TYPE 过滤
+    #[tokio::test]
+    #[ignore]
+    async fn test_scan_type_filter() {
+        init_test_logger();
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+
+        let prefix = gen_key("scan_type");
+        let string_key = format!("{}:s", prefix);
+        let hash_key = format!("{}:h", prefix);
+        svc.set(0, &string_key, "1", None, false).await.unwrap();
+        svc.hset(0, &hash_key, "f", "v").await.unwrap();
+
+        let pattern = format!("{}:*", prefix);
+        let mut cursor: u64 = 0;
+        let mut acc: Vec<String> = Vec::new();
+        loop {
+            let (next, keys) = svc.scan(0, cursor, Some(pattern.clone()), Some(1000), Some("string".to_string())).await.unwrap();
+            acc.extend(keys);
+            cursor = next;
+            if cursor == 0 { break; }
+        }
+        assert_eq!(acc, vec![string_key.clone()]);
+
+        assert!(svc.scan(0, 0, None, None, Some("document".to_string())).await.is_err());
+
+        svc.del(0, &string_key).await.unwrap();
+        svc.del(0, &hash_key).await.unwrap();
+    }
 }
@@ -0,0 +1,781 @@
+//! 数据导入导出模块
+//!
+//! 本模块负责在本地文件与 Redis 之间批量传输键值数据。
+//!
+//! # 功能特性
+//!
+//! - **流式读取**：文件在阻塞线程中逐条解析，按批次通过通道交给异步写入端，大文件不会整体载入内存
+//! - **流式写出**：导出时逐页 SCAN，每个键读取后立即交给阻塞线程写入文件
+//! - **批量写入**：每批使用 MSET 写入，集群模式下按槽位分组后再写入
+//! - **错误汇总**：解析失败或写入失败的条目计入报告，不会中断整个导入
+//! - **连接间迁移**：逐页 SCAN 源连接，以 DUMP/RESTORE 复制到另一个连接，保留类型与过期时间
+//!
+//! # 支持的格式
+//!
+//! - `json`：顶层为对象，键为 Redis 键名，字符串值原样写入，其他值写入其 JSON 文本
+//! - `csv`：每行 `key,value`，支持双引号包裹及 `""` 转义；首行为 `key,value` 时视为表头跳过
+//!
+//! 导出文件为带类型的 JSON 文档：
+//!
+//! ```json
+//! { "version": 1, "keys": [ { "key": "user:1", "type": "hash", "value": { "name": "a" } } ] }
+//! ```
+//!
+//! # 使用示例
+//!
+//! ```rust
+//! let report = transfer::import_keys(&svc, 0, "seed.json".into(), ImportFormat::Json).await?;
+//! println!("imported={} failed={}", report.imported, report.failed);
+//! ```
+
+use anyhow::{anyhow, Context, Result};
+use futures::stream::{self, StreamExt};
+use serde::de::{Deserializer as _, MapAccess, Visitor};
+use std::collections::BTreeMap;
+use std::io::{BufRead, Write};
+use tokio::sync::mpsc;
+
+use crate::logging;
+use crate::redis_service::{is_busy_key, is_wrong_type, key_slot, RedisService};
+
+/// 每批写入的键数量
+const IMPORT_BATCH_SIZE: usize = 500;
+
+/// 报告中保留的错误信息条数上限
+const MAX_REPORTED_ERRORS: usize = 100;
+
+/// 导出时每次 SCAN 的 COUNT 提示
+const EXPORT_SCAN_COUNT: usize = 500;
+
+/// 导出文件格式版本
+const EXPORT_VERSION: u32 = 1;
+
+/// 迁移时每次 SCAN 的 COUNT 提示
+const MIGRATE_SCAN_COUNT: usize = 500;
+
+/// 迁移时同时进行 DUMP/RESTORE 的键数量
+const MIGRATE_CONCURRENCY: usize = 16;
+
+/// 导入文件格式
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportFormat {
+    Json,
+    Csv,
+}
+
+/// 导入结果报告
+///
+/// - `imported`: 成功写入的键数量
+/// - `failed`: 解析或写入失败的条目数量
+/// - `errors`: 失败原因（最多保留 100 条）
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ImportReport {
+    pub imported: u64,
+    pub failed: u64,
+    pub errors: Vec<String>,
+}
+
+impl ImportReport {
+    /// 记录失败条目
+    fn fail(&mut self, count: u64, error: String) {
+        self.failed += count;
+        if self.errors.len() < MAX_REPORTED_ERRORS {
+            self.errors.push(error);
+        }
+    }
+}
+
+/// 迁移结果报告
+///
+/// - `migrated`: 成功写入目标端的键数量
+/// - `skipped`: 目标端已存在（未开启覆盖）或读取前已被删除的键数量
+/// - `failed`: DUMP 或 RESTORE 失败的键数量
+/// - `errors`: 失败原因（最多保留 100 条）
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct MigrateReport {
+    pub migrated: u64,
+    pub skipped: u64,
+    pub failed: u64,
+    pub errors: Vec<String>,
+}
+
+impl MigrateReport {
+    /// 记录失败的键
+    fn fail(&mut self, error: String) {
+        self.failed += 1;
+        if self.errors.len() < MAX_REPORTED_ERRORS {
+            self.errors.push(error);
+        }
+    }
+}
+
+/// 导出结果报告
+///
+/// - `exported`: 写入文件的键数量
+/// - `skipped`: 读取前已被删除、读取时类型已改变或类型不支持而跳过的键数量
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ExportReport {
+    pub exported: u64,
+    pub skipped: u64,
+}
+
+/// 单个键的迁移结果
+enum MigrateOutcome {
+    Migrated,
+    Skipped,
+    Failed(String),
+}
+
+/// 一批解析结果，`Err` 为无法解析的条目及原因
+type Batch = Vec<std::result::Result<(String, String), String>>;
+
+/// 从文件批量导入键值对
+///
+/// 解析在阻塞线程中进行，每凑满 500 条就发送给写入端执行 MSET，
+/// 因此内存中最多只保留少量批次。
+///
+/// # 参数
+///
+/// - `db`: 目标数据库索引
+/// - `path`: 文件路径
+/// - `format`: 文件格式
+///
+/// # 返回值
+///
+/// 返回 `ImportReport`。文件无法打开或 JSON 结构不是对象时返回错误；
+/// 单条记录的解析或写入失败只计入报告。
+pub async fn import_keys(svc: &RedisService, db: u32, path: String, format: ImportFormat) -> Result<ImportReport> {
+    let (tx, mut rx) = mpsc::channel::<Batch>(2);
+    let reader_path = path.clone();
+    let reader = tokio::task::spawn_blocking(move || -> Result<()> {
+        let file = std::fs::File::open(&reader_path).with_context(|| format!("open {}", reader_path))?;
+        let reader = std::io::BufReader::new(file);
+        match format {
+            ImportFormat::Json => read_json(reader, &tx),
+            ImportFormat::Csv => read_csv(reader, &tx),
+        }
+    });
+
+    let mut report = ImportReport::default();
+    while let Some(batch) = rx.recv().await {
+        let mut pairs = Vec::with_capacity(batch.len());
+        for item in batch {
+            match item {
+                Ok(pair) => pairs.push(pair),
+                Err(e) => report.fail(1, e),
+            }
+        }
+        write_batch(svc, db, pairs, &mut report).await;
+    }
+
+    reader.await.context("import reader task")??;
+    logging::info("IMPORT", &format!("path={} imported={} failed={}", path, report.imported, report.failed));
+    Ok(report)
+}
+
+/// 导出文件中的一个键
+///
+/// `key` 为键名，`value` 的结构由 `type` 决定：
+/// - `string`: 字符串
+/// - `hash`: 字段到值的对象；存在非 UTF-8 字段名时改为 `[字段, 值]` 数组
+/// - `list` / `set`: 字符串数组
+/// - `zset`: `[成员, 分数]` 数组，分数写为字符串（如 `"1.5"`、`"inf"`），JSON 数字无法表示 `±inf`
+///
+/// 其中键名与每个字符串位置的内容不是合法 UTF-8 时写为 `{"base64": "..."}`，不会有损转换。
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ExportedKey {
+    pub key: serde_json::Value,
+    #[serde(rename = "type")]
+    pub key_type: String,
+    pub value: serde_json::Value,
+}
+
+/// 将匹配模式的键导出为 JSON 文件
+///
+/// 逐页执行 SCAN 并管道化获取类型，按类型读取值后立即发送给写文件线程，
+/// 内存中只保留当前页的键。
+///
+/// # 参数
+///
+/// - `db`: 数据库索引
+/// - `pattern`: 匹配模式，如 `user:*`
+/// - `path`: 输出文件路径，已存在时覆盖
+///
+/// # 返回值
+///
+/// 返回 `ExportReport`。SCAN 或读取失败时返回错误并删除未写完的文件；
+/// 读取前已被删除或类型已改变（`WRONGTYPE`）的键只计入 `skipped`。
+///
+/// # 注意事项
+///
+/// - 仅导出 string/hash/list/set/zset，其他类型（如 stream）会被跳过并记录警告
+/// - 键名按原始字节读取，非 UTF-8 的键名同样可以导出
+/// - SCAN 期间键被修改时，同一个键可能被导出多次
+pub async fn export_keys(svc: &RedisService, db: u32, pattern: String, path: String) -> Result<ExportReport> {
+    let (tx, mut rx) = mpsc::channel::<ExportedKey>(EXPORT_SCAN_COUNT);
+    let writer_path = path.clone();
+    let writer = tokio::task::spawn_blocking(move || -> Result<()> {
+        let file = std::fs::File::create(&writer_path).with_context(|| format!("create {}", writer_path))?;
+        let mut out = std::io::BufWriter::new(file);
+        write!(out, "{{\"version\":{},\"keys\":[", EXPORT_VERSION)?;
+        let mut first = true;
+        while let Some(entry) = rx.blocking_recv() {
+            if !first {
+                out.write_all(b",")?;
+            }
+            first = false;
+            serde_json::to_writer(&mut out, &entry).context("write export entry")?;
+        }
+        out.write_all(b"]}")?;
+        out.flush().context("flush export file")?;
+        Ok(())
+    });
+
+    let report = async {
+        let mut report = ExportReport::default();
+        let mut cursor = 0u64;
+        loop {
+            let (next, page) = svc.scan_with_types_bytes(db, cursor, Some(pattern.clone()), Some(EXPORT_SCAN_COUNT)).await?;
+            for (key, key_type) in page {
+                let value = match read_value(svc, db, &key, &key_type).await {
+                    Ok(Some(value)) => value,
+                    Ok(None) => {
+                        if !matches!(key_type.as_str(), "none" | "string" | "hash" | "list" | "set" | "zset") {
+                            logging::warn("EXPORT", &format!("skip key {} of unsupported type {}", String::from_utf8_lossy(&key), key_type));
+                        }
+                        report.skipped += 1;
+                        continue;
+                    }
+                    // SCAN 与读取之间键被删除后以其他类型重新写入
+                    Err(e) if is_wrong_type(&e) => {
+                        report.skipped += 1;
+                        continue;
+                    }
+                    Err(e) => return Err(e),
+                };
+                let key = bytes_to_json(key);
+                tx.send(ExportedKey { key, key_type, value }).await.map_err(|_| anyhow!("export writer stopped"))?;
+                report.exported += 1;
+            }
+            cursor = next;
+            if cursor == 0 {
+                break;
+            }
+        }
+        Ok::<ExportReport, anyhow::Error>(report)
+    }.await;
+    drop(tx);
+
+    // 先等待写文件线程结束，写入错误优先于读取错误返回；
+    // 写文件线程在通道关闭后总会补上结尾，失败时删除文件，避免被当作完整的导出
+    let written = writer.await.context("export writer task").and_then(|r| r);
+    let report = match written.and(report) {
+        Ok(report) => report,
+        Err(e) => {
+            let _ = std::fs::remove_file(&path);
+            return Err(e);
+        }
+    };
+    logging::info("EXPORT", &format!(
+        "path={} pattern={} exported={} skipped={}",
+        path, pattern, report.exported, report.skipped
+    ));
+    Ok(report)
+}
+
+/// 将匹配模式的键从一个连接迁移到另一个连接
+///
+/// 逐页 SCAN 源端，每页内并发对键执行 DUMP + PTTL，再在目标端 RESTORE，
+/// 内存中只保留当前页的序列化数据。
+///
+/// # 参数
+///
+/// - `source` / `source_db`: 源连接与数据库
+/// - `dest` / `dest_db`: 目标连接与数据库
+/// - `pattern`: 匹配模式，如 `user:*`
+/// - `overwrite`: 目标端已存在同名键时是否覆盖，为 `false` 时计入 `skipped`
+///
+/// # 返回值
+///
+/// 返回 `MigrateReport`。SCAN 失败时返回错误；单个键的失败只计入报告。
+///
+/// # 注意事项
+///
+/// - 过期时间按读取时的剩余毫秒数恢复
+/// - SCAN 期间键被修改时，同一个键可能被迁移多次
+/// - 序列化数据与 RDB 版本绑定，目标端版本低于源端时 RESTORE 可能失败
+pub async fn migrate_keys(
+    source: &RedisService,
+    source_db: u32,
+    dest: &RedisService,
+    dest_db: u32,
+    pattern: String,
+    overwrite: bool,
+) -> Result<MigrateReport> {
+    let mut report = MigrateReport::default();
+    let mut cursor = 0u64;
+    loop {
+        let (next, keys) = source.scan(source_db, cursor, Some(pattern.clone()), Some(MIGRATE_SCAN_COUNT), None).await?;
+        let mut outcomes = stream::iter(keys)
+            .map(|key| async move {
+                let outcome = migrate_key(source, source_db, dest, dest_db, &key, overwrite).await;
+                (key, outcome)
+            })
+            .buffer_unordered(MIGRATE_CONCURRENCY);
+        while let Some((key, outcome)) = outcomes.next().await {
+            match outcome {
+                MigrateOutcome::Migrated => report.migrated += 1,
+                MigrateOutcome::Skipped => report.skipped += 1,
+                MigrateOutcome::Failed(e) => report.fail(format!("{}: {}", key, e)),
+            }
+        }
+        cursor = next;
+        if cursor == 0 {
+            break;
+        }
+    }
+
+    logging::info("MIGRATE", &format!(
+        "pattern={} db={}->{} migrated={} skipped={} failed={}",
+        pattern, source_db, dest_db, report.migrated, report.skipped, report.failed
+    ));
+    Ok(report)
+}
+
+/// 迁移单个键
+async fn migrate_key(source: &RedisService, source_db: u32, dest: &RedisService, dest_db: u32, key: &str, overwrite: bool) -> MigrateOutcome {
+    let (payload, ttl_ms) = match source.dump_with_ttl(source_db, key).await {
+        Ok(Some(dumped)) => dumped,
+        Ok(None) => return MigrateOutcome::Skipped,
+        Err(e) => return MigrateOutcome::Failed(format!("DUMP failed: {:#}", e)),
+    };
+    match dest.restore(dest_db, key, ttl_ms, &payload, overwrite).await {
+        Ok(()) => MigrateOutcome::Migrated,
+        Err(e) if is_busy_key(&e) => MigrateOutcome::Skipped,
+        Err(e) => MigrateOutcome::Failed(format!("RESTORE failed: {:#}", e)),
+    }
+}
+
+/// 按类型读取键的值并转换为 JSON
+///
+/// 键名与所有内容按原始字节读取，非 UTF-8 内容经 `bytes_to_json` 编码，不会因二进制数据失败。
+/// 不支持的类型或键已被删除时返回 `None`；Redis 不保存空的集合类型，读到空集合同样视为已删除。
+async fn read_value(svc: &RedisService, db: u32, key: &[u8], key_type: &str) -> Result<Option<serde_json::Value>> {
+    use serde_json::{json, Value};
+    let value = match key_type {
+        "string" => match svc.get_bytes(db, key).await? {
+            Some(b) => bytes_to_json(b),
+            None => return Ok(None),
+        },
+        "hash" => {
+            let fields = svc.hgetall_bytes(db, key).await?;
+            if fields.is_empty() {
+                return Ok(None);
+            }
+            hash_to_json(fields)
+        }
+        "list" | "set" => {
+            let items = if key_type == "list" {
+                svc.lrange_bytes(db, key, 0, -1).await?
+            } else {
+                svc.smembers_bytes(db, key).await?
+            };
+            if items.is_empty() {
+                return Ok(None);
+            }
+            Value::Array(items.into_iter().map(bytes_to_json).collect())
+        }
+        "zset" => {
+            let members = svc.zrange_withscores_bytes(db, key, 0, -1).await?;
+            if members.is_empty() {
+                return Ok(None);
+            }
+            Value::Array(
+                members.into_iter()
+                    .map(|(member, score)| json!([bytes_to_json(member), score_to_string(score)]))
+                    .collect(),
+            )
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(value))
+}
+
+/// 将有序集合的分数格式化为 `ZADD` 可以接受的字符串
+///
+/// `f64` 的 `Display` 输出能精确还原的最短表示，无穷大为 `inf` / `-inf`，与 Redis 的写法一致。
+fn score_to_string(score: f64) -> String {
+    score.to_string()
+}
+
+/// 将二进制内容转换为 JSON
+///
+/// 合法 UTF-8 时为字符串，否则为 `{"base64": "..."}`。
+fn bytes_to_json(bytes: Vec<u8>) -> serde_json::Value {
+    use base64::Engine;
+    match String::from_utf8(bytes) {
+        Ok(s) => serde_json::Value::String(s),
+        Err(e) => serde_json::json!({ "base64": base64::engine::general_purpose::STANDARD.encode(e.into_bytes()) }),
+    }
+}
+
+/// 将哈希表的字段和值转换为 JSON
+///
+/// 字段名均为合法 UTF-8 时为对象，否则为 `[字段, 值]` 数组，字段名同样经 `bytes_to_json` 编码。
+fn hash_to_json(fields: Vec<(Vec<u8>, Vec<u8>)>) -> serde_json::Value {
+    use serde_json::Value;
+    if fields.iter().all(|(field, _)| std::str::from_utf8(field).is_ok()) {
+        Value::Object(
+            fields.into_iter()
+                .map(|(field, value)| (String::from_utf8(field).unwrap_or_default(), bytes_to_json(value)))
+                .collect(),
+        )
+    } else {
+        Value::Array(
+            fields.into_iter()
+                .map(|(field, value)| Value::Array(vec![bytes_to_json(field), bytes_to_json(value)]))
+                .collect(),
+        )
+    }
+}
+
+/// 写入一批键值对
+///
+/// 集群模式下 MSET 要求所有键位于同一槽位，因此按槽位分组分别写入。
+async fn write_batch(svc: &RedisService, db: u32, pairs: Vec<(String, String)>, report: &mut ImportReport) {
+    if pairs.is_empty() {
+        return;
+    }
+    let groups: Vec<Vec<(String, String)>> = if svc.config().cluster {
+        let mut by_slot: BTreeMap<u16, Vec<(String, String)>> = BTreeMap::new();
+        for pair in pairs {
+            by_slot.entry(key_slot(&pair.0)).or_default().push(pair);
+        }
+        by_slot.into_values().collect()
+    } else {
+        vec![pairs]
+    };
+
+    for group in groups {
+        let count = group.len() as u64;
+        match svc.mset(db, &group).await {
+            Ok(()) => report.imported += count,
+            Err(e) => report.fail(count, format!("MSET of {} keys starting at {:?} failed: {:#}", count, group[0].0, e)),
+        }
+    }
+}
+
+/// 发送一批解析结果，写入端已停止时返回错误
+fn send_batch(tx: &mpsc::Sender<Batch>, batch: &mut Batch) -> Result<()> {
+    if batch.is_empty() {
+        return Ok(());
+    }
+    tx.blocking_send(std::mem::take(batch)).map_err(|_| anyhow!("import cancelled"))
+}
+
+/// 流式解析 JSON 对象
+fn read_json<R: std::io::Read>(reader: R, tx: &mpsc::Sender<Batch>) -> Result<()> {
+    struct EntryVisitor<'a> {
+        tx: &'a mpsc::Sender<Batch>,
+    }
+
+    impl<'de, 'a> Visitor<'de> for EntryVisitor<'a> {
+        type Value = ();
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("a JSON object of key to value")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> std::result::Result<(), A::Error> {
+            let mut batch = Batch::with_capacity(IMPORT_BATCH_SIZE);
+            while let Some((key, value)) = map.next_entry::<String, serde_json::Value>()? {
+                let value = match value {
+                    serde_json::Value::String(s) => s,
+                    other => other.to_string(),
+                };
+                batch.push(Ok((key, value)));
+                if batch.len() >= IMPORT_BATCH_SIZE {
+                    send_batch(self.tx, &mut batch).map_err(<A::Error as serde::de::Error>::custom)?;
+                }
+            }
+            send_batch(self.tx, &mut batch).map_err(<A::Error as serde::de::Error>::custom)
+        }
+    }
+
+    let mut de = serde_json::Deserializer::from_reader(reader);
+    (&mut de).deserialize_map(EntryVisitor { tx }).context("parse JSON import file")?;
+    de.end().context("parse JSON import file")?;
+    Ok(())
+}
+
+/// 逐行解析 CSV
+fn read_csv<R: BufRead>(reader: R, tx: &mpsc::Sender<Batch>) -> Result<()> {
+    let mut batch = Batch::with_capacity(IMPORT_BATCH_SIZE);
+    for (index, line) in reader.lines().enumerate() {
+        let line_no = index + 1;
+        let line = line.with_context(|| format!("read line {}", line_no))?;
+        let line = line.trim_end_matches('\r');
+        if line.trim().is_empty() {
+            continue;
+        }
+        let item = match parse_csv_line(line) {
+            Ok(fields) if fields.len() == 2 => {
+                if line_no == 1 && fields[0] == "key" && fields[1] == "value" {
+                    continue;
+                }
+                let mut fields = fields.into_iter();
+                Ok((fields.next().unwrap(), fields.next().unwrap()))
+            }
+            Ok(fields) => Err(format!("line {}: expected 2 fields, got {}", line_no, fields.len())),
+            Err(e) => Err(format!("line {}: {}", line_no, e)),
+        };
+        batch.push(item);
+        if batch.len() >= IMPORT_BATCH_SIZE {
+            send_batch(tx, &mut batch)?;
+        }
+    }
+    send_batch(tx, &mut batch)
+}
+
+/// 解析单行 CSV
+///
+/// 字段以逗号分隔，包含逗号或引号的字段需用双引号包裹，字段内的 `""` 表示一个双引号。
+/// 不支持跨行的字段。
+fn parse_csv_line(line: &str) -> Result<Vec<String>> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars().peekable();
+    let mut quoted = false;
+    let mut at_start = true;
+
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => {
+                    quoted = false;
+                    if !matches!(chars.peek(), None | Some(',')) {
+                        return Err(anyhow!("unexpected character after closing quote"));
+                    }
+                }
+                _ => field.push(c),
+            }
+        } else {
+            match c {
+                '"' if at_start => {
+                    quoted = true;
+                    at_start = false;
+                }
+                ',' => {
+                    fields.push(std::mem::take(&mut field));
+                    at_start = true;
+                }
+                _ => {
+                    field.push(c);
+                    at_start = false;
+                }
+            }
+        }
+    }
+    if quoted {
+        return Err(anyhow!("unterminated quoted field"));
+    }
+    fields.push(field);
+    Ok(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::redis_service::RedisConfig;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    /// 生成唯一的测试前缀
+    fn gen_prefix(name: &str) -> String {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+        format!("{}-{}", name, nanos)
+    }
+
+    /// 测试 CSV 行解析
+    #[test]
+    fn test_parse_csv_line() {
+        assert_eq!(parse_csv_line("a,1").unwrap(), vec!["a", "1"]);
+        assert_eq!(parse_csv_line("\"a,b\",\"say \"\"hi\"\"\"").unwrap(), vec!["a,b", "say \"hi\""]);
+        assert_eq!(parse_csv_line("k,").unwrap(), vec!["k", ""]);
+        assert_eq!(parse_csv_line("only").unwrap(), vec!["only"]);
+        assert!(parse_csv_line("\"open,1").is_err());
+        assert!(parse_csv_line("\"a\"x,1").is_err());
+    }
+
+    /// 测试二进制内容转换为 JSON
+    #[test]
+    fn test_bytes_to_json() {
+        use serde_json::json;
+        assert_eq!(bytes_to_json(b"hello".to_vec()), json!("hello"));
+        assert_eq!(bytes_to_json(vec![0xff, 0x00, 0xfe]), json!({ "base64": "/wD+" }));
+        assert_eq!(
+            hash_to_json(vec![(b"f".to_vec(), vec![0xff])]),
+            json!({ "f": { "base64": "/w==" } })
+        );
+        assert_eq!(
+            hash_to_json(vec![(b"f".to_vec(), b"v".to_vec()), (vec![0xff], b"w".to_vec())]),
+            json!([["f", "v"], [{ "base64": "/w==" }, "w"]])
+        );
+    }
+
+    /// 测试有序集合分数的格式化
+    #[test]
+    fn test_score_to_string() {
+        assert_eq!(score_to_string(1.5), "1.5");
+        assert_eq!(score_to_string(3.0), "3");
+        assert_eq!(score_to_string(f64::INFINITY), "inf");
+        assert_eq!(score_to_string(f64::NEG_INFINITY), "-inf");
+        assert_eq!(score_to_string(0.1).parse::<f64>().unwrap(), 0.1);
+    }
+
+    /// 测试 JSON 流式解析按批次发送
+    #[test]
+    fn test_read_json_batches() {
+        let (tx, mut rx) = mpsc::channel::<Batch>(16);
+        let doc = r#"{"a": "1", "b": 2, "c": {"x": true}}"#;
+        read_json(doc.as_bytes(), &tx).unwrap();
+        drop(tx);
+        let batch = rx.blocking_recv().unwrap();
+        assert_eq!(batch, vec![
+            Ok(("a".to_string(), "1".to_string())),
+            Ok(("b".to_string(), "2".to_string())),
+            Ok(("c".to_string(), "{\"x\":true}".to_string())),
+        ]);
+        assert!(rx.blocking_recv().is_none());
+
+        let (tx, _rx) = mpsc::channel::<Batch>(1);
+        assert!(read_json("[1, 2]".as_bytes(), &tx).is_err());
+    }
+
+    /// 测试导出混合类型的键（需要本地 Redis）
+    #[tokio::test]
+    #[ignore]
+    async fn test_export_keys_file() {
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let prefix = gen_prefix("export");
+        let key = |suffix: &str| format!("{}:{}", prefix, suffix);
+        svc.set(0, &key("str"), "v", None, false).await.unwrap();
+        svc.hset(0, &key("hash"), "f", "v").await.unwrap();
+        svc.lpush(0, &key("list"), "a").await.unwrap();
+        svc.sadd(0, &key("set"), "m").await.unwrap();
+        svc.zadd(0, &key("zset"), "z", 1.5).await.unwrap();
+        svc.zadd(0, &key("zset"), "top", f64::INFINITY).await.unwrap();
+        svc.set(0, &key("bin"), vec![0xffu8, 0x00], None, false).await.unwrap();
+        svc.lpush(0, &key("binlist"), vec![0xfeu8]).await.unwrap();
+        // 非 UTF-8 的键名无法通过 `&str` 接口写入，直接使用客户端
+        let raw_key = [key("raw").into_bytes(), vec![0xff]].concat();
+        let mut conn = redis::Client::open("redis://127.0.0.1/").unwrap().get_connection().unwrap();
+        redis::cmd("SET").arg(&raw_key).arg("r").query::<()>(&mut conn).unwrap();
+
+        let path = std::env::temp_dir().join(format!("{}.json", prefix));
+        let report = export_keys(&svc, 0, format!("{}:*", prefix), path.to_string_lossy().into_owned()).await.unwrap();
+        assert_eq!(report, ExportReport { exported: 8, skipped: 0 });
+
+        let doc: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(doc["version"], 1);
+        let entries: Vec<ExportedKey> = serde_json::from_value(doc["keys"].clone()).unwrap();
+        let raw = entries.iter().find(|e| e.key.is_object()).unwrap();
+        use base64::Engine;
+        assert_eq!(raw.key, serde_json::json!({ "base64": base64::engine::general_purpose::STANDARD.encode(&raw_key) }));
+        assert_eq!(raw.value, serde_json::json!("r"));
+        let by_key: std::collections::HashMap<_, _> = entries.into_iter()
+            .filter_map(|e| Some((e.key.as_str()?.to_string(), e)))
+            .collect();
+        assert_eq!(by_key[&key("str")].key_type, "string");
+        assert_eq!(by_key[&key("str")].value, serde_json::json!("v"));
+        assert_eq!(by_key[&key("hash")].value, serde_json::json!({"f": "v"}));
+        assert_eq!(by_key[&key("list")].value, serde_json::json!(["a"]));
+        assert_eq!(by_key[&key("set")].key_type, "set");
+        assert_eq!(by_key[&key("zset")].value, serde_json::json!([["z", "1.5"], ["top", "inf"]]));
+        // 二进制值不会被有损转换
+        assert_eq!(by_key[&key("bin")].value, serde_json::json!({"base64": "/wA="}));
+        assert_eq!(by_key[&key("binlist")].value, serde_json::json!([{"base64": "/g=="}]));
+
+        // 清理
+        for suffix in ["str", "hash", "list", "set", "zset", "bin", "binlist"] {
+            svc.del(0, &key(suffix)).await.unwrap();
+        }
+        redis::cmd("DEL").arg(&raw_key).query::<()>(&mut conn).unwrap();
+        let _ = std::fs::remove_file(&path);
+    }
+
+    /// 测试从 JSON 文件导入（需要本地 Redis）
+    #[tokio::test]
+    #[ignore]
+    async fn test_import_json_file() {
+        let svc = RedisService::new(RedisConfig::default()).await.unwrap();
+        let prefix = gen_prefix("import");
+        let path = std::env::temp_dir().join(format!("{}.json", prefix));
+        let doc = serde_json::json!({
+            format!("{}:a", prefix): "alpha",
+            format!("{}:b", prefix): 42,
+        });
+        std::fs::write(&path, doc.to_string()).unwrap();
+
+        let report = import_keys(&svc, 0, path.to_string_lossy().into_owned(), ImportFormat::Json).await.unwrap();
+        assert_eq!(report.imported, 2);
+        assert_eq!(report.failed, 0);
+
+        let a: Option<String> = svc.get(0, &format!("{}:a", prefix)).await.unwrap();
+        let b: Option<String> = svc.get(0, &format!("{}:b", prefix)).await.unwrap();
+        assert_eq!(a, Some("alpha".into()));
+        assert_eq!(b, Some("42".into()));
+
+        // 清理
+        svc.del(0, &format!("{}:a", prefix)).await.unwrap();
+        svc.del(0, &format!("{}:b", prefix)).await.unwrap();
+        let _ = std::fs::remove_file(&path);
+    }
+
+    /// 测试在两个连接之间迁移键（需要本地 Redis）
+    #[tokio::test]
+    #[ignore]
+    async fn test_migrate_keys() {
+        let source = RedisService::new(RedisConfig::default()).await.unwrap();
+        let dest = RedisService::new(RedisConfig { default_db: 1, ..RedisConfig::default() }).await.unwrap();
+        let prefix = gen_prefix("migrate");
+        let key = |suffix: &str| format!("{}:{}", prefix, suffix);
+        source.set(0, &key("str"), "v", None, false).await.unwrap();
+        source.expire(0, &key("str"), 600).await.unwrap();
+        source.hset(0, &key("hash"), "f", "v").await.unwrap();
+        source.lpush(0, &key("list"), "a").await.unwrap();
+
+        let report = migrate_keys(&source, 0, &dest, 1, format!("{}:*", prefix), false).await.unwrap();
+        assert_eq!(report, MigrateReport { migrated: 3, ..MigrateReport::default() });
+
+        let s: Option<String> = dest.get(1, &key("str")).await.unwrap();
+        assert_eq!(s, Some("v".into()));
+        let ttl = dest.ttl(1, &key("str")).await.unwrap();
+        assert!(ttl > 0 && ttl <= 600, "ttl {}", ttl);
+        assert_eq!(dest.ttl(1, &key("hash")).await.unwrap(), -1);
+        let hash = dest.hgetall::<String>(1, &key("hash")).await.unwrap();
+        assert_eq!(hash.get("f").map(String::as_str), Some("v"));
+        assert_eq!(dest.lrange::<String>(1, &key("list"), 0, -1).await.unwrap(), vec!["a"]);
+        // 源端保持不变
+        assert!(source.exists(0, &key("str")).await.unwrap());
+
+        // 目标端已存在时跳过，开启覆盖后重新写入
+        source.set(0, &key("str"), "v2", None, false).await.unwrap();
+        let report = migrate_keys(&source, 0, &dest, 1, format!("{}:*", prefix), false).await.unwrap();
+        assert_eq!(report, MigrateReport { skipped: 3, ..MigrateReport::default() });
+        let report = migrate_keys(&source, 0, &dest, 1, format!("{}:*", prefix), true).await.unwrap();
+        assert_eq!(report.migrated, 3);
+        let s: Option<String> = dest.get(1, &key("str")).await.unwrap();
+        assert_eq!(s, Some("v2".into()));
+
+        // 清理
+        for suffix in ["str", "hash", "list"] {
+            source.del(0, &key(suffix)).await.unwrap();
+            dest.del(1, &key(suffix)).await.unwrap();
+        }
+    }
+}
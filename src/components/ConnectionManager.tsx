@@ -1,6 +1,6 @@
 import { useState, useEffect } from 'react';
 import { useTranslation } from 'react-i18next';
-import { listConfigs, addConnection, removeConnection, checkConnection, testConnectionConfig, ConfigItem, RedisConfig } from '../types/tauri';
+import { listConfigs, addConnection, updateConnection, removeConnection, checkConnection, testConnectionConfig, ConfigItem, RedisConfig } from '../types/tauri';
 import { Button } from './ui/Button';
 import { Input } from './ui/Input';
 import { Modal } from './ui/Modal';
@@ -201,8 +201,14 @@ export function ConnectionManager({ selectedName, onSelect, onCollapse }: Connec
     try {
       setLoading(true);
       const config = buildConfig();
-      await testConnectionConfig(config);
+      const result = await testConnectionConfig(config);
+      if (!result.ok) {
+        setErrorMessage(result.warnings.join('\n'));
+        setErrorModalOpen(true);
+        return;
+      }
       toast(t('connection.test_success'), 'success');
+      result.warnings.forEach(w => toast(w, 'info'));
     } catch (e: any) {
       const msg = typeof e === 'string' ? e : (e.message || JSON.stringify(e));
       setErrorMessage(msg);
@@ -232,10 +238,12 @@ export function ConnectionManager({ selectedName, onSelect, onCollapse }: Connec
         await addConnection(finalName, config);
         await removeConnection(editingName);
         toast(t('connection.updated'), 'success');
+      } else if (editingName) {
+        await updateConnection(finalName, config);
+        toast(t('connection.updated'), 'success');
       } else {
-        // Add or Update same name
         await addConnection(finalName, config);
-        toast(editingName ? t('connection.updated') : t('connection.added'), 'success');
+        toast(t('connection.added'), 'success');
       }
 
       setIsModalOpen(false);
@@ -1,6 +1,6 @@
 import { useState, useEffect, useCallback, useMemo } from 'react';
 import { useTranslation } from 'react-i18next';
-import { scanKeys, delKey, getValue, setValue, ttlKey, expireKey, persistKey, getDbSize, getKeyType, hgetAll, hset, hdel, lpush, rpop, lrange, sadd, smembers, srem, zadd, zrem, zrangeWithScores, jsonGet, jsonSet } from '../types/tauri';
+import { scanKeys, delKey, getValue, setValue, ttlKey, expireKey, persistKey, getDbSize, getDatabaseCount, getKeyType, hgetAll, hset, hdel, lpush, rpop, lrange, sadd, smembers, srem, zadd, zrem, zrangeWithScores, jsonGet, jsonSet } from '../types/tauri';
 import { Button } from './ui/Button';
 import { Input } from './ui/Input';
 import { useToast } from './ui/Toast';
@@ -34,6 +34,7 @@ export function KeyManager({ connectionName }: KeyManagerProps) {
   const [pattern, setPattern] = useState('*');
   const [db, setDb] = useState(0);
   const [dbSize, setDbSize] = useState<number | null>(null);
+  const [dbCount, setDbCount] = useState(16);
   const [loading, setLoading] = useState(false);
   const [selectedKey, setSelectedKey] = useState<string | null>(null);
   const [keyDetail, setKeyDetail] = useState<KeyDetail | null>(null);
@@ -125,6 +126,13 @@ export function KeyManager({ connectionName }: KeyManagerProps) {
     } catch {}
   }, [collapsedGroups, collapsedTree, recentPath, viewMode, autoRefresh, autoIntervalMs, sidebarCollapsed, storageKey]);
 
+  useEffect(() => {
+    if (!connectionName) return;
+    getDatabaseCount(connectionName)
+      .then(setDbCount)
+      .catch((e) => console.error("Failed to get database count:", e));
+  }, [connectionName]);
+
   const loadDbSize = useCallback(async () => {
     if (!connectionName) return;
     try {
@@ -356,7 +364,7 @@ export function KeyManager({ connectionName }: KeyManagerProps) {
     if (!keyDetail) return;
     try {
       if (keyType === 'string') {
-        await setValue(connectionName, keyDetail.key, keyDetail.value || '', undefined, db);
+        await setValue(connectionName, keyDetail.key, keyDetail.value || '', undefined, db, true);
       }
       toast(t('key_manager.value_updated'), 'success');
     } catch (e: any) {
@@ -742,7 +750,7 @@ export function KeyManager({ connectionName }: KeyManagerProps) {
                  onChange={(e) => setDb(parseInt(e.target.value))}
                  className="bg-slate-900 border border-slate-700 text-slate-200 text-sm rounded px-2 py-1 focus:outline-none focus:ring-1 focus:ring-blue-500 w-24"
                >
-                 {[...Array(16)].map((_, i) => (
+                 {[...Array(dbCount)].map((_, i) => (
                    <option key={i} value={i}>{t('key_manager.db')} {i}</option>
                  ))}
                </select>
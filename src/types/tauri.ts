@@ -5,14 +5,51 @@
  * - `code`: Response code (OK or error code)
  * - `message`: Response message (error description or empty string)
  * - `data`: Actual data payload (returned on success, null/undefined on failure)
+ * - `meta`: Execution info, only present for commands that report it
  */
 export type CommandResponse<T> = {
   success: boolean;
   code: string;
   message: string;
   data?: T | null;
+  meta?: ResponseMeta;
 };
 
+/**
+ * Execution info attached to some responses (aligned with Rust `ResponseMeta`)
+ */
+export type ResponseMeta = {
+  /** Round-trip time of the command in milliseconds */
+  duration_ms: number;
+  /** Cluster node (`host:port`) that served the command, null outside cluster mode */
+  node: string | null;
+};
+
+/**
+ * Error codes returned in `CommandResponse.code` (aligned with Rust `AppError::code`)
+ *
+ * Server errors carry the reply prefix as their code, e.g. `WRONGTYPE`, `READONLY`, `MOVED`;
+ * a plain `ERR` reply is reported as `SERVER_ERROR`. `REDIS_ERROR` covers other failures
+ * such as network or protocol errors. Invalid arguments are reported as `INVALID_ARGUMENT`
+ * (formerly `VALIDATION_ERROR`).
+ */
+export type AppErrorCode =
+  | "NOT_FOUND"
+  | "NAME_EXISTS"
+  | "NOT_CLUSTER"
+  | "INVALID_ARGUMENT"
+  | "TIMEOUT"
+  | "CONNECT_TIMEOUT"
+  | "AUTH_FAILED"
+  | "CONNECTION_REFUSED"
+  | "JSON_MODULE_MISSING"
+  | "POLICY_BLOCKED"
+  | "READONLY_CONNECTION"
+  | "SERVER_ERROR"
+  | "REDIS_ERROR"
+  | "DB_ERROR"
+  | (string & {});
+
 import { invoke } from "@tauri-apps/api/core";
 import { listen, UnlistenFn } from "@tauri-apps/api/event";
 
@@ -40,6 +77,20 @@ export async function invokeCommand<T>(
   }
 }
 
+/**
+ * Like invokeCommand, but also returns the response's execution info
+ */
+export async function invokeCommandWithMeta<T>(
+  cmd: string,
+  payload?: Record<string, unknown>
+): Promise<{ data: T; meta?: ResponseMeta }> {
+  const resp = await invoke<CommandResponse<T>>(cmd, payload ?? {});
+  if (resp.success) {
+    return { data: (resp.data ?? null) as T, meta: resp.meta };
+  }
+  throw new Error(`${resp.code}: ${resp.message}`);
+}
+
 /**
  * Frontend Redis configuration object (aligned with backend `RedisConfig`)
  * 
@@ -70,7 +121,7 @@ export type RedisConfig = {
   urls: string[];
   /** Whether to enable cluster mode */
   cluster?: boolean;
-  /** Connection pool size (default 16) */
+  /** Number of pooled connections used round-robin in standalone/sentinel mode (default 16, ignored in cluster mode) */
   pool_size?: number;
   /** Retry count (default 3) */
   retries?: number;
@@ -82,6 +133,31 @@ export type RedisConfig = {
   sentinel_master_name?: string | null;
   /** Sentinel node address list (required for sentinel mode) */
   sentinel_urls?: string[];
+  /** Whether to negotiate the RESP3 protocol (default false, RESP2) */
+  resp3?: boolean;
+  /** Database used when a command is called without an explicit `db` (default 0) */
+  default_db?: number;
+  /** Cluster only: route read-only commands to replicas (writes still go to masters) */
+  read_from_replicas?: boolean;
+  /** Deadline for establishing the connection in milliseconds (default 3000, 0 disables) */
+  connect_timeout_ms?: number;
+  /** Cache GET/HGETALL/TYPE results for this many milliseconds; writes through the same connection invalidate them (default off) */
+  read_cache_ttl_ms?: number | null;
+  /** Block dangerous commands on this connection (default none); checked by raw commands and destructive operations */
+  command_policy?: CommandPolicy | null;
+  /** Reject every write on this connection before it reaches Redis (default false); reads still work */
+  readonly?: boolean;
+};
+
+/**
+ * Connection-level command allow/deny list
+ *
+ * Names are case-insensitive; `CONFIG` matches every CONFIG subcommand, `CONFIG SET` only that one.
+ * Key deletions (including delete by pattern) are checked as `DEL`.
+ */
+export type CommandPolicy = {
+  mode: "allow" | "deny";
+  commands: string[];
 };
 
 export type ConfigItem = {
@@ -99,10 +175,26 @@ export type ConfigItem = {
  * 
  * @param name Connection name (unique identifier, cannot be repeated)
  * @param config Redis connection configuration object
+ * @param validate Connect before saving (default true). Pass false to save a server that is
+ * currently unreachable; the connection is then made on first use and errors surface there.
+ * @returns true on success
+ */
+export async function addConnection(name: string, config: RedisConfig, validate?: boolean): Promise<boolean> {
+  return invokeCommand<boolean>("add_connection", { name, config, validate });
+}
+
+/**
+ * Update an existing Redis connection
+ * 
+ * Reconnects with the new configuration, then saves it and replaces the in-memory service.
+ * `addConnection` rejects existing names with NAME_EXISTS, so use this when editing a connection.
+ * 
+ * @param name Existing connection name
+ * @param config New Redis connection configuration
  * @returns true on success
  */
-export async function addConnection(name: string, config: RedisConfig): Promise<boolean> {
-  return invokeCommand<boolean>("add_connection", { name, config });
+export async function updateConnection(name: string, config: RedisConfig): Promise<boolean> {
+  return invokeCommand<boolean>("update_connection", { name, config });
 }
 
 /**
@@ -117,6 +209,40 @@ export async function removeConnection(name: string): Promise<boolean> {
   return invokeCommand<boolean>("remove_connection", { name });
 }
 
+/**
+ * What `purgeConnection` actually cleaned up
+ */
+export type PurgeSummary = {
+  /** The saved configuration existed and was deleted */
+  config_deleted: boolean;
+  /** A live (or not yet connected) service was removed */
+  service_removed: boolean;
+  /** Number of running subscriptions on this connection that were stopped */
+  tasks_aborted: number;
+};
+
+/**
+ * Fully remove a connection: delete its saved configuration, drop the live service
+ * and stop every subscription opened on it
+ *
+ * @param name Connection name
+ */
+export async function purgeConnection(name: string): Promise<PurgeSummary> {
+  return invokeCommand<PurgeSummary>("purge_connection", { name });
+}
+
+/**
+ * Rebuild a single connection from its saved configuration
+ *
+ * Other connections are left untouched.
+ *
+ * @param name Connection name
+ * @returns 'reconnected' on success
+ */
+export async function reconnectService(name: string): Promise<string> {
+  return invokeCommand<string>("reconnect_service", { name });
+}
+
 /**
  * Check if connection is available (PING)
  * 
@@ -127,6 +253,93 @@ export async function checkConnection(name: string): Promise<boolean> {
   return invokeCommand<boolean>("check_connection", { name });
 }
 
+/**
+ * Measure the round-trip time of a PING
+ * 
+ * @param name Connection name
+ * @returns Latency in milliseconds
+ */
+export async function pingLatency(name: string): Promise<number> {
+  return invokeCommand<number>("ping_latency", { name });
+}
+
+/**
+ * Per-connection latency metrics (every attempt, including retries)
+ */
+export type ConnMetrics = {
+  count: number;
+  avg_ms: number;
+  p95_ms: number;
+  errors: number;
+  /** Read cache hits (0 when the read cache is disabled) */
+  cache_hits: number;
+  /** Read cache misses (0 when the read cache is disabled) */
+  cache_misses: number;
+};
+
+/**
+ * Get latency metrics of a connection
+ *
+ * @param name Connection name
+ */
+export async function getConnectionMetrics(name: string): Promise<ConnMetrics> {
+  return invokeCommand<ConnMetrics>("get_connection_metrics", { name });
+}
+
+/**
+ * Get how long a connection has been up
+ *
+ * @param name Connection name
+ * @returns Uptime in milliseconds
+ */
+export async function getConnectionUptime(name: string): Promise<number> {
+  return invokeCommand<number>("get_connection_uptime", { name });
+}
+
+/**
+ * Reset the latency metrics of a connection
+ *
+ * @param name Connection name
+ */
+export async function resetConnectionMetrics(name: string): Promise<boolean> {
+  return invokeCommand<boolean>("reset_connection_metrics", { name });
+}
+
+/**
+ * Result of a SET/GET benchmark; each op is one SET plus one GET
+ */
+export type BenchmarkResult = {
+  total_ops: number;
+  duration_ms: number;
+  ops_per_sec: number;
+  p50_ms: number;
+  p99_ms: number;
+  /** true when stopped early by cancelBenchmark */
+  cancelled: boolean;
+};
+
+/**
+ * Run a SET/GET throughput benchmark against a connection
+ *
+ * @param name Connection name
+ * @param ops Total ops (max 1,000,000)
+ * @param concurrency Concurrent tasks (max 256)
+ * @param valueSize Value size in bytes (max 1 MiB)
+ */
+export async function runBenchmark(name: string, ops: number, concurrency: number, valueSize: number, db?: number): Promise<BenchmarkResult> {
+  return invokeCommand<BenchmarkResult>("run_benchmark", { name, ops, concurrency, value_size: valueSize, db });
+}
+
+/**
+ * Stop a running benchmark on a connection
+ *
+ * @param name Connection name
+ * @returns true when a benchmark was running; a cancel sent while nothing runs does not affect later runs
+ */
+export async function cancelBenchmark(name: string): Promise<boolean> {
+  return invokeCommand<boolean>("cancel_benchmark", { name });
+}
+
 /**
  * Get string value (GET)
  * 
@@ -144,10 +357,65 @@ export async function getValue(name: string, key: string, db?: number): Promise<
  * @param name Connection name
  * @param key Key name
  * @param value String value
+ * @param keepTtl Keep the key's existing expiry (KEEPTTL) instead of clearing it; ignored when expireSeconds is set
+ * @returns true on success
+ */
+export async function setValue(name: string, key: string, value: string, expireSeconds?: number, db?: number, keepTtl?: boolean): Promise<boolean> {
+  return invokeCommand<boolean>("set_value", { name, key, value, expire_seconds: expireSeconds, keep_ttl: keepTtl, db });
+}
+
+/**
+ * Typed key value used by writeKey, tagged by the Redis type
+ */
+export type KeyPayload =
+  | { type: "string"; value: string }
+  | { type: "hash"; fields: Record<string, string> }
+  | { type: "list"; items: string[] }
+  | { type: "set"; members: string[] }
+  | { type: "zset"; members: [string, number][] };
+
+/**
+ * Replace a key's value with a typed payload
+ * 
+ * The old value is deleted and rewritten in one transaction, so fields or members
+ * missing from the payload are removed rather than merged. The key's existing TTL
+ * is preserved. An empty collection deletes the key.
+ * 
+ * @param name Connection name
+ * @param key Key name
+ * @param payload New value, its type may differ from the key's current type
  * @returns true on success
  */
-export async function setValue(name: string, key: string, value: string, expireSeconds?: number, db?: number): Promise<boolean> {
-  return invokeCommand<boolean>("set_value", { name, key, value, expire_seconds: expireSeconds, db });
+export async function writeKey(name: string, key: string, payload: KeyPayload, db?: number): Promise<boolean> {
+  return invokeCommand<boolean>("write_key", { name, key, payload, db });
+}
+
+/**
+ * Atomically get and delete a string value (GETDEL, Redis 6.2+)
+ * 
+ * @param name Connection name
+ * @param key Key name
+ * @returns String value, or null if key does not exist
+ */
+export async function getdelValue(name: string, key: string, db?: number): Promise<string | null> {
+  return invokeCommand<string | null>("getdel_value", { name, key, db });
+}
+
+/**
+ * Expiry option for GETEX
+ */
+export type SetExpiry = { EX: number } | { PX: number } | { EXAT: number } | "PERSIST";
+
+/**
+ * Get a string value and update its expiry in one call (GETEX, Redis 6.2+)
+ * 
+ * @param name Connection name
+ * @param key Key name
+ * @param expiry New expiry; omit to behave like GET
+ * @returns String value, or null if key does not exist
+ */
+export async function getexValue(name: string, key: string, expiry?: SetExpiry, db?: number): Promise<string | null> {
+  return invokeCommand<string | null>("getex_value", { name, key, expiry, db });
 }
 
 /**
@@ -161,6 +429,62 @@ export async function delKey(name: string, key: string, db?: number): Promise<bo
   return invokeCommand<boolean>("del_key", { name, key, db });
 }
 
+/**
+ * Result of `delIfVolatile`
+ *
+ * - `deleted`: the key had an expiry and was deleted
+ * - `skipped_persistent`: the key has no expiry and was kept
+ * - `not_found`: the key does not exist
+ */
+export type VolatileDeleteStatus = "deleted" | "skipped_persistent" | "not_found";
+
+/**
+ * Delete a key only if it has an expiry (soft delete)
+ *
+ * Persistent keys are never deleted, guarding against accidental removal.
+ *
+ * @param name Connection name
+ * @param key Key name
+ */
+export async function delIfVolatile(name: string, key: string, db?: number): Promise<VolatileDeleteStatus> {
+  return invokeCommand<VolatileDeleteStatus>("del_if_volatile", { name, key, db });
+}
+
+/**
+ * Return a random key (RANDOMKEY)
+ *
+ * @returns Key name, null when the database is empty
+ */
+export async function randomKey(name: string, db?: number): Promise<string | null> {
+  return invokeCommand<string | null>("random_key", { name, db });
+}
+
+/**
+ * Refresh last access time of keys (TOUCH)
+ *
+ * @returns Number of existing keys that were touched
+ */
+export async function touchKeys(name: string, keys: string[], db?: number): Promise<number> {
+  return invokeCommand<number>("touch_keys", { name, keys, db });
+}
+
+/**
+ * Move a key to another database (MOVE, standalone only)
+ *
+ * @param destDb Destination database index
+ * @returns false when the key is missing in the source or already exists in the destination
+ */
+export async function moveKey(name: string, key: string, destDb: number, db?: number): Promise<boolean> {
+  return invokeCommand<boolean>("move_key", { name, key, dest_db: destDb, db });
+}
+
+/**
+ * Atomically swap two databases (SWAPDB, standalone only)
+ */
+export async function swapDatabases(name: string, db1: number, db2: number): Promise<boolean> {
+  return invokeCommand<boolean>("swap_databases", { name, db1, db2 });
+}
+
 /**
  * Batch get values (MGET)
  * 
@@ -198,21 +522,160 @@ export async function publishMessage(name: string, channel: string, message: str
 /**
  * Subscribe to channel messages
  * 
+ * Subscriptions on a connection share one backend Pub/Sub connection; subscribing to the same
+ * channel twice only subscribes on the server once.
+ * 
  * @param name Connection name
  * @param channel Channel name
  * @param callback Callback function when message is received
- * @returns Unsubscribe function
+ * @param bufferSize Keep the last N messages on the backend so they can be fetched with `getBufferedMessages`
+ * @returns Unsubscribe function that stops listening and cancels the backend subscription,
+ * carrying the subscription `id`
  */
 export async function subscribeChannel(
   name: string,
   channel: string,
   event: string,
-  callback: (msg: string) => void
-): Promise<UnlistenFn> {
-  await invokeCommand("subscribe_channel", { name, channel, event });
-  return await listen<string>(event, (evt) => {
+  callback: (msg: string) => void,
+  bufferSize?: number
+): Promise<UnlistenFn & { id: number }> {
+  const id = await invokeCommand<number>("subscribe_channel", { name, channel, event, buffer_size: bufferSize });
+  const unlisten = await listen<string>(event, (evt) => {
+    callback(evt.payload);
+  });
+  return Object.assign(() => {
+    unlisten();
+    void cancelSubscription(id);
+  }, { id });
+}
+
+/**
+ * Listen for channel subscriptions (`subscribeChannel`, `subscribeChannels`, batched) that were re-established after the connection dropped
+ *
+ * Messages published while the connection was down are lost, so views may want to refresh.
+ *
+ * @param callback Called with the channel name after each successful resubscribe
+ */
+export async function onResubscribed(callback: (channel: string) => void): Promise<UnlistenFn> {
+  return await listen<string>("redis:resubscribed", (evt) => {
+    callback(evt.payload);
+  });
+}
+
+/**
+ * A message received from a multi-channel subscription
+ */
+export type PubSubMessage = {
+  channel: string;
+  payload: string;
+};
+
+/**
+ * Subscribe to several channels over a single connection
+ *
+ * @param name Connection name
+ * @param channels Channel names
+ * @param event Event name used for all channels
+ * @param callback Callback function when a message is received on any channel
+ * @param bufferSize Keep the last N messages (across all channels) for `getBufferedMessages`
+ * @returns Subscription id and a `cancel` function that unsubscribes all channels and stops listening
+ */
+export async function subscribeChannels(
+  name: string,
+  channels: string[],
+  event: string,
+  callback: (msg: PubSubMessage) => void,
+  bufferSize?: number
+): Promise<{ id: number; cancel: () => Promise<void> }> {
+  const id = await invokeCommand<number>("subscribe_channels", { name, channels, event, buffer_size: bufferSize });
+  const unlisten = await listen<PubSubMessage>(event, (evt) => {
+    callback(evt.payload);
+  });
+  return {
+    id,
+    cancel: async () => {
+      unlisten();
+      await cancelSubscription(id);
+    },
+  };
+}
+
+/**
+ * Subscribe to a channel and receive messages in batches
+ *
+ * Messages are buffered on the backend and delivered every `flushMs` milliseconds,
+ * or as soon as `maxBatch` messages have accumulated, to reduce event overhead on busy channels.
+ *
+ * @param name Connection name
+ * @param channel Channel name
+ * @param event Event name
+ * @param flushMs Flush interval in milliseconds
+ * @param maxBatch Maximum number of messages per batch
+ * @param callback Callback function receiving each batch of payloads
+ * @param bufferSize Keep the last N messages for `getBufferedMessages`
+ * @returns Subscription id and a `cancel` function that unsubscribes and stops listening
+ */
+export async function subscribeChannelBatched(
+  name: string,
+  channel: string,
+  event: string,
+  flushMs: number,
+  maxBatch: number,
+  callback: (msgs: string[]) => void,
+  bufferSize?: number
+): Promise<{ id: number; cancel: () => Promise<void> }> {
+  const id = await invokeCommand<number>("subscribe_channel_batched", {
+    name,
+    channel,
+    event,
+    flush_ms: flushMs,
+    max_batch: maxBatch,
+    buffer_size: bufferSize,
+  });
+  const unlisten = await listen<string[]>(event, (evt) => {
     callback(evt.payload);
   });
+  return {
+    id,
+    cancel: async () => {
+      unlisten();
+      await cancelSubscription(id);
+    },
+  };
+}
+
+/**
+ * Cancel a subscription created by `subscribeChannels` or `subscribeChannelBatched`
+ *
+ * @returns false when the subscription no longer exists
+ */
+export async function cancelSubscription(id: number): Promise<boolean> {
+  return invokeCommand<boolean>("cancel_subscription", { id });
+}
+
+/**
+ * A message kept in a subscription's backend buffer
+ *
+ * `seq` increases by one per message, so a gap means older messages were dropped from the buffer.
+ */
+export type BufferedMessage = {
+  seq: number;
+  channel: string;
+  payload: string;
+  /** Unix timestamp in milliseconds */
+  received_at: number;
+};
+
+/**
+ * Fetch messages buffered for a subscription created with `bufferSize`,
+ * e.g. to catch up after navigating back to the channel view
+ *
+ * @param subscriptionId Subscription id
+ * @param limit Return only the newest N messages
+ * @returns Messages oldest first
+ */
+export async function getBufferedMessages(subscriptionId: number, limit?: number): Promise<BufferedMessage[]> {
+  return invokeCommand<BufferedMessage[]>("get_buffered_messages", { subscription_id: subscriptionId, limit });
 }
 
 /**
@@ -240,6 +703,17 @@ export async function unlock(name: string, resource: string, token: string): Pro
   return invokeCommand<boolean>("unlock", { name, resource, token });
 }
 
+/**
+ * Atomically increment a counter without exceeding a cap (rate limiting, Lua Script)
+ *
+ * @param max Highest value the counter may reach; must be positive
+ * @param ttlSecs Expiry set when the counter is first created
+ * @returns New value, or null if incrementing would exceed max (the counter is left unchanged)
+ */
+export async function incrCapped(name: string, key: string, max: number, ttlSecs?: number, db?: number): Promise<number | null> {
+  return invokeCommand<number | null>("incr_capped", { name, key, max, ttl_secs: ttlSecs, db });
+}
+
 /**
  * Remove key expiration time (PERSIST)
  * 
@@ -263,6 +737,32 @@ export async function expireKey(name: string, key: string, seconds: number, db?:
   return invokeCommand<boolean>("expire_key", { name, key, seconds, db });
 }
 
+/**
+ * Conditional expiration flag (Redis 7.0+)
+ */
+export type ExpireFlag = "NX" | "XX" | "GT" | "LT";
+
+/**
+ * Set key expiration time with a condition (EXPIRE NX/XX/GT/LT)
+ *
+ * @param flag NX: only when no TTL, XX: only when a TTL exists, GT/LT: only when greater/less than the current TTL
+ * @returns false when the key is missing or the condition is not met
+ */
+export async function expireKeyFlag(name: string, key: string, seconds: number, flag?: ExpireFlag, db?: number): Promise<boolean> {
+  return invokeCommand<boolean>("expire_key_flag", { name, key, seconds, flag, db });
+}
+
+/**
+ * Expire a key at an exact instant (PEXPIREAT)
+ *
+ * @param unixMs Expiry instant as a unix timestamp in milliseconds
+ * @param allowPast Allow an instant in the past, which deletes the key immediately (default false)
+ * @returns false when the key does not exist
+ */
+export async function pexpireAtKey(name: string, key: string, unixMs: number, allowPast?: boolean, db?: number): Promise<boolean> {
+  return invokeCommand<boolean>("pexpireat_key", { name, key, unix_ms: unixMs, allow_past: allowPast, db });
+}
+
 /**
  * Query key remaining time to live (TTL)
  * 
@@ -274,46 +774,281 @@ export async function ttlKey(name: string, key: string, db?: number): Promise<nu
   return invokeCommand<number>("ttl_key", { name, key, db });
 }
 
-export type ClusterNodeInfo = {
-  id: string;
-  addr: string;
-  flags: string;
-  master_id: string;
-  ping_sent: number;
-  pong_recv: number;
-  config_epoch: number;
-  link_state: string;
-  slots: string[];
+/**
+ * Key expiry information derived from PTTL
+ *
+ * - missing key: `{ exists: false, has_expiry: false, ttl_ms: null }`
+ * - persistent key: `{ exists: true, has_expiry: false, ttl_ms: null }`
+ * - expiring key: `{ exists: true, has_expiry: true, ttl_ms: <remaining ms> }`
+ */
+export type KeyTtlInfo = {
+  exists: boolean;
+  has_expiry: boolean;
+  ttl_ms: number | null;
 };
 
 /**
- * Get cluster node information
- * 
+ * Query key expiry information (PTTL) without sentinel values
+ *
  * @param name Connection name
- * @returns Node information array
+ * @param key Key name
  */
-export async function getClusterInfo(name: string): Promise<ClusterNodeInfo[]> {
-  return invokeCommand<ClusterNodeInfo[]>("get_cluster_info", { name });
+export async function keyTtlInfo(name: string, key: string, db?: number): Promise<KeyTtlInfo> {
+  return invokeCommand<KeyTtlInfo>("key_ttl_info", { name, key, db });
 }
 
 /**
- * Scan keys (SCAN)
- * 
- * @param name Connection name
- * @param db Database index
- * @param cursor Cursor
- * @param pattern Match pattern
- * @param count Count
- * @returns [New cursor, Key list]
+ * Summary of a key shown before deleting it
+ *
+ * - `type`: key type, "none" when the key does not exist
+ * - `ttl`: remaining seconds (-1 persistent, -2 missing)
+ * - `element_count`: byte length for strings, element count for collections, null otherwise
+ * - `sample`: a few members or a truncated value (only filled by `previewKeys`)
+ * - `error`: set when this key could not be read
  */
-export async function scanKeys(name: string, db: number, cursor: number, pattern?: string, count?: number): Promise<[number, string[]]> {
-  // Rust u64 fits in JS number (safe integer limit 2^53 - 1). 
-  // If cursor exceeds this, we might need BigInt or string, but for Redis scan usually fine.
-  // Actually Tauri handles u64 as number if it fits, or null? 
-  // Let's assume number for now as typical SCAN cursors are small enough or handled.
-  // Wait, invoke returns serialized JSON. u64 in serde_json is number.
-  return invokeCommand<[number, string[]]>("scan_keys", { name, db, cursor, pattern, count });
-}
+export type KeyPreview = {
+  type: string;
+  ttl: number;
+  element_count: number | null;
+  sample: string[];
+  error: string | null;
+};
+
+/**
+ * Preview a key's type, TTL and size without reading its value
+ *
+ * @param name Connection name
+ * @param key Key name
+ */
+export async function previewKey(name: string, key: string, db?: number): Promise<KeyPreview> {
+  return invokeCommand<KeyPreview>("preview_key", { name, key, db });
+}
+
+/**
+ * Preview several keys of any type in one call, with a small sample of each
+ *
+ * Keys that fail to read carry an `error` instead of failing the whole call.
+ *
+ * @param name Connection name
+ * @param keys Key names
+ */
+export async function previewKeys(name: string, keys: string[], db?: number): Promise<KeyPreview[]> {
+  return invokeCommand<KeyPreview[]>("preview_keys", { name, keys, db });
+}
+
+/**
+ * Key metadata for the inspector panel, read in a single round trip
+ *
+ * - `ttl_ms`: remaining milliseconds (-1 persistent, -2 missing)
+ * - `encoding`: internal encoding such as "listpack", null for missing keys
+ * - `memory_usage`: bytes used, null when MEMORY USAGE is unavailable
+ * - `element_count`: byte length for strings, element count for collections, null otherwise
+ */
+export type KeyInspection = {
+  type: string;
+  ttl_ms: number;
+  encoding: string | null;
+  memory_usage: number | null;
+  element_count: number | null;
+};
+
+/**
+ * Inspect a key's type, TTL, encoding, memory usage and size at once
+ *
+ * @param name Connection name
+ * @param key Key name
+ */
+export async function inspectKey(name: string, key: string, db?: number): Promise<KeyInspection> {
+  return invokeCommand<KeyInspection>("inspect_key", { name, key, db });
+}
+
+/**
+ * One page of a string value read via GETRANGE
+ *
+ * `chunk` is base64-encoded when `base64` is true (binary data).
+ */
+export type ValuePage = {
+  total_len: number;
+  chunk: string;
+  base64: boolean;
+  /** Offset of the next page; text pages end on a character boundary, so this can be less than offset + length */
+  next_offset: number;
+};
+
+/**
+ * Read a page of a string value (STRLEN + GETRANGE)
+ *
+ * @param name Connection name
+ * @param key Key name
+ * @param offset Start byte offset
+ * @param length Maximum number of bytes in this page
+ */
+export async function getValuePage(name: string, key: string, offset: number, length: number, db?: number): Promise<ValuePage> {
+  return invokeCommand<ValuePage>("get_value_page", { name, key, offset, length, db });
+}
+
+/**
+ * Inspect internal details of a key (DEBUG OBJECT)
+ *
+ * Throws `SERVER_ERROR` when DEBUG is disabled on the server (enable-debug-command).
+ *
+ * @returns Parsed fields such as `encoding`, `refcount`, `serializedlength`
+ */
+export async function debugObjectKey(name: string, key: string, db?: number): Promise<Record<string, string>> {
+  return invokeCommand<Record<string, string>>("debug_object_key", { name, key, db });
+}
+
+/**
+ * Get the LFU access frequency counter of a key (OBJECT FREQ)
+ *
+ * Throws `SERVER_ERROR` unless the server uses an LFU maxmemory-policy.
+ *
+ * @returns Frequency counter, null when the key does not exist
+ */
+export async function objectFreqKey(name: string, key: string, db?: number): Promise<number | null> {
+  return invokeCommand<number | null>("object_freq_key", { name, key, db });
+}
+
+export type ClusterNodeInfo = {
+  id: string;
+  /** Raw `ip:port@cport[,hostname]` address */
+  addr: string;
+  flags: string;
+  master_id: string;
+  ping_sent: number;
+  pong_recv: number;
+  config_epoch: number;
+  link_state: string;
+  /** Raw slot tokens, including migration markers */
+  slots: string[];
+  /** Empty for nodes without an address yet */
+  ip: string;
+  port: number;
+  cport: number;
+  /** Derived from flags; null while the role is unknown (e.g. handshake) */
+  role: "master" | "slave" | null;
+  /** Owned slot ranges as inclusive [start, end] pairs, migration markers excluded */
+  slot_ranges: Array<[number, number]>;
+};
+
+/**
+ * Get cluster node information
+ * 
+ * @param name Connection name
+ * @returns Node information array
+ */
+export async function getClusterInfo(name: string): Promise<ClusterNodeInfo[]> {
+  return invokeCommand<ClusterNodeInfo[]>("get_cluster_info", { name });
+}
+
+/**
+ * Overall cluster state from CLUSTER INFO
+ */
+export type ClusterStatus = {
+  /** False when the server is not running in cluster mode; other fields are then zero/empty */
+  cluster_enabled: boolean;
+  /** "ok" or "fail" */
+  cluster_state: string;
+  cluster_slots_assigned: number;
+  cluster_slots_ok: number;
+  cluster_slots_pfail: number;
+  cluster_slots_fail: number;
+  cluster_known_nodes: number;
+  /** Number of masters serving at least one slot */
+  cluster_size: number;
+  cluster_current_epoch: number;
+};
+
+/**
+ * Get the overall cluster state (CLUSTER INFO)
+ */
+export async function getClusterStatus(name: string): Promise<ClusterStatus> {
+  return invokeCommand<ClusterStatus>("get_cluster_status", { name });
+}
+
+/**
+ * Scan keys (SCAN)
+ * 
+ * @param name Connection name
+ * @param db Database index
+ * @param cursor Cursor
+ * @param pattern Match pattern
+ * @param count Count
+ * @param typeFilter Only return keys of this type, e.g. "string" or "hash" (Redis 6.0+)
+ * @returns [New cursor, Key list]
+ */
+export async function scanKeys(name: string, db: number, cursor: number, pattern?: string, count?: number, typeFilter?: string): Promise<[number, string[]]> {
+  // Rust u64 fits in JS number (safe integer limit 2^53 - 1). 
+  // If cursor exceeds this, we might need BigInt or string, but for Redis scan usually fine.
+  // Actually Tauri handles u64 as number if it fits, or null? 
+  // Let's assume number for now as typical SCAN cursors are small enough or handled.
+  // Wait, invoke returns serialized JSON. u64 in serde_json is number.
+  return invokeCommand<[number, string[]]>("scan_keys", { name, db, cursor, pattern, count, type_filter: typeFilter });
+}
+
+/**
+ * Scan a page of keys together with their types (SCAN + pipelined TYPE)
+ *
+ * @returns [New cursor, [key, type][]]
+ */
+export async function scanKeysWithTypes(name: string, db: number, cursor: number, pattern?: string, count?: number): Promise<[number, [string, string][]]> {
+  return invokeCommand<[number, [string, string][]]>("scan_keys_with_types", { name, db, cursor, pattern, count });
+}
+
+/**
+ * Find keys idle for at least minIdleSecs (SCAN + pipelined OBJECT IDLETIME)
+ *
+ * Not available when the server uses an LFU maxmemory-policy.
+ *
+ * @param limit Max keys to return
+ * @returns [key, idle seconds][]
+ */
+export async function findIdleKeys(name: string, minIdleSecs: number, pattern: string | undefined, limit: number, db?: number): Promise<[string, number][]> {
+  return invokeCommand<[string, number][]>("find_idle_keys", { name, min_idle_secs: minIdleSecs, pattern, limit, db });
+}
+
+/**
+ * Result of counting keys by pattern
+ */
+export type KeyCount = {
+  count: number;
+  /** false when the scan stopped at the iteration cap and count is partial */
+  complete: boolean;
+  iterations: number;
+};
+
+/**
+ * Count keys matching a pattern without loading them (SCAN MATCH)
+ *
+ * @param pattern Match pattern, e.g. "session:*"
+ */
+export async function countKeysByPattern(name: string, pattern: string, db?: number): Promise<KeyCount> {
+  return invokeCommand<KeyCount>("count_keys_by_pattern", { name, pattern, db });
+}
+
+/**
+ * Delete all keys matching a pattern (SCAN + UNLINK)
+ *
+ * @param pattern Match pattern, e.g. "cache:*"; must not be empty
+ * @param allowWildcardAll Must be true to accept patterns such as "*" that match every key
+ * @param batchSize Keys scanned per page (default 500)
+ * @returns Number of keys deleted
+ */
+export async function deleteKeysByPattern(name: string, pattern: string, allowWildcardAll?: boolean, batchSize?: number, db?: number): Promise<number> {
+  return invokeCommand<number>("delete_keys_by_pattern", { name, pattern, allow_wildcard_all: allowWildcardAll, batch_size: batchSize, db });
+}
+
+/**
+ * Set an expiry on all keys matching a pattern (SCAN + EXPIRE)
+ *
+ * @param pattern Match pattern, e.g. "temp:*"; must not be empty
+ * @param seconds Expiry in seconds
+ * @param batchSize Keys scanned per page (default 500)
+ * @returns Number of keys the expiry was applied to
+ */
+export async function expireKeysByPattern(name: string, pattern: string, seconds: number, batchSize?: number, db?: number): Promise<number> {
+  return invokeCommand<number>("expire_keys_by_pattern", { name, pattern, seconds, batch_size: batchSize, db });
+}
 
 /**
  * Get database key count (DBSIZE)
@@ -326,6 +1061,47 @@ export async function getDbSize(name: string, db: number): Promise<number> {
   return invokeCommand<number>("get_db_size", { name, db });
 }
 
+/**
+ * Get the number of databases configured on the server (CONFIG GET databases)
+ *
+ * Cached per connection; falls back to 16 in cluster mode or when CONFIG is unavailable.
+ */
+export async function getDatabaseCount(name: string): Promise<number> {
+  return invokeCommand<number>("get_database_count", { name });
+}
+
+/**
+ * Features supported by the server, derived from its version and loaded modules
+ */
+export type Capabilities = {
+  server_version: string;
+  modules: string[];
+  has_json: boolean;
+  has_search: boolean;
+  supports_resp3: boolean;
+  supports_getdel: boolean;
+  supports_expire_flags: boolean;
+};
+
+/**
+ * Detect which features the server supports (INFO server + MODULE LIST)
+ *
+ * Cached per connection; use it to hide actions the server cannot run.
+ */
+export async function getCapabilities(name: string): Promise<Capabilities> {
+  return invokeCommand<Capabilities>("get_capabilities", { name });
+}
+
+/**
+ * Get key counts of all databases in one call
+ *
+ * @param name Connection name
+ * @returns [Database index, Key count][] (cluster mode only returns db 0)
+ */
+export async function getAllDbSizes(name: string): Promise<[number, number][]> {
+  return invokeCommand<[number, number][]>("get_all_db_sizes", { name });
+}
+
 /**
  * List all saved configurations
  * 
@@ -335,6 +1111,15 @@ export async function listConfigs(): Promise<ConfigItem[]> {
   return invokeCommand<ConfigItem[]>("list_configs");
 }
 
+/**
+ * List saved configurations, most recently used first
+ *
+ * Never-used configurations come last, sorted by name.
+ */
+export async function listConfigsRecent(): Promise<ConfigItem[]> {
+  return invokeCommand<ConfigItem[]>("list_configs_recent");
+}
+
 /**
  * Get configuration by name
  * 
@@ -371,7 +1156,43 @@ export async function deleteConfig(name: string): Promise<boolean> {
 }
 
 /**
- * List current in-memory service connection names (AppState.services)
+ * Back up the saved connections database to a file
+ *
+ * Uses SQLite's VACUUM INTO, so the copy is consistent while the app is running.
+ *
+ * @param destPath Backup file path, overwritten if it exists
+ * @returns Size of the backup file in bytes
+ */
+export async function backupDatabase(destPath: string): Promise<number> {
+  return invokeCommand<number>("backup_database", { dest_path: destPath });
+}
+
+/**
+ * Result of restoring configurations from a backup file
+ */
+export type ConfigImportReport = {
+  /** Configurations added or overwritten */
+  imported: string[];
+  /** Existing configurations left unchanged because overwrite was off */
+  skipped: string[];
+  /** Configurations that could not be parsed, as "name: reason" */
+  errors: string[];
+};
+
+/**
+ * Restore or merge saved connections from a backup file
+ *
+ * Services are reloaded afterwards, so imported connections are available immediately.
+ *
+ * @param srcPath Backup file path, opened read-only
+ * @param overwrite Replace configurations with the same name (default false)
+ */
+export async function restoreDatabase(srcPath: string, overwrite?: boolean): Promise<ConfigImportReport> {
+  return invokeCommand<ConfigImportReport>("restore_database", { src_path: srcPath, overwrite });
+}
+
+/**
+ * List service connection names, including saved connections not established yet
  *
  * @returns Name array
  */
@@ -380,7 +1201,35 @@ export async function listServices(): Promise<string[]> {
 }
 
 /**
- * Reload all connections from database to memory (clear then rebuild)
+ * Summary of an active connection
+ */
+export type ConnectionDetail = {
+  name: string;
+  mode: "standalone" | "cluster" | "sentinel";
+  /** First configured address (first sentinel in sentinel mode) with the password masked */
+  primary_url: string;
+  /** False for saved connections that have not been established yet (they connect on first use) */
+  connected: boolean;
+  /** Whether a PING succeeded within the health check timeout, false when not connected */
+  healthy: boolean;
+  /** Configured database count, null when the connection is unhealthy */
+  db_count: number | null;
+  /** Whether the connection is read-only */
+  readonly: boolean;
+};
+
+/**
+ * List connections with their mode, address and health
+ *
+ * Health checks run concurrently with a short timeout; unhealthy connections are reported, not dropped.
+ * Saved connections that are not established yet are included with `connected: false`.
+ */
+export async function listConnectionsDetailed(): Promise<ConnectionDetail[]> {
+  return invokeCommand<ConnectionDetail[]>("list_connections_detailed");
+}
+
+/**
+ * Reload connections from database to memory (only added/changed/removed configs are touched)
  *
  * @returns String 'ok' on success
  */
@@ -398,6 +1247,36 @@ export async function serviceExists(name: string): Promise<boolean> {
   return invokeCommand<boolean>("service_exists", { name });
 }
 
+/**
+ * Command history (audit log) entry
+ */
+export type AuditEntry = {
+  /** Unix timestamp in milliseconds */
+  timestamp: number;
+  connection: string;
+  command: string;
+  /** Database passed by the caller, null means the connection default */
+  db: number | null;
+  success: boolean;
+  duration_ms: number;
+};
+
+/**
+ * Get executed command history, oldest first
+ *
+ * @param limit Only return the most recent N entries
+ */
+export async function getCommandHistory(limit?: number): Promise<AuditEntry[]> {
+  return invokeCommand<AuditEntry[]>("get_command_history", { limit });
+}
+
+/**
+ * Clear command history
+ */
+export async function clearCommandHistory(): Promise<boolean> {
+  return invokeCommand<boolean>("clear_command_history");
+}
+
 /**
  * Get key type
  */
@@ -412,6 +1291,27 @@ export async function hgetAll(name: string, key: string, db?: number): Promise<R
   return invokeCommand<Record<string, string>>("hgetall_hash", { name, key, db });
 }
 
+/**
+ * A hash field value with its JSON parse result
+ *
+ * `is_json` is true only for JSON objects and arrays; `json` holds the parsed value in that case.
+ */
+export type ParsedHashField = {
+  field: string;
+  raw: string;
+  is_json: boolean;
+  json: any | null;
+};
+
+/**
+ * Get all fields of a hash, detecting values that hold JSON
+ *
+ * @returns Fields sorted by name
+ */
+export async function hgetAllParsed(name: string, key: string, db?: number): Promise<ParsedHashField[]> {
+  return invokeCommand<ParsedHashField[]>("hgetall_parsed", { name, key, db });
+}
+
 export async function hset(name: string, key: string, field: string, value: string, db?: number): Promise<boolean> {
   return invokeCommand<boolean>("hset_field", { name, key, field, value, db });
 }
@@ -438,6 +1338,59 @@ export async function lrange(name: string, key: string, start: number, stop: num
   return invokeCommand<string[]>("lrange_list", { name, key, start, stop, db });
 }
 
+/**
+ * Pop from the first non-empty list (LMPOP, Redis 7.0+)
+ *
+ * @param fromLeft true pops from the head, false from the tail
+ * @returns [key, elements] or null when every list is empty
+ */
+export async function lmpop(name: string, keys: string[], fromLeft: boolean, count: number, db?: number): Promise<[string, string[]] | null> {
+  return invokeCommand<[string, string[]] | null>("lmpop_lists", { name, keys, from_left: fromLeft, count, db });
+}
+
+/**
+ * Atomically take all elements of a list and delete the key
+ *
+ * @returns Elements from head to tail, empty if the key does not exist
+ */
+export async function drainList(name: string, key: string, db?: number): Promise<string[]> {
+  return invokeCommand<string[]>("drain_list", { name, key, db });
+}
+
+/**
+ * Blocking pop from the first non-empty list (BLPOP / BRPOP)
+ *
+ * Runs on a dedicated connection so other commands are not delayed.
+ *
+ * @param timeoutSecs Server-side block timeout in seconds, must be greater than 0
+ * @param fromLeft false uses BRPOP (default true)
+ * @returns [key, element] or null on timeout
+ */
+export async function blpop(name: string, keys: string[], timeoutSecs: number, fromLeft?: boolean, db?: number): Promise<[string, string] | null> {
+  return invokeCommand<[string, string] | null>("blpop_lists", { name, keys, timeout_secs: timeoutSecs, from_left: fromLeft, db });
+}
+
+/**
+ * Find positions of a list element (LPOS)
+ *
+ * @param rank Start from the Nth match, negative searches from the tail
+ * @param count Max matches to return, 0 for all (default first match only)
+ * @returns Matching indexes
+ */
+export async function lpos(name: string, key: string, value: string, rank?: number, count?: number, db?: number): Promise<number[]> {
+  return invokeCommand<number[]>("lpos_list", { name, key, value, rank, count, db });
+}
+
+/**
+ * Remove list elements (LREM)
+ *
+ * @param count >0 from head, <0 from tail, 0 removes all matches
+ * @returns Number of removed elements
+ */
+export async function lrem(name: string, key: string, count: number, value: string, db?: number): Promise<number> {
+  return invokeCommand<number>("lrem_list", { name, key, count, value, db });
+}
+
 /**
  * Add member to set
  */
@@ -456,6 +1409,42 @@ export async function srem(name: string, key: string, member: string, db?: numbe
   return invokeCommand<boolean>("srem_set", { name, key, member, db });
 }
 
+/**
+ * Store the intersection of sets into dest (SINTERSTORE)
+ *
+ * @returns Cardinality of the resulting set
+ */
+export async function sinterstore(name: string, dest: string, keys: string[], db?: number): Promise<number> {
+  return invokeCommand<number>("sinterstore_set", { name, dest, keys, db });
+}
+
+/**
+ * Store the union of sets into dest (SUNIONSTORE)
+ *
+ * @returns Cardinality of the resulting set
+ */
+export async function sunionstore(name: string, dest: string, keys: string[], db?: number): Promise<number> {
+  return invokeCommand<number>("sunionstore_set", { name, dest, keys, db });
+}
+
+/**
+ * Store the difference between the first set and the others into dest (SDIFFSTORE)
+ *
+ * @returns Cardinality of the resulting set
+ */
+export async function sdiffstore(name: string, dest: string, keys: string[], db?: number): Promise<number> {
+  return invokeCommand<number>("sdiffstore_set", { name, dest, keys, db });
+}
+
+/**
+ * Count the members of the intersection of sets without returning them (SINTERCARD, Redis 7.0+)
+ *
+ * @param limit Stop counting once this many members are found
+ */
+export async function sintercard(name: string, keys: string[], limit?: number, db?: number): Promise<number> {
+  return invokeCommand<number>("sintercard_sets", { name, keys, limit, db });
+}
+
 export async function zadd(name: string, key: string, member: string, score: number, db?: number): Promise<number> {
   return invokeCommand<number>("zadd_zset", { name, key, member, score, db });
 }
@@ -468,22 +1457,638 @@ export async function zrangeWithScores(name: string, key: string, start: number,
   return invokeCommand<[string, number][]>("zrange_zset", { name, key, start, stop, db });
 }
 
-export async function jsonGet(name: string, key: string, path?: string, db?: number): Promise<any | null> {
-  return invokeCommand<any | null>("json_get_value", { name, key, path, db });
+/**
+ * Store an index range of a sorted set into dest (ZRANGESTORE)
+ *
+ * @returns Number of elements in dest
+ */
+export async function zrangestore(name: string, dest: string, src: string, start: number, stop: number, db?: number): Promise<number> {
+  return invokeCommand<number>("zrangestore_zset", { name, dest, src, start, stop, db });
 }
 
-export async function jsonSet(name: string, key: string, value: unknown, path?: string, db?: number): Promise<boolean> {
-  const value_json = JSON.stringify(value);
-  return invokeCommand<boolean>("json_set_value", { name, key, path, value_json, db });
+/**
+ * Pop from the first non-empty sorted set (ZMPOP, Redis 7.0+)
+ *
+ * @param min true pops the lowest scores, false the highest
+ * @returns [key, [member, score][]] or null when every sorted set is empty
+ */
+export async function zmpop(name: string, keys: string[], min: boolean, count: number, db?: number): Promise<[string, [string, number][]] | null> {
+  return invokeCommand<[string, [string, number][]] | null>("zmpop_zsets", { name, keys, min, count, db });
+}
+
+/**
+ * Count the members of the intersection of sorted sets without returning them (ZINTERCARD, Redis 7.0+)
+ *
+ * @param limit Stop counting once this many members are found
+ */
+export async function zintercard(name: string, keys: string[], limit?: number, db?: number): Promise<number> {
+  return invokeCommand<number>("zintercard_zsets", { name, keys, limit, db });
+}
+
+export async function jsonGet(name: string, key: string, path?: string, db?: number): Promise<any | null> {
+  return invokeCommand<any | null>("json_get_value", { name, key, path, db });
+}
+
+export async function jsonSet(name: string, key: string, value: unknown, path?: string, db?: number): Promise<boolean> {
+  const value_json = JSON.stringify(value);
+  return invokeCommand<boolean>("json_set_value", { name, key, path, value_json, db });
+}
+
+/**
+ * Batch write JSON documents (JSON.MSET)
+ *
+ * Falls back to pipelined JSON.SET on servers without JSON.MSET.
+ *
+ * @param items Array of [key, path, value] triples
+ * @returns true on success
+ */
+export async function jsonMset(name: string, items: [string, string, unknown][], db?: number): Promise<boolean> {
+  return invokeCommand<boolean>("json_mset_values", { name, items, db });
+}
+
+/**
+ * Get the type of the value at a JSON path (JSON.TYPE)
+ *
+ * @param path Defaults to "$"
+ * @returns object | array | string | integer | number | boolean | null, or null when the path does not exist
+ */
+export async function jsonType(name: string, key: string, path?: string, db?: number): Promise<string | null> {
+  return invokeCommand<string | null>("json_type", { name, key, path, db });
+}
+
+/**
+ * List the keys of a JSON object (JSON.OBJKEYS)
+ *
+ * @param path Defaults to "$"
+ * @returns Object keys, empty when the path is not an object
+ */
+export async function jsonObjKeys(name: string, key: string, path?: string, db?: number): Promise<string[]> {
+  return invokeCommand<string[]>("json_objkeys", { name, key, path, db });
+}
+
+/**
+ * Delete values at a JSON path (JSON.DEL)
+ *
+ * @param path Defaults to "$", which deletes the whole document
+ * @returns Number of values deleted
+ */
+export async function jsonDel(name: string, key: string, path?: string, db?: number): Promise<number> {
+  return invokeCommand<number>("json_del_path", { name, key, path, db });
 }
 
+/**
+ * Append values to a JSON array (JSON.ARRAPPEND)
+ *
+ * @returns New array length, or null when the path is not an array
+ */
+export async function jsonArrAppend(name: string, key: string, path: string, values: unknown[], db?: number): Promise<number | null> {
+  return invokeCommand<number | null>("json_arr_append", { name, key, path, values_json: JSON.stringify(values), db });
+}
+
+/**
+ * Increment a JSON number (JSON.NUMINCRBY)
+ *
+ * @returns The new value, or null when the path is not a number
+ */
+export async function jsonNumIncrBy(name: string, key: string, path: string, delta: number, db?: number): Promise<number | null> {
+  return invokeCommand<number | null>("json_num_incr_by", { name, key, path, delta, db });
+}
+
+/**
+ * Add geo members (GEOADD)
+ *
+ * @param members Array of [longitude, latitude, member]
+ * @returns Number of newly added members
+ */
+export async function geoadd(name: string, key: string, members: [number, number, string][], db?: number): Promise<number> {
+  return invokeCommand<number>("geoadd_geo", { name, key, members, db });
+}
+
+/**
+ * Get member coordinates (GEOPOS)
+ *
+ * @returns [longitude, latitude] per member, null for missing members
+ */
+export async function geopos(name: string, key: string, members: string[], db?: number): Promise<([number, number] | null)[]> {
+  return invokeCommand<([number, number] | null)[]>("geopos_geo", { name, key, members, db });
+}
+
+/**
+ * Distance between two members (GEODIST)
+ *
+ * @param unit m | km | mi | ft (default m)
+ * @returns Distance, null if either member is missing
+ */
+export async function geodist(name: string, key: string, member1: string, member2: string, unit?: string, db?: number): Promise<number | null> {
+  return invokeCommand<number | null>("geodist_geo", { name, key, member1, member2, unit, db });
+}
+
+/**
+ * Search members within a radius (GEOSEARCH FROMLONLAT BYRADIUS)
+ *
+ * @param center [longitude, latitude]
+ * @returns Members sorted by distance
+ */
+export async function geosearch(name: string, key: string, center: [number, number], radius: number, unit?: string, db?: number): Promise<string[]> {
+  return invokeCommand<string[]>("geosearch_geo", { name, key, center, radius, unit, db });
+}
+
+/**
+ * Sort a list, set or sorted set on the server (SORT)
+ *
+ * Numeric sorting fails when an element is not a number; pass `alpha: true` to sort as strings.
+ *
+ * @param options.by External key pattern to sort by, e.g. "weight_*"
+ * @param options.limit [offset, count]
+ * @param options.get Patterns of external keys to return instead of the elements ("#" is the element)
+ */
+export async function sortKey(
+  name: string,
+  key: string,
+  options: { by?: string; limit?: [number, number]; get?: string[]; alpha?: boolean; desc?: boolean } = {},
+  db?: number
+): Promise<string[]> {
+  return invokeCommand<string[]>("sort_key", { name, key, ...options, db });
+}
+
+/**
+ * A stream message; `fields` is empty when the entry was deleted but is still pending
+ */
+export type StreamEntry = {
+  id: string;
+  fields: [string, string][];
+};
+
+/**
+ * Create a consumer group (XGROUP CREATE)
+ *
+ * @param id Start ID, defaults to "$" (only new messages); use "0" to consume from the beginning
+ * @param mkstream Create the stream if it does not exist (default true)
+ * @returns true when created, false when the group already existed
+ */
+export async function xgroupCreate(name: string, key: string, group: string, id?: string, mkstream?: boolean, db?: number): Promise<boolean> {
+  return invokeCommand<boolean>("xgroup_create_stream", { name, key, group, id, mkstream, db });
+}
+
+/**
+ * Read messages as a group consumer (XREADGROUP)
+ *
+ * @param streams Stream keys
+ * @param ids One start ID per stream, ">" for messages never delivered before
+ * @param blockMs Wait up to this long for new messages (omit to return immediately)
+ * @returns [stream key, entries] pairs; empty when the wait timed out
+ */
+export async function xreadgroup(
+  name: string,
+  group: string,
+  consumer: string,
+  streams: string[],
+  ids: string[],
+  count?: number,
+  blockMs?: number,
+  db?: number
+): Promise<[string, StreamEntry[]][]> {
+  return invokeCommand<[string, StreamEntry[]][]>("xreadgroup_stream", { name, group, consumer, streams, ids, count, block_ms: blockMs, db });
+}
+
+/**
+ * Acknowledge processed messages (XACK)
+ *
+ * @returns Number of messages removed from the pending list
+ */
+export async function xack(name: string, key: string, group: string, ids: string[], db?: number): Promise<number> {
+  return invokeCommand<number>("xack_stream", { name, key, group, ids, db });
+}
+
+/**
+ * Delete stream entries (XDEL)
+ *
+ * @returns Number of entries deleted
+ */
+export async function xdel(name: string, key: string, ids: string[], db?: number): Promise<number> {
+  return invokeCommand<number>("xdel_stream", { name, key, ids, db });
+}
+
+/**
+ * Trim a stream by length or minimum ID (XTRIM); pass exactly one of `maxlen` / `minid`
+ *
+ * @param approximate Use `~` trimming: faster, but may keep slightly more entries than requested
+ * @returns Number of entries removed
+ */
+export async function xtrim(
+  name: string,
+  key: string,
+  options: { maxlen?: number; minid?: string; approximate?: boolean },
+  db?: number
+): Promise<number> {
+  return invokeCommand<number>("xtrim_stream", { name, key, maxlen: options.maxlen, minid: options.minid, approximate: options.approximate, db });
+}
+
+/**
+ * Add elements to a HyperLogLog (PFADD)
+ *
+ * @returns true if the estimated cardinality may have changed
+ */
+export async function pfadd(name: string, key: string, elements: string[], db?: number): Promise<boolean> {
+  return invokeCommand<boolean>("pfadd_hll", { name, key, elements, db });
+}
+
+/**
+ * Estimated cardinality of one or more HyperLogLogs (PFCOUNT)
+ */
+export async function pfcount(name: string, keys: string[], db?: number): Promise<number> {
+  return invokeCommand<number>("pfcount_hll", { name, keys, db });
+}
+
+/**
+ * Merge HyperLogLogs into dest (PFMERGE)
+ */
+export async function pfmerge(name: string, dest: string, sources: string[], db?: number): Promise<boolean> {
+  return invokeCommand<boolean>("pfmerge_hll", { name, dest, sources, db });
+}
+
+/**
+ * Set a bit (SETBIT)
+ *
+ * @returns Previous bit value
+ */
+export async function setbit(name: string, key: string, offset: number, value: boolean, db?: number): Promise<boolean> {
+  return invokeCommand<boolean>("setbit_bitmap", { name, key, offset, value, db });
+}
+
+/**
+ * Get a bit (GETBIT)
+ */
+export async function getbit(name: string, key: string, offset: number, db?: number): Promise<boolean> {
+  return invokeCommand<boolean>("getbit_bitmap", { name, key, offset, db });
+}
+
+/**
+ * Count set bits (BITCOUNT)
+ *
+ * @param range Optional byte range [start, end]
+ */
+export async function bitcount(name: string, key: string, range?: [number, number], db?: number): Promise<number> {
+  return invokeCommand<number>("bitcount_bitmap", { name, key, range, db });
+}
+
+/**
+ * Bitwise operation between keys stored into dest (BITOP)
+ *
+ * @param op AND | OR | XOR | NOT
+ * @returns Length of the resulting string in bytes
+ */
+export async function bitop(name: string, op: string, dest: string, keys: string[], db?: number): Promise<number> {
+  return invokeCommand<number>("bitop_bitmap", { name, op, dest, keys, db });
+}
+
+/**
+ * Wait for replicas to acknowledge previous writes (WAIT)
+ *
+ * Returns as soon as `num_replicas` acknowledged, or after `timeout_ms`.
+ *
+ * @returns Number of replicas that acknowledged
+ */
+export async function waitReplicas(name: string, num_replicas: number, timeout_ms: number): Promise<number> {
+  return invokeCommand<number>("wait_replicas", { name, num_replicas, timeout_ms });
+}
+
+/**
+ * Get the time of the last successful snapshot (LASTSAVE)
+ *
+ * @returns Unix timestamp in seconds
+ */
+export async function lastSave(name: string): Promise<number> {
+  return invokeCommand<number>("last_save", { name });
+}
+
+/**
+ * Server time from TIME and its skew against the local clock
+ */
+export type ServerTime = {
+  seconds: number;
+  microseconds: number;
+  /** Server time minus local time in milliseconds; positive means the server clock is ahead */
+  skew_ms: number;
+};
+
+/**
+ * Get the server time (TIME) and the clock skew versus the local machine
+ */
+export async function getServerTime(name: string): Promise<ServerTime> {
+  return invokeCommand<ServerTime>("get_server_time", { name });
+}
+
+/**
+ * Block the server for the given number of seconds (DEBUG SLEEP, at most 60)
+ *
+ * Blocks every client on the server; only use it against test instances to
+ * exercise loading states and timeout handling.
+ */
+export async function debugSleep(name: string, seconds: number): Promise<boolean> {
+  return invokeCommand<boolean>("debug_sleep", { name, seconds });
+}
+
+/**
+ * Save a snapshot synchronously (SAVE)
+ *
+ * Blocks the Redis server until the snapshot is written; prefer BGSAVE in production.
+ *
+ * @returns 'saved' on success
+ */
+export async function saveSnapshot(name: string): Promise<string> {
+  return invokeCommand<string>("save_snapshot", { name });
+}
+
+/**
+ * RDB/AOF persistence state parsed from `INFO persistence`
+ */
+export type PersistenceStatus = {
+  loading: boolean;
+  rdb_changes_since_last_save: number;
+  rdb_bgsave_in_progress: boolean;
+  /** Unix timestamp (seconds) of the last successful snapshot */
+  rdb_last_save_time: number;
+  rdb_last_bgsave_status: string;
+  aof_enabled: boolean;
+  aof_rewrite_in_progress: boolean;
+  aof_rewrite_scheduled: boolean;
+  aof_last_bgrewrite_status: string;
+};
+
+/**
+ * Trigger a background AOF rewrite (BGREWRITEAOF)
+ *
+ * @returns 'started' on success
+ */
+export async function bgrewriteAof(name: string): Promise<string> {
+  return invokeCommand<string>("bgrewrite_aof", { name });
+}
+
+/**
+ * Get RDB/AOF persistence status (INFO persistence)
+ */
+export async function getPersistenceStatus(name: string): Promise<PersistenceStatus> {
+  return invokeCommand<PersistenceStatus>("get_persistence_status", { name });
+}
+
+/**
+ * Sentinel view of a monitored master
+ */
+export type SentinelMasterInfo = {
+  master_name: string;
+  /** Current master address as [ip, port], null when the sentinel does not monitor this name */
+  addr: [string, number] | null;
+  /** All masters reported by SENTINEL MASTERS */
+  masters: Record<string, string>[];
+};
+
+/**
+ * Query sentinels for the current master address
+ *
+ * @param master Master name, defaults to the connection's `sentinel_master_name`
+ */
+export async function sentinelMasterInfo(name: string, master?: string): Promise<SentinelMasterInfo> {
+  return invokeCommand<SentinelMasterInfo>("sentinel_master_info", { name, master });
+}
+
+/**
+ * Trigger a manual sentinel failover (SENTINEL FAILOVER)
+ *
+ * @param master Master name, defaults to the connection's `sentinel_master_name`
+ * @returns 'failover started' on success
+ */
+export async function sentinelTriggerFailover(name: string, master?: string): Promise<string> {
+  return invokeCommand<string>("sentinel_trigger_failover", { name, master });
+}
+
+/**
+ * Compute the cluster slot of a key (CLUSTER KEYSLOT, computed locally)
+ *
+ * @returns Slot number in 0..16384
+ */
+export async function clusterKeyslot(name: string, key: string): Promise<number> {
+  return invokeCommand<number>("cluster_keyslot", { name, key });
+}
+
+/**
+ * Count keys stored in a cluster slot (CLUSTER COUNTKEYSINSLOT)
+ *
+ * @param slot Slot number in 0..16384
+ */
+export async function clusterCountKeysInSlot(name: string, slot: number): Promise<number> {
+  return invokeCommand<number>("cluster_count_keys_in_slot", { name, slot });
+}
+
+/**
+ * Assign slots to the node (CLUSTER ADDSLOTS)
+ *
+ * Slot commands act on the node that receives them; use a standalone connection to target a specific node.
+ */
+export async function clusterAddSlots(name: string, slots: number[]): Promise<string> {
+  return invokeCommand<string>("cluster_add_slots", { name, slots });
+}
+
+/**
+ * Remove slot assignments from the node (CLUSTER DELSLOTS)
+ */
+export async function clusterDelSlots(name: string, slots: number[]): Promise<string> {
+  return invokeCommand<string>("cluster_del_slots", { name, slots });
+}
+
+/**
+ * Set slot state for manual resharding (CLUSTER SETSLOT)
+ *
+ * @param slotState IMPORTING | MIGRATING | STABLE | NODE
+ * @param nodeId Required for every state except STABLE
+ */
+export async function clusterSetSlot(name: string, slot: number, slotState: string, nodeId?: string): Promise<string> {
+  return invokeCommand<string>("cluster_set_slot", { name, slot, slot_state: slotState, node_id: nodeId });
+}
+
+/**
+ * Get the number of commands supported by the server (COMMAND COUNT)
+ */
+export async function commandCount(name: string): Promise<number> {
+  return invokeCommand<number>("command_count", { name });
+}
+
+/**
+ * List the commands supported by the server (COMMAND LIST, falling back to COMMAND)
+ *
+ * @returns Lowercase command names sorted alphabetically
+ */
+export async function listCommands(name: string): Promise<string[]> {
+  return invokeCommand<string[]>("list_commands", { name });
+}
+
+/**
+ * Run an arbitrary command and render the raw reply structure
+ *
+ * Unlike JSON conversion this keeps RESP types and nesting, e.g. `Array[BulkString("a"), Int(5), Nil]`.
+ *
+ * @param args Command name followed by its arguments, e.g. ["HGETALL", "user:1"]
+ */
+export async function runCommandDebug(name: string, args: string[], db?: number): Promise<string> {
+  return invokeCommand<string>("run_command_debug", { name, args, db });
+}
+
+/**
+ * Run an arbitrary command like runCommandDebug, also returning its duration and serving node
+ *
+ * @param args Command name followed by its arguments, e.g. ["GET", "user:1"]
+ */
+export async function runCommandTimed(name: string, args: string[], db?: number): Promise<{ data: string; meta?: ResponseMeta }> {
+  return invokeCommandWithMeta<string>("run_command_debug", { name, args, db });
+}
+
+/**
+ * Latency samples recorded by the server's latency monitor (LATENCY HISTORY)
+ *
+ * @param event Event name, e.g. "command" or "fast-command"
+ * @returns [unix timestamp in seconds, latency in ms] pairs, oldest first
+ */
+export async function latencyHistory(name: string, event: string): Promise<Array<[number, number]>> {
+  return invokeCommand<Array<[number, number]>>("latency_history", { name, event });
+}
+
+/**
+ * Clear all latency monitor samples (LATENCY RESET)
+ *
+ * @returns Number of events that were reset
+ */
+export async function latencyReset(name: string): Promise<number> {
+  return invokeCommand<number>("latency_reset", { name });
+}
+
+/**
+ * List ACL user rules (ACL LIST)
+ *
+ * @returns One rule line per user, e.g. "user default on nopass ~* &* +@all"
+ */
+export async function aclListUsers(name: string): Promise<string[]> {
+  return invokeCommand<string[]>("acl_list_users", { name });
+}
+
+/**
+ * Get ACL details of a user (ACL GETUSER)
+ *
+ * @returns Field map (flags, passwords, commands, keys, channels, selectors...), empty when the user does not exist
+ */
+export async function aclGetUser(name: string, user: string): Promise<Record<string, any>> {
+  return invokeCommand<Record<string, any>>("acl_get_user", { name, user });
+}
+
+/**
+ * Create or modify an ACL user (ACL SETUSER)
+ *
+ * @param rules Rules applied in order, e.g. ["on", ">secret", "~cache:*", "+get"]
+ */
+export async function aclSetUser(name: string, user: string, rules: string[]): Promise<string> {
+  return invokeCommand<string>("acl_set_user", { name, user, rules });
+}
+
+/**
+ * Delete an ACL user (ACL DELUSER)
+ *
+ * @returns Number of users deleted
+ */
+export async function aclDelUser(name: string, user: string): Promise<number> {
+  return invokeCommand<number>("acl_del_user", { name, user });
+}
+
+/**
+ * Result of a bulk import
+ */
+export type ImportReport = {
+  imported: number;
+  failed: number;
+  /** Failure reasons, at most 100 entries */
+  errors: string[];
+};
+
+/**
+ * Bulk import key/value pairs from a local file (batched MSET)
+ *
+ * @param path Local file path
+ * @param format "json" (object of key to value) or "csv" (key,value rows)
+ */
+export async function importKeysFromFile(name: string, path: string, format: "json" | "csv", db?: number): Promise<ImportReport> {
+  return invokeCommand<ImportReport>("import_keys_from_file", { name, path, format, db });
+}
+
+/**
+ * Result of exporting keys to a file
+ */
+export type ExportReport = {
+  exported: number;
+  /** Keys that vanished or changed type before they were read, or have an unsupported type */
+  skipped: number;
+};
+
+/**
+ * Export keys matching a pattern to a typed JSON file
+ *
+ * The file looks like {"version":1,"keys":[{"key","type","value"}]}; only string/hash/list/set/zset are exported.
+ * Non-UTF-8 key names and values are written as {"base64": "..."}; zset scores are strings such as "1.5" or "inf".
+ *
+ * @param pattern Match pattern, e.g. "user:*"
+ * @param path Output file path (overwritten)
+ */
+export async function exportKeysToFile(name: string, pattern: string, path: string, db?: number): Promise<ExportReport> {
+  return invokeCommand<ExportReport>("export_keys_to_file", { name, pattern, path, db });
+}
+
+/**
+ * Result of migrating keys between two connections
+ */
+export type MigrateReport = {
+  migrated: number;
+  /** Keys that already existed in the destination (without overwrite) or vanished before DUMP */
+  skipped: number;
+  failed: number;
+  /** Failure reasons, at most 100 entries */
+  errors: string[];
+};
+
+/**
+ * Copy keys matching a pattern from one saved connection to another (DUMP/RESTORE, TTLs preserved)
+ *
+ * @param overwrite Replace keys that already exist in the destination (default false)
+ */
+export async function migrateKeys(sourceName: string, destName: string, pattern: string, overwrite?: boolean, sourceDb?: number, destDb?: number): Promise<MigrateReport> {
+  return invokeCommand<MigrateReport>("migrate_keys", { source_name: sourceName, dest_name: destName, source_db: sourceDb, dest_db: destDb, pattern, overwrite });
+}
+
+/**
+ * Result of a connection test
+ */
+export type TestResult = {
+  /** false when the server was reachable but the configured mode does not match */
+  ok: boolean;
+  server_version: string;
+  /** standalone | cluster | sentinel, as reported by INFO server */
+  redis_mode: string;
+  latency_ms: number;
+  warnings: string[];
+};
+
 /**
  * Test Redis connection configuration (without saving)
  * 
  * @param config Redis connection configuration
- * @returns true on success
+ * @returns Server version, mode, latency and any mode-mismatch warnings
+ */
+export async function testConnectionConfig(config: RedisConfig): Promise<TestResult> {
+  return invokeCommand<TestResult>("test_connection_config", { config });
+}
+
+/**
+ * Change the backend log level at runtime (reset to the default on restart)
+ *
+ * @param level One of "trace", "debug", "info", "warn", "error"
+ * @returns The level now in effect, e.g. "DEBUG"
  */
-export async function testConnectionConfig(config: RedisConfig): Promise<boolean> {
-  await invokeCommand<string>("test_connection_config", { config });
-  return true;
+export async function setLogLevel(level: "trace" | "debug" | "info" | "warn" | "error"): Promise<string> {
+  return invokeCommand<string>("set_log_level", { level });
 }